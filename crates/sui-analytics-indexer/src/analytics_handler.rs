@@ -6,38 +6,66 @@ use crate::{
     analytics_metrics::AnalyticsMetrics,
     csv_writer::CSVWriter,
     errors::AnalyticsIndexerError,
+    parquet_writer::ParquetWriter,
     tables::{
-        CheckpointEntry, EventEntry, InputObjectKind, ObjectEntry, ObjectStatus, OwnerType,
-        TransactionEntry, TransactionObjectEntry,
+        BalanceChangeEntry, CheckpointEntry, DynamicFieldEntry, EventEntry, InputObjectKind,
+        MoveCallEntry, MovePackageEntry, ObjectEntry, ObjectStatus, OwnerType, TransactionEntry,
+        TransactionObjectEntry,
     },
-    writer::CheckpointWriter,
-    AnalyticsIndexerConfig,
+    writer::{CheckpointWriter, TableWriter},
+    AnalyticsIndexerConfig, FileFormat,
 };
 use fastcrypto::{
     encoding::{Base64, Encoding},
     traits::EncodeDecodeBase64,
 };
-use std::collections::BTreeSet;
+use object_store::{path::Path, DynObjectStore};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use sui_indexer::framework::interface::Handler;
 use sui_rest_api::{CheckpointData, CheckpointTransaction};
+use sui_storage::object_store::{util::copy_file, ObjectStoreConfig, ObjectStoreType};
 use sui_types::{
     base_types::ObjectID,
+    coin::Coin,
     digests::TransactionDigest,
+    dynamic_field::{DynamicFieldInfo, DynamicFieldType},
     effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
     event::Event,
     messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSummary},
     object::{Object, Owner},
     transaction::{TransactionData, TransactionDataAPI},
+    TypeTag,
 };
-use tracing::info;
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::{error, info, warn};
+
+// Number of entries to collect in memory before writing them to files.
+const MAX_BUFFERED_ENTRIES: usize = 10_000;
+// Number of times a file is uploaded before giving up on it, and how long to wait before the
+// first retry, doubling with each retry after that.
+const MAX_UPLOAD_ATTEMPTS: u32 = 5;
+const INITIAL_UPLOAD_RETRY_DELAY: Duration = Duration::from_secs(10);
 
 // The main processor for analytics indexer.
 pub struct AnalyticsProcessor {
     config: AnalyticsIndexerConfig,
     metrics: AnalyticsMetrics,
     writer: CheckpointWriter,
-    data_writer: Option<CSVWriter>,
+    data_writer: Option<Box<dyn TableWriter>>,
     start_checkpoint_to_commit: u64,
+    start_time_to_commit: Instant,
+    // Directory the files are written to before they are uploaded.
+    checkpoint_dir: PathBuf,
+    // Sends finished files to the upload task, if there is a remote store to upload to.
+    upload_sender: Option<mpsc::Sender<Vec<PathBuf>>>,
+    // The upload task, which only finishes early if it failed to upload a file.
+    upload_task: Option<JoinHandle<anyhow::Result<()>>>,
 }
 
 // Main callback from the indexer framework.
@@ -82,21 +110,58 @@ impl Handler for AnalyticsProcessor {
                 self.process_events(epoch, checkpoint_num, digest, timestamp, events);
             }
         }
-        self.save_locally(checkpoint_num);
-        self.upload_to_datastore(checkpoint_num);
-        Ok(())
+        self.save_locally(checkpoint_num)?;
+        self.upload_to_datastore(checkpoint_num).await
     }
 }
 
 impl AnalyticsProcessor {
-    pub fn new(config: AnalyticsIndexerConfig, metrics: AnalyticsMetrics) -> Self {
-        Self {
+    pub fn new(
+        config: AnalyticsIndexerConfig,
+        metrics: AnalyticsMetrics,
+    ) -> Result<Self, AnalyticsIndexerError> {
+        let checkpoint_dir = if let Some(checkpoint_dir) = &config.checkpoint_dir {
+            PathBuf::from(checkpoint_dir)
+        } else {
+            env::current_dir().map_err(|_| AnalyticsIndexerError::CurrentDirError)?
+        };
+
+        let (upload_sender, upload_task) = if config.remote_store_config.object_store.is_some() {
+            let local_store_config = ObjectStoreConfig {
+                object_store: Some(ObjectStoreType::File),
+                directory: Some(checkpoint_dir.clone()),
+                ..Default::default()
+            };
+            let make_store = |store_config: &ObjectStoreConfig| {
+                store_config
+                    .make()
+                    .map_err(|e| AnalyticsIndexerError::GenericError(e.to_string()))
+            };
+            let local_store = make_store(&local_store_config)?;
+            let remote_store = make_store(&config.remote_store_config)?;
+            let (sender, receiver) = mpsc::channel(100);
+            let upload_task = tokio::spawn(upload_files(
+                checkpoint_dir.clone(),
+                local_store,
+                remote_store,
+                receiver,
+            ));
+            (Some(sender), Some(upload_task))
+        } else {
+            (None, None)
+        };
+
+        Ok(Self {
             config,
             metrics,
             writer: CheckpointWriter::new(),
             data_writer: None,
             start_checkpoint_to_commit: 0,
-        }
+            start_time_to_commit: Instant::now(),
+            checkpoint_dir,
+            upload_sender,
+            upload_task,
+        })
     }
 
     // Overall checkpoint data.
@@ -225,6 +290,19 @@ impl AnalyticsProcessor {
         };
         self.writer.write_transaction(transaction_entry);
 
+        // move calls
+        for (package, module, function) in &move_calls {
+            self.writer.write_move_call(MoveCallEntry {
+                transaction_digest: transaction_digest.clone(),
+                checkpoint,
+                epoch,
+                timestamp_ms,
+                package: package.to_string(),
+                module: module.to_string(),
+                function: function.to_string(),
+            });
+        }
+
         // transaction objects
         txn_data
             .input_objects()
@@ -273,6 +351,180 @@ impl AnalyticsProcessor {
                     &object_status_tracker,
                 )
             });
+
+        // packages and dynamic fields
+        for object in &checkpoint_transaction.output_objects {
+            self.process_package(
+                epoch,
+                checkpoint,
+                timestamp_ms,
+                &transaction_digest,
+                object,
+                &object_status_tracker,
+            );
+            self.process_dynamic_field(
+                epoch,
+                checkpoint,
+                timestamp_ms,
+                &transaction_digest,
+                object,
+                &object_status_tracker,
+            );
+        }
+
+        // balance changes
+        self.process_balance_changes(
+            epoch,
+            checkpoint,
+            timestamp_ms,
+            &transaction_digest,
+            checkpoint_transaction,
+        );
+    }
+
+    // Package data. Only packages created by the transaction are recorded, which covers
+    // both publish and upgrade.
+    fn process_package(
+        &mut self,
+        epoch: u64,
+        checkpoint: u64,
+        timestamp_ms: u64,
+        transaction_digest: &str,
+        object: &Object,
+        object_status_tracker: &ObjectStatusTracker,
+    ) {
+        let Some(package) = object.data.try_as_package() else {
+            return;
+        };
+        if !object_status_tracker.is_created(&package.id()) {
+            return;
+        }
+        let modules = package
+            .serialized_module_map()
+            .keys()
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        let entry = MovePackageEntry {
+            package_id: package.id().to_string(),
+            checkpoint,
+            epoch,
+            timestamp_ms,
+            package_version: package.version().value(),
+            original_package_id: package.original_package_id().to_string(),
+            transaction_digest: transaction_digest.to_owned(),
+            modules,
+            bcs: Base64::encode(bcs::to_bytes(package).unwrap()),
+        };
+        self.writer.write_move_package(entry);
+    }
+
+    // Dynamic field data. Only called for objects written by the transaction, so deleted
+    // fields are not recorded.
+    fn process_dynamic_field(
+        &mut self,
+        epoch: u64,
+        checkpoint: u64,
+        timestamp_ms: u64,
+        transaction_digest: &str,
+        object: &Object,
+        object_status_tracker: &ObjectStatusTracker,
+    ) {
+        let Some(move_obj) = object.data.try_as_move() else {
+            return;
+        };
+        if !move_obj.type_().is_dynamic_field() {
+            return;
+        }
+        let Owner::ObjectOwner(parent) = object.owner else {
+            error!("Dynamic field {} is not owned by an object", object.id());
+            return;
+        };
+        // A dynamic object field stores `Field<Wrapper<Name>, ID>`, the object in the field
+        // is the ID at the end of the field contents.
+        let is_dynamic_object = matches!(
+            move_obj.type_().type_params().first(),
+            Some(TypeTag::Struct(tag)) if DynamicFieldInfo::is_dynamic_object_field_wrapper(tag)
+        );
+        let (dynamic_field_type, child_object_id) = if is_dynamic_object {
+            let contents = move_obj.contents();
+            let child = contents
+                .len()
+                .checked_sub(ObjectID::LENGTH)
+                .and_then(|start| ObjectID::from_bytes(&contents[start..]).ok());
+            (DynamicFieldType::DynamicObject, child)
+        } else {
+            (DynamicFieldType::DynamicField, None)
+        };
+        let name_type = match move_obj.type_().try_extract_field_name(&dynamic_field_type) {
+            Ok(name_type) => name_type,
+            Err(e) => {
+                error!("Cannot get name type of dynamic field {}: {e}", object.id());
+                return;
+            }
+        };
+        let entry = DynamicFieldEntry {
+            parent_object_id: parent.to_string(),
+            field_object_id: object.id().to_string(),
+            version: object.version().value(),
+            transaction_digest: transaction_digest.to_owned(),
+            checkpoint,
+            epoch,
+            timestamp_ms,
+            dynamic_field_type: dynamic_field_type.to_string(),
+            name_type: name_type.to_string(),
+            child_object_id: child_object_id.map(|id| id.to_string()),
+            object_status: object_status_tracker
+                .get_object_status(&object.id())
+                .expect("Object must be in output objects")
+                .to_string(),
+        };
+        self.writer.write_dynamic_field(entry);
+    }
+
+    // Coin balance changes. Sums the balance of coins owned by each address, per coin type,
+    // before and after the transaction and records the difference.
+    fn process_balance_changes(
+        &mut self,
+        epoch: u64,
+        checkpoint: u64,
+        timestamp_ms: u64,
+        transaction_digest: &str,
+        checkpoint_transaction: &CheckpointTransaction,
+    ) {
+        let mut balances: BTreeMap<(String, String), i128> = BTreeMap::new();
+        let mut add_coins = |objects: &[Object], sign: i128| {
+            for object in objects {
+                let (Some(owner), Some(coin_type), Ok(Some(balance))) = (
+                    get_owner_address(object),
+                    object.coin_type_maybe(),
+                    Coin::extract_balance_if_coin(object),
+                ) else {
+                    continue;
+                };
+                *balances
+                    .entry((owner, coin_type.to_canonical_string()))
+                    .or_default() += sign * balance as i128;
+            }
+        };
+        add_coins(&checkpoint_transaction.input_objects, -1);
+        add_coins(&checkpoint_transaction.output_objects, 1);
+
+        for ((owner, coin_type), amount) in balances {
+            if amount == 0 {
+                continue;
+            }
+            let entry = BalanceChangeEntry {
+                transaction_digest: transaction_digest.to_owned(),
+                checkpoint,
+                epoch,
+                timestamp_ms,
+                owner,
+                coin_type,
+                amount: amount.to_string(),
+            };
+            self.writer.write_balance_change(entry);
+        }
     }
 
     // Events data. Only called if there are events in the transaction.
@@ -332,11 +584,12 @@ impl AnalyticsProcessor {
             checkpoint,
             epoch,
             timestamp_ms,
-            owner_type: get_owner_type(object),
+            owner_type: get_owner_type(object).to_string(),
             owner_address: get_owner_address(object),
             object_status: object_status_tracker
                 .get_object_status(&object_id)
-                .expect("Object must be in output objects"),
+                .expect("Object must be in output objects")
+                .to_string(),
             initial_shared_version: initial_shared_version(object),
             previous_transaction: object.previous_transaction.base58_encode(),
             has_public_transfer,
@@ -366,36 +619,138 @@ impl AnalyticsProcessor {
             checkpoint,
             epoch,
             timestamp_ms,
-            input_kind: input_object_tracker.get_input_object_kind(object_id),
-            object_status: object_status_tracker.get_object_status(object_id),
+            input_kind: input_object_tracker
+                .get_input_object_kind(object_id)
+                .map(|kind| kind.to_string()),
+            object_status: object_status_tracker
+                .get_object_status(object_id)
+                .map(|status| status.to_string()),
         };
         self.writer.write_transaction_object(entry);
     }
 
     // Ensure a writer is in place
-    fn check_writer(&mut self, checkpoint: u64) -> Result<(), AnalyticsIndexerError> {
+    fn check_writer(&mut self, checkpoint: u64) -> anyhow::Result<()> {
         if self.data_writer.is_none() {
-            info!("Create csv writer at checkpoint {checkpoint}");
+            info!(
+                "Create {:?} writer at checkpoint {checkpoint}",
+                self.config.file_format
+            );
             self.start_checkpoint_to_commit = checkpoint;
-            self.data_writer = Some(CSVWriter::new(&self.config, checkpoint)?);
+            self.start_time_to_commit = Instant::now();
+            let data_writer: Box<dyn TableWriter> = match self.config.file_format {
+                FileFormat::Csv => Box::new(CSVWriter::new(&self.checkpoint_dir, checkpoint)?),
+                FileFormat::Parquet => {
+                    Box::new(ParquetWriter::new(&self.checkpoint_dir, checkpoint)?)
+                }
+            };
+            self.data_writer = Some(data_writer);
         }
         Ok(())
     }
 
-    // Write entries to files if so desired
-    fn save_locally(&mut self, _checkpoint: u64) {
-        info!("Save data locally");
-        // check some condition if we do not want to flush every checkpoint
-        self.writer.flush(self.data_writer.as_mut().unwrap());
+    // Write entries to files once enough of them have been collected
+    fn save_locally(&mut self, _checkpoint: u64) -> anyhow::Result<()> {
+        if self.writer.entry_count() >= MAX_BUFFERED_ENTRIES {
+            info!("Save data locally");
+            self.writer
+                .flush(self.data_writer.as_deref_mut().unwrap())?;
+        }
+        Ok(())
     }
 
-    // Upload to datastore if desired.
+    // Upload to datastore once the files cover enough checkpoints, have grown large enough, or
+    // have been written to for long enough.
     // Rotate writer and notify upload thread.
-    fn upload_to_datastore(&mut self, checkpoint: u64) {
-        if checkpoint - self.start_checkpoint_to_commit > self.config.checkpoint_interval {
-            info!("Upload to datastore and rotate writer");
-            let _data_writer = self.data_writer.take().unwrap();
-            // notify upload thread
+    async fn upload_to_datastore(&mut self, checkpoint: u64) -> anyhow::Result<()> {
+        let file_size = self.data_writer.as_ref().unwrap().file_size()?;
+        let checkpoints = checkpoint + 1 - self.start_checkpoint_to_commit;
+        let should_commit = checkpoints >= self.config.checkpoint_interval
+            || file_size >= self.config.max_file_size_mb * 1024 * 1024
+            || self.start_time_to_commit.elapsed()
+                >= Duration::from_secs(self.config.time_interval_s);
+        if !should_commit {
+            return Ok(());
+        }
+
+        info!("Upload to datastore and rotate writer");
+        let mut data_writer = self.data_writer.take().unwrap();
+        self.writer.flush(data_writer.as_mut())?;
+        let files = data_writer.close(checkpoint + 1)?;
+        if let Some(sender) = &self.upload_sender {
+            if sender.send(files).await.is_err() {
+                // The upload task has stopped, so report why.
+                if let Some(upload_task) = self.upload_task.take() {
+                    upload_task.await??;
+                }
+                anyhow::bail!("Upload task stopped");
+            }
+        }
+        Ok(())
+    }
+}
+
+// Copies finished files from the checkpoint directory to the remote store, removing them locally
+// once they have been uploaded. Files keep their path relative to the checkpoint directory.
+// Uploads are retried with backoff, and the task fails if a file cannot be uploaded after
+// `MAX_UPLOAD_ATTEMPTS` attempts.
+async fn upload_files(
+    checkpoint_dir: PathBuf,
+    local_store: Arc<DynObjectStore>,
+    remote_store: Arc<DynObjectStore>,
+    mut receiver: mpsc::Receiver<Vec<PathBuf>>,
+) -> anyhow::Result<()> {
+    while let Some(files) = receiver.recv().await {
+        for file in files {
+            let location = file
+                .strip_prefix(&checkpoint_dir)
+                .ok()
+                .and_then(|relative| relative.to_str())
+                .map(Path::from)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "File {} is outside the checkpoint directory",
+                        file.display()
+                    )
+                })?;
+            upload_file(location, local_store.clone(), remote_store.clone())
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to upload {}: {e}", file.display()))?;
+            info!("Uploaded {}", file.display());
+            if let Err(e) = fs::remove_file(&file) {
+                error!("Failed to remove uploaded file {}: {e}", file.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn upload_file(
+    location: Path,
+    local_store: Arc<DynObjectStore>,
+    remote_store: Arc<DynObjectStore>,
+) -> Result<(), object_store::Error> {
+    let mut delay = INITIAL_UPLOAD_RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        match copy_file(
+            location.clone(),
+            location.clone(),
+            local_store.clone(),
+            remote_store.clone(),
+        )
+        .await
+        {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt < MAX_UPLOAD_ATTEMPTS => {
+                warn!(
+                    "Failed to upload {location} on attempt {attempt}, retrying in {delay:?}: {e}"
+                );
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
         }
     }
 }
@@ -465,15 +820,19 @@ impl ObjectStatusTracker {
 
     fn get_object_status(&self, object_id: &ObjectID) -> Option<ObjectStatus> {
         if self.mutated.contains(object_id) {
-            Some(ObjectStatus::Created)
-        } else if self.deleted.contains(object_id) {
             Some(ObjectStatus::Mutated)
-        } else if self.created.contains(object_id) {
+        } else if self.deleted.contains(object_id) {
             Some(ObjectStatus::Deleted)
+        } else if self.created.contains(object_id) {
+            Some(ObjectStatus::Created)
         } else {
             None
         }
     }
+
+    fn is_created(&self, object_id: &ObjectID) -> bool {
+        self.created.contains(object_id)
+    }
 }
 
 // Helper class to track input object kind.
@@ -519,3 +878,364 @@ impl InputObjectTracker {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use move_binary_format::file_format::empty_module;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+    use prometheus::Registry;
+    use serde_json::{json, Value};
+    use sui_types::base_types::{SequenceNumber, SuiAddress};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::dynamic_field::DynamicFieldInfo;
+    use sui_types::execution_status::ExecutionStatus;
+    use sui_types::gas::GasCostSummary;
+    use sui_types::object::MoveObject;
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::transaction::TransactionData;
+    use sui_types::utils::to_sender_signed_transaction;
+    use sui_types::Identifier;
+
+    use super::*;
+    use crate::{
+        analytics_metrics::AnalyticsMetrics,
+        tables::{
+            CheckpointEntry, DynamicFieldEntry, EventEntry, MovePackageEntry, ObjectEntry,
+            TransactionEntry, TransactionObjectEntry,
+        },
+    };
+
+    // Records the entries written to each table as JSON, so tests can inspect them.
+    #[derive(Default)]
+    struct RecordingWriter {
+        tables: BTreeMap<&'static str, Vec<Value>>,
+    }
+
+    impl RecordingWriter {
+        fn record<T: serde::Serialize>(&mut self, table: &'static str, entries: &[T]) {
+            let rows = self.tables.entry(table).or_default();
+            rows.extend(entries.iter().map(|e| serde_json::to_value(e).unwrap()));
+        }
+
+        fn table(&self, table: &str) -> &[Value] {
+            self.tables.get(table).map_or(&[], |rows| rows.as_slice())
+        }
+    }
+
+    impl TableWriter for RecordingWriter {
+        fn write_checkpoints(&mut self, entries: &[CheckpointEntry]) -> anyhow::Result<()> {
+            self.record("checkpoints", entries);
+            Ok(())
+        }
+
+        fn write_transactions(&mut self, entries: &[TransactionEntry]) -> anyhow::Result<()> {
+            self.record("transactions", entries);
+            Ok(())
+        }
+
+        fn write_transaction_objects(
+            &mut self,
+            entries: &[TransactionObjectEntry],
+        ) -> anyhow::Result<()> {
+            self.record("transaction_objects", entries);
+            Ok(())
+        }
+
+        fn write_objects(&mut self, entries: &[ObjectEntry]) -> anyhow::Result<()> {
+            self.record("objects", entries);
+            Ok(())
+        }
+
+        fn write_events(&mut self, entries: &[EventEntry]) -> anyhow::Result<()> {
+            self.record("events", entries);
+            Ok(())
+        }
+
+        fn write_move_calls(&mut self, entries: &[MoveCallEntry]) -> anyhow::Result<()> {
+            self.record("move_calls", entries);
+            Ok(())
+        }
+
+        fn write_move_packages(&mut self, entries: &[MovePackageEntry]) -> anyhow::Result<()> {
+            self.record("move_packages", entries);
+            Ok(())
+        }
+
+        fn write_dynamic_fields(&mut self, entries: &[DynamicFieldEntry]) -> anyhow::Result<()> {
+            self.record("dynamic_fields", entries);
+            Ok(())
+        }
+
+        fn write_balance_changes(&mut self, entries: &[BalanceChangeEntry]) -> anyhow::Result<()> {
+            self.record("balance_changes", entries);
+            Ok(())
+        }
+
+        fn file_size(&self) -> anyhow::Result<u64> {
+            Ok(0)
+        }
+
+        fn close(self: Box<Self>, _end_checkpoint: u64) -> anyhow::Result<Vec<PathBuf>> {
+            Ok(vec![])
+        }
+    }
+
+    fn file_store(directory: &std::path::Path) -> Arc<DynObjectStore> {
+        ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(directory.to_path_buf()),
+            ..Default::default()
+        }
+        .make()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_upload_parquet_to_local_store() {
+        let checkpoint_dir = tempfile::tempdir().unwrap();
+        let remote_dir = tempfile::tempdir().unwrap();
+
+        let move_calls = ["mint", "burn"].map(|function| MoveCallEntry {
+            transaction_digest: TransactionDigest::random().to_string(),
+            checkpoint: 3,
+            epoch: 0,
+            timestamp_ms: 1_000,
+            package: ObjectID::from_single_byte(2).to_string(),
+            module: "coin".to_string(),
+            function: function.to_string(),
+        });
+        let mut data_writer = Box::new(ParquetWriter::new(checkpoint_dir.path(), 3).unwrap());
+        data_writer.write_move_calls(&move_calls).unwrap();
+        let files = data_writer.close(5).unwrap();
+
+        let (sender, receiver) = mpsc::channel(1);
+        let upload_task = tokio::spawn(upload_files(
+            checkpoint_dir.path().to_path_buf(),
+            file_store(checkpoint_dir.path()),
+            file_store(remote_dir.path()),
+            receiver,
+        ));
+        sender.send(files.clone()).await.unwrap();
+        drop(sender);
+        upload_task.await.unwrap().unwrap();
+
+        // Uploaded files are removed locally.
+        assert!(files.iter().all(|file| !file.exists()));
+
+        let uploaded = remote_dir.path().join("move_calls").join("3_5.parquet");
+        let reader = SerializedFileReader::new(fs::File::open(uploaded).unwrap()).unwrap();
+        let rows = reader
+            .get_row_iter(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rows.len(), move_calls.len());
+        for (row, entry) in rows.iter().zip(&move_calls) {
+            assert_eq!(row.get_string(0).unwrap(), &entry.transaction_digest);
+            assert_eq!(row.get_string(6).unwrap(), &entry.function);
+        }
+
+        // Tables without entries are still uploaded, as empty files.
+        assert!(remote_dir
+            .path()
+            .join("checkpoints")
+            .join("3_5.parquet")
+            .exists());
+    }
+
+    // A dynamic field object owned by `parent`, holding `value` of type `value_type`.
+    fn dynamic_field(
+        id: ObjectID,
+        parent: ObjectID,
+        name_type: TypeTag,
+        value_type: TypeTag,
+        value: Vec<u8>,
+    ) -> Object {
+        let type_ = DynamicFieldInfo::dynamic_field_type(name_type, value_type);
+        // The field's UID, then its name, a u64 in every field made by these tests.
+        let mut contents = id.to_vec();
+        contents.extend_from_slice(&[0; 8]);
+        contents.extend(value);
+        let move_object = unsafe {
+            MoveObject::new_from_execution_with_limit(
+                type_.into(),
+                false,
+                SequenceNumber::from(2),
+                contents,
+                u64::MAX,
+            )
+            .unwrap()
+        };
+        Object::new_move(
+            move_object,
+            Owner::ObjectOwner(parent.into()),
+            TransactionDigest::genesis(),
+        )
+    }
+
+    #[test]
+    fn test_move_call_package_dynamic_field_and_balance_change_entries() {
+        let checkpoint_dir = tempfile::tempdir().unwrap();
+        let config = AnalyticsIndexerConfig::parse_from([
+            "sui-analytics-indexer",
+            "--rest-url",
+            "http://127.0.0.1:9000",
+            "--checkpoint-dir",
+            checkpoint_dir.path().to_str().unwrap(),
+        ]);
+        let mut processor =
+            AnalyticsProcessor::new(config, AnalyticsMetrics::new(&Registry::new())).unwrap();
+
+        let (sender, key): (_, AccountKeyPair) = get_key_pair();
+        let recipient = SuiAddress::random_for_testing_only();
+        let gas_id = ObjectID::random();
+        let gas_in = Object::with_id_owner_gas_for_testing(gas_id, sender, 1_000);
+        let gas_out = Object::with_id_owner_gas_for_testing(gas_id, sender, 700);
+
+        // The recipient ends up with more than an i64 can hold.
+        let coins = [ObjectID::random(), ObjectID::random()]
+            .map(|id| Object::with_id_owner_gas_for_testing(id, recipient, u64::MAX));
+
+        let package_id = ObjectID::random();
+        let modules = ["coin", "nft"].map(|name| {
+            let mut module = empty_module();
+            module.address_identifiers[0] = package_id.into();
+            module.identifiers[0] = Identifier::new(name).unwrap();
+            module
+        });
+        let package =
+            Object::new_package_for_testing(&modules, TransactionDigest::genesis(), []).unwrap();
+
+        let parent = ObjectID::random();
+        let child = ObjectID::random();
+        let field = dynamic_field(
+            ObjectID::random(),
+            parent,
+            TypeTag::U64,
+            TypeTag::U64,
+            42u64.to_le_bytes().to_vec(),
+        );
+        let object_field = dynamic_field(
+            ObjectID::random(),
+            parent,
+            TypeTag::Struct(Box::new(DynamicFieldInfo::dynamic_object_field_wrapper(
+                TypeTag::U64,
+            ))),
+            TypeTag::Struct(Box::new(sui_types::id::ID::type_())),
+            child.to_vec(),
+        );
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.programmable_move_call(
+            package_id,
+            Identifier::new("nft").unwrap(),
+            Identifier::new("mint").unwrap(),
+            vec![],
+            vec![],
+        );
+        let data = TransactionData::new_programmable(
+            sender,
+            vec![gas_in.compute_object_reference()],
+            builder.finish(),
+            1_000,
+            1,
+        );
+        let transaction = to_sender_signed_transaction(data, &key);
+
+        let created = coins
+            .iter()
+            .chain([&package, &field, &object_field])
+            .map(|o| (o.compute_object_reference(), o.owner))
+            .collect();
+        let gas = (gas_out.compute_object_reference(), gas_out.owner);
+        let effects = TransactionEffects::new_from_execution(
+            1.into(),
+            ExecutionStatus::Success,
+            0,
+            GasCostSummary::default(),
+            vec![],
+            vec![],
+            *transaction.digest(),
+            created,
+            vec![gas],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            gas,
+            None,
+            vec![],
+        );
+        let digest = transaction.digest().base58_encode();
+
+        let mut output_objects = vec![gas_out, package, field.clone(), object_field.clone()];
+        output_objects.extend(coins);
+        let checkpoint_transaction = CheckpointTransaction {
+            transaction,
+            effects: effects.clone(),
+            events: None,
+            input_objects: vec![gas_in],
+            output_objects,
+        };
+        processor.process_transaction(3, 10, 1_000, &checkpoint_transaction, &effects);
+
+        let mut recorder = RecordingWriter::default();
+        processor.writer.flush(&mut recorder).unwrap();
+
+        assert_eq!(
+            recorder.table("move_calls"),
+            [json!({
+                "transaction_digest": digest,
+                "checkpoint": 10,
+                "epoch": 3,
+                "timestamp_ms": 1_000,
+                "package": package_id.to_string(),
+                "module": "nft",
+                "function": "mint",
+            })]
+        );
+
+        let packages = recorder.table("move_packages");
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0]["package_id"], json!(package_id.to_string()));
+        assert_eq!(
+            packages[0]["original_package_id"],
+            json!(package_id.to_string())
+        );
+        assert_eq!(packages[0]["package_version"], json!(1));
+        assert_eq!(packages[0]["modules"], json!("coin,nft"));
+
+        let fields = recorder.table("dynamic_fields");
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0]["field_object_id"], json!(field.id().to_string()));
+        assert_eq!(fields[0]["parent_object_id"], json!(parent.to_string()));
+        assert_eq!(fields[0]["dynamic_field_type"], json!("DynamicField"));
+        assert_eq!(fields[0]["name_type"], json!("u64"));
+        assert_eq!(fields[0]["child_object_id"], Value::Null);
+        assert_eq!(fields[0]["object_status"], json!("Created"));
+        assert_eq!(
+            fields[1]["field_object_id"],
+            json!(object_field.id().to_string())
+        );
+        assert_eq!(fields[1]["dynamic_field_type"], json!("DynamicObject"));
+        assert_eq!(fields[1]["child_object_id"], json!(child.to_string()));
+
+        let mut balance_changes: Vec<_> = recorder
+            .table("balance_changes")
+            .iter()
+            .map(|change| (change["owner"].clone(), change["amount"].clone()))
+            .collect();
+        balance_changes.sort_by_key(|(owner, _)| owner.to_string());
+        let mut expected = vec![
+            (json!(sender.to_string()), json!("-300")),
+            (
+                json!(recipient.to_string()),
+                json!((2 * u64::MAX as i128).to_string()),
+            ),
+        ];
+        expected.sort_by_key(|(owner, _)| owner.to_string());
+        assert_eq!(balance_changes, expected);
+    }
+}
@@ -3,13 +3,18 @@
 #![allow(dead_code)]
 
 use crate::{
-    errors::AnalyticsIndexerError,
-    tables::{CheckpointEntry, EventEntry, ObjectEntry, TransactionEntry, TransactionObjectEntry},
-    writer::TableWriter,
-    AnalyticsIndexerConfig,
+    tables::{
+        BalanceChangeEntry, CheckpointEntry, DynamicFieldEntry, EventEntry, MoveCallEntry,
+        MovePackageEntry, ObjectEntry, TransactionEntry, TransactionObjectEntry,
+    },
+    writer::{files_size, finish_table_file, table_file_path, TableWriter},
 };
+use anyhow::Result;
 use csv::Writer;
-use std::{env, fs::File, path::PathBuf};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
 
 // Save table entries to csv files.
 pub(crate) struct CSVWriter {
@@ -18,74 +23,173 @@ pub(crate) struct CSVWriter {
     transaction_object_csv: Writer<File>,
     object_csv: Writer<File>,
     event_csv: Writer<File>,
+    move_call_csv: Writer<File>,
+    move_package_csv: Writer<File>,
+    dynamic_field_csv: Writer<File>,
+    balance_change_csv: Writer<File>,
+    paths: Vec<PathBuf>,
 }
 use tracing::info;
 
 impl CSVWriter {
-    pub(crate) fn new(
-        config: &AnalyticsIndexerConfig,
-        starting_checkpoint: u64,
-    ) -> Result<Self, AnalyticsIndexerError> {
-        let dir = if let Some(checkpoint_dir) = &config.checkpoint_dir {
-            PathBuf::from(checkpoint_dir)
-        } else {
-            env::current_dir().map_err(|_| AnalyticsIndexerError::CurrentDirError)?
-        };
-        let dir = dir.as_path();
-        let checkpoint = dir.join(format!("checkpoint_{}.csv", starting_checkpoint));
-        let transaction = dir.join(format!("transaction_{}.csv", starting_checkpoint));
+    pub(crate) fn new(dir: &Path, starting_checkpoint: u64) -> Result<Self> {
+        let checkpoint = table_file_path(dir, "checkpoints", starting_checkpoint, "csv")?;
+        let transaction = table_file_path(dir, "transactions", starting_checkpoint, "csv")?;
         let transaction_object =
-            dir.join(format!("transaction_object_{}.csv", starting_checkpoint));
-        let object_csv = dir.join(format!("object_{}.csv", starting_checkpoint));
-        let event_csv = dir.join(format!("event_{}.csv", starting_checkpoint));
+            table_file_path(dir, "transaction_objects", starting_checkpoint, "csv")?;
+        let object_csv = table_file_path(dir, "objects", starting_checkpoint, "csv")?;
+        let event_csv = table_file_path(dir, "events", starting_checkpoint, "csv")?;
+        let move_call_csv = table_file_path(dir, "move_calls", starting_checkpoint, "csv")?;
+        let move_package_csv = table_file_path(dir, "move_packages", starting_checkpoint, "csv")?;
+        let dynamic_field_csv = table_file_path(dir, "dynamic_fields", starting_checkpoint, "csv")?;
+        let balance_change_csv =
+            table_file_path(dir, "balance_changes", starting_checkpoint, "csv")?;
         Ok(Self {
-            checkpoint_csv: Writer::from_path(checkpoint).unwrap(),
-            transaction_csv: Writer::from_path(transaction).unwrap(),
-            transaction_object_csv: Writer::from_path(transaction_object).unwrap(),
-            object_csv: Writer::from_path(object_csv).unwrap(),
-            event_csv: Writer::from_path(event_csv).unwrap(),
+            checkpoint_csv: Writer::from_path(&checkpoint)?,
+            transaction_csv: Writer::from_path(&transaction)?,
+            transaction_object_csv: Writer::from_path(&transaction_object)?,
+            object_csv: Writer::from_path(&object_csv)?,
+            event_csv: Writer::from_path(&event_csv)?,
+            move_call_csv: Writer::from_path(&move_call_csv)?,
+            move_package_csv: Writer::from_path(&move_package_csv)?,
+            dynamic_field_csv: Writer::from_path(&dynamic_field_csv)?,
+            balance_change_csv: Writer::from_path(&balance_change_csv)?,
+            paths: vec![
+                checkpoint,
+                transaction,
+                transaction_object,
+                object_csv,
+                event_csv,
+                move_call_csv,
+                move_package_csv,
+                dynamic_field_csv,
+                balance_change_csv,
+            ],
         })
     }
 }
 
 impl TableWriter for CSVWriter {
-    fn write_checkpoints(&mut self, checkpoint_entries: &[CheckpointEntry]) {
+    fn write_checkpoints(&mut self, checkpoint_entries: &[CheckpointEntry]) -> Result<()> {
         info!("Write checkpoints");
         for entry in checkpoint_entries {
-            self.checkpoint_csv.serialize(entry).unwrap();
+            self.checkpoint_csv.serialize(entry)?;
         }
-        self.checkpoint_csv.flush().unwrap();
+        self.checkpoint_csv.flush()?;
+        Ok(())
     }
 
-    fn write_transactions(&mut self, transaction_entries: &[TransactionEntry]) {
+    fn write_transactions(&mut self, transaction_entries: &[TransactionEntry]) -> Result<()> {
         info!("Write transactions");
         for entry in transaction_entries {
-            self.transaction_csv.serialize(entry).unwrap();
+            self.transaction_csv.serialize(entry)?;
         }
-        self.transaction_csv.flush().unwrap();
+        self.transaction_csv.flush()?;
+        Ok(())
     }
 
-    fn write_transaction_objects(&mut self, transaction_object_entries: &[TransactionObjectEntry]) {
+    fn write_transaction_objects(
+        &mut self,
+        transaction_object_entries: &[TransactionObjectEntry],
+    ) -> Result<()> {
         info!("Write transaction objects");
         for entry in transaction_object_entries {
-            self.transaction_object_csv.serialize(entry).unwrap();
+            self.transaction_object_csv.serialize(entry)?;
         }
-        self.transaction_object_csv.flush().unwrap();
+        self.transaction_object_csv.flush()?;
+        Ok(())
     }
 
-    fn write_objects(&mut self, object_entries: &[ObjectEntry]) {
+    fn write_objects(&mut self, object_entries: &[ObjectEntry]) -> Result<()> {
         info!("Write objects");
         for entry in object_entries {
-            self.object_csv.serialize(entry).unwrap();
+            self.object_csv.serialize(entry)?;
         }
-        self.object_csv.flush().unwrap();
+        self.object_csv.flush()?;
+        Ok(())
     }
 
-    fn write_events(&mut self, event_entries: &[EventEntry]) {
+    fn write_events(&mut self, event_entries: &[EventEntry]) -> Result<()> {
         info!("Write events");
         for entry in event_entries {
-            self.event_csv.serialize(entry).unwrap();
+            self.event_csv.serialize(entry)?;
+        }
+        self.event_csv.flush()?;
+        Ok(())
+    }
+
+    fn write_move_calls(&mut self, move_call_entries: &[MoveCallEntry]) -> Result<()> {
+        info!("Write move calls");
+        for entry in move_call_entries {
+            self.move_call_csv.serialize(entry)?;
+        }
+        self.move_call_csv.flush()?;
+        Ok(())
+    }
+
+    fn write_move_packages(&mut self, move_package_entries: &[MovePackageEntry]) -> Result<()> {
+        info!("Write move packages");
+        for entry in move_package_entries {
+            self.move_package_csv.serialize(entry)?;
+        }
+        self.move_package_csv.flush()?;
+        Ok(())
+    }
+
+    fn write_dynamic_fields(&mut self, dynamic_field_entries: &[DynamicFieldEntry]) -> Result<()> {
+        info!("Write dynamic fields");
+        for entry in dynamic_field_entries {
+            self.dynamic_field_csv.serialize(entry)?;
+        }
+        self.dynamic_field_csv.flush()?;
+        Ok(())
+    }
+
+    fn write_balance_changes(
+        &mut self,
+        balance_change_entries: &[BalanceChangeEntry],
+    ) -> Result<()> {
+        info!("Write balance changes");
+        for entry in balance_change_entries {
+            self.balance_change_csv.serialize(entry)?;
+        }
+        self.balance_change_csv.flush()?;
+        Ok(())
+    }
+
+    fn file_size(&self) -> Result<u64> {
+        files_size(&self.paths)
+    }
+
+    fn close(self: Box<Self>, end_checkpoint: u64) -> Result<Vec<PathBuf>> {
+        let Self {
+            checkpoint_csv,
+            transaction_csv,
+            transaction_object_csv,
+            object_csv,
+            event_csv,
+            move_call_csv,
+            move_package_csv,
+            dynamic_field_csv,
+            balance_change_csv,
+            paths,
+        } = *self;
+        for mut csv in [
+            checkpoint_csv,
+            transaction_csv,
+            transaction_object_csv,
+            object_csv,
+            event_csv,
+            move_call_csv,
+            move_package_csv,
+            dynamic_field_csv,
+            balance_change_csv,
+        ] {
+            csv.flush()?;
         }
-        self.event_csv.flush().unwrap();
+        paths
+            .iter()
+            .map(|path| finish_table_file(path, end_checkpoint))
+            .collect()
     }
 }
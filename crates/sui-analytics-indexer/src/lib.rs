@@ -2,14 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::*;
+use sui_storage::object_store::ObjectStoreConfig;
 
 pub mod analytics_handler;
 pub mod analytics_metrics;
 pub mod csv_writer;
 pub mod errors;
+pub mod parquet_writer;
 pub mod tables;
 pub mod writer;
 
+/// Format of the files that table entries are written to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum FileFormat {
+    Csv,
+    Parquet,
+}
+
 #[derive(Parser, Clone, Debug)]
 #[clap(
     name = "Sui Analytics Indexer",
@@ -37,4 +46,17 @@ pub struct AnalyticsIndexerConfig {
     /// Number of checkpoints to process before uploading to the datastore.
     #[clap(long, default_value = "30", global = true)]
     pub checkpoint_interval: u64,
+    /// Maximum size in MB of the files for a range of checkpoints, before they are uploaded.
+    #[clap(long, default_value = "100", global = true)]
+    pub max_file_size_mb: u64,
+    /// Maximum number of seconds to spend writing a range of checkpoints before uploading it.
+    #[clap(long, default_value = "600", global = true)]
+    pub time_interval_s: u64,
+    /// Format of the files that entries are written to.
+    #[clap(long, value_enum, default_value = "csv", global = true)]
+    pub file_format: FileFormat,
+    /// Where finished files are uploaded to. If no object store is given, files are left in the
+    /// checkpoint directory.
+    #[command(flatten)]
+    pub remote_store_config: ObjectStoreConfig,
 }
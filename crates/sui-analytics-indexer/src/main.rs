@@ -36,7 +36,7 @@ async fn main() -> Result<(), AnalyticsIndexerError> {
     //       or some stable source..
     let last_downloaded_checkpoint = config.starting_checkpoint;
     let rest_url = config.rest_url.clone();
-    let processor = AnalyticsProcessor::new(config, metrics);
+    let processor = AnalyticsProcessor::new(config, metrics)?;
     IndexerBuilder::new()
         .last_downloaded_checkpoint(last_downloaded_checkpoint)
         .rest_url(&rest_url)
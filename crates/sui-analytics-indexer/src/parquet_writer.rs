@@ -0,0 +1,181 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    tables::{
+        BalanceChangeEntry, CheckpointEntry, DynamicFieldEntry, EventEntry, MoveCallEntry,
+        MovePackageEntry, ObjectEntry, TransactionEntry, TransactionObjectEntry,
+    },
+    writer::{files_size, finish_table_file, table_file_path, TableWriter},
+};
+use anyhow::Result;
+use parquet::{
+    basic::Compression,
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    record::RecordWriter,
+};
+use std::{
+    fs::File,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+// Save table entries to parquet files, with a column for each field of the entry.
+// Every batch of entries written becomes a row group.
+pub(crate) struct ParquetWriter {
+    checkpoint_file: ParquetFile<CheckpointEntry>,
+    transaction_file: ParquetFile<TransactionEntry>,
+    transaction_object_file: ParquetFile<TransactionObjectEntry>,
+    object_file: ParquetFile<ObjectEntry>,
+    event_file: ParquetFile<EventEntry>,
+    move_call_file: ParquetFile<MoveCallEntry>,
+    move_package_file: ParquetFile<MovePackageEntry>,
+    dynamic_field_file: ParquetFile<DynamicFieldEntry>,
+    balance_change_file: ParquetFile<BalanceChangeEntry>,
+}
+
+// A parquet file holding the entries of one table.
+struct ParquetFile<T> {
+    path: PathBuf,
+    writer: SerializedFileWriter<File>,
+    _entry: PhantomData<T>,
+}
+
+impl ParquetWriter {
+    pub(crate) fn new(dir: &Path, starting_checkpoint: u64) -> Result<Self> {
+        Ok(Self {
+            checkpoint_file: ParquetFile::new(dir, "checkpoints", starting_checkpoint)?,
+            transaction_file: ParquetFile::new(dir, "transactions", starting_checkpoint)?,
+            transaction_object_file: ParquetFile::new(
+                dir,
+                "transaction_objects",
+                starting_checkpoint,
+            )?,
+            object_file: ParquetFile::new(dir, "objects", starting_checkpoint)?,
+            event_file: ParquetFile::new(dir, "events", starting_checkpoint)?,
+            move_call_file: ParquetFile::new(dir, "move_calls", starting_checkpoint)?,
+            move_package_file: ParquetFile::new(dir, "move_packages", starting_checkpoint)?,
+            dynamic_field_file: ParquetFile::new(dir, "dynamic_fields", starting_checkpoint)?,
+            balance_change_file: ParquetFile::new(dir, "balance_changes", starting_checkpoint)?,
+        })
+    }
+}
+
+impl<T> ParquetFile<T>
+where
+    for<'a> &'a [T]: RecordWriter<T>,
+{
+    fn new(dir: &Path, table: &str, starting_checkpoint: u64) -> Result<Self> {
+        let path = table_file_path(dir, table, starting_checkpoint, "parquet")?;
+        let entries: &[T] = &[];
+        let schema = entries.schema()?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let writer = SerializedFileWriter::new(File::create(&path)?, schema, Arc::new(properties))?;
+        Ok(Self {
+            path,
+            writer,
+            _entry: PhantomData,
+        })
+    }
+
+    fn write(&mut self, entries: &[T]) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut row_group = self.writer.next_row_group()?;
+        entries.write_to_row_group(&mut row_group)?;
+        row_group.close()?;
+        Ok(())
+    }
+
+    fn close(self, end_checkpoint: u64) -> Result<PathBuf> {
+        self.writer.close()?;
+        finish_table_file(&self.path, end_checkpoint)
+    }
+}
+
+impl TableWriter for ParquetWriter {
+    fn write_checkpoints(&mut self, checkpoint_entries: &[CheckpointEntry]) -> Result<()> {
+        info!("Write checkpoints");
+        self.checkpoint_file.write(checkpoint_entries)
+    }
+
+    fn write_transactions(&mut self, transaction_entries: &[TransactionEntry]) -> Result<()> {
+        info!("Write transactions");
+        self.transaction_file.write(transaction_entries)
+    }
+
+    fn write_transaction_objects(
+        &mut self,
+        transaction_object_entries: &[TransactionObjectEntry],
+    ) -> Result<()> {
+        info!("Write transaction objects");
+        self.transaction_object_file
+            .write(transaction_object_entries)
+    }
+
+    fn write_objects(&mut self, object_entries: &[ObjectEntry]) -> Result<()> {
+        info!("Write objects");
+        self.object_file.write(object_entries)
+    }
+
+    fn write_events(&mut self, event_entries: &[EventEntry]) -> Result<()> {
+        info!("Write events");
+        self.event_file.write(event_entries)
+    }
+
+    fn write_move_calls(&mut self, move_call_entries: &[MoveCallEntry]) -> Result<()> {
+        info!("Write move calls");
+        self.move_call_file.write(move_call_entries)
+    }
+
+    fn write_move_packages(&mut self, move_package_entries: &[MovePackageEntry]) -> Result<()> {
+        info!("Write move packages");
+        self.move_package_file.write(move_package_entries)
+    }
+
+    fn write_dynamic_fields(&mut self, dynamic_field_entries: &[DynamicFieldEntry]) -> Result<()> {
+        info!("Write dynamic fields");
+        self.dynamic_field_file.write(dynamic_field_entries)
+    }
+
+    fn write_balance_changes(
+        &mut self,
+        balance_change_entries: &[BalanceChangeEntry],
+    ) -> Result<()> {
+        info!("Write balance changes");
+        self.balance_change_file.write(balance_change_entries)
+    }
+
+    fn file_size(&self) -> Result<u64> {
+        files_size([
+            &self.checkpoint_file.path,
+            &self.transaction_file.path,
+            &self.transaction_object_file.path,
+            &self.object_file.path,
+            &self.event_file.path,
+            &self.move_call_file.path,
+            &self.move_package_file.path,
+            &self.dynamic_field_file.path,
+            &self.balance_change_file.path,
+        ])
+    }
+
+    fn close(self: Box<Self>, end_checkpoint: u64) -> Result<Vec<PathBuf>> {
+        Ok(vec![
+            self.checkpoint_file.close(end_checkpoint)?,
+            self.transaction_file.close(end_checkpoint)?,
+            self.transaction_object_file.close(end_checkpoint)?,
+            self.object_file.close(end_checkpoint)?,
+            self.event_file.close(end_checkpoint)?,
+            self.move_call_file.close(end_checkpoint)?,
+            self.move_package_file.close(end_checkpoint)?,
+            self.dynamic_field_file.close(end_checkpoint)?,
+            self.balance_change_file.close(end_checkpoint)?,
+        ])
+    }
+}
@@ -2,16 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 #![allow(dead_code)]
 
+use parquet_derive::ParquetRecordWriter;
 use serde::Serialize;
+use strum_macros::Display;
 // use std::collections::BTreeSet;
 
 //
 // Table entries for the analytics database.
 // Each entry is a row in the database.
+// Enums are stored by the name of their variant, so columns only hold primitive types.
 //
 
 // Checkpoint information.
-#[derive(Serialize)]
+#[derive(Serialize, ParquetRecordWriter)]
 pub(crate) struct CheckpointEntry {
     // indexes
     pub(crate) checkpoint_digest: String,
@@ -37,7 +40,7 @@ pub(crate) struct CheckpointEntry {
 }
 
 // Transaction information.
-#[derive(Serialize)]
+#[derive(Serialize, ParquetRecordWriter)]
 pub(crate) struct TransactionEntry {
     // main indexes
     pub(crate) transaction_digest: String,
@@ -84,7 +87,7 @@ pub(crate) struct TransactionEntry {
 
 // Event information.
 // Events identity is via `transaction_digest` and `event_index`.
-#[derive(Serialize)]
+#[derive(Serialize, ParquetRecordWriter)]
 pub(crate) struct EventEntry {
     // indexes
     pub(crate) transaction_digest: String,
@@ -106,7 +109,7 @@ pub(crate) struct EventEntry {
 }
 
 // Used in the transaction object table to identify the type of input object.
-#[derive(Serialize)]
+#[derive(Serialize, Display)]
 pub enum InputObjectKind {
     Input,
     SharedInput,
@@ -115,7 +118,7 @@ pub enum InputObjectKind {
 
 // Used in the object table to identify the status of object, its result in the last transaction
 // effect.
-#[derive(Serialize)]
+#[derive(Serialize, Display)]
 pub enum ObjectStatus {
     Created,
     Mutated,
@@ -123,7 +126,7 @@ pub enum ObjectStatus {
 }
 
 // Object owner information.
-#[derive(Serialize)]
+#[derive(Serialize, Display)]
 pub enum OwnerType {
     AddressOwner,
     ObjectOwner,
@@ -133,7 +136,7 @@ pub enum OwnerType {
 
 // Object information.
 // A row in the live object table.
-#[derive(Serialize)]
+#[derive(Serialize, ParquetRecordWriter)]
 pub(crate) struct ObjectEntry {
     // indexes
     pub(crate) object_id: String,
@@ -144,10 +147,10 @@ pub(crate) struct ObjectEntry {
     pub(crate) epoch: u64,
     pub(crate) timestamp_ms: u64,
     // owner info
-    pub(crate) owner_type: OwnerType,
+    pub(crate) owner_type: String,
     pub(crate) owner_address: Option<String>,
     // object info
-    pub(crate) object_status: ObjectStatus,
+    pub(crate) object_status: String,
     pub(crate) initial_shared_version: Option<u64>,
     pub(crate) previous_transaction: String,
     pub(crate) has_public_transfer: bool,
@@ -164,7 +167,7 @@ pub(crate) struct ObjectEntry {
 // input kind (for input objects) and status (for objets in effects).
 // An object may appear twice as an input and output object. In that case, the
 // version will be different.
-#[derive(Serialize)]
+#[derive(Serialize, ParquetRecordWriter)]
 pub(crate) struct TransactionObjectEntry {
     // indexes
     pub(crate) object_id: String,
@@ -174,6 +177,76 @@ pub(crate) struct TransactionObjectEntry {
     pub(crate) epoch: u64,
     pub(crate) timestamp_ms: u64,
     // input/output information
-    pub(crate) input_kind: Option<InputObjectKind>,
-    pub(crate) object_status: Option<ObjectStatus>,
+    pub(crate) input_kind: Option<String>,
+    pub(crate) object_status: Option<String>,
+}
+
+// Move calls made by a transaction, one row per call.
+#[derive(Serialize, ParquetRecordWriter)]
+pub(crate) struct MoveCallEntry {
+    // indexes
+    pub(crate) transaction_digest: String,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // call target
+    pub(crate) package: String,
+    pub(crate) module: String,
+    pub(crate) function: String,
+}
+
+// Packages published or upgraded by a transaction.
+#[derive(Serialize, ParquetRecordWriter)]
+pub(crate) struct MovePackageEntry {
+    // indexes
+    pub(crate) package_id: String,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // package info
+    pub(crate) package_version: u64,
+    // the id of the first version of the package, same as `package_id` when it is first published
+    pub(crate) original_package_id: String,
+    pub(crate) transaction_digest: String,
+    // commas separated list of the names of the modules in the package.
+    pub(crate) modules: String,
+    // raw package bytes
+    // We represent them in base64 encoding so they work with the csv.
+    pub(crate) bcs: String,
+}
+
+// Dynamic fields created or mutated by a transaction.
+// The parent is the object the field is attached to. For dynamic object fields the child is the
+// object stored in the field.
+#[derive(Serialize, ParquetRecordWriter)]
+pub(crate) struct DynamicFieldEntry {
+    // indexes
+    pub(crate) parent_object_id: String,
+    pub(crate) field_object_id: String,
+    pub(crate) version: u64,
+    pub(crate) transaction_digest: String,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // field info
+    pub(crate) dynamic_field_type: String,
+    pub(crate) name_type: String,
+    pub(crate) child_object_id: Option<String>,
+    pub(crate) object_status: String,
+}
+
+// Net change to the balance of a coin type owned by an address, over a transaction.
+#[derive(Serialize, ParquetRecordWriter)]
+pub(crate) struct BalanceChangeEntry {
+    // indexes
+    pub(crate) transaction_digest: String,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // balance change
+    pub(crate) owner: String,
+    pub(crate) coin_type: String,
+    // Signed change in the coin's smallest unit, as a decimal string because the sum of several
+    // u64 balances does not always fit in an i64.
+    pub(crate) amount: String,
 }
@@ -2,19 +2,78 @@
 // SPDX-License-Identifier: Apache-2.0
 #![allow(dead_code)]
 
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
 use crate::tables::{
-    CheckpointEntry, EventEntry, ObjectEntry, TransactionEntry, TransactionObjectEntry,
+    BalanceChangeEntry, CheckpointEntry, DynamicFieldEntry, EventEntry, MoveCallEntry,
+    MovePackageEntry, ObjectEntry, TransactionEntry, TransactionObjectEntry,
 };
 
-// Trait for writing entries to a temporary store (e.g. csv files).
+// Trait for writing entries to a temporary store (e.g. csv or parquet files).
 // The entries are collected and written in batches.
 // Eventually, they are uploaded to the database.
-pub(crate) trait TableWriter {
-    fn write_checkpoints(&mut self, checkpoint_entries: &[CheckpointEntry]);
-    fn write_transactions(&mut self, transaction_entries: &[TransactionEntry]);
-    fn write_transaction_objects(&mut self, transaction_object_entries: &[TransactionObjectEntry]);
-    fn write_objects(&mut self, object_entries: &[ObjectEntry]);
-    fn write_events(&mut self, event_entries: &[EventEntry]);
+pub(crate) trait TableWriter: Send {
+    fn write_checkpoints(&mut self, checkpoint_entries: &[CheckpointEntry]) -> Result<()>;
+    fn write_transactions(&mut self, transaction_entries: &[TransactionEntry]) -> Result<()>;
+    fn write_transaction_objects(
+        &mut self,
+        transaction_object_entries: &[TransactionObjectEntry],
+    ) -> Result<()>;
+    fn write_objects(&mut self, object_entries: &[ObjectEntry]) -> Result<()>;
+    fn write_events(&mut self, event_entries: &[EventEntry]) -> Result<()>;
+    fn write_move_calls(&mut self, move_call_entries: &[MoveCallEntry]) -> Result<()>;
+    fn write_move_packages(&mut self, move_package_entries: &[MovePackageEntry]) -> Result<()>;
+    fn write_dynamic_fields(&mut self, dynamic_field_entries: &[DynamicFieldEntry]) -> Result<()>;
+    fn write_balance_changes(
+        &mut self,
+        balance_change_entries: &[BalanceChangeEntry],
+    ) -> Result<()>;
+    // Total size in bytes of the files written so far.
+    fn file_size(&self) -> Result<u64>;
+    // Finish writing the files, and name them after the range of checkpoints they contain, which
+    // ends (exclusively) at `end_checkpoint`. Returns the paths of the finished files.
+    fn close(self: Box<Self>, end_checkpoint: u64) -> Result<Vec<PathBuf>>;
+}
+
+// Path of the file that a table's entries are written to, starting at `starting_checkpoint`.
+pub(crate) fn table_file_path(
+    dir: &Path,
+    table: &str,
+    starting_checkpoint: u64,
+    extension: &str,
+) -> Result<PathBuf> {
+    let table_dir = dir.join(table);
+    fs::create_dir_all(&table_dir)?;
+    Ok(table_dir.join(format!("{starting_checkpoint}.{extension}.tmp")))
+}
+
+// Renames a finished table file so it covers `[starting_checkpoint, end_checkpoint)`, which is
+// also the path it is uploaded to, relative to the checkpoint directory.
+pub(crate) fn finish_table_file(path: &Path, end_checkpoint: u64) -> Result<PathBuf> {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_suffix(".tmp"))
+        .ok_or_else(|| anyhow::anyhow!("Unexpected table file name: {}", path.display()))?;
+    let (starting_checkpoint, extension) = name
+        .split_once('.')
+        .ok_or_else(|| anyhow::anyhow!("Unexpected table file name: {}", path.display()))?;
+    let finished = path.with_file_name(format!(
+        "{starting_checkpoint}_{end_checkpoint}.{extension}"
+    ));
+    fs::rename(path, &finished)?;
+    Ok(finished)
+}
+
+// Total size in bytes of the given files.
+pub(crate) fn files_size<'a>(paths: impl IntoIterator<Item = &'a PathBuf>) -> Result<u64> {
+    paths
+        .into_iter()
+        .map(|path| Ok(fs::metadata(path)?.len()))
+        .sum()
 }
 
 const INITIAL_CAPACITY: usize = 10_000;
@@ -30,6 +89,10 @@ pub(crate) struct CheckpointWriter {
     transaction_object_entries: Vec<TransactionObjectEntry>,
     object_entries: Vec<ObjectEntry>,
     event_entries: Vec<EventEntry>,
+    move_call_entries: Vec<MoveCallEntry>,
+    move_package_entries: Vec<MovePackageEntry>,
+    dynamic_field_entries: Vec<DynamicFieldEntry>,
+    balance_change_entries: Vec<BalanceChangeEntry>,
 }
 
 impl CheckpointWriter {
@@ -40,21 +103,47 @@ impl CheckpointWriter {
             transaction_object_entries: Vec::with_capacity(INITIAL_CAPACITY),
             object_entries: Vec::with_capacity(INITIAL_CAPACITY),
             event_entries: Vec::with_capacity(INITIAL_CAPACITY),
+            move_call_entries: Vec::with_capacity(INITIAL_CAPACITY),
+            move_package_entries: Vec::new(),
+            dynamic_field_entries: Vec::with_capacity(INITIAL_CAPACITY),
+            balance_change_entries: Vec::with_capacity(INITIAL_CAPACITY),
         }
     }
 
+    // Number of entries collected since the last flush.
+    pub(crate) fn entry_count(&self) -> usize {
+        self.checkpoint_entries.len()
+            + self.transaction_entries.len()
+            + self.transaction_object_entries.len()
+            + self.object_entries.len()
+            + self.event_entries.len()
+            + self.move_call_entries.len()
+            + self.move_package_entries.len()
+            + self.dynamic_field_entries.len()
+            + self.balance_change_entries.len()
+    }
+
     // Write all collected entries to files, via the given writer. Reset the entries after writing.
-    pub(crate) fn flush(&mut self, writer: &mut impl TableWriter) {
-        writer.write_checkpoints(&self.checkpoint_entries);
-        writer.write_transactions(&self.transaction_entries);
-        writer.write_transaction_objects(&self.transaction_object_entries);
-        writer.write_objects(&self.object_entries);
-        writer.write_events(&self.event_entries);
+    pub(crate) fn flush(&mut self, writer: &mut dyn TableWriter) -> Result<()> {
+        writer.write_checkpoints(&self.checkpoint_entries)?;
+        writer.write_transactions(&self.transaction_entries)?;
+        writer.write_transaction_objects(&self.transaction_object_entries)?;
+        writer.write_objects(&self.object_entries)?;
+        writer.write_events(&self.event_entries)?;
+        writer.write_move_calls(&self.move_call_entries)?;
+        writer.write_move_packages(&self.move_package_entries)?;
+        writer.write_dynamic_fields(&self.dynamic_field_entries)?;
+        writer.write_balance_changes(&self.balance_change_entries)?;
         self.checkpoint_entries.clear();
         self.transaction_entries.clear();
         self.transaction_object_entries.clear();
         self.object_entries.clear();
         self.event_entries.clear();
+        self.move_call_entries.clear();
+        self.move_package_entries.clear();
+        self.dynamic_field_entries.clear();
+        self.balance_change_entries.clear();
+        Ok(())
     }
 
     pub(crate) fn write_checkpoint(&mut self, entry: CheckpointEntry) {
@@ -76,4 +165,20 @@ impl CheckpointWriter {
     pub(crate) fn write_events(&mut self, entry: EventEntry) {
         self.event_entries.push(entry);
     }
+
+    pub(crate) fn write_move_call(&mut self, entry: MoveCallEntry) {
+        self.move_call_entries.push(entry);
+    }
+
+    pub(crate) fn write_move_package(&mut self, entry: MovePackageEntry) {
+        self.move_package_entries.push(entry);
+    }
+
+    pub(crate) fn write_dynamic_field(&mut self, entry: DynamicFieldEntry) {
+        self.dynamic_field_entries.push(entry);
+    }
+
+    pub(crate) fn write_balance_change(&mut self, entry: BalanceChangeEntry) {
+        self.balance_change_entries.push(entry);
+    }
 }
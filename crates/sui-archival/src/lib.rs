@@ -9,7 +9,8 @@ pub mod writer;
 mod tests;
 
 use crate::reader::{ArchiveReader, ArchiveReaderMetrics};
-use anyhow::{anyhow, Result};
+use crate::writer::{ArchiveMetrics, ArchiveWriter, CheckpointWriter};
+use anyhow::{anyhow, Context, Result};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use bytes::Bytes;
 use fastcrypto::hash::{HashFunction, Sha3_256};
@@ -31,10 +32,13 @@ use sui_config::node::ArchiveReaderConfig;
 use sui_storage::blob::{Blob, BlobEncoding};
 use sui_storage::object_store::util::{get, put};
 use sui_storage::object_store::ObjectStoreConfig;
-use sui_storage::{compute_sha3_checksum, SHA3_BYTES};
+use sui_storage::{compute_sha3_checksum, FileCompression, StorageFormat, SHA3_BYTES};
 use sui_types::base_types::ExecutionData;
-use sui_types::messages_checkpoint::{FullCheckpointContents, VerifiedCheckpointContents};
+use sui_types::messages_checkpoint::{
+    CheckpointDigest, CheckpointSequenceNumber, FullCheckpointContents, VerifiedCheckpointContents,
+};
 use sui_types::storage::{ReadStore, SingleCheckpointSharedInMemoryStore, WriteStore};
+use tokio::sync::mpsc;
 use tracing::info;
 
 /// Checkpoints and summaries are persisted as blob files. Files are committed to local store
@@ -431,3 +435,149 @@ where
     info!("Highest verified checkpoint: {}", end);
     Ok(())
 }
+
+/// Re-chunk the archive in the source store into the destination store by packing checkpoints
+/// into files of up to `target_file_size` bytes, encoded and compressed as requested. The size is
+/// measured before compression, so compressed files end up smaller than the target. Files are
+/// still cut at epoch boundaries. Files are staged in the local store before being uploaded and
+/// the destination manifest is updated after every upload, so an interrupted run resumes from the
+/// last uploaded checkpoint, which the first copied checkpoint must chain to. Checksums of the new
+/// archive are verified once all checkpoints are copied over
+pub async fn rechunk_archive(
+    source_store_config: ObjectStoreConfig,
+    local_store_config: ObjectStoreConfig,
+    destination_store_config: ObjectStoreConfig,
+    file_compression: FileCompression,
+    blob_encoding: BlobEncoding,
+    target_file_size: usize,
+    concurrency: usize,
+) -> Result<()> {
+    let metrics = ArchiveReaderMetrics::new(&Registry::default());
+    let config = ArchiveReaderConfig {
+        remote_store_config: source_store_config,
+        download_concurrency: NonZeroUsize::new(concurrency).unwrap(),
+        use_for_pruning_watermark: false,
+    };
+    let archive_reader = ArchiveReader::new(config, &metrics)?;
+    archive_reader.sync_manifest_once().await?;
+    let end_checkpoint_sequence_number = archive_reader
+        .get_manifest()
+        .await?
+        .next_checkpoint_seq_num();
+
+    let destination_object_store = destination_store_config.make()?;
+    let destination_is_empty = destination_object_store
+        .list_with_delimiter(None)
+        .await?
+        .common_prefixes
+        .is_empty();
+    let manifest = if destination_is_empty {
+        Manifest::new(0, 0)
+    } else {
+        read_manifest(destination_object_store.clone()).await?
+    };
+    let start_checkpoint_sequence_number = manifest.next_checkpoint_seq_num();
+    if start_checkpoint_sequence_number < end_checkpoint_sequence_number {
+        info!(
+            "Re-chunking checkpoints {}..{} into files of {} bytes",
+            start_checkpoint_sequence_number, end_checkpoint_sequence_number, target_file_size
+        );
+        let previous_digest = match start_checkpoint_sequence_number.checked_sub(1) {
+            Some(last_checkpoint_sequence_number) => Some(
+                read_checkpoint_digest(
+                    destination_store_config.clone(),
+                    last_checkpoint_sequence_number,
+                    &metrics,
+                )
+                .await?,
+            ),
+            None => None,
+        };
+        let local_staging_dir_root = local_store_config
+            .directory
+            .clone()
+            .context("Missing local dir")?;
+        let (update_sender, update_receiver) = mpsc::channel::<CheckpointUpdates>(100);
+        // Files are only cut by size so the commit duration never elapses
+        let mut checkpoint_writer = CheckpointWriter::new(
+            local_staging_dir_root.clone(),
+            file_compression,
+            StorageFormat::Blob,
+            blob_encoding,
+            update_sender,
+            manifest,
+            Duration::MAX,
+            target_file_size,
+        )?;
+        // The sync loop exits as soon as the kill channel closes, so hold on to the sender until
+        // all updates are uploaded
+        let (kill_sender, kill_receiver) = tokio::sync::broadcast::channel::<()>(1);
+        let sync_handle = tokio::spawn(ArchiveWriter::start_syncing_with_remote(
+            destination_object_store.clone(),
+            local_store_config.make()?,
+            local_staging_dir_root,
+            update_receiver,
+            kill_receiver,
+            ArchiveMetrics::new(&Registry::default()),
+        ));
+        let (checkpoint_sender, mut checkpoint_receiver) = mpsc::channel(100);
+        let write_handle = tokio::task::spawn_blocking(move || {
+            while let Some((summary, contents)) = checkpoint_receiver.blocking_recv() {
+                checkpoint_writer.write(contents, summary)?;
+            }
+            // Commit whatever is left in the last file
+            checkpoint_writer.cut()
+        });
+        let read_result = archive_reader
+            .stream_checkpoints(
+                start_checkpoint_sequence_number..end_checkpoint_sequence_number,
+                previous_digest,
+                checkpoint_sender,
+            )
+            .await;
+        let write_result = write_handle.await?;
+        sync_handle.await??;
+        drop(kill_sender);
+        read_result?;
+        write_result?;
+    } else {
+        info!(
+            "Destination archive is already up to date at checkpoint: {}",
+            start_checkpoint_sequence_number
+        );
+    }
+
+    let manifest = read_manifest(destination_object_store).await?;
+    if manifest.next_checkpoint_seq_num() != end_checkpoint_sequence_number {
+        return Err(anyhow!(
+            "Next checkpoint in destination archive is {} but expected {}",
+            manifest.next_checkpoint_seq_num(),
+            end_checkpoint_sequence_number
+        ));
+    }
+    verify_archive_with_checksums(destination_store_config, concurrency).await
+}
+
+/// Read the digest of checkpoint `sequence_number` from the archive in the given store
+async fn read_checkpoint_digest(
+    store_config: ObjectStoreConfig,
+    sequence_number: CheckpointSequenceNumber,
+    metrics: &Arc<ArchiveReaderMetrics>,
+) -> Result<CheckpointDigest> {
+    let config = ArchiveReaderConfig {
+        remote_store_config: store_config,
+        download_concurrency: NonZeroUsize::new(1).unwrap(),
+        use_for_pruning_watermark: false,
+    };
+    let archive_reader = ArchiveReader::new(config, metrics)?;
+    archive_reader.sync_manifest_once().await?;
+    let (sender, mut receiver) = mpsc::channel(1);
+    archive_reader
+        .stream_checkpoints(sequence_number..sequence_number + 1, None, sender)
+        .await?;
+    let (summary, _contents) = receiver
+        .recv()
+        .await
+        .with_context(|| format!("Missing checkpoint {sequence_number} in archive"))?;
+    Ok(*summary.digest())
+}
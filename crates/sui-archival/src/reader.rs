@@ -21,12 +21,12 @@ use sui_config::node::ArchiveReaderConfig;
 use sui_storage::object_store::util::get;
 use sui_storage::{compute_sha3_checksum_for_bytes, make_iterator, verify_checkpoint};
 use sui_types::messages_checkpoint::{
-    CertifiedCheckpointSummary, CheckpointSequenceNumber,
+    CertifiedCheckpointSummary, CheckpointDigest, CheckpointSequenceNumber,
     FullCheckpointContents as CheckpointContents, VerifiedCheckpoint, VerifiedCheckpointContents,
 };
 use sui_types::storage::{ReadStore, WriteStore};
 use tokio::sync::oneshot::Sender;
-use tokio::sync::{oneshot, Mutex};
+use tokio::sync::{mpsc, oneshot, Mutex};
 use tracing::info;
 
 #[derive(Debug)]
@@ -357,35 +357,7 @@ impl ArchiveReader {
         S: WriteStore + Clone,
         <S as ReadStore>::Error: std::error::Error,
     {
-        let manifest = self.manifest.lock().await.clone();
-
-        let latest_available_checkpoint = manifest
-            .next_checkpoint_seq_num()
-            .checked_sub(1)
-            .context("Checkpoint seq num underflow")?;
-
-        if checkpoint_range.start > latest_available_checkpoint {
-            return Err(anyhow!(
-                "Latest available checkpoint is: {}",
-                latest_available_checkpoint
-            ));
-        }
-
-        let files: Vec<(FileMetadata, FileMetadata)> = self.verify_manifest(manifest).await?;
-
-        let start_index = match files.binary_search_by_key(&checkpoint_range.start, |(s, _c)| {
-            s.checkpoint_seq_range.start
-        }) {
-            Ok(index) => index,
-            Err(index) => index - 1,
-        };
-
-        let end_index = match files.binary_search_by_key(&checkpoint_range.end, |(s, _c)| {
-            s.checkpoint_seq_range.start
-        }) {
-            Ok(index) => index,
-            Err(index) => index,
-        };
+        let (files, start_index, end_index) = self.get_files(&checkpoint_range).await?;
 
         let remote_object_store = self.remote_object_store.clone();
         futures::stream::iter(files.iter())
@@ -458,6 +430,76 @@ impl ArchiveReader {
             .await
     }
 
+    /// Stream checkpoint summaries and contents from archive in order for the given checkpoint
+    /// range. Files are checked against their checksum in manifest, contents against their
+    /// summary and every summary against the digest of the one before it, starting with
+    /// `previous_digest` if it is given. Summaries are not verified against the committee since
+    /// there is no store to verify them with
+    pub async fn stream_checkpoints(
+        &self,
+        checkpoint_range: Range<CheckpointSequenceNumber>,
+        mut previous_digest: Option<CheckpointDigest>,
+        sender: mpsc::Sender<(CertifiedCheckpointSummary, CheckpointContents)>,
+    ) -> Result<()> {
+        let (files, start_index, end_index) = self.get_files(&checkpoint_range).await?;
+
+        let remote_object_store = self.remote_object_store.clone();
+        let mut stream = futures::stream::iter(files.iter())
+            .enumerate()
+            .filter(|(index, (_s, _c))| future::ready(*index >= start_index && *index < end_index))
+            .map(|(_, (summary_metadata, content_metadata))| {
+                let remote_object_store = remote_object_store.clone();
+                async move {
+                    let summary_data =
+                        get(&summary_metadata.file_path(), remote_object_store.clone()).await?;
+                    Self::verify_checksum(summary_data.clone(), summary_metadata)?;
+                    let content_data =
+                        get(&content_metadata.file_path(), remote_object_store.clone()).await?;
+                    Self::verify_checksum(content_data.clone(), content_metadata)?;
+                    Ok::<(Bytes, Bytes), anyhow::Error>((summary_data, content_data))
+                }
+            })
+            .boxed()
+            .buffered(self.concurrency);
+
+        while let Some((summary_data, content_data)) = stream.try_next().await? {
+            let summary_iter = make_iterator::<CertifiedCheckpointSummary, Reader<Bytes>>(
+                SUMMARY_FILE_MAGIC,
+                summary_data.reader(),
+            )?;
+            let content_iter = make_iterator::<CheckpointContents, Reader<Bytes>>(
+                CHECKPOINT_FILE_MAGIC,
+                content_data.reader(),
+            )?;
+            for (summary, contents) in summary_iter.zip(content_iter).filter(|(s, _c)| {
+                s.sequence_number >= checkpoint_range.start
+                    && s.sequence_number < checkpoint_range.end
+            }) {
+                if previous_digest.is_some() && summary.previous_digest != previous_digest {
+                    return Err(anyhow!(
+                        "Checkpoint {} doesn't chain to the previous checkpoint",
+                        summary.sequence_number
+                    ));
+                }
+                contents.verify_digests(summary.content_digest)?;
+                previous_digest = Some(*summary.digest());
+                self.archive_reader_metrics
+                    .archive_txns_read
+                    .with_label_values(&[&self.bucket])
+                    .inc_by(contents.size() as u64);
+                self.archive_reader_metrics
+                    .archive_checkpoints_read
+                    .with_label_values(&[&self.bucket])
+                    .inc_by(1);
+                sender
+                    .send((summary, contents))
+                    .await
+                    .map_err(|_| anyhow!("Checkpoint receiver dropped"))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Return latest available checkpoint in archive
     pub async fn latest_available_checkpoint(&self) -> Result<CheckpointSequenceNumber> {
         let manifest = self.manifest.lock().await.clone();
@@ -494,6 +536,15 @@ impl ArchiveReader {
         Ok(())
     }
 
+    fn verify_checksum(data: Bytes, file_metadata: &FileMetadata) -> Result<()> {
+        (compute_sha3_checksum_for_bytes(data)? == file_metadata.sha3_digest)
+            .then_some(())
+            .ok_or(anyhow!(
+                "Checksum doesn't match for file: {:?}",
+                file_metadata.file_path()
+            ))
+    }
+
     /// Insert checkpoint summary without verifying it
     fn insert_certified_checkpoint<S>(
         store: &S,
@@ -554,6 +605,18 @@ impl ArchiveReader {
         &self,
         checkpoint_range: Range<CheckpointSequenceNumber>,
     ) -> Result<(Vec<FileMetadata>, usize, usize)> {
+        let (files, start_index, end_index) = self.get_files(&checkpoint_range).await?;
+        let summary_files = files.into_iter().map(|(s, _c)| s).collect();
+        Ok((summary_files, start_index, end_index))
+    }
+
+    /// Verifies the manifest and returns its (summary, contents) file pairs along with the index
+    /// of the first file holding checkpoints in `checkpoint_range` and the index past the last
+    /// one. Fails if the archive ends before the start of the range
+    async fn get_files(
+        &self,
+        checkpoint_range: &Range<CheckpointSequenceNumber>,
+    ) -> Result<(Vec<(FileMetadata, FileMetadata)>, usize, usize)> {
         let manifest = self.manifest.lock().await.clone();
 
         let latest_available_checkpoint = manifest
@@ -568,28 +631,23 @@ impl ArchiveReader {
             ));
         }
 
-        let summary_files: Vec<FileMetadata> = self
-            .verify_manifest(manifest)
-            .await?
-            .iter()
-            .map(|(s, _)| s.clone())
-            .collect();
+        let files: Vec<(FileMetadata, FileMetadata)> = self.verify_manifest(manifest).await?;
 
-        let start_index = match summary_files
-            .binary_search_by_key(&checkpoint_range.start, |s| s.checkpoint_seq_range.start)
-        {
+        let start_index = match files.binary_search_by_key(&checkpoint_range.start, |(s, _c)| {
+            s.checkpoint_seq_range.start
+        }) {
             Ok(index) => index,
             Err(index) => index - 1,
         };
 
-        let end_index = match summary_files
-            .binary_search_by_key(&checkpoint_range.end, |s| s.checkpoint_seq_range.start)
-        {
+        let end_index = match files.binary_search_by_key(&checkpoint_range.end, |(s, _c)| {
+            s.checkpoint_seq_range.start
+        }) {
             Ok(index) => index,
             Err(index) => index,
         };
 
-        Ok((summary_files, start_index, end_index))
+        Ok((files, start_index, end_index))
     }
 
     fn spawn_manifest_sync_task(
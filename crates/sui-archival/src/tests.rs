@@ -3,7 +3,9 @@
 
 use crate::reader::{ArchiveReader, ArchiveReaderMetrics};
 use crate::writer::ArchiveWriter;
-use crate::{read_manifest, verify_archive_with_local_store, write_manifest, Manifest};
+use crate::{
+    read_manifest, rechunk_archive, verify_archive_with_local_store, write_manifest, Manifest,
+};
 use anyhow::{anyhow, Context, Result};
 use more_asserts as ma;
 use object_store::DynObjectStore;
@@ -17,6 +19,7 @@ use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::time::Duration;
 use sui_config::node::ArchiveReaderConfig;
+use sui_storage::blob::BlobEncoding;
 use sui_storage::object_store::util::path_to_filesystem;
 use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
 use sui_storage::{FileCompression, StorageFormat};
@@ -371,3 +374,73 @@ async fn test_verify_archive_with_oneshot_store_bad_data() -> Result<(), anyhow:
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_rechunk_archive() -> Result<(), anyhow::Error> {
+    let test_store = SharedInMemoryStore::default();
+    let test_state = setup_test_state(temp_dir()).await?;
+    let kill = test_state.archive_writer.start(test_store.clone()).await?;
+    let mut latest_archived_checkpoint_seq_num = 0;
+    while latest_archived_checkpoint_seq_num < 10 {
+        insert_checkpoints_and_verify_manifest(&test_state, test_store.clone(), None).await?;
+        let new_latest_archived_checkpoint_seq_num = test_state
+            .archive_reader
+            .latest_available_checkpoint()
+            .await?;
+        ma::assert_ge!(
+            new_latest_archived_checkpoint_seq_num,
+            latest_archived_checkpoint_seq_num
+        );
+        latest_archived_checkpoint_seq_num = new_latest_archived_checkpoint_seq_num;
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    kill.send(())?;
+    let source_manifest = read_manifest(test_state.remote_store.clone()).await?;
+
+    let rechunked_path = temp_dir();
+    let rechunked_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(rechunked_path.join("remote_dir")),
+        ..Default::default()
+    };
+    let staging_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(rechunked_path.join("local_dir")),
+        ..Default::default()
+    };
+    rechunk_archive(
+        test_state.remote_store_config.clone(),
+        staging_store_config,
+        rechunked_store_config.clone(),
+        FileCompression::None,
+        BlobEncoding::Bcs,
+        1024 * 1024,
+        2,
+    )
+    .await?;
+
+    // All checkpoints fit in a single pair of files
+    let rechunked_manifest = read_manifest(rechunked_store_config.make()?).await?;
+    assert_eq!(
+        rechunked_manifest.next_checkpoint_seq_num(),
+        source_manifest.next_checkpoint_seq_num()
+    );
+    assert_eq!(rechunked_manifest.files().len(), 2);
+    ma::assert_gt!(source_manifest.files().len(), 2);
+
+    // Re-chunked archive should still verify against genesis
+    let genesis_checkpoint = test_store
+        .get_checkpoint_by_sequence_number(0)?
+        .context("Missing genesis checkpoint")?;
+    let genesis_checkpoint_content = test_store
+        .get_full_checkpoint_contents_by_sequence_number(0)?
+        .context("Missing genesis checkpoint")?;
+    let mut read_store = SingleCheckpointSharedInMemoryStore::default();
+    read_store.insert_genesis_state(
+        genesis_checkpoint,
+        VerifiedCheckpointContents::new_unchecked(genesis_checkpoint_content),
+        test_state.committee.committee().to_owned(),
+    );
+    verify_archive_with_local_store(read_store, rechunked_store_config, 1, false).await?;
+    Ok(())
+}
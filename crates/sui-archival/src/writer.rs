@@ -53,7 +53,7 @@ impl ArchiveMetrics {
 }
 
 /// CheckpointWriter writes checkpoints and summaries. It creates multiple *.chk and *.sum files
-struct CheckpointWriter {
+pub(crate) struct CheckpointWriter {
     root_dir_path: PathBuf,
     epoch_num: u64,
     checkpoint_range: Range<u64>,
@@ -63,6 +63,7 @@ struct CheckpointWriter {
     checkpoint_buf_offset: usize,
     file_compression: FileCompression,
     storage_format: StorageFormat,
+    blob_encoding: BlobEncoding,
     manifest: Manifest,
     last_commit_instant: Instant,
     commit_duration: Duration,
@@ -70,10 +71,11 @@ struct CheckpointWriter {
 }
 
 impl CheckpointWriter {
-    fn new(
+    pub(crate) fn new(
         root_dir_path: PathBuf,
         file_compression: FileCompression,
         storage_format: StorageFormat,
+        blob_encoding: BlobEncoding,
         sender: Sender<CheckpointUpdates>,
         manifest: Manifest,
         commit_duration: Duration,
@@ -112,6 +114,7 @@ impl CheckpointWriter {
             sender,
             file_compression,
             storage_format,
+            blob_encoding,
             manifest,
             last_commit_instant: Instant::now(),
             commit_duration,
@@ -161,7 +164,7 @@ impl CheckpointWriter {
             *checkpoint_contents.checkpoint_contents().digest()
         );
 
-        let contents_blob = Blob::encode(&checkpoint_contents, BlobEncoding::Bcs)?;
+        let contents_blob = Blob::encode(&checkpoint_contents, self.blob_encoding)?;
         let blob_size = contents_blob.size();
         let cut_new_checkpoint_file = (self.checkpoint_buf_offset + blob_size)
             > self.commit_file_size
@@ -173,7 +176,7 @@ impl CheckpointWriter {
 
         self.checkpoint_buf_offset += contents_blob.write(&mut self.wbuf)?;
 
-        let summary_blob = Blob::encode(&checkpoint_summary, BlobEncoding::Bcs)?;
+        let summary_blob = Blob::encode(&checkpoint_summary, self.blob_encoding)?;
         summary_blob.write(&mut self.summary_wbuf)?;
 
         self.checkpoint_range.end = self
@@ -219,7 +222,7 @@ impl CheckpointWriter {
         )?;
         Ok(file_metadata)
     }
-    fn cut(&mut self) -> Result<()> {
+    pub(crate) fn cut(&mut self) -> Result<()> {
         if !self.checkpoint_range.is_empty() {
             let checkpoint_file_metadata = self.finalize()?;
             let summary_file_metadata = self.finalize_summary()?;
@@ -370,6 +373,7 @@ impl ArchiveWriter {
             self.local_staging_dir_root.clone(),
             self.file_compression,
             self.storage_format,
+            BlobEncoding::Bcs,
             sender,
             manifest,
             self.commit_duration,
@@ -434,7 +438,7 @@ impl ArchiveWriter {
         Ok(())
     }
 
-    async fn start_syncing_with_remote(
+    pub(crate) async fn start_syncing_with_remote(
         remote_object_store: Arc<DynObjectStore>,
         local_object_store: Arc<DynObjectStore>,
         local_staging_root_dir: PathBuf,
@@ -111,6 +111,10 @@ pub struct NodeConfig {
     #[serde(default)]
     pub db_checkpoint_config: DBCheckpointConfig,
 
+    /// Incremental backups of the node's databases, which can be restored with `sui-tool`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_backup_config: Option<DBBackupConfig>,
+
     #[serde(default)]
     pub indirect_objects_threshold: usize,
 
@@ -141,6 +145,11 @@ pub struct NodeConfig {
     #[serde(default)]
     pub state_archive_read_config: Vec<StateArchiveConfig>,
 
+    /// Reconstruct object versions that were pruned from the local store from the state
+    /// archives in `state_archive_read_config`, to answer historical object queries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub historical_object_read_config: Option<HistoricalObjectReadConfig>,
+
     #[serde(default)]
     pub state_snapshot_write_config: StateSnapshotConfig,
 
@@ -158,11 +167,16 @@ pub struct NodeConfig {
 #[serde(rename_all = "kebab-case")]
 pub struct TransactionKeyValueStoreReadConfig {
     pub base_url: String,
+    /// Read transactions from an object store, or a local directory, written by the key value
+    /// store uploader instead of the http store at `base_url`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_store_config: Option<ObjectStoreConfig>,
 }
 
 fn default_transaction_kv_store_config() -> TransactionKeyValueStoreReadConfig {
     TransactionKeyValueStoreReadConfig {
         base_url: "https://transactions.sui.io/".to_string(),
+        object_store_config: None,
     }
 }
 
@@ -254,6 +268,10 @@ impl NodeConfig {
         self.db_path.join("db_checkpoints")
     }
 
+    pub fn db_backup_staging_path(&self) -> PathBuf {
+        self.db_path.join("db_backup_staging")
+    }
+
     pub fn archive_path(&self) -> PathBuf {
         self.db_path.join("archive")
     }
@@ -604,6 +622,27 @@ pub struct DBCheckpointConfig {
     pub prune_and_compact_before_upload: Option<bool>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DBBackupConfig {
+    /// Where the backups are uploaded to.
+    pub object_store_config: ObjectStoreConfig,
+    /// Local directory the databases are checkpointed to before their files are uploaded. It
+    /// must be on the same filesystem as the databases, so that checkpoints only hard link their
+    /// files. Defaults to `db_backup_staging` next to the databases.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staging_path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup_interval_secs: Option<u64>,
+    /// Number of backups in a chain. Every backup of a chain only uploads the files that are
+    /// not part of an earlier backup of the same chain, and the first one uploads all of them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backups_per_chain: Option<u64>,
+    /// Number of backup chains to keep in the object store. Older chains are deleted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_chains_to_retain: Option<usize>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ArchiveReaderConfig {
     pub remote_store_config: ObjectStoreConfig,
@@ -620,6 +659,31 @@ pub struct StateArchiveConfig {
     pub use_for_pruning_watermark: bool,
 }
 
+#[derive(Default, Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HistoricalObjectReadConfig {
+    /// Number of reconstructed objects kept in memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_cache_size: Option<usize>,
+    /// Number of checkpoints downloaded from the state archive kept in memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_cache_size: Option<usize>,
+    /// How many levels of pruned input objects may be reconstructed to answer a single query.
+    /// Every level replays the transaction that wrote the objects of the level above.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_reconstruction_depth: Option<usize>,
+    /// How many transactions may be replayed in total to answer a single query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_replays_per_query: Option<usize>,
+    /// How many queries may reconstruct objects at the same time. The others wait for a
+    /// reconstruction to finish before downloading checkpoints or replaying transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_reconstructions: Option<usize>,
+    /// How long a query waits for other reconstructions to finish before failing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reconstruction_wait_timeout_ms: Option<u64>,
+}
+
 #[derive(Default, Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct StateSnapshotConfig {
@@ -631,12 +695,21 @@ pub struct StateSnapshotConfig {
 #[derive(Default, Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct TransactionKeyValueStoreWriteConfig {
+    #[serde(default)]
     pub aws_access_key_id: String,
+    #[serde(default)]
     pub aws_secret_access_key: String,
+    #[serde(default)]
     pub aws_region: String,
+    #[serde(default)]
     pub table_name: String,
+    #[serde(default)]
     pub bucket_name: String,
     pub concurrency: usize,
+    /// Upload to an object store, or a local directory, instead of DynamoDB and S3. The aws
+    /// settings above are ignored when this is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_store_config: Option<ObjectStoreConfig>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq)]
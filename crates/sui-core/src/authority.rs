@@ -125,10 +125,13 @@ use sui_types::{
 use sui_types::{is_system_package, TypeTag};
 use typed_store::Map;
 
-use crate::authority::authority_per_epoch_store::{AuthorityPerEpochStore, CertTxGuard};
+use crate::authority::authority_per_epoch_store::{
+    AuthorityEpochTables, AuthorityPerEpochStore, CertTxGuard,
+};
 use crate::authority::authority_per_epoch_store_pruner::AuthorityPerEpochStorePruner;
 use crate::authority::authority_store::{ExecutionLockReadGuard, InputKey, ObjectLockStatus};
 use crate::authority::authority_store_pruner::AuthorityStorePruner;
+use crate::authority::authority_store_tables::AuthorityPerpetualTables;
 use crate::authority::epoch_start_configuration::EpochStartConfigTrait;
 use crate::authority::epoch_start_configuration::EpochStartConfiguration;
 use crate::checkpoints::checkpoint_executor::CheckpointExecutor;
@@ -2369,6 +2372,43 @@ impl AuthorityState {
         Ok(())
     }
 
+    /// Checkpoints the databases needed to restore the node, including the current epoch's
+    /// database, into `checkpoint_path` so they can be backed up. Returns the highest executed
+    /// checkpoint, which the node can be restored to from these databases.
+    pub fn checkpoint_dbs_for_backup(
+        &self,
+        checkpoint_path: &Path,
+        cur_epoch_store: &AuthorityPerEpochStore,
+    ) -> SuiResult<VerifiedCheckpoint> {
+        let highest_executed = self
+            .checkpoint_store
+            .get_highest_executed_checkpoint()?
+            .ok_or_else(|| SuiError::from("No checkpoint has been executed yet"))?;
+
+        if checkpoint_path.exists() {
+            fs::remove_dir_all(checkpoint_path)
+                .map_err(|e| SuiError::FileIOError(e.to_string()))?;
+        }
+        let store_checkpoint_path = checkpoint_path.join("store");
+        fs::create_dir_all(&store_checkpoint_path)
+            .map_err(|e| SuiError::FileIOError(e.to_string()))?;
+
+        // NOTE: As in `checkpoint_all_dbs`, the checkpoint db goes first so that it holds at
+        // least every checkpoint the other dbs have executed
+        self.checkpoint_store
+            .checkpoint_db(&checkpoint_path.join("checkpoints"))?;
+        self.database
+            .perpetual_tables
+            .checkpoint_db(&AuthorityPerpetualTables::path(&store_checkpoint_path))?;
+        cur_epoch_store.checkpoint_db(&AuthorityEpochTables::path(
+            cur_epoch_store.epoch(),
+            &store_checkpoint_path,
+        ))?;
+        self.committee_store
+            .checkpoint_db(&checkpoint_path.join("epochs"))?;
+        Ok(highest_executed)
+    }
+
     /// Load the current epoch store. This can change during reconfiguration. To ensure that
     /// we never end up accessing different epoch stores in a single task, we need to make sure
     /// that this is called once per task. Each call needs to be carefully audited to ensure it is
@@ -2578,7 +2618,7 @@ impl AuthorityState {
         Ok(Some((object, layout)))
     }
 
-    fn get_object_layout(&self, object: &Object) -> SuiResult<Option<MoveStructLayout>> {
+    pub(crate) fn get_object_layout(&self, object: &Object) -> SuiResult<Option<MoveStructLayout>> {
         let layout = object
             .data
             .try_as_move()
@@ -2784,7 +2824,7 @@ impl AuthorityState {
             .ok_or(SuiError::TransactionEventsNotFound { digest: *digest })
     }
 
-    fn get_indexes(&self) -> SuiResult<Arc<IndexStore>> {
+    pub(crate) fn get_indexes(&self) -> SuiResult<Arc<IndexStore>> {
         match &self.indexes {
             Some(i) => Ok(i.clone()),
             None => Err(SuiError::UnsupportedFeatureError {
@@ -2816,8 +2856,11 @@ impl AuthorityState {
         ));
         self.get_transactions(&kv_store, filter, cursor, limit, reverse)
             .await
+            .map(|(digests, _)| digests)
     }
 
+    /// Returns the transactions matching `filter`, along with the cursor to resume from when the
+    /// scan of a compound filter stopped before `limit` transactions were found.
     pub async fn get_transactions(
         &self,
         kv_store: &Arc<TransactionKeyValueStore>,
@@ -2826,7 +2869,7 @@ impl AuthorityState {
         cursor: Option<TransactionDigest>,
         limit: Option<usize>,
         reverse: bool,
-    ) -> SuiResult<Vec<TransactionDigest>> {
+    ) -> SuiResult<(Vec<TransactionDigest>, Option<TransactionDigest>)> {
         if let Some(TransactionFilter::Checkpoint(sequence_number)) = filter {
             let checkpoint_contents = kv_store.get_checkpoint_contents(sequence_number).await?;
             let iter = checkpoint_contents.iter().map(|c| c.transaction);
@@ -2835,12 +2878,18 @@ impl AuthorityState {
                     .rev()
                     .skip_while(|d| cursor.is_some() && Some(*d) != cursor)
                     .skip(usize::from(cursor.is_some()));
-                return Ok(iter.take(limit.unwrap_or(usize::max_value())).collect());
+                return Ok((
+                    iter.take(limit.unwrap_or(usize::max_value())).collect(),
+                    None,
+                ));
             } else {
                 let iter = iter
                     .skip_while(|d| cursor.is_some() && Some(*d) != cursor)
                     .skip(usize::from(cursor.is_some()));
-                return Ok(iter.take(limit.unwrap_or(usize::max_value())).collect());
+                return Ok((
+                    iter.take(limit.unwrap_or(usize::max_value())).collect(),
+                    None,
+                ));
             }
         }
         self.get_indexes()?
@@ -2972,6 +3021,8 @@ impl AuthorityState {
         }
     }
 
+    /// Returns the events matching `query`, along with the cursor to resume from when the scan of
+    /// a compound filter stopped before `limit` events were found.
     pub async fn query_events(
         &self,
         kv_store: &Arc<TransactionKeyValueStore>,
@@ -2980,7 +3031,7 @@ impl AuthorityState {
         cursor: Option<EventID>,
         limit: usize,
         descending: bool,
-    ) -> SuiResult<Vec<SuiEvent>> {
+    ) -> SuiResult<(Vec<SuiEvent>, Option<EventID>)> {
         let index_store = self.get_indexes()?;
 
         //Get the tx_num from tx_digest
@@ -2998,18 +3049,20 @@ impl AuthorityState {
         };
 
         let limit = limit + 1;
+        let mut scan_cursor = None;
         let mut event_keys = match query {
-            EventFilter::All(filters) => {
-                if filters.is_empty() {
-                    index_store.all_events(tx_num, event_num, limit, descending)?
-                } else {
-                    return Err(SuiError::UserInputError {
-                        error: UserInputError::Unsupported(
-                            "This query type does not currently support filter combinations"
-                                .to_string(),
-                        ),
-                    });
-                }
+            EventFilter::All(filters) if filters.is_empty() => {
+                index_store.all_events(tx_num, event_num, limit, descending)?
+            }
+            EventFilter::All(_)
+            | EventFilter::Any(_)
+            | EventFilter::And(_, _)
+            | EventFilter::Or(_, _)
+            | EventFilter::Not(_) => {
+                let (event_keys, cursor) =
+                    index_store.events_by_filter(&query, tx_num, event_num, limit, descending)?;
+                scan_cursor = cursor;
+                event_keys
             }
             EventFilter::Transaction(digest) => {
                 index_store.events_by_transaction(&digest, tx_num, event_num, limit, descending)?
@@ -3043,11 +3096,7 @@ impl AuthorityState {
                     descending,
                 )?,
             // not using "_ =>" because we want to make sure we remember to add new variants here
-            EventFilter::Package(_)
-            | EventFilter::MoveEventField { .. }
-            | EventFilter::Any(_)
-            | EventFilter::And(_, _)
-            | EventFilter::Or(_, _) => {
+            EventFilter::Package(_) | EventFilter::MoveEventField { .. } => {
                 return Err(SuiError::UserInputError {
                     error: UserInputError::Unsupported(
                         "This query type is not supported by the full node.".to_string(),
@@ -3056,15 +3105,19 @@ impl AuthorityState {
             }
         };
 
-        // skip one event if exclusive cursor is provided,
-        // otherwise truncate to the original limit.
-        if cursor.is_some() {
-            if !event_keys.is_empty() {
+        // skip the event at the exclusive cursor, which may not match the filter if the cursor
+        // is where the scan of a compound filter stopped, and truncate to the original limit.
+        if let Some(cursor) = cursor.as_ref() {
+            if event_keys
+                .first()
+                .is_some_and(|(_, tx_digest, event_seq, _)| {
+                    *tx_digest == cursor.tx_digest && *event_seq as u64 == cursor.event_seq
+                })
+            {
                 event_keys.remove(0);
             }
-        } else {
-            event_keys.truncate(limit - 1);
         }
+        event_keys.truncate(limit - 1);
 
         // get the unique set of digests from the event_keys
         let event_digests = event_keys
@@ -3107,7 +3160,7 @@ impl AuthorityState {
                 &**self.epoch_store.load().module_cache(),
             )?)
         }
-        Ok(events)
+        Ok((events, scan_cursor))
     }
 
     pub async fn insert_genesis_object(&self, object: Object) {
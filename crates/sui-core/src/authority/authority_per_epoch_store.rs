@@ -544,6 +544,14 @@ impl AuthorityPerEpochStore {
         self.parent_path.clone()
     }
 
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        // This checkpoints the entire db and not one column family
+        self.tables
+            .effects_signatures
+            .checkpoint_db(path)
+            .map_err(SuiError::StorageError)
+    }
+
     /// Returns `&Arc<EpochStartConfiguration>`
     /// User can treat this `Arc` as `&EpochStartConfiguration`, or clone the Arc to pass as owned object
     pub fn epoch_start_config(&self) -> &Arc<EpochStartConfiguration> {
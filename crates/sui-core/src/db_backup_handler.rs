@@ -0,0 +1,909 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Incremental backups of the node's databases to an object store.
+//!
+//! The databases are checkpointed into a local staging directory, which only hard links their
+//! files, and the checkpoint is uploaded. Backups are grouped in chains: SST files never change
+//! once written, so a backup only uploads the SST files that no earlier backup of its chain
+//! uploaded, along with the small files (MANIFEST, CURRENT, OPTIONS and WAL files) describing
+//! which SST files make up the databases at that point. The first backup of a chain uploads every
+//! file, so that chains can be deleted independently of each other. A node starts a new chain
+//! every time it restarts, as its databases may have been replaced in the meantime. Within a chain,
+//! SST files are only told apart by name and size, so only the files being uploaded are read and
+//! hashed; a database whose IDENTITY file changed also starts a new chain.
+//!
+//! The backup store is laid out as:
+//! ```text
+//! backup_manifest.json                  -- every backup chain and the files of each backup
+//! chain_{id}/sst/{db}/{file}.sst        -- SST files shared by the backups of a chain
+//! chain_{id}/backup_{id}/{db}/{file}    -- other files of a backup
+//! ```
+//! where `{db}` is the path of a database relative to the node's db path, e.g. `store/perpetual`.
+
+use crate::authority::AuthorityState;
+use crate::checkpoints::CheckpointStore;
+use anyhow::{anyhow, bail, Result};
+use bytes::Bytes;
+use fastcrypto::hash::{HashFunction, Sha3_256};
+use futures::StreamExt;
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use prometheus::{register_int_gauge_with_registry, IntGauge, Registry};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sui_config::node::DBBackupConfig;
+use sui_storage::object_store::util::{copy_files, delete_recursively, put};
+use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+use sui_storage::{compute_sha3_checksum, SHA3_BYTES};
+use sui_types::committee::EpochId;
+use sui_types::message_envelope::Message;
+use sui_types::messages_checkpoint::{CheckpointDigest, CheckpointSequenceNumber};
+use tracing::{error, info};
+use typed_store::rocks::MetricConf;
+
+pub const BACKUP_MANIFEST_FILE: &str = "backup_manifest.json";
+/// Directory of the staging path the databases are checkpointed to
+const STAGING_DIR: &str = "backup";
+const DEFAULT_BACKUP_INTERVAL_SECS: u64 = 60 * 60;
+const DEFAULT_BACKUPS_PER_CHAIN: u64 = 24;
+const DEFAULT_NUM_CHAINS_TO_RETAIN: usize = 2;
+const TRANSFER_CONCURRENCY: usize = 20;
+
+/// Describes every backup in a backup store.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BackupManifest {
+    pub chains: Vec<BackupChain>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BackupChain {
+    pub id: u64,
+    pub backups: Vec<BackupInfo>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BackupInfo {
+    pub id: u64,
+    pub timestamp_ms: u64,
+    pub epoch: EpochId,
+    /// The highest checkpoint executed by the node when the databases were backed up
+    pub checkpoint: CheckpointSequenceNumber,
+    pub checkpoint_digest: CheckpointDigest,
+    pub files: Vec<BackupFile>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BackupFile {
+    /// Path of the file relative to the node's db path
+    pub path: String,
+    /// Location of the file in the backup store
+    pub location: String,
+    pub size: u64,
+    pub sha3_digest: [u8; SHA3_BYTES],
+}
+
+/// The point the backed up databases can be restored to.
+#[derive(Clone, Copy, Debug)]
+pub struct BackupPoint {
+    pub epoch: EpochId,
+    pub checkpoint: CheckpointSequenceNumber,
+    pub checkpoint_digest: CheckpointDigest,
+}
+
+impl BackupManifest {
+    pub fn backups(&self) -> impl Iterator<Item = &BackupInfo> {
+        self.chains.iter().flat_map(|chain| chain.backups.iter())
+    }
+
+    /// Finds the backup with the given id, or else the latest backup taken at or before
+    /// `checkpoint`, or else the latest backup.
+    pub fn find_backup(
+        &self,
+        backup_id: Option<u64>,
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> Option<&BackupInfo> {
+        match (backup_id, checkpoint) {
+            (Some(backup_id), _) => self.backups().find(|backup| backup.id == backup_id),
+            (None, Some(checkpoint)) => self
+                .backups()
+                .filter(|backup| backup.checkpoint <= checkpoint)
+                .max_by_key(|backup| (backup.checkpoint, backup.id)),
+            (None, None) => self.backups().max_by_key(|backup| backup.id),
+        }
+    }
+
+    fn next_backup_id(&self) -> u64 {
+        self.backups()
+            .map(|backup| backup.id + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn next_chain_id(&self) -> u64 {
+        self.chains.last().map(|chain| chain.id + 1).unwrap_or(0)
+    }
+}
+
+fn is_sst_file(path: &str) -> bool {
+    path.ends_with(".sst")
+}
+
+/// The IDENTITY file of a database holds an id generated when the database is created
+fn is_identity_file(path: &str) -> bool {
+    path.rsplit('/').next() == Some("IDENTITY")
+}
+
+async fn compute_sha3_checksum_for_object(
+    store: &DynObjectStore,
+    location: &Path,
+) -> Result<[u8; SHA3_BYTES]> {
+    let mut hasher = Sha3_256::default();
+    let mut stream = store.get(location).await?.into_stream();
+    while let Some(bytes) = stream.next().await {
+        hasher.update(bytes?);
+    }
+    Ok(hasher.finalize().digest)
+}
+
+async fn compute_sha3_checksums(
+    store: &DynObjectStore,
+    locations: &[Path],
+) -> Result<Vec<[u8; SHA3_BYTES]>> {
+    futures::stream::iter(locations)
+        .map(|location| compute_sha3_checksum_for_object(store, location))
+        .buffered(TRANSFER_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect()
+}
+
+/// Reads and writes the backups of a backup store.
+pub struct BackupStore {
+    store: Arc<DynObjectStore>,
+}
+
+impl BackupStore {
+    pub fn new(config: &ObjectStoreConfig) -> Result<Self> {
+        Ok(Self {
+            store: config.make()?,
+        })
+    }
+
+    pub async fn read_manifest(&self) -> Result<BackupManifest> {
+        match self.store.get(&Path::from(BACKUP_MANIFEST_FILE)).await {
+            Ok(result) => Ok(serde_json::from_slice(&result.bytes().await?)?),
+            Err(object_store::Error::NotFound { .. }) => Ok(BackupManifest::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn write_manifest(&self, manifest: &BackupManifest) -> Result<()> {
+        let bytes = Bytes::from(serde_json::to_vec_pretty(manifest)?);
+        put(&Path::from(BACKUP_MANIFEST_FILE), bytes, self.store.clone()).await?;
+        Ok(())
+    }
+
+    /// Uploads the databases checkpointed in `local_dir` of `local_store` as a new backup. The
+    /// backup starts a new chain if `new_chain` is set, or the latest chain already has
+    /// `backups_per_chain` backups.
+    pub async fn upload_backup(
+        &self,
+        local_store: Arc<DynObjectStore>,
+        local_dir: &Path,
+        point: BackupPoint,
+        backups_per_chain: u64,
+        new_chain: bool,
+    ) -> Result<BackupInfo> {
+        let mut local_files = vec![];
+        let mut listing = local_store.list(Some(local_dir)).await?;
+        while let Some(entry) = listing.next().await {
+            let entry = entry?;
+            let path = entry
+                .location
+                .as_ref()
+                .strip_prefix(&format!("{local_dir}/"))
+                .ok_or_else(|| anyhow!("Unexpected file in backup: {}", entry.location))?
+                .to_string();
+            local_files.push((path, entry.location, entry.size as u64));
+        }
+
+        let mut manifest = self.read_manifest().await?;
+        let backup_id = manifest.next_backup_id();
+        // SST files uploaded by earlier backups of the latest chain
+        let uploaded: HashMap<String, (u64, [u8; SHA3_BYTES])> = manifest
+            .chains
+            .last()
+            .into_iter()
+            .flat_map(|chain| chain.backups.iter())
+            .flat_map(|backup| backup.files.iter())
+            .filter(|file| is_sst_file(&file.path))
+            .map(|file| (file.path.clone(), (file.size, file.sha3_digest)))
+            .collect();
+        // Identities of the databases in the latest backup of the latest chain
+        let identities: HashMap<String, [u8; SHA3_BYTES]> = manifest
+            .chains
+            .last()
+            .and_then(|chain| chain.backups.last())
+            .into_iter()
+            .flat_map(|backup| backup.files.iter())
+            .filter(|file| is_identity_file(&file.path))
+            .map(|file| (file.path.clone(), file.sha3_digest))
+            .collect();
+
+        // The small files are uploaded by every backup, so they are always hashed
+        let (small_paths, small_locations): (Vec<_>, Vec<_>) = local_files
+            .iter()
+            .filter(|(path, _, _)| !is_sst_file(path))
+            .map(|(path, location, _)| (path.clone(), location.clone()))
+            .unzip();
+        let mut digests: HashMap<String, [u8; SHA3_BYTES]> = small_paths
+            .into_iter()
+            .zip(compute_sha3_checksums(&local_store, &small_locations).await?)
+            .collect();
+        // SST files are never modified once written, so they are only told apart by name and size.
+        // A database with another identity, or an SST file whose size differs from the one
+        // uploaded under the same name, means that the databases were replaced and can't share the
+        // files of the chain.
+        let replaced = local_files.iter().any(|(path, _, size)| {
+            if is_sst_file(path) {
+                uploaded
+                    .get(path)
+                    .map_or(false, |(uploaded_size, _)| uploaded_size != size)
+            } else {
+                identities
+                    .get(path)
+                    .map_or(false, |identity| digests.get(path) != Some(identity))
+            }
+        });
+        let chain_full = manifest.chains.last().map_or(true, |chain| {
+            chain.backups.len() as u64 >= backups_per_chain
+        });
+        if new_chain || chain_full || replaced {
+            manifest.chains.push(BackupChain {
+                id: manifest.next_chain_id(),
+                backups: vec![],
+            });
+        }
+        let chain = manifest.chains.last_mut().unwrap();
+        let chain_started = !chain.backups.is_empty();
+
+        // Only the SST files which are not shared with the chain are read and hashed
+        let (new_sst_paths, new_sst_locations): (Vec<_>, Vec<_>) = local_files
+            .iter()
+            .filter(|(path, _, _)| {
+                is_sst_file(path) && !(chain_started && uploaded.contains_key(path))
+            })
+            .map(|(path, location, _)| (path.clone(), location.clone()))
+            .unzip();
+        digests.extend(
+            new_sst_paths
+                .into_iter()
+                .zip(compute_sha3_checksums(&local_store, &new_sst_locations).await?),
+        );
+
+        let mut files = vec![];
+        let mut files_in = vec![];
+        let mut files_out = vec![];
+        for (path, local_location, size) in local_files {
+            let location = if is_sst_file(&path) {
+                format!("chain_{}/sst/{}", chain.id, path)
+            } else {
+                format!("chain_{}/backup_{}/{}", chain.id, backup_id, path)
+            };
+            let sha3_digest = match digests.remove(&path) {
+                Some(sha3_digest) => sha3_digest,
+                None => uploaded[&path].1,
+            };
+            let already_uploaded =
+                chain_started && is_sst_file(&path) && uploaded.contains_key(&path);
+            // Empty files are not uploaded, and are created empty when restoring
+            if !already_uploaded && size > 0 {
+                files_in.push(local_location);
+                files_out.push(Path::from(location.as_str()));
+            }
+            files.push(BackupFile {
+                path,
+                location,
+                size,
+                sha3_digest,
+            });
+        }
+        info!(
+            "Uploading {} of the {} files of backup {backup_id}",
+            files_in.len(),
+            files.len()
+        );
+        copy_files(
+            &files_in,
+            &files_out,
+            local_store,
+            self.store.clone(),
+            NonZeroUsize::new(TRANSFER_CONCURRENCY).unwrap(),
+        )
+        .await?;
+
+        let backup = BackupInfo {
+            id: backup_id,
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+            epoch: point.epoch,
+            checkpoint: point.checkpoint,
+            checkpoint_digest: point.checkpoint_digest,
+            files,
+        };
+        chain.backups.push(backup.clone());
+        // The backup only exists once the manifest refers to it
+        self.write_manifest(&manifest).await?;
+        Ok(backup)
+    }
+
+    /// Deletes the oldest backup chains, keeping the latest `num_chains_to_retain` ones.
+    /// Returns the ids of the deleted chains.
+    pub async fn prune(&self, num_chains_to_retain: usize) -> Result<Vec<u64>> {
+        let mut manifest = self.read_manifest().await?;
+        let num_chains_to_retain = num_chains_to_retain.max(1);
+        if manifest.chains.len() <= num_chains_to_retain {
+            return Ok(vec![]);
+        }
+        let num_chains_to_prune = manifest.chains.len() - num_chains_to_retain;
+        let pruned: Vec<_> = manifest
+            .chains
+            .drain(..num_chains_to_prune)
+            .map(|chain| chain.id)
+            .collect();
+        // Update the manifest first, so that it never refers to deleted files
+        self.write_manifest(&manifest).await?;
+        for chain_id in pruned.iter() {
+            info!("Deleting backup chain: {chain_id}");
+            delete_recursively(
+                &Path::from(format!("chain_{chain_id}")),
+                self.store.clone(),
+                NonZeroUsize::new(TRANSFER_CONCURRENCY).unwrap(),
+            )
+            .await?;
+        }
+        Ok(pruned)
+    }
+
+    /// Downloads the files of `backup` into `db_path`.
+    pub async fn restore(&self, backup: &BackupInfo, db_path: &std::path::Path) -> Result<()> {
+        let local_store = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(db_path.to_path_buf()),
+            ..Default::default()
+        }
+        .make()?;
+        let mut files_in = vec![];
+        let mut files_out = vec![];
+        for file in backup.files.iter() {
+            if file.size == 0 {
+                let local_path = db_path.join(&file.path);
+                if let Some(parent) = local_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::File::create(local_path)?;
+            } else {
+                files_in.push(Path::from(file.location.as_str()));
+                files_out.push(Path::from(file.path.as_str()));
+            }
+        }
+        info!(
+            "Downloading {} files of backup {} to {}",
+            files_in.len(),
+            backup.id,
+            db_path.display()
+        );
+        copy_files(
+            &files_in,
+            &files_out,
+            self.store.clone(),
+            local_store,
+            NonZeroUsize::new(TRANSFER_CONCURRENCY).unwrap(),
+        )
+        .await?;
+        for file in backup.files.iter() {
+            let local_path = db_path.join(&file.path);
+            let size = fs::metadata(&local_path)?.len();
+            if size != file.size {
+                bail!(
+                    "Restored file {} has {size} bytes instead of {}",
+                    file.path,
+                    file.size
+                );
+            }
+            if compute_sha3_checksum(&local_path)? != file.sha3_digest {
+                bail!("Restored file {} has an unexpected sha3 digest", file.path);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks that the databases restored in `db_path` have executed the checkpoint `backup` was
+/// taken at, and that it has the same digest as when it was backed up.
+pub fn verify_restored_backup(backup: &BackupInfo, db_path: &std::path::Path) -> Result<()> {
+    let checkpoint_store = CheckpointStore::open_tables_read_write(
+        db_path.join("checkpoints"),
+        MetricConf::default(),
+        None,
+        None,
+    );
+    verify_restored_checkpoint_store(backup, &checkpoint_store)
+}
+
+fn verify_restored_checkpoint_store(
+    backup: &BackupInfo,
+    checkpoint_store: &CheckpointStore,
+) -> Result<()> {
+    let checkpoint = checkpoint_store
+        .get_checkpoint_by_sequence_number(backup.checkpoint)?
+        .ok_or_else(|| {
+            anyhow!(
+                "Restored databases are missing checkpoint {}",
+                backup.checkpoint
+            )
+        })?;
+    if *checkpoint.digest() != backup.checkpoint_digest {
+        bail!(
+            "Restored checkpoint {} has digest {} instead of {}",
+            backup.checkpoint,
+            checkpoint.digest(),
+            backup.checkpoint_digest
+        );
+    }
+    let highest_executed = checkpoint_store.get_highest_executed_checkpoint_seq_number()?;
+    if highest_executed.map_or(true, |highest_executed| {
+        highest_executed < backup.checkpoint
+    }) {
+        bail!(
+            "Restored databases have only executed up to checkpoint {:?}, expected {}",
+            highest_executed,
+            backup.checkpoint
+        );
+    }
+    Ok(())
+}
+
+pub struct DBBackupMetrics {
+    pub last_db_backup_checkpoint: IntGauge,
+}
+
+impl DBBackupMetrics {
+    pub fn new(registry: &Registry) -> Arc<Self> {
+        let this = Self {
+            last_db_backup_checkpoint: register_int_gauge_with_registry!(
+                "last_db_backup_checkpoint",
+                "Highest executed checkpoint of the latest backup of the databases",
+                registry
+            )
+            .unwrap(),
+        };
+        Arc::new(this)
+    }
+}
+
+pub struct DBBackupHandler {
+    state: Arc<AuthorityState>,
+    /// Local directory where the databases are checkpointed before being uploaded
+    staging_path: PathBuf,
+    /// Object store on top of `staging_path`
+    staging_store: Arc<DynObjectStore>,
+    backup_store: BackupStore,
+    /// Time interval between backups
+    interval: Duration,
+    backups_per_chain: u64,
+    num_chains_to_retain: usize,
+    metrics: Arc<DBBackupMetrics>,
+}
+
+impl DBBackupHandler {
+    pub fn new(
+        state: Arc<AuthorityState>,
+        config: &DBBackupConfig,
+        default_staging_path: PathBuf,
+        registry: &Registry,
+    ) -> Result<Arc<Self>> {
+        let staging_path = config.staging_path.clone().unwrap_or(default_staging_path);
+        let staging_store_config = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(staging_path.clone()),
+            ..Default::default()
+        };
+        Ok(Arc::new(DBBackupHandler {
+            state,
+            staging_store: staging_store_config.make()?,
+            staging_path,
+            backup_store: BackupStore::new(&config.object_store_config)?,
+            interval: Duration::from_secs(
+                config
+                    .backup_interval_secs
+                    .unwrap_or(DEFAULT_BACKUP_INTERVAL_SECS),
+            ),
+            backups_per_chain: config
+                .backups_per_chain
+                .unwrap_or(DEFAULT_BACKUPS_PER_CHAIN),
+            num_chains_to_retain: config
+                .num_chains_to_retain
+                .unwrap_or(DEFAULT_NUM_CHAINS_TO_RETAIN),
+            metrics: DBBackupMetrics::new(registry),
+        }))
+    }
+
+    pub fn start(self: Arc<Self>) -> tokio::sync::broadcast::Sender<()> {
+        let (kill_sender, _kill_receiver) = tokio::sync::broadcast::channel::<()>(1);
+        tokio::task::spawn(Self::run_db_backup_loop(self, kill_sender.subscribe()));
+        kill_sender
+    }
+
+    async fn run_db_backup_loop(
+        self: Arc<Self>,
+        mut recv: tokio::sync::broadcast::Receiver<()>,
+    ) -> Result<()> {
+        let mut interval = tokio::time::interval(self.interval);
+        let mut new_chain = true;
+        info!("DB backup loop started");
+        loop {
+            tokio::select! {
+                _now = interval.tick() => {
+                    match self.backup(new_chain).await {
+                        Ok(backup) => {
+                            info!("Backed up databases at checkpoint {} as backup {}", backup.checkpoint, backup.id);
+                            new_chain = false;
+                        }
+                        Err(err) => error!("Failed to back up databases with err: {:?}", err),
+                    }
+                },
+                _ = recv.recv() => break,
+            }
+        }
+        Ok(())
+    }
+
+    async fn backup(&self, new_chain: bool) -> Result<BackupInfo> {
+        let state = self.state.clone();
+        let epoch_store = self.state.load_epoch_store_one_call_per_task().clone();
+        let epoch = epoch_store.epoch();
+        let local_path = self.staging_path.join(STAGING_DIR);
+        let checkpoint_path = local_path.clone();
+        let checkpoint = tokio::task::spawn_blocking(move || {
+            state.checkpoint_dbs_for_backup(&checkpoint_path, &epoch_store)
+        })
+        .await??;
+        let point = BackupPoint {
+            epoch,
+            checkpoint: *checkpoint.sequence_number(),
+            checkpoint_digest: *checkpoint.digest(),
+        };
+        let result = self
+            .backup_store
+            .upload_backup(
+                self.staging_store.clone(),
+                &Path::from(STAGING_DIR),
+                point,
+                self.backups_per_chain,
+                new_chain,
+            )
+            .await;
+        // Release the hard links, so that the files compacted away can be deleted
+        fs::remove_dir_all(&local_path)?;
+        let backup = result?;
+        self.metrics
+            .last_db_backup_checkpoint
+            .set(backup.checkpoint as i64);
+        self.backup_store.prune(self.num_chains_to_retain).await?;
+        Ok(backup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checkpoints::CheckpointStore;
+    use crate::db_backup_handler::{
+        verify_restored_backup, verify_restored_checkpoint_store, BackupInfo, BackupPoint,
+        BackupStore, BACKUP_MANIFEST_FILE,
+    };
+    use object_store::path::Path;
+    use std::fs;
+    use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+    use sui_types::committee::Committee;
+    use sui_types::messages_checkpoint::{CheckpointDigest, VerifiedCheckpoint};
+    use sui_types::utils::mock_certified_checkpoint;
+    use tempfile::TempDir;
+
+    fn file_store_config(path: &std::path::Path) -> ObjectStoreConfig {
+        ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(path.to_path_buf()),
+            ..Default::default()
+        }
+    }
+
+    // Replaces the local checkpoint of the databases with the given files
+    fn write_db_files(dir: &std::path::Path, files: &[(&str, &str)]) -> anyhow::Result<()> {
+        if dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+        for (path, contents) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, contents)?;
+        }
+        Ok(())
+    }
+
+    fn point(checkpoint: u64) -> BackupPoint {
+        BackupPoint {
+            epoch: 0,
+            checkpoint,
+            checkpoint_digest: CheckpointDigest::random(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_incremental_backup_and_restore() -> anyhow::Result<()> {
+        let local_dir = TempDir::new()?;
+        let local_db_dir = local_dir.path().join("backup");
+        let local_store = file_store_config(local_dir.path()).make()?;
+        let remote_dir = TempDir::new()?;
+        let backup_store = BackupStore::new(&file_store_config(remote_dir.path()))?;
+
+        write_db_files(
+            &local_db_dir,
+            &[
+                ("store/perpetual/000001.sst", "aaaa"),
+                ("store/perpetual/CURRENT", "MANIFEST-000002"),
+                ("store/perpetual/MANIFEST-000002", "manifest 2"),
+                ("store/perpetual/000003.log", ""),
+            ],
+        )?;
+        let first = backup_store
+            .upload_backup(
+                local_store.clone(),
+                &Path::from("backup"),
+                point(10),
+                10,
+                true,
+            )
+            .await?;
+
+        // The second backup shares the first SST file, which is not uploaded again
+        write_db_files(
+            &local_db_dir,
+            &[
+                ("store/perpetual/000001.sst", "aaaa"),
+                ("store/perpetual/000004.sst", "cccc"),
+                ("store/perpetual/CURRENT", "MANIFEST-000005"),
+                ("store/perpetual/MANIFEST-000005", "manifest 5"),
+            ],
+        )?;
+        let second = backup_store
+            .upload_backup(
+                local_store.clone(),
+                &Path::from("backup"),
+                point(20),
+                10,
+                false,
+            )
+            .await?;
+
+        let remote_sst_dir = remote_dir.path().join("chain_0/sst/store/perpetual");
+        assert_eq!(
+            fs::read_to_string(remote_sst_dir.join("000001.sst"))?,
+            "aaaa"
+        );
+        assert_eq!(
+            fs::read_to_string(remote_sst_dir.join("000004.sst"))?,
+            "cccc"
+        );
+        assert!(remote_dir.path().join(BACKUP_MANIFEST_FILE).exists());
+
+        let manifest = backup_store.read_manifest().await?;
+        assert_eq!(manifest.chains.len(), 1);
+        assert_eq!(
+            manifest.chains[0].backups,
+            vec![first.clone(), second.clone()]
+        );
+        assert_eq!(manifest.find_backup(None, None), Some(&second));
+        assert_eq!(manifest.find_backup(None, Some(15)), Some(&first));
+        assert_eq!(manifest.find_backup(Some(first.id), None), Some(&first));
+        assert_eq!(manifest.find_backup(None, Some(5)), None);
+
+        let restore_dir = TempDir::new()?;
+        backup_store.restore(&first, restore_dir.path()).await?;
+        let restored = restore_dir.path().join("store/perpetual");
+        assert_eq!(fs::read_to_string(restored.join("000001.sst"))?, "aaaa");
+        assert_eq!(
+            fs::read_to_string(restored.join("CURRENT"))?,
+            "MANIFEST-000002"
+        );
+        assert_eq!(fs::read_to_string(restored.join("000003.log"))?, "");
+        assert!(!restored.join("000004.sst").exists());
+
+        let restore_dir = TempDir::new()?;
+        backup_store.restore(&second, restore_dir.path()).await?;
+        let restored = restore_dir.path().join("store/perpetual");
+        assert_eq!(fs::read_to_string(restored.join("000004.sst"))?, "cccc");
+        assert_eq!(
+            fs::read_to_string(restored.join("CURRENT"))?,
+            "MANIFEST-000005"
+        );
+        assert!(!restored.join("MANIFEST-000002").exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_backup_chains() -> anyhow::Result<()> {
+        let local_dir = TempDir::new()?;
+        let local_db_dir = local_dir.path().join("backup");
+        let local_store = file_store_config(local_dir.path()).make()?;
+        let remote_dir = TempDir::new()?;
+        let backup_store = BackupStore::new(&file_store_config(remote_dir.path()))?;
+
+        write_db_files(
+            &local_db_dir,
+            &[
+                ("checkpoints/000001.sst", "aaaa"),
+                ("checkpoints/IDENTITY", "db-1"),
+            ],
+        )?;
+        for checkpoint in [1, 2, 3] {
+            backup_store
+                .upload_backup(
+                    local_store.clone(),
+                    &Path::from("backup"),
+                    point(checkpoint),
+                    2,
+                    false,
+                )
+                .await?;
+        }
+        // A chain holds at most two backups
+        let manifest = backup_store.read_manifest().await?;
+        assert_eq!(manifest.chains.len(), 2);
+        assert_eq!(manifest.chains[1].backups.len(), 1);
+        assert!(remote_dir
+            .path()
+            .join("chain_1/sst/checkpoints/000001.sst")
+            .exists());
+
+        // A replaced database starts a new chain, even if its SST files have the same names and
+        // sizes as the ones of the chain
+        write_db_files(
+            &local_db_dir,
+            &[
+                ("checkpoints/000001.sst", "bbbb"),
+                ("checkpoints/IDENTITY", "db-2"),
+            ],
+        )?;
+        backup_store
+            .upload_backup(
+                local_store.clone(),
+                &Path::from("backup"),
+                point(4),
+                2,
+                false,
+            )
+            .await?;
+        let manifest = backup_store.read_manifest().await?;
+        assert_eq!(manifest.chains.len(), 3);
+        assert_eq!(
+            fs::read_to_string(remote_dir.path().join("chain_2/sst/checkpoints/000001.sst"))?,
+            "bbbb"
+        );
+        assert_eq!(
+            fs::read_to_string(remote_dir.path().join("chain_1/sst/checkpoints/000001.sst"))?,
+            "aaaa"
+        );
+
+        assert_eq!(backup_store.prune(1).await?, vec![0, 1]);
+        let manifest = backup_store.read_manifest().await?;
+        assert_eq!(manifest.chains.len(), 1);
+        assert_eq!(manifest.chains[0].id, 2);
+        // Empty directories may be left behind, but none of the files
+        assert!(!remote_dir
+            .path()
+            .join("chain_0/sst/checkpoints/000001.sst")
+            .exists());
+        assert!(!remote_dir
+            .path()
+            .join("chain_1/sst/checkpoints/000001.sst")
+            .exists());
+        assert!(remote_dir
+            .path()
+            .join("chain_2/sst/checkpoints/000001.sst")
+            .exists());
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_restore_rejects_modified_files() -> anyhow::Result<()> {
+        let local_dir = TempDir::new()?;
+        let local_store = file_store_config(local_dir.path()).make()?;
+        let remote_dir = TempDir::new()?;
+        let backup_store = BackupStore::new(&file_store_config(remote_dir.path()))?;
+
+        write_db_files(
+            &local_dir.path().join("backup"),
+            &[("store/perpetual/000001.sst", "aaaa")],
+        )?;
+        let backup = backup_store
+            .upload_backup(local_store, &Path::from("backup"), point(1), 10, true)
+            .await?;
+
+        // The backed up file is replaced by one of the same size
+        fs::write(
+            remote_dir
+                .path()
+                .join("chain_0/sst/store/perpetual/000001.sst"),
+            "bbbb",
+        )?;
+        let restore_dir = TempDir::new()?;
+        let err = backup_store
+            .restore(&backup, restore_dir.path())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("unexpected sha3 digest"));
+        Ok(())
+    }
+
+    fn backup_info(checkpoint: &VerifiedCheckpoint) -> BackupInfo {
+        BackupInfo {
+            id: 0,
+            timestamp_ms: 0,
+            epoch: checkpoint.epoch(),
+            checkpoint: *checkpoint.sequence_number(),
+            checkpoint_digest: *checkpoint.digest(),
+            files: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_restored_backup() -> anyhow::Result<()> {
+        let (committee, keys) = Committee::new_simple_test_committee();
+        let checkpoints: Vec<_> = (0..2)
+            .map(|sequence_number| {
+                VerifiedCheckpoint::new_unchecked(mock_certified_checkpoint(
+                    keys.iter(),
+                    committee.clone(),
+                    sequence_number,
+                ))
+            })
+            .collect();
+
+        let db_dir = TempDir::new()?;
+        let checkpoint_store = CheckpointStore::new(&db_dir.path().join("checkpoints"));
+        for checkpoint in checkpoints.iter() {
+            checkpoint_store.insert_verified_checkpoint(checkpoint)?;
+        }
+        checkpoint_store.update_highest_executed_checkpoint(&checkpoints[0])?;
+
+        verify_restored_checkpoint_store(&backup_info(&checkpoints[0]), &checkpoint_store)?;
+
+        // The databases have not executed the checkpoint the backup was taken at
+        let err =
+            verify_restored_checkpoint_store(&backup_info(&checkpoints[1]), &checkpoint_store)
+                .unwrap_err();
+        assert!(err.to_string().contains("only executed up to checkpoint"));
+
+        // The checkpoint the backup was taken at is not the restored one
+        let mut backup = backup_info(&checkpoints[0]);
+        backup.checkpoint_digest = CheckpointDigest::random();
+        let err = verify_restored_checkpoint_store(&backup, &checkpoint_store).unwrap_err();
+        assert!(err.to_string().contains("has digest"));
+
+        // Nothing was restored
+        let empty_dir = TempDir::new()?;
+        let err =
+            verify_restored_backup(&backup_info(&checkpoints[0]), empty_dir.path()).unwrap_err();
+        assert!(err.to_string().contains("missing checkpoint 0"));
+        Ok(())
+    }
+}
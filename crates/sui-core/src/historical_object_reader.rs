@@ -0,0 +1,570 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reconstructs object versions that were pruned from the local store, so fullnodes can prune
+//! aggressively and still answer historical object queries.
+//!
+//! The transaction that wrote the requested version is found with the index of transactions by
+//! mutated object, and its checkpoint is downloaded from the state archive and verified against
+//! the certified checkpoint summary in the local checkpoint store, which is never pruned. The
+//! transaction is then executed again on top of its input objects, which are either still in the
+//! local store or reconstructed the same way. The objects it writes are only used if the replayed
+//! effects are identical to the archived ones. The work a single query may cause is bounded by
+//! the depth of the reconstruction and the number of transactions it replays.
+//!
+//! Transactions are replayed with the packages currently in the local store, so a transaction
+//! that used a package at a version that was since upgraded, such as a system package before a
+//! framework upgrade, generally can't be replayed: its effects won't match and the versions it
+//! wrote can't be reconstructed.
+
+use crate::authority::{AuthorityState, AuthorityStore};
+use crate::transaction_input_checker::check_objects;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use lru::LruCache;
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
+use move_core_types::language_storage::ModuleId;
+use move_core_types::value::MoveStructLayout;
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_archival::reader::ArchiveReaderBalancer;
+use sui_config::node::HistoricalObjectReadConfig;
+use sui_execution::Executor;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_storage::key_value_store::TransactionKeyValueStore;
+use sui_types::base_types::{ExecutionData, ObjectID, ObjectRef, SequenceNumber};
+use sui_types::committee::EpochId;
+use sui_types::digests::TransactionDigest;
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::gas::SuiGasStatus;
+use sui_types::inner_temporary_store::WrittenObjects;
+use sui_types::message_envelope::Message;
+use sui_types::messages_checkpoint::{CheckpointSequenceNumber, FullCheckpointContents};
+use sui_types::object::{Object, Owner};
+use sui_types::storage::{
+    get_module_by_id, BackingPackageStore, ChildObjectResolver, ObjectKey, ObjectStore, ParentSync,
+    ReadStore, SharedInMemoryStore,
+};
+use sui_types::transaction::{InputObjectKind, TransactionDataAPI};
+use tokio::sync::Semaphore;
+use tracing::{debug, warn};
+
+const DEFAULT_OBJECT_CACHE_SIZE: usize = 10_000;
+const DEFAULT_CHECKPOINT_CACHE_SIZE: usize = 64;
+const DEFAULT_MAX_RECONSTRUCTION_DEPTH: usize = 10;
+const DEFAULT_MAX_REPLAYS_PER_QUERY: usize = 32;
+const DEFAULT_MAX_CONCURRENT_RECONSTRUCTIONS: usize = 4;
+const DEFAULT_RECONSTRUCTION_WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct HistoricalObjectReader {
+    state: Arc<AuthorityState>,
+    kv_store: Arc<TransactionKeyValueStore>,
+    archive_readers: ArchiveReaderBalancer,
+    max_reconstruction_depth: usize,
+    max_replays_per_query: usize,
+    /// Bounds how many queries reconstruct objects at the same time. The objects a query needs
+    /// recursively are reconstructed under the permit of the query.
+    reconstruction_permits: Semaphore,
+    reconstruction_wait_timeout: Duration,
+    objects: Mutex<LruCache<ObjectKey, Object>>,
+    /// Verified contents of the checkpoints downloaded from the archive.
+    checkpoints: Mutex<LruCache<CheckpointSequenceNumber, Arc<FullCheckpointContents>>>,
+    executors: Mutex<HashMap<ProtocolVersion, Arc<dyn Executor + Send + Sync>>>,
+}
+
+impl HistoricalObjectReader {
+    pub fn new(
+        state: Arc<AuthorityState>,
+        kv_store: Arc<TransactionKeyValueStore>,
+        archive_readers: ArchiveReaderBalancer,
+        config: &HistoricalObjectReadConfig,
+    ) -> Self {
+        let object_cache_size = config
+            .object_cache_size
+            .and_then(NonZeroUsize::new)
+            .unwrap_or(NonZeroUsize::new(DEFAULT_OBJECT_CACHE_SIZE).unwrap());
+        let checkpoint_cache_size = config
+            .checkpoint_cache_size
+            .and_then(NonZeroUsize::new)
+            .unwrap_or(NonZeroUsize::new(DEFAULT_CHECKPOINT_CACHE_SIZE).unwrap());
+        Self {
+            state,
+            kv_store,
+            archive_readers,
+            max_reconstruction_depth: config
+                .max_reconstruction_depth
+                .unwrap_or(DEFAULT_MAX_RECONSTRUCTION_DEPTH),
+            max_replays_per_query: config
+                .max_replays_per_query
+                .unwrap_or(DEFAULT_MAX_REPLAYS_PER_QUERY),
+            reconstruction_permits: Semaphore::new(
+                config
+                    .max_concurrent_reconstructions
+                    .unwrap_or(DEFAULT_MAX_CONCURRENT_RECONSTRUCTIONS)
+                    .max(1),
+            ),
+            reconstruction_wait_timeout: config
+                .reconstruction_wait_timeout_ms
+                .map_or(DEFAULT_RECONSTRUCTION_WAIT_TIMEOUT, Duration::from_millis),
+            objects: Mutex::new(LruCache::new(object_cache_size)),
+            checkpoints: Mutex::new(LruCache::new(checkpoint_cache_size)),
+            executors: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Read the object at the given version, reconstructing it from the state archive if it was
+    /// pruned. Returns `None` if no transaction wrote the object at this version, or if the
+    /// version could not be reconstructed.
+    pub async fn get_past_object(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<(ObjectRef, Object, Option<MoveStructLayout>)>> {
+        let replays = AtomicUsize::new(self.max_replays_per_query);
+        let Some(object) = self
+            .get_object_at_version(object_id, version, 0, &replays)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let layout = self.state.get_object_layout(&object)?;
+        Ok(Some((object.compute_object_reference(), object, layout)))
+    }
+
+    /// Read the object at the given version, reconstructing it if needed by replaying at most
+    /// `replays` transactions, which is shared by all the objects reconstructed for a query.
+    fn get_object_at_version<'a>(
+        &'a self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+        depth: usize,
+        replays: &'a AtomicUsize,
+    ) -> BoxFuture<'a, SuiResult<Option<Object>>> {
+        async move {
+            let key = ObjectKey(object_id, version);
+            let cached = self.objects.lock().get(&key).cloned();
+            if cached.is_some() {
+                return Ok(cached);
+            }
+            if let Some(object) = self.state.database.get_object_by_key(&object_id, version)? {
+                return Ok(Some(object));
+            }
+            if depth > self.max_reconstruction_depth {
+                debug!(
+                    "Not reconstructing {object_id:?} at version {version:?}, too many pruned \
+                     objects are needed to answer this query"
+                );
+                return Ok(None);
+            }
+            // Nested reconstructions run under the permit of the query, waiting for another
+            // permit could deadlock
+            let _permit = if depth == 0 {
+                let permit = tokio::time::timeout(
+                    self.reconstruction_wait_timeout,
+                    self.reconstruction_permits.acquire(),
+                )
+                .await
+                .map_err(|_| {
+                    SuiError::from("Too many past objects are being reconstructed, retry later")
+                })?
+                .map_err(|e| SuiError::from(e.to_string().as_str()))?;
+                Some(permit)
+            } else {
+                None
+            };
+
+            let Some(digest) = self.find_transaction_writing(object_id, version).await? else {
+                return Ok(None);
+            };
+            if replays
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                .is_err()
+            {
+                debug!(
+                    "Not reconstructing {object_id:?} at version {version:?}, too many \
+                     transactions must be replayed to answer this query"
+                );
+                return Ok(None);
+            }
+            let Some(written) = self.replay_transaction(digest, depth, replays).await? else {
+                return Ok(None);
+            };
+
+            let object = written.get(&object_id).map(|(_, object, _)| object.clone());
+            let mut objects = self.objects.lock();
+            for (obj_ref, object, _) in written.into_values() {
+                objects.put(ObjectKey(obj_ref.0, obj_ref.1), object);
+            }
+            Ok(object)
+        }
+        .boxed()
+    }
+
+    /// Binary search the transactions that created or mutated the object for the one that wrote
+    /// the given version, relying on every such transaction writing a higher version of the
+    /// object than the transactions indexed before it.
+    async fn find_transaction_writing(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<TransactionDigest>> {
+        let indexes = self.state.get_indexes()?;
+        let (mut low, mut high) = (0, indexes.next_sequence_number());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let Some((sequence, digest)) =
+                indexes.get_next_transaction_by_mutated_object(object_id, mid)?
+            else {
+                high = mid;
+                continue;
+            };
+            if sequence >= high {
+                high = mid;
+                continue;
+            }
+            let effects = self.kv_store.get_fx_by_tx_digest(digest).await?;
+            match written_version(&effects, object_id) {
+                Some(written) if written == version => return Ok(Some(digest)),
+                Some(written) if written < version => low = sequence + 1,
+                _ => high = mid,
+            }
+        }
+        Ok(None)
+    }
+
+    /// Execute the transaction again and return the objects it wrote, if its effects match the
+    /// archived effects.
+    async fn replay_transaction(
+        &self,
+        digest: TransactionDigest,
+        depth: usize,
+        replays: &AtomicUsize,
+    ) -> SuiResult<Option<WrittenObjects>> {
+        let Some(checkpoint) = self
+            .kv_store
+            .deprecated_get_transaction_checkpoint(digest)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let Some(ExecutionData {
+            transaction,
+            effects,
+        }) = self.read_archived_transaction(checkpoint, digest).await?
+        else {
+            debug!("Transaction {digest:?} is not in the archive for checkpoint {checkpoint}");
+            return Ok(None);
+        };
+        let epoch = effects.executed_epoch();
+        let Some((protocol_config, epoch_start_timestamp_ms)) = self.epoch_config(epoch)? else {
+            debug!("Cannot replay transaction {digest:?} executed in epoch {epoch}");
+            return Ok(None);
+        };
+
+        let tx_data = transaction.data().transaction_data();
+        let input_object_kinds = tx_data.input_objects()?;
+        let shared_object_versions: HashMap<_, _> = effects
+            .input_shared_objects()
+            .into_iter()
+            .map(|((id, version, _), _)| (id, version))
+            .collect();
+        let mut input_objects = Vec::with_capacity(input_object_kinds.len());
+        for kind in &input_object_kinds {
+            let object = match kind {
+                InputObjectKind::MovePackage(id) => self.state.database.get_package_object(id)?,
+                InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => {
+                    self.get_object_at_version(*id, *version, depth + 1, replays)
+                        .await?
+                }
+                InputObjectKind::SharedMoveObject { id, .. } => {
+                    match shared_object_versions.get(id) {
+                        Some(version) => {
+                            self.get_object_at_version(*id, *version, depth + 1, replays)
+                                .await?
+                        }
+                        None => None,
+                    }
+                }
+            };
+            let Some(object) = object else {
+                debug!(
+                    "Cannot replay transaction {digest:?}, input object {:?} is not available",
+                    kind.object_id()
+                );
+                return Ok(None);
+            };
+            input_objects.push(object);
+        }
+
+        // Child objects are read at runtime, so they have to be available before execution
+        // starts.
+        let mut child_objects = BTreeMap::new();
+        for (id, version) in self
+            .state
+            .loaded_child_object_versions(&digest)?
+            .unwrap_or_default()
+        {
+            if let Some(object) = self
+                .get_object_at_version(id, version, depth + 1, replays)
+                .await?
+            {
+                child_objects.insert(id, object);
+            }
+        }
+
+        let input_objects = check_objects(tx_data, input_object_kinds, input_objects)?;
+        let shared_object_refs = input_objects.filter_shared_objects();
+        let (kind, signer, gas) = tx_data.execution_parts();
+        let gas_status = if kind.is_system_tx() {
+            SuiGasStatus::new_unmetered()
+        } else {
+            // The reference gas price of the epoch only bounds the gas price from below, which
+            // the certified transaction is known to satisfy.
+            SuiGasStatus::new(
+                tx_data.gas_budget(),
+                tx_data.gas_price(),
+                tx_data.gas_price(),
+                &protocol_config,
+            )?
+        };
+        let store = Arc::new(ReplayStore {
+            store: self.state.database.clone(),
+            child_objects,
+        });
+        let executor = self.executor(&protocol_config)?;
+        let metrics = self.state.metrics.limits_metrics.clone();
+        let dependencies = effects.dependencies().iter().copied().collect();
+        // Execution is CPU bound, so it must not run on the threads serving requests
+        let (inner_temporary_store, replayed_effects, _) = tokio::task::spawn_blocking(move || {
+            executor.execute_transaction_to_effects(
+                store,
+                &protocol_config,
+                metrics,
+                false,
+                &HashSet::new(),
+                &epoch,
+                epoch_start_timestamp_ms,
+                input_objects,
+                shared_object_refs,
+                gas,
+                gas_status,
+                kind,
+                signer,
+                digest,
+                dependencies,
+            )
+        })
+        .await
+        .map_err(|e| SuiError::from(format!("Failed to replay transaction: {e}").as_str()))?;
+
+        if replayed_effects.digest() != effects.digest() {
+            warn!(
+                "Replaying transaction {digest:?} produced effects {:?}, expected {:?}",
+                replayed_effects.digest(),
+                effects.digest()
+            );
+            return Ok(None);
+        }
+        Ok(Some(inner_temporary_store.written))
+    }
+
+    async fn read_archived_transaction(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        digest: TransactionDigest,
+    ) -> SuiResult<Option<ExecutionData>> {
+        let Some(contents) = self.read_archived_checkpoint(checkpoint).await? else {
+            return Ok(None);
+        };
+        Ok(contents
+            .iter()
+            .find(|data| *data.transaction.digest() == digest)
+            .cloned())
+    }
+
+    /// The verified contents of the checkpoint, downloaded from the archive unless they are cached.
+    async fn read_archived_checkpoint(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<Option<Arc<FullCheckpointContents>>> {
+        let cached = self.checkpoints.lock().get(&checkpoint).cloned();
+        if cached.is_some() {
+            return Ok(cached);
+        }
+        let Some(reader) = self
+            .archive_readers
+            .pick_one_random(checkpoint..checkpoint + 1)
+            .await
+        else {
+            return Ok(None);
+        };
+        // With the local summary already in the store, the archive reader verifies the
+        // downloaded contents against it.
+        let verified_checkpoint = self
+            .state
+            .get_verified_checkpoint_by_sequence_number(checkpoint)?;
+        let store = SharedInMemoryStore::default();
+        store.insert_certified_checkpoint(&verified_checkpoint);
+        reader
+            .read(
+                store.clone(),
+                checkpoint..checkpoint + 1,
+                Arc::new(AtomicU64::new(0)),
+                Arc::new(AtomicU64::new(0)),
+            )
+            .await
+            .map_err(|e| SuiError::from(format!("Failed to read archive: {e}").as_str()))?;
+        let Some(contents) = store
+            .get_full_checkpoint_contents_by_sequence_number(checkpoint)
+            .ok()
+            .flatten()
+        else {
+            return Ok(None);
+        };
+        let contents = Arc::new(contents);
+        self.checkpoints.lock().put(checkpoint, contents.clone());
+        Ok(Some(contents))
+    }
+
+    /// The protocol config and start timestamp of the epoch. Every epoch starts at the timestamp
+    /// of the last checkpoint of the previous epoch, which also records its protocol version.
+    fn epoch_config(&self, epoch: EpochId) -> SuiResult<Option<(ProtocolConfig, u64)>> {
+        let epoch_store = self.state.load_epoch_store_one_call_per_task();
+        if epoch == epoch_store.epoch() {
+            return Ok(Some((
+                epoch_store.protocol_config().clone(),
+                epoch_store.epoch_start_config().epoch_start_timestamp_ms(),
+            )));
+        }
+        let Some(previous_epoch) = epoch.checked_sub(1) else {
+            return Ok(None);
+        };
+        let Some(checkpoint) = self
+            .state
+            .checkpoint_store
+            .get_epoch_last_checkpoint(previous_epoch)?
+        else {
+            return Ok(None);
+        };
+        let Some(end_of_epoch_data) = &checkpoint.end_of_epoch_data else {
+            return Ok(None);
+        };
+        let chain = epoch_store.get_chain_identifier().chain();
+        Ok(ProtocolConfig::get_for_version_if_supported(
+            end_of_epoch_data.next_epoch_protocol_version,
+            chain,
+        )
+        .map(|protocol_config| (protocol_config, checkpoint.timestamp_ms)))
+    }
+
+    fn executor(
+        &self,
+        protocol_config: &ProtocolConfig,
+    ) -> SuiResult<Arc<dyn Executor + Send + Sync>> {
+        let mut executors = self.executors.lock();
+        if let Some(executor) = executors.get(&protocol_config.version) {
+            return Ok(executor.clone());
+        }
+        let executor = sui_execution::executor(protocol_config, false, true)?;
+        executors.insert(protocol_config.version, executor.clone());
+        Ok(executor)
+    }
+}
+
+fn written_version(effects: &TransactionEffects, object_id: ObjectID) -> Option<SequenceNumber> {
+    effects
+        .all_changed_objects()
+        .into_iter()
+        .find(|((id, _, _), _, _)| *id == object_id)
+        .map(|((_, version, _), _, _)| version)
+}
+
+/// Serves the child objects loaded by the replayed transaction at the versions it originally
+/// loaded, and everything else from the local store. In particular, packages are served at their
+/// current version rather than at the version the transaction originally used.
+struct ReplayStore {
+    store: Arc<AuthorityStore>,
+    child_objects: BTreeMap<ObjectID, Object>,
+}
+
+impl BackingPackageStore for ReplayStore {
+    fn get_package_object(&self, package_id: &ObjectID) -> SuiResult<Option<Object>> {
+        self.store.get_package_object(package_id)
+    }
+}
+
+impl ChildObjectResolver for ReplayStore {
+    fn read_child_object(
+        &self,
+        parent: &ObjectID,
+        child: &ObjectID,
+        child_version_upper_bound: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        let Some(child_object) = self
+            .child_objects
+            .get(child)
+            .filter(|object| object.version() <= child_version_upper_bound)
+        else {
+            return self
+                .store
+                .read_child_object(parent, child, child_version_upper_bound);
+        };
+
+        if child_object.owner != Owner::ObjectOwner((*parent).into()) {
+            return Err(SuiError::InvalidChildObjectAccess {
+                object: *child,
+                given_parent: *parent,
+                actual_owner: child_object.owner,
+            });
+        }
+        Ok(Some(child_object.clone()))
+    }
+}
+
+impl ObjectStore for ReplayStore {
+    fn get_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        match self.child_objects.get(object_id) {
+            Some(object) => Ok(Some(object.clone())),
+            None => self.store.get_object(object_id),
+        }
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        match self.child_objects.get(object_id) {
+            Some(object) if object.version() == version => Ok(Some(object.clone())),
+            _ => self.store.get_object_by_key(object_id, version),
+        }
+    }
+}
+
+impl ParentSync for ReplayStore {
+    fn get_latest_parent_entry_ref_deprecated(
+        &self,
+        object_id: ObjectID,
+    ) -> SuiResult<Option<ObjectRef>> {
+        self.store.get_latest_parent_entry_ref_deprecated(object_id)
+    }
+}
+
+impl GetModule for ReplayStore {
+    type Error = SuiError;
+    type Item = CompiledModule;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> Result<Option<Self::Item>, Self::Error> {
+        get_module_by_id(self, id)
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/historical_object_reader_tests.rs"]
+mod historical_object_reader_tests;
@@ -12,8 +12,10 @@ pub mod checkpoints;
 pub mod consensus_adapter;
 pub mod consensus_handler;
 pub mod consensus_validator;
+pub mod db_backup_handler;
 pub mod db_checkpoint_handler;
 pub mod epoch;
+pub mod historical_object_reader;
 mod execution_driver;
 mod math;
 pub mod metrics;
@@ -0,0 +1,513 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{written_version, HistoricalObjectReader, ReplayStore};
+use crate::authority::authority_test_utils::send_and_confirm_transaction;
+use crate::authority::test_authority_builder::TestAuthorityBuilder;
+use crate::authority::AuthorityState;
+use prometheus::Registry;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_archival::reader::ArchiveReaderBalancer;
+use sui_archival::writer::ArchiveWriter;
+use sui_config::node::{ArchiveReaderConfig, HistoricalObjectReadConfig};
+use sui_storage::key_value_store::TransactionKeyValueStore;
+use sui_storage::key_value_store_metrics::KeyValueStoreMetrics;
+use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+use sui_storage::{FileCompression, StorageFormat};
+use sui_test_transaction_builder::TestTransactionBuilder;
+use sui_types::base_types::{ExecutionData, ObjectID, ObjectRef, SuiAddress};
+use sui_types::committee::Committee;
+use sui_types::crypto::{get_key_pair, AccountKeyPair, AuthorityKeyPair, KeypairTraits};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::error::SuiError;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointSummary, FullCheckpointContents, SignedCheckpointSummary,
+    VerifiedCheckpoint, VerifiedCheckpointContents,
+};
+use sui_types::object::{Object, OBJECT_START_VERSION};
+use sui_types::storage::{ChildObjectResolver, ObjectKey, ObjectStore, SharedInMemoryStore};
+use tempfile::TempDir;
+use typed_store::Map;
+
+struct TestState {
+    state: Arc<AuthorityState>,
+    /// Objects transferred by the transactions
+    objects: [ObjectID; 2],
+    /// Gas coins paying for the transfers of each object
+    gas: [ObjectID; 2],
+    transactions: Vec<ExecutionData>,
+}
+
+async fn latest_ref(state: &AuthorityState, object_id: ObjectID) -> ObjectRef {
+    state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .compute_object_reference()
+}
+
+/// Transfers `object_id` to its owner, so that it is written at a new version along with `gas`.
+async fn transfer(
+    state: &AuthorityState,
+    sender: SuiAddress,
+    key: &AccountKeyPair,
+    object_id: ObjectID,
+    gas: ObjectID,
+) -> ExecutionData {
+    let rgp = state.reference_gas_price_for_testing().unwrap();
+    let transaction = TestTransactionBuilder::new(sender, latest_ref(state, gas).await, rgp)
+        .transfer(latest_ref(state, object_id).await, sender)
+        .build_and_sign(key);
+    let (_, effects) = send_and_confirm_transaction(state, transaction.clone())
+        .await
+        .unwrap();
+    ExecutionData::new(transaction, effects.into_data())
+}
+
+fn file_store_config(path: &Path) -> ObjectStoreConfig {
+    ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(path.to_path_buf()),
+        ..Default::default()
+    }
+}
+
+fn certify(
+    sequence_number: u64,
+    previous: Option<&VerifiedCheckpoint>,
+    contents: &FullCheckpointContents,
+    committee: &Committee,
+    keys: &[AuthorityKeyPair],
+) -> VerifiedCheckpoint {
+    let summary = CheckpointSummary::new(
+        committee.epoch,
+        sequence_number,
+        0,
+        &contents.checkpoint_contents(),
+        previous.map(|checkpoint| *checkpoint.digest()),
+        Default::default(),
+        None,
+        0,
+    );
+    let signatures: Vec<_> = keys
+        .iter()
+        .map(|key| {
+            SignedCheckpointSummary::sign(committee.epoch, &summary, key, key.public().into())
+        })
+        .collect();
+    VerifiedCheckpoint::new_unchecked(
+        CertifiedCheckpointSummary::new(summary, signatures, committee).unwrap(),
+    )
+}
+
+impl TestState {
+    async fn new() -> Self {
+        let (sender, key): (_, AccountKeyPair) = get_key_pair();
+        let objects = [ObjectID::random(), ObjectID::random()];
+        let gas = [ObjectID::random(), ObjectID::random()];
+        let starting_objects: Vec<_> = objects
+            .iter()
+            .chain(gas.iter())
+            .map(|id| Object::with_id_owner_for_testing(*id, sender))
+            .collect();
+        let state = TestAuthorityBuilder::new()
+            .with_starting_objects(&starting_objects)
+            .build()
+            .await;
+        // The transfers of both objects are interleaved, so that the transactions writing an
+        // object are not next to each other in the index
+        let mut transactions = vec![];
+        for i in [0, 1, 1, 0, 0] {
+            transactions.push(transfer(&state, sender, &key, objects[i], gas[i]).await);
+        }
+        Self {
+            state,
+            objects,
+            gas,
+            transactions,
+        }
+    }
+
+    fn reader(&self, archive_readers: ArchiveReaderBalancer) -> HistoricalObjectReader {
+        self.reader_with_config(archive_readers, &HistoricalObjectReadConfig::default())
+    }
+
+    fn reader_with_config(
+        &self,
+        archive_readers: ArchiveReaderBalancer,
+        config: &HistoricalObjectReadConfig,
+    ) -> HistoricalObjectReader {
+        let kv_store = Arc::new(TransactionKeyValueStore::new(
+            "rocksdb",
+            KeyValueStoreMetrics::new_for_tests(),
+            self.state.clone(),
+        ));
+        HistoricalObjectReader::new(self.state.clone(), kv_store, archive_readers, config)
+    }
+
+    /// The versions written by the transactions, and the objects at these versions.
+    fn written_objects(&self) -> BTreeMap<ObjectKey, Object> {
+        let mut written = BTreeMap::new();
+        for data in self.transactions.iter() {
+            for id in self.objects.iter().chain(self.gas.iter()) {
+                if let Some(version) = written_version(&data.effects, *id) {
+                    let object = self
+                        .state
+                        .database
+                        .get_object_by_key(id, version)
+                        .unwrap()
+                        .unwrap();
+                    written.insert(ObjectKey(*id, version), object);
+                }
+            }
+        }
+        written
+    }
+
+    /// Deletes every written version but the latest one of each object from the local store.
+    async fn prune(&self) {
+        for key in self.written_objects().keys() {
+            if latest_ref(&self.state, key.0).await.1 != key.1 {
+                self.state
+                    .database
+                    .perpetual_tables
+                    .objects
+                    .remove(key)
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Archives the transactions in checkpoint 1 in `dir`, after altering the effects of the
+    /// transaction at index `tampered` if any. The checkpoint summaries are certified by a
+    /// different committee than the one of the node, which is never checked since the summary
+    /// of checkpoint 1 is inserted in the checkpoint store of the node as if it was synced.
+    async fn archive(&self, dir: &Path, tampered: Option<usize>) -> ArchiveReaderBalancer {
+        let mut transactions = self.transactions.clone();
+        if let Some(index) = tampered {
+            transactions[index]
+                .effects
+                .gas_cost_summary_mut_for_testing()
+                .computation_cost += 1;
+        }
+        // Checkpoints are only archived once the next one is, hence the empty checkpoint 2
+        let contents = [
+            FullCheckpointContents::new_with_causally_ordered_transactions(vec![]),
+            FullCheckpointContents::new_with_causally_ordered_transactions(transactions),
+            FullCheckpointContents::new_with_causally_ordered_transactions(vec![]),
+        ];
+        let (committee, keys) = Committee::new_simple_test_committee();
+        let mut checkpoints: Vec<VerifiedCheckpoint> = vec![];
+        for (sequence_number, contents) in contents.iter().enumerate() {
+            let checkpoint = certify(
+                sequence_number as u64,
+                checkpoints.last(),
+                contents,
+                &committee,
+                &keys,
+            );
+            checkpoints.push(checkpoint);
+        }
+
+        self.state
+            .checkpoint_store
+            .insert_verified_checkpoint(&checkpoints[1])
+            .unwrap();
+        let digests: Vec<_> = self
+            .transactions
+            .iter()
+            .map(|data| *data.transaction.digest())
+            .collect();
+        self.state
+            .database
+            .deprecated_insert_finalized_transactions(&digests, 0, 1)
+            .unwrap();
+
+        let store = SharedInMemoryStore::default();
+        store.inner_mut().insert_genesis_state(
+            checkpoints[0].clone(),
+            VerifiedCheckpointContents::new_unchecked(contents[0].clone()),
+            committee,
+        );
+        for (checkpoint, contents) in checkpoints.iter().zip(contents).skip(1) {
+            store.inner_mut().insert_checkpoint(checkpoint);
+            store.inner_mut().insert_checkpoint_contents(
+                checkpoint,
+                VerifiedCheckpointContents::new_unchecked(contents),
+            );
+        }
+        let remote_store_config = file_store_config(&dir.join("remote"));
+        // Every checkpoint is written to its own file
+        let archive_writer = ArchiveWriter::new(
+            file_store_config(&dir.join("local")),
+            remote_store_config.clone(),
+            FileCompression::Zstd,
+            StorageFormat::Blob,
+            Duration::from_secs(10),
+            1,
+            &Registry::default(),
+        )
+        .await
+        .unwrap();
+        let kill = archive_writer.start(store).await.unwrap();
+
+        let archive_readers = ArchiveReaderBalancer::new(
+            vec![ArchiveReaderConfig {
+                remote_store_config,
+                download_concurrency: NonZeroUsize::new(1).unwrap(),
+                use_for_pruning_watermark: false,
+            }],
+            &Registry::default(),
+        )
+        .unwrap();
+        let archive_reader = archive_readers.pick_one_random(0..u64::MAX).await.unwrap();
+        loop {
+            if archive_reader.sync_manifest_once().await.is_ok()
+                && archive_reader
+                    .latest_available_checkpoint()
+                    .await
+                    .map_or(false, |checkpoint| checkpoint >= 1)
+            {
+                break;
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        kill.send(()).unwrap();
+        archive_readers
+    }
+}
+
+#[tokio::test]
+async fn test_find_transaction_writing() {
+    let test_state = TestState::new().await;
+    let reader = test_state.reader(ArchiveReaderBalancer::default());
+    let ids: Vec<_> = test_state
+        .objects
+        .iter()
+        .chain(test_state.gas.iter())
+        .collect();
+
+    for data in test_state.transactions.iter() {
+        for id in ids.iter() {
+            if let Some(version) = written_version(&data.effects, **id) {
+                assert_eq!(
+                    reader
+                        .find_transaction_writing(**id, version)
+                        .await
+                        .unwrap(),
+                    Some(*data.transaction.digest())
+                );
+            }
+        }
+    }
+    for id in ids {
+        // The starting objects were not written by any transaction
+        assert_eq!(
+            reader
+                .find_transaction_writing(*id, OBJECT_START_VERSION)
+                .await
+                .unwrap(),
+            None
+        );
+        let latest_version = latest_ref(&test_state.state, *id).await.1;
+        assert_eq!(
+            reader
+                .find_transaction_writing(*id, latest_version.next())
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_reconstruct_pruned_objects() {
+    let test_state = TestState::new().await;
+    let dir = TempDir::new().unwrap();
+    let archive_readers = test_state.archive(dir.path(), None).await;
+    let reader = test_state.reader(archive_readers.clone());
+    let written = test_state.written_objects();
+    test_state.prune().await;
+
+    // The second version of the first object is written by the fourth transaction, whose input
+    // objects are pruned as well and reconstructed by replaying the first one
+    let object_id = test_state.objects[0];
+    let version = written_version(&test_state.transactions[3].effects, object_id).unwrap();
+    assert!(test_state
+        .state
+        .database
+        .get_object_by_key(&object_id, version)
+        .unwrap()
+        .is_none());
+    let (object_ref, object, _) = reader
+        .get_past_object(object_id, version)
+        .await
+        .unwrap()
+        .unwrap();
+    let expected = &written[&ObjectKey(object_id, version)];
+    assert_eq!(&object, expected);
+    assert_eq!(object_ref, expected.compute_object_reference());
+
+    let gas_version = written_version(&test_state.transactions[0].effects, test_state.gas[0]);
+    assert!(reader
+        .objects
+        .lock()
+        .contains(&ObjectKey(test_state.gas[0], gas_version.unwrap())));
+    // Both transactions are in the same checkpoint, which is downloaded once
+    assert!(reader.checkpoints.lock().contains(&1));
+
+    for (key, expected) in written.iter() {
+        let (_, object, _) = reader.get_past_object(key.0, key.1).await.unwrap().unwrap();
+        assert_eq!(&object, expected);
+    }
+
+    let latest_version = latest_ref(&test_state.state, object_id).await.1;
+    assert!(reader
+        .get_past_object(object_id, latest_version.next())
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_replay_with_different_effects_is_rejected() {
+    let test_state = TestState::new().await;
+    let dir = TempDir::new().unwrap();
+    // The archived effects of the first transaction are not the ones replaying it produces
+    let reader = test_state.reader(test_state.archive(dir.path(), Some(0)).await);
+    test_state.prune().await;
+
+    let object_id = test_state.objects[0];
+    let version = written_version(&test_state.transactions[0].effects, object_id).unwrap();
+    assert!(reader
+        .get_past_object(object_id, version)
+        .await
+        .unwrap()
+        .is_none());
+    // Neither can the versions be reconstructed which need it as an input
+    let version = written_version(&test_state.transactions[3].effects, object_id).unwrap();
+    assert!(reader
+        .get_past_object(object_id, version)
+        .await
+        .unwrap()
+        .is_none());
+
+    // The other transactions are replayed as usual
+    let object_id = test_state.objects[1];
+    let version = written_version(&test_state.transactions[1].effects, object_id).unwrap();
+    assert!(reader
+        .get_past_object(object_id, version)
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_replay_store_child_objects() {
+    let parent = ObjectID::random();
+    let stored_child = Object::with_object_owner_for_testing(ObjectID::random(), parent);
+    let state = TestAuthorityBuilder::new()
+        .with_starting_objects(&[stored_child.clone()])
+        .build()
+        .await;
+
+    let child = Object::with_object_owner_for_testing(ObjectID::random(), parent);
+    let store = ReplayStore {
+        store: state.database.clone(),
+        child_objects: BTreeMap::from([(child.id(), child.clone())]),
+    };
+    let version = child.version();
+
+    // Loaded child objects are served at the version the transaction loaded
+    assert_eq!(
+        store
+            .read_child_object(&parent, &child.id(), version)
+            .unwrap(),
+        Some(child.clone())
+    );
+    assert_eq!(store.get_object(&child.id()).unwrap(), Some(child.clone()));
+    assert_eq!(
+        store.get_object_by_key(&child.id(), version).unwrap(),
+        Some(child.clone())
+    );
+    assert_eq!(
+        store
+            .get_object_by_key(&child.id(), version.next())
+            .unwrap(),
+        None
+    );
+
+    // Unless they are read at an earlier version, or by another parent
+    assert_eq!(
+        store
+            .read_child_object(&parent, &child.id(), version.one_before().unwrap())
+            .unwrap(),
+        None
+    );
+    assert!(matches!(
+        store.read_child_object(&ObjectID::random(), &child.id(), version),
+        Err(SuiError::InvalidChildObjectAccess { .. })
+    ));
+
+    // Other child objects are read from the local store
+    assert_eq!(
+        store
+            .read_child_object(&parent, &stored_child.id(), version)
+            .unwrap(),
+        Some(stored_child)
+    );
+}
+
+#[tokio::test]
+async fn test_reconstruction_limits() {
+    let test_state = TestState::new().await;
+    let dir = TempDir::new().unwrap();
+    let archive_readers = test_state.archive(dir.path(), None).await;
+    test_state.prune().await;
+    let object_id = test_state.objects[0];
+    // Reconstructing this version replays both the first and the fourth transactions
+    let version = written_version(&test_state.transactions[3].effects, object_id).unwrap();
+
+    let reader = test_state.reader_with_config(
+        archive_readers.clone(),
+        &HistoricalObjectReadConfig {
+            max_replays_per_query: Some(1),
+            ..Default::default()
+        },
+    );
+    assert!(reader
+        .get_past_object(object_id, version)
+        .await
+        .unwrap()
+        .is_none());
+    // A version written by a transaction whose inputs are not pruned only needs one replay
+    let version = written_version(&test_state.transactions[0].effects, object_id).unwrap();
+    assert!(reader
+        .get_past_object(object_id, version)
+        .await
+        .unwrap()
+        .is_some());
+
+    // Queries fail once they waited too long for other reconstructions to finish
+    let reader = test_state.reader_with_config(
+        archive_readers,
+        &HistoricalObjectReadConfig {
+            max_concurrent_reconstructions: Some(1),
+            reconstruction_wait_timeout_ms: Some(100),
+            ..Default::default()
+        },
+    );
+    let permit = reader.reconstruction_permits.acquire().await.unwrap();
+    assert!(reader.get_past_object(object_id, version).await.is_err());
+    drop(permit);
+    assert!(reader
+        .get_past_object(object_id, version)
+        .await
+        .unwrap()
+        .is_some());
+}
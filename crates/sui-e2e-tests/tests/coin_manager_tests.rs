@@ -0,0 +1,103 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::future::try_join_all;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_config::SUI_KEYSTORE_FILENAME;
+use sui_keys::keystore::{FileBasedKeystore, Keystore};
+use sui_sdk::coin_manager::{CoinManager, CoinManagerConfig};
+use sui_sdk::{SuiClientBuilder, SUI_COIN_TYPE};
+use sui_swarm_config::genesis_config::DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT;
+use test_cluster::TestClusterBuilder;
+
+#[tokio::test]
+async fn test_coin_manager() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let address = test_cluster.get_address_0();
+    let client = SuiClientBuilder::default()
+        .build(test_cluster.rpc_url())
+        .await?;
+    let keystore = Arc::new(Keystore::from(FileBasedKeystore::new(
+        &test_cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME),
+    )?));
+
+    let config = CoinManagerConfig {
+        gas_pool_size: 8,
+        ..Default::default()
+    };
+    let manager = Arc::new(CoinManager::new(
+        client.clone(),
+        keystore.clone(),
+        address,
+        config.clone(),
+    ));
+    let report = manager.maintain().await?;
+    // All genesis coins but the reserve are already part of the pool.
+    assert_eq!(report.merged, 0);
+    assert_eq!(
+        report.split,
+        config.gas_pool_size - (DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT - 1)
+    );
+    assert!(report.digest.is_some());
+    let split = report.split;
+
+    // Concurrent callers are handed out distinct coins.
+    let leases = try_join_all(
+        (0..config.gas_pool_size).map(|_| manager.acquire_gas(config.gas_coin_balance)),
+    )
+    .await?;
+    let coins = leases
+        .iter()
+        .map(|lease| lease.object_ref().0)
+        .collect::<BTreeSet<_>>();
+    assert_eq!(coins.len(), config.gas_pool_size);
+    for lease in leases {
+        lease.release(None).await?;
+    }
+
+    // A coin whose lease is dropped may be used by a transaction in flight, so it is not handed
+    // out again at the same version.
+    let dropped = manager
+        .acquire_gas(config.gas_coin_balance)
+        .await?
+        .object_ref()
+        .0;
+    let leases = try_join_all(
+        (1..config.gas_pool_size).map(|_| manager.acquire_gas(config.gas_coin_balance)),
+    )
+    .await?;
+    assert!(leases.iter().all(|lease| lease.object_ref().0 != dropped));
+    assert!(tokio::time::timeout(
+        Duration::from_secs(3),
+        manager.acquire_gas(config.gas_coin_balance)
+    )
+    .await
+    .is_err());
+    for lease in leases {
+        lease.release(None).await?;
+    }
+
+    // The split coins are merged back as dust.
+    let manager = CoinManager::new(
+        client.clone(),
+        keystore,
+        address,
+        CoinManagerConfig {
+            gas_pool_size: 0,
+            dust_threshold: config.gas_coin_balance + 1,
+            ..config
+        },
+    );
+    let report = manager.maintain().await?;
+    assert_eq!(report.merged, split);
+    assert_eq!(report.split, 0);
+
+    let coins = client
+        .coin_read_api()
+        .get_coins(address, Some(SUI_COIN_TYPE.to_string()), None, None)
+        .await?;
+    assert_eq!(coins.data.len(), DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT);
+    Ok(())
+}
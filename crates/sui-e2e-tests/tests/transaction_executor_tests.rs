@@ -0,0 +1,83 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
+use sui_sdk::error::Error;
+use sui_sdk::transaction_executor::{
+    TransactionExecutor, TransactionExecutorConfig, TransactionOutcome,
+};
+use sui_sdk::SuiClientBuilder;
+use sui_swarm_config::genesis_config::DEFAULT_GAS_AMOUNT;
+use sui_types::transaction::Transaction;
+use test_cluster::TestClusterBuilder;
+
+#[tokio::test]
+async fn test_transaction_executor() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let client = SuiClientBuilder::default()
+        .build(test_cluster.rpc_url())
+        .await?;
+    let executor = TransactionExecutor::new(client, TransactionExecutorConfig::default());
+    let recipient = test_cluster.get_address_1();
+
+    let tx_data = test_cluster
+        .test_transaction_builder()
+        .await
+        .transfer_sui(Some(1_000), recipient)
+        .build();
+    let tx = test_cluster.wallet.sign_transaction(&tx_data);
+    let TransactionOutcome::Committed(response) = executor
+        .execute(tx.clone(), SuiTransactionBlockResponseOptions::new())
+        .await?
+    else {
+        panic!("Transaction should be committed");
+    };
+    assert_eq!(response.digest, *tx.digest());
+    assert!(response.effects.is_some());
+
+    // Executing the same transaction again returns the outcome of the committed one.
+    let outcome = executor
+        .execute(tx.clone(), SuiTransactionBlockResponseOptions::new())
+        .await?;
+    assert!(outcome.is_success());
+    assert_eq!(outcome.response().digest, *tx.digest());
+
+    // Execution failures are committed.
+    let tx_data = test_cluster
+        .test_transaction_builder()
+        .await
+        .transfer_sui(Some(DEFAULT_GAS_AMOUNT), recipient)
+        .build();
+    let tx = test_cluster.wallet.sign_transaction(&tx_data);
+    let outcome = executor
+        .execute(tx, SuiTransactionBlockResponseOptions::new())
+        .await?;
+    assert!(matches!(outcome, TransactionOutcome::Failed { .. }));
+
+    // Invalid signatures are rejected without retrying.
+    let tx_data = test_cluster
+        .test_transaction_builder()
+        .await
+        .transfer_sui(Some(1_000), recipient)
+        .build();
+    let other_tx = test_cluster.wallet.sign_transaction(
+        &test_cluster
+            .test_transaction_builder()
+            .await
+            .transfer_sui(Some(2_000), recipient)
+            .build(),
+    );
+    let tx = Transaction::from_generic_sig_data(
+        tx_data,
+        other_tx.intent_message().intent.clone(),
+        other_tx.tx_signatures().to_vec(),
+    );
+    let result = executor
+        .execute(tx.clone(), SuiTransactionBlockResponseOptions::new())
+        .await;
+    assert!(
+        matches!(result, Err(Error::TransactionRejected(digest, _)) if digest == *tx.digest()),
+        "{result:?}"
+    );
+    Ok(())
+}
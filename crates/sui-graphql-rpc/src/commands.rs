@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::extensions::query_limits_checker::Limits;
 use clap::*;
 use std::path::PathBuf;
 
@@ -21,11 +22,68 @@ pub enum Command {
         /// URL of the RPC server for data fetching
         #[clap(short, long, default_value = "https://fullnode.testnet.sui.io:443/")]
         rpc_url: String,
+        /// URL of the RPC server's WebSocket endpoint, required for subscriptions
+        #[clap(short, long)]
+        ws_url: Option<String>,
+        /// URL of the indexer's Postgres database to serve queries from, instead of the RPC server
+        #[clap(long)]
+        db_url: Option<String>,
         /// Port to bind the server to
         #[clap(short, long, default_value = "8000")]
         port: u16,
         /// Host to bind the server to
         #[clap(long, default_value = "127.0.0.1")]
         host: String,
+        #[clap(flatten)]
+        limits: LimitsArgs,
     },
 }
+
+/// Limits of the queries, read from the config file if any, then overridden by the flags.
+#[derive(Args)]
+pub struct LimitsArgs {
+    /// Path to a TOML file setting the limits of queries, e.g. `max-query-depth = 10`
+    #[clap(long)]
+    pub config: Option<PathBuf>,
+    /// Maximum nesting of the fields of a query
+    #[clap(long)]
+    pub max_query_depth: Option<u32>,
+    /// Maximum number of fields in a query, once its fragments are expanded
+    #[clap(long)]
+    pub max_query_nodes: Option<u32>,
+    /// Maximum number of values in the response, assuming every page is full
+    #[clap(long)]
+    pub max_output_nodes: Option<u64>,
+    /// Maximum value of the `first` and `last` arguments of connections
+    #[clap(long)]
+    pub max_page_size: Option<u64>,
+    /// Maximum number of requests to the data provider a query is estimated to require
+    #[clap(long)]
+    pub max_backend_cost: Option<u64>,
+}
+
+impl LimitsArgs {
+    pub fn limits(&self) -> std::io::Result<Limits> {
+        let mut limits = match &self.config {
+            Some(path) => toml::from_str(&std::fs::read_to_string(path)?)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?,
+            None => Limits::default(),
+        };
+        if let Some(max) = self.max_query_depth {
+            limits.max_query_depth = max;
+        }
+        if let Some(max) = self.max_query_nodes {
+            limits.max_query_nodes = max;
+        }
+        if let Some(max) = self.max_output_nodes {
+            limits.max_output_nodes = max;
+        }
+        if let Some(max) = self.max_page_size {
+            limits.max_page_size = max;
+        }
+        if let Some(max) = self.max_backend_cost {
+            limits.max_backend_cost = max;
+        }
+        Ok(limits)
+    }
+}
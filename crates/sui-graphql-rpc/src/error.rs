@@ -10,6 +10,7 @@ use async_graphql_axum::GraphQLResponse;
 pub mod code {
     pub const BAD_REQUEST: &str = "BAD_REQUEST";
     pub const BAD_USER_INPUT: &str = "BAD_USER_INPUT";
+    pub const GRAPHQL_VALIDATION_FAILED: &str = "GRAPHQL_VALIDATION_FAILED";
     pub const INTERNAL_SERVER_ERROR: &str = "INTERNAL_SERVER_ERROR";
 }
 
@@ -46,6 +47,14 @@ pub enum Error {
     InvalidCursor(String),
     #[error("Data has changed since cursor was generated: {0}")]
     CursorConnectionFetchFailed(String),
+    #[error("Unsupported filter: {0}")]
+    UnsupportedFilter(String),
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+    #[error("Invalid transaction: {0}")]
+    InvalidTransaction(String),
+    #[error("{0}")]
+    QueryLimitExceeded(String),
     #[error("Internal error occurred while processing request.")]
     Internal(String),
 }
@@ -57,9 +66,15 @@ impl ErrorExtensions for Error {
             | Error::CursorNoFirstLast
             | Error::CursorNoReversePagination
             | Error::InvalidCursor(_)
-            | Error::CursorConnectionFetchFailed(_) => {
+            | Error::CursorConnectionFetchFailed(_)
+            | Error::UnsupportedFilter(_)
+            | Error::InvalidFilter(_)
+            | Error::InvalidTransaction(_) => {
                 e.set("code", code::BAD_USER_INPUT);
             }
+            Error::QueryLimitExceeded(_) => {
+                e.set("code", code::GRAPHQL_VALIDATION_FAILED);
+            }
             Error::Internal(_) => {
                 e.set("code", code::INTERNAL_SERVER_ERROR);
             }
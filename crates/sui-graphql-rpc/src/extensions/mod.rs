@@ -2,4 +2,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod logger;
+pub mod query_limits_checker;
 pub mod timeout;
@@ -0,0 +1,504 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use crate::server::sui_sdk_data_provider::DEFAULT_PAGE_SIZE;
+use async_graphql::{
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery,
+        NextPrepareRequest,
+    },
+    parser::types::{
+        DocumentOperations, ExecutableDocument, Field, OperationDefinition, Selection,
+        SelectionSet, VariableDefinition,
+    },
+    value, ErrorExtensions, Name, Positioned, Request, Response, ServerResult, Value, Variables,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Limits of the queries, which can be set in the config file of the server with the
+/// kebab-case names of the fields, e.g. `max-query-depth = 10`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Limits {
+    /// Maximum nesting of the fields of a query.
+    pub max_query_depth: u32,
+    /// Maximum number of fields in a query, once its fragments are expanded.
+    pub max_query_nodes: u32,
+    /// Maximum number of values in the response, assuming every page is full.
+    pub max_output_nodes: u64,
+    /// Maximum value of the `first` and `last` arguments of connections.
+    pub max_page_size: u64,
+    /// Maximum number of requests to the data provider a query is estimated to require.
+    pub max_backend_cost: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_query_depth: 20,
+            max_query_nodes: 300,
+            max_output_nodes: 100_000,
+            max_page_size: DEFAULT_PAGE_SIZE as u64,
+            max_backend_cost: 1_000,
+        }
+    }
+}
+
+/// Rejects the queries that exceed the [Limits] before they are executed, and reports what the
+/// executed query uses of them in the `usage` extension of the response.
+#[derive(Clone, Debug, Default)]
+pub struct QueryLimitsChecker {
+    limits: Limits,
+}
+
+impl QueryLimitsChecker {
+    pub fn new(limits: Limits) -> Self {
+        Self { limits }
+    }
+}
+
+impl ExtensionFactory for QueryLimitsChecker {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(QueryLimitsCheckerExtension {
+            limits: self.limits,
+            operation_name: Mutex::new(None),
+            usage: Mutex::new(None),
+        })
+    }
+}
+
+struct QueryLimitsCheckerExtension {
+    limits: Limits,
+    /// Name of the operation of the document to execute, as requested.
+    operation_name: Mutex<Option<String>>,
+    /// Usage of the operation to execute.
+    usage: Mutex<Option<Usage>>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Usage {
+    depth: u32,
+    nodes: u32,
+    output_nodes: u64,
+    backend_cost: u64,
+}
+
+impl Usage {
+    fn to_value(self) -> Value {
+        value!({
+            "depth": self.depth,
+            "nodes": self.nodes,
+            "outputNodes": self.output_nodes,
+            "backendCost": self.backend_cost,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for QueryLimitsCheckerExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        *self.operation_name.lock().await = request.operation_name.clone();
+        next.run(ctx, request).await
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        let operation_name = self.operation_name.lock().await.clone();
+        // Only the operation to execute is checked, if there is none the request fails anyway.
+        let usage = match selected_operation(&document, operation_name.as_deref()) {
+            Some(operation) => {
+                let mut checker = LimitsChecker {
+                    limits: &self.limits,
+                    document: &document,
+                    variables,
+                    variable_definitions: &operation.node.variable_definitions,
+                    fragments: vec![],
+                    usage: Usage::default(),
+                };
+                checker.check_selection_set(&operation.node.selection_set, 0, 1, None)?;
+                Some(checker.usage)
+            }
+            None => None,
+        };
+        *self.usage.lock().await = usage;
+        Ok(document)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let response = next.run(ctx, operation_name).await;
+        match *self.usage.lock().await {
+            Some(usage) => response.extension("usage", usage.to_value()),
+            None => response,
+        }
+    }
+}
+
+/// The operation of the document that is executed for the request naming `operation_name`, the
+/// same way the schema selects it.
+fn selected_operation<'a>(
+    document: &'a ExecutableDocument,
+    operation_name: Option<&str>,
+) -> Option<&'a Positioned<OperationDefinition>> {
+    match (&document.operations, operation_name) {
+        (DocumentOperations::Single(operation), None) => Some(operation),
+        (DocumentOperations::Multiple(operations), Some(name)) => operations.get(name),
+        (DocumentOperations::Multiple(operations), None) if operations.len() == 1 => {
+            operations.values().next()
+        }
+        _ => None,
+    }
+}
+
+/// Accumulates the usage of an operation while walking through its selections, and fails as soon
+/// as a limit is exceeded, which also bounds the work spent on documents that expand
+/// exponentially through their fragments.
+struct LimitsChecker<'a> {
+    limits: &'a Limits,
+    document: &'a ExecutableDocument,
+    variables: &'a Variables,
+    variable_definitions: &'a [Positioned<VariableDefinition>],
+    /// Fragments being expanded, to stop at cycles, which are rejected by validation later on.
+    fragments: Vec<&'a Name>,
+    usage: Usage,
+}
+
+impl<'a> LimitsChecker<'a> {
+    /// Checks the selections of a field resolved `multiplicity` times and nested `depth` fields
+    /// deep. `page_size` is set when the field is a connection.
+    fn check_selection_set(
+        &mut self,
+        selection_set: &'a Positioned<SelectionSet>,
+        depth: u32,
+        multiplicity: u64,
+        page_size: Option<u64>,
+    ) -> ServerResult<()> {
+        for selection in &selection_set.node.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    self.check_field(field, depth, multiplicity, page_size)?
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = &spread.node.fragment_name.node;
+                    if self.fragments.contains(&name) {
+                        continue;
+                    }
+                    // Unknown fragments are rejected by validation.
+                    let Some(fragment) = self.document.fragments.get(name) else {
+                        continue;
+                    };
+                    self.fragments.push(name);
+                    self.check_selection_set(
+                        &fragment.node.selection_set,
+                        depth,
+                        multiplicity,
+                        page_size,
+                    )?;
+                    self.fragments.pop();
+                }
+                Selection::InlineFragment(fragment) => self.check_selection_set(
+                    &fragment.node.selection_set,
+                    depth,
+                    multiplicity,
+                    page_size,
+                )?,
+            }
+        }
+        Ok(())
+    }
+
+    fn check_field(
+        &mut self,
+        field: &'a Positioned<Field>,
+        depth: u32,
+        multiplicity: u64,
+        parent_page_size: Option<u64>,
+    ) -> ServerResult<()> {
+        let name = field.node.name.node.as_str();
+        // Introspection is served by the schema itself.
+        if name.starts_with("__") {
+            return Ok(());
+        }
+        let exceeded = |limit: &str, max: u64| {
+            Error::QueryLimitExceeded(format!("Query exceeds the maximum {limit} of {max}"))
+                .extend()
+                .into_server_error(field.pos)
+        };
+
+        let depth = depth + 1;
+        self.usage.depth = self.usage.depth.max(depth);
+        if depth > self.limits.max_query_depth {
+            return Err(exceeded("depth", self.limits.max_query_depth as u64));
+        }
+
+        self.usage.nodes += 1;
+        if self.usage.nodes > self.limits.max_query_nodes {
+            return Err(exceeded(
+                "number of nodes",
+                self.limits.max_query_nodes as u64,
+            ));
+        }
+
+        // The items of a connection are resolved once per item of the page.
+        let multiplicity = match parent_page_size {
+            Some(page_size) if name == "edges" || name == "nodes" => {
+                multiplicity.saturating_mul(page_size)
+            }
+            _ => multiplicity,
+        };
+        self.usage.output_nodes = self.usage.output_nodes.saturating_add(multiplicity);
+        if self.usage.output_nodes > self.limits.max_output_nodes {
+            return Err(exceeded(
+                "number of output nodes",
+                self.limits.max_output_nodes,
+            ));
+        }
+
+        // Connections, and fields looking something up by their arguments, are assumed to
+        // require a request to the data provider each time they are resolved.
+        let page_size = self.page_size(field)?;
+        if page_size.is_some() || !field.node.arguments.is_empty() {
+            self.usage.backend_cost = self.usage.backend_cost.saturating_add(multiplicity);
+            if self.usage.backend_cost > self.limits.max_backend_cost {
+                return Err(exceeded("backend cost", self.limits.max_backend_cost));
+            }
+        }
+
+        self.check_selection_set(&field.node.selection_set, depth, multiplicity, page_size)
+    }
+
+    /// The number of items of a page of the field, if it is a connection.
+    fn page_size(&self, field: &Positioned<Field>) -> ServerResult<Option<u64>> {
+        if !field.node.name.node.ends_with("Connection") {
+            return Ok(None);
+        }
+        let mut page_size = None;
+        for (name, argument) in &field.node.arguments {
+            if name.node != "first" && name.node != "last" {
+                continue;
+            }
+            // Missing variables and values of the wrong type are rejected before execution.
+            let Ok(Value::Number(size)) = argument
+                .node
+                .clone()
+                .into_const_with(|variable| self.variable(&variable).ok_or(()))
+            else {
+                continue;
+            };
+            let Some(size) = size.as_u64() else {
+                continue;
+            };
+            if size > self.limits.max_page_size {
+                return Err(Error::QueryLimitExceeded(format!(
+                    "Page size of '{}' exceeds the maximum of {}",
+                    field.node.name.node, self.limits.max_page_size
+                ))
+                .extend()
+                .into_server_error(argument.pos));
+            }
+            page_size = Some(size);
+        }
+        Ok(Some(page_size.unwrap_or(DEFAULT_PAGE_SIZE as u64)))
+    }
+
+    fn variable(&self, name: &Name) -> Option<Value> {
+        self.variables.get(name).cloned().or_else(|| {
+            self.variable_definitions
+                .iter()
+                .find(|d| &d.node.name.node == name)
+                .and_then(|d| d.node.default_value.as_ref())
+                .map(|v| v.node.clone())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{mutation::Mutation, query::Query, subscription::Subscription};
+    use async_graphql::Schema;
+
+    async fn execute(limits: Limits, request: impl Into<Request>) -> Response {
+        Schema::build(Query, Mutation, Subscription)
+            .extension(QueryLimitsChecker::new(limits))
+            .finish()
+            .execute(request)
+            .await
+    }
+
+    fn error_message(response: &Response) -> &str {
+        assert_eq!(response.errors.len(), 1, "{:?}", response.errors);
+        &response.errors[0].message
+    }
+
+    #[tokio::test]
+    async fn reports_usage() {
+        let response = execute(
+            Limits::default(),
+            "{ address(address: \"0x1\") { location } }",
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.extensions.get("usage"),
+            Some(&value!({
+                "depth": 2,
+                "nodes": 2,
+                "outputNodes": 2,
+                "backendCost": 1,
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn counts_connection_items() {
+        let query = "query ($n: Int = 10) {
+            address(address: \"0x1\") {
+                objectConnection(first: $n) { pageInfo { hasNextPage } nodes { version } }
+            }
+        }";
+        let limits = Limits {
+            max_output_nodes: 23,
+            ..Limits::default()
+        };
+        // address, objectConnection, pageInfo, hasNextPage, 10 nodes and their 10 versions.
+        let response = execute(limits, query).await;
+        assert_eq!(
+            error_message(&response),
+            "Query exceeds the maximum number of output nodes of 23"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_deep_queries() {
+        let limits = Limits {
+            max_query_depth: 2,
+            ..Limits::default()
+        };
+        let response = execute(
+            limits,
+            "{ address(address: \"0x1\") { balance { coinObjectCount } } }",
+        )
+        .await;
+        assert_eq!(
+            error_message(&response),
+            "Query exceeds the maximum depth of 2"
+        );
+        assert_eq!(
+            response.errors[0]
+                .extensions
+                .as_ref()
+                .and_then(|e| e.get("code")),
+            Some(&Value::from(crate::error::code::GRAPHQL_VALIDATION_FAILED))
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_large_pages() {
+        let response = execute(
+            Limits::default(),
+            "{ checkpointConnection(first: 1000) { nodes { digest } } }",
+        )
+        .await;
+        assert_eq!(
+            error_message(&response),
+            "Page size of 'checkpointConnection' exceeds the maximum of 50"
+        );
+    }
+
+    #[tokio::test]
+    async fn counts_fragments() {
+        let limits = Limits {
+            max_query_nodes: 4,
+            ..Limits::default()
+        };
+        let query = "
+            { a: checkpointConnection { ...Page } b: checkpointConnection { ...Page } }
+            fragment Page on CheckpointConnection { nodes { digest } }
+        ";
+        let response = execute(limits, query).await;
+        assert_eq!(
+            error_message(&response),
+            "Query exceeds the maximum number of nodes of 4"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_costly_queries() {
+        let limits = Limits {
+            max_backend_cost: 50,
+            ..Limits::default()
+        };
+        // One request for the transactions, and one for the objects of each of their senders.
+        let query = "{
+            transactionBlockConnection(first: 50) {
+                nodes { sender { objectConnection(first: 5) { nodes { version } } } }
+            }
+        }";
+        let response = execute(limits, query).await;
+        assert_eq!(
+            error_message(&response),
+            "Query exceeds the maximum backend cost of 50"
+        );
+    }
+
+    #[tokio::test]
+    async fn checks_selected_operation_only() {
+        let limits = Limits {
+            max_query_depth: 2,
+            ..Limits::default()
+        };
+        let query = "
+            query Shallow { address(address: \"0x1\") { location } }
+            query Deep { address(address: \"0x1\") { balance { coinObjectCount } } }
+        ";
+        let response = execute(limits, Request::new(query).operation_name("Shallow")).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.extensions.get("usage"),
+            Some(&value!({
+                "depth": 2,
+                "nodes": 2,
+                "outputNodes": 2,
+                "backendCost": 1,
+            }))
+        );
+
+        let response = execute(limits, Request::new(query).operation_name("Deep")).await;
+        assert_eq!(
+            error_message(&response),
+            "Query exceeds the maximum depth of 2"
+        );
+    }
+
+    #[test]
+    fn reads_limits_from_config() {
+        let limits: Limits = toml::from_str("max-query-depth = 10\nmax-page-size = 20").unwrap();
+        assert_eq!(
+            limits,
+            Limits {
+                max_query_depth: 10,
+                max_page_size: 20,
+                ..Limits::default()
+            }
+        );
+    }
+}
@@ -10,10 +10,12 @@ pub mod extensions;
 pub mod server;
 pub mod types;
 
+use crate::types::mutation::Mutation;
 use crate::types::query::Query;
+use crate::types::subscription::Subscription;
 
 pub fn schema_sdl_export() -> String {
-    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    let schema = Schema::build(Query, Mutation, Subscription)
         .register_output_type::<ObjectOwner>()
         .finish();
     schema.sdl()
@@ -22,13 +22,21 @@ async fn main() {
         }
         Command::StartServer {
             rpc_url,
+            ws_url,
+            db_url,
             port,
             host,
+            limits,
         } => {
             let config = ServerConfig {
                 port,
                 host,
                 rpc_url,
+                ws_url,
+                db_url,
+                limits: limits
+                    .limits()
+                    .expect("Failed to read the limits of queries"),
             };
 
             println!("Starting server...");
@@ -2,19 +2,33 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::types::balance::Balance;
+use crate::types::base64::Base64;
 use crate::types::checkpoint::Checkpoint;
+use crate::types::event::{Event, EventFilter};
+use crate::types::mutation::ExecutionResult;
 use crate::types::object::ObjectFilter;
 use crate::types::protocol_config::ProtocolConfigs;
-use crate::types::transaction_block::TransactionBlock;
+use crate::types::transaction_block::{TransactionBlock, TransactionBlockFilter};
 use crate::types::{object::Object, sui_address::SuiAddress};
 use async_graphql::connection::Connection;
 use async_graphql::*;
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 
 #[async_trait]
 pub(crate) trait DataProvider: Send + Sync {
     async fn fetch_obj(&self, address: SuiAddress, version: Option<u64>) -> Result<Option<Object>>;
 
+    /// Fetch the given versions of objects, in the same order, with `None` for the versions that
+    /// cannot be found. Providers should override this to fetch all of them at once.
+    async fn multi_fetch_obj(&self, keys: Vec<(SuiAddress, u64)>) -> Result<Vec<Option<Object>>> {
+        let mut objects = Vec::with_capacity(keys.len());
+        for (address, version) in keys {
+            objects.push(self.fetch_obj(address, Some(version)).await?);
+        }
+        Ok(objects)
+    }
+
     async fn fetch_owned_objs(
         &self,
         owner: &SuiAddress,
@@ -46,7 +60,41 @@ pub(crate) trait DataProvider: Send + Sync {
 
     async fn fetch_tx(&self, digest: &str) -> Result<Option<TransactionBlock>>;
 
+    async fn fetch_tx_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<Connection<String, TransactionBlock>>;
+
+    async fn fetch_event_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<EventFilter>,
+    ) -> Result<Connection<String, Event>>;
+
     async fn fetch_chain_id(&self) -> Result<String>;
 
     async fn fetch_protocol_config(&self, version: Option<u64>) -> Result<ProtocolConfigs>;
+
+    async fn execute_tx(
+        &self,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+    ) -> Result<ExecutionResult>;
+
+    async fn subscribe_events(
+        &self,
+        filter: Option<EventFilter>,
+    ) -> Result<BoxStream<'static, Result<Event>>>;
+
+    async fn subscribe_txs(
+        &self,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<BoxStream<'static, Result<TransactionBlock>>>;
 }
@@ -3,6 +3,7 @@
 
 pub mod context_ext;
 pub mod data_provider;
+pub mod pg_data_provider;
 pub mod simple_server;
 pub mod sui_sdk_data_provider;
 mod version;
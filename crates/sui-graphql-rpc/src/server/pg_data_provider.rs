@@ -0,0 +1,1136 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Serves queries from the Postgres database of the indexer, falling back to the RPC server for
+// the data that is not indexed.
+
+use crate::error::Error;
+use crate::server::data_provider::DataProvider;
+use crate::server::sui_sdk_data_provider::{
+    changed_objects, convert_object_kind, convert_to_gas_cost_summary,
+    convert_to_transaction_block_kind, convert_to_validators, ensure_forward_pagination,
+    fetch_objs_by_key, DEFAULT_PAGE_SIZE,
+};
+use crate::types::address::Address;
+use crate::types::balance::Balance;
+use crate::types::base64::Base64;
+use crate::types::big_int::BigInt;
+use crate::types::checkpoint::Checkpoint;
+use crate::types::committee_member::CommitteeMember;
+use crate::types::date_time::DateTime;
+use crate::types::end_of_epoch_data::EndOfEpochData;
+use crate::types::epoch::Epoch;
+use crate::types::event::{Event, EventFilter};
+use crate::types::gas::{GasCostSummary, GasEffects, GasInput};
+use crate::types::mutation::ExecutionResult;
+use crate::types::object::{Object, ObjectFilter};
+use crate::types::protocol_config::ProtocolConfigs;
+use crate::types::safe_mode::SafeMode;
+use crate::types::stake_subsidy::StakeSubsidy;
+use crate::types::sui_address::SuiAddress;
+use crate::types::system_parameters::SystemParameters;
+use crate::types::transaction_block::{
+    ExecutionStatus, TransactionBlock, TransactionBlockEffects, TransactionBlockFilter,
+    TransactionBlockKindInput,
+};
+use crate::types::tx_digest::TransactionDigest;
+use crate::types::validator_set::ValidatorSet;
+
+use async_graphql::connection::{Connection, Edge};
+use async_graphql::{ErrorExtensions, Result, ID};
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::PgConnection;
+use fastcrypto::traits::ToFromBytes;
+use futures::future::try_join_all;
+use futures::stream::BoxStream;
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+use sui_indexer::models::checkpoints::Checkpoint as StoredCheckpoint;
+use sui_indexer::models::epoch::DBEpochInfo as StoredEpoch;
+use sui_indexer::models::events::Event as StoredEvent;
+use sui_indexer::models::objects::{Object as StoredObject, ObjectStatus};
+use sui_indexer::models::owners::OwnerType;
+use sui_indexer::models::system_state::{
+    DBSystemStateSummary as StoredSystemState, DBValidatorSummary as StoredValidator,
+};
+use sui_indexer::models::transactions::Transaction as StoredTransaction;
+use sui_indexer::schema::{
+    changed_objects, checkpoints, epochs, events, input_objects, move_calls, objects,
+    objects_history, recipients, system_states, transactions, validators,
+};
+use sui_indexer::{get_pg_pool_connection, PgConnectionPool};
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+};
+use sui_sdk::{
+    types::{
+        base_types::{
+            ObjectID as NativeObjectID, ObjectType as NativeObjectType,
+            SuiAddress as NativeSuiAddress,
+        },
+        crypto::AuthorityPublicKeyBytes,
+        digests::{
+            ChainIdentifier, CheckpointDigest as NativeCheckpointDigest,
+            TransactionDigest as NativeTransactionDigest,
+        },
+        object::{Data as NativeData, Object as NativeObject},
+        sui_system_state::sui_system_state_summary::SuiValidatorSummary,
+        transaction::{SenderSignedData, TransactionDataAPI, TransactionExpiration},
+    },
+    SuiClient,
+};
+
+pub(crate) struct PgDataProvider {
+    pool: PgConnectionPool,
+    /// Serves the data that is not indexed, and the transactions to execute.
+    client: SuiClient,
+}
+
+/// An epoch, as stored across the tables of the indexer.
+struct IndexedEpoch {
+    epoch: Epoch,
+    end_of_epoch: EndOfEpochData,
+}
+
+impl PgDataProvider {
+    pub(crate) fn new(pool: PgConnectionPool, client: SuiClient) -> Self {
+        Self { pool, client }
+    }
+
+    /// Runs `query` in a read-only transaction on a connection of the pool, off the async runtime.
+    async fn run_query<T, F>(&self, query: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut PgConnection) -> QueryResult<T> + Send + 'static,
+    {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_pg_pool_connection(&pool)
+                .map_err(|e| Error::Internal(e.to_string()).extend())?;
+            conn.build_transaction()
+                .read_only()
+                .run(query)
+                .map_err(|e| {
+                    Error::Internal(format!("Failed to read from the indexer database: {e}"))
+                        .extend()
+                })
+        })
+        .await
+        .map_err(|e| Error::Internal(e.to_string()).extend())?
+    }
+
+    async fn fetch_epochs(&self, ids: BTreeSet<i64>) -> Result<HashMap<i64, IndexedEpoch>> {
+        let ids: Vec<_> = ids.into_iter().collect();
+        let (stored_epochs, system_states, stored_validators) = self
+            .run_query(move |conn| {
+                let stored_epochs = epochs::table
+                    .filter(epochs::epoch.eq_any(ids.clone()))
+                    .load::<StoredEpoch>(conn)?;
+                let system_states = system_states::table
+                    .filter(system_states::epoch.eq_any(ids.clone()))
+                    .load::<StoredSystemState>(conn)?;
+                let stored_validators = validators::table
+                    .filter(validators::epoch.eq_any(ids))
+                    .load::<StoredValidator>(conn)?;
+                Ok((stored_epochs, system_states, stored_validators))
+            })
+            .await?;
+
+        let mut system_states: HashMap<_, _> =
+            system_states.into_iter().map(|s| (s.epoch, s)).collect();
+        let mut validators_by_epoch: HashMap<_, Vec<_>> = HashMap::new();
+        for v in stored_validators {
+            validators_by_epoch.entry(v.epoch).or_default().push(v);
+        }
+
+        // Epochs share protocol versions, so each version's config is fetched only once.
+        let versions: BTreeSet<_> = stored_epochs
+            .iter()
+            .filter_map(|e| e.protocol_version)
+            .collect();
+        let configs = try_join_all(
+            versions
+                .iter()
+                .map(|version| self.client.fetch_protocol_config(Some(*version as u64))),
+        )
+        .await?;
+        let configs: HashMap<_, _> = versions.into_iter().zip(configs).collect();
+
+        let mut result = HashMap::new();
+        for stored in stored_epochs {
+            let protocol_configs = stored
+                .protocol_version
+                .and_then(|version| configs.get(&version).cloned());
+            let epoch = convert_stored_epoch(
+                &stored,
+                system_states.remove(&stored.epoch),
+                validators_by_epoch
+                    .remove(&stored.epoch)
+                    .unwrap_or_default(),
+                protocol_configs,
+            )?;
+            let end_of_epoch = convert_to_end_of_epoch_data(&stored)?;
+            result.insert(
+                stored.epoch,
+                IndexedEpoch {
+                    epoch,
+                    end_of_epoch,
+                },
+            );
+        }
+        Ok(result)
+    }
+
+    async fn convert_stored_transactions(
+        &self,
+        stored: Vec<StoredTransaction>,
+    ) -> Result<Vec<(i64, TransactionBlock)>> {
+        let mut txs = vec![];
+        let mut epoch_ids = BTreeSet::new();
+        for tx in stored {
+            let digest = NativeTransactionDigest::from_str(&tx.transaction_digest)
+                .map_err(|e| internal(format!("Invalid transaction digest: {e}")))?;
+            let data: SenderSignedData = bcs::from_bytes(&tx.raw_transaction)
+                .map_err(|e| internal(format!("Cannot deserialize transaction {digest}: {e}")))?;
+            let effects: SuiTransactionBlockEffects =
+                serde_json::from_str(&tx.transaction_effects_content).map_err(|e| {
+                    internal(format!(
+                        "Cannot deserialize effects of transaction {digest}: {e}"
+                    ))
+                })?;
+            epoch_ids.insert(effects.executed_epoch() as i64);
+            if let TransactionExpiration::Epoch(epoch) = data.transaction_data().expiration() {
+                epoch_ids.insert(*epoch as i64);
+            }
+            let changes = changed_objects(&effects);
+            txs.push((tx, digest, data, effects, changes));
+        }
+        let epochs = self.fetch_epochs(epoch_ids).await?;
+        let epoch = |id: u64| epochs.get(&(id as i64)).map(|e| e.epoch.clone());
+
+        // Fetch the gas coins and the states of the changed objects of the whole page at once.
+        let gas_object_key = |effects: &SuiTransactionBlockEffects| {
+            let reference = &effects.gas_object().reference;
+            (
+                SuiAddress::from_array(**reference.object_id),
+                reference.version.value(),
+            )
+        };
+        let mut keys = vec![];
+        for (_, _, data, effects, changes) in &txs {
+            keys.extend(
+                data.transaction_data()
+                    .gas_data()
+                    .payment
+                    .iter()
+                    .map(|(id, version, _)| (SuiAddress::from_array(***id), version.value())),
+            );
+            keys.push(gas_object_key(effects));
+            keys.extend(changes.iter().flat_map(|change| change.keys()));
+        }
+        let objects = fetch_objs_by_key(self, keys).await?;
+
+        let mut result = vec![];
+        for (tx, digest, data, effects, changes) in txs {
+            let txd = TransactionDigest::from_array(digest.into_inner());
+            let tx_data = data.transaction_data();
+            let gas_data = tx_data.gas_data();
+            let gas_payment = gas_data
+                .payment
+                .iter()
+                .filter_map(|(id, version, _)| {
+                    objects
+                        .get(&(SuiAddress::from_array(***id), version.value()))
+                        .cloned()
+                })
+                .collect();
+            let gas_object = objects.get(&gas_object_key(&effects)).cloned();
+            let status = effects.status();
+            let expiration = match tx_data.expiration() {
+                TransactionExpiration::Epoch(e) => epoch(*e),
+                TransactionExpiration::None => None,
+            };
+
+            let block = TransactionBlock {
+                digest: txd,
+                effects: Some(TransactionBlockEffects {
+                    digest: txd,
+                    gas_effects: Some(GasEffects {
+                        gas_object,
+                        gas_summary: Some(convert_to_gas_cost_summary(effects.gas_cost_summary())?),
+                    }),
+                    epoch: epoch(effects.executed_epoch()),
+                    status: Some(match status {
+                        SuiExecutionStatus::Success => ExecutionStatus::Success,
+                        SuiExecutionStatus::Failure { error: _ } => ExecutionStatus::Failure,
+                    }),
+                    errors: match status {
+                        SuiExecutionStatus::Success => None,
+                        SuiExecutionStatus::Failure { error } => Some(error.clone()),
+                    },
+                    dependencies: Some(
+                        effects
+                            .dependencies()
+                            .iter()
+                            .map(|d| TransactionDigest::from_array(d.into_inner()))
+                            .collect(),
+                    ),
+                    object_changes: Some(
+                        changes
+                            .into_iter()
+                            .map(|change| change.into_object_change(&objects))
+                            .collect(),
+                    ),
+                    // Balance changes are derived from the objects the transaction changed, and
+                    // are not indexed.
+                    balance_changes: None,
+                }),
+                sender: Some(Address {
+                    address: SuiAddress::from(tx_data.sender()),
+                }),
+                bcs: Some(Base64::from(&tx.raw_transaction)),
+                gas_input: Some(GasInput {
+                    gas_sponsor: Some(Address::from(SuiAddress::from(gas_data.owner))),
+                    gas_payment: Some(gas_payment),
+                    gas_price: Some(BigInt::from(gas_data.price)),
+                    gas_budget: Some(BigInt::from(gas_data.budget)),
+                }),
+                kind: Some(convert_to_transaction_block_kind(tx_data.kind())),
+                signatures: Some(
+                    data.tx_signatures()
+                        .iter()
+                        .map(|s| Base64::from(s.as_ref().to_vec()))
+                        .collect(),
+                ),
+                expiration,
+            };
+            result.push((tx.id.unwrap_or_default(), block));
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl DataProvider for PgDataProvider {
+    async fn fetch_obj(&self, address: SuiAddress, version: Option<u64>) -> Result<Option<Object>> {
+        let object_id = object_id(address);
+        let stored = self
+            .run_query(move |conn| match version {
+                Some(version) => objects_history::table
+                    .select(OBJECTS_HISTORY_COLUMNS)
+                    .filter(objects_history::object_id.eq(object_id))
+                    .filter(objects_history::version.eq(version as i64))
+                    // Rows of the fast path are replaced by the ones of the checkpoint.
+                    .order(objects_history::checkpoint.desc())
+                    .first::<StoredObject>(conn)
+                    .optional(),
+                None => objects::table
+                    .filter(objects::object_id.eq(object_id))
+                    .first::<StoredObject>(conn)
+                    .optional(),
+            })
+            .await?;
+
+        match stored {
+            Some(o) if is_live(&o) => convert_stored_object(o).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    async fn multi_fetch_obj(&self, keys: Vec<(SuiAddress, u64)>) -> Result<Vec<Option<Object>>> {
+        let ids: BTreeSet<_> = keys
+            .iter()
+            .map(|(address, _)| object_id(*address))
+            .collect();
+        let versions: BTreeSet<_> = keys.iter().map(|(_, version)| *version as i64).collect();
+        let stored = self
+            .run_query(move |conn| {
+                objects_history::table
+                    .select(OBJECTS_HISTORY_COLUMNS)
+                    .filter(objects_history::object_id.eq_any(Vec::from_iter(ids)))
+                    .filter(objects_history::version.eq_any(Vec::from_iter(versions)))
+                    .order(objects_history::checkpoint.asc())
+                    .load::<StoredObject>(conn)
+            })
+            .await?;
+
+        // Rows of the fast path are replaced by the ones of the checkpoint, which come later.
+        let stored: HashMap<_, _> = stored
+            .into_iter()
+            .map(|o| ((o.object_id.clone(), o.version), o))
+            .collect();
+        keys.into_iter()
+            .map(
+                |(address, version)| match stored.get(&(object_id(address), version as i64)) {
+                    Some(o) if is_live(o) => convert_stored_object(o.clone()).map(Some),
+                    _ => Ok(None),
+                },
+            )
+            .collect()
+    }
+
+    async fn fetch_owned_objs(
+        &self,
+        owner: &SuiAddress,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        _filter: Option<ObjectFilter>,
+    ) -> Result<Connection<String, Object>> {
+        ensure_forward_pagination(&first, &after, &last, &before)?;
+
+        let limit = page_limit(first);
+        let owner = NativeSuiAddress::from(owner).to_string();
+        let after = match after {
+            Some(q) => Some(
+                NativeObjectID::from_hex_literal(&q)
+                    .map_err(|w| Error::InvalidCursor(w.to_string()).extend())?
+                    .to_string(),
+            ),
+            None => None,
+        };
+
+        let stored = self
+            .run_query(move |conn| {
+                let mut query = objects::table
+                    .filter(objects::owner_type.eq(OwnerType::AddressOwner))
+                    .filter(objects::owner_address.eq(owner))
+                    .into_boxed();
+                if let Some(after) = after {
+                    query = query.filter(objects::object_id.gt(after));
+                }
+                query
+                    .order(objects::object_id.asc())
+                    .limit(limit + 1)
+                    .load::<StoredObject>(conn)
+            })
+            .await?;
+
+        let (stored, has_next_page) = split_page(stored, limit);
+        let mut connection = Connection::new(false, has_next_page);
+        for o in stored {
+            let cursor = o.object_id.clone();
+            connection
+                .edges
+                .push(Edge::new(cursor, convert_stored_object(o)?));
+        }
+        Ok(connection)
+    }
+
+    async fn fetch_balance(&self, address: &SuiAddress, type_: Option<String>) -> Result<Balance> {
+        self.client.fetch_balance(address, type_).await
+    }
+
+    async fn fetch_balance_connection(
+        &self,
+        address: &SuiAddress,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Connection<String, Balance>> {
+        self.client
+            .fetch_balance_connection(address, first, after, last, before)
+            .await
+    }
+
+    async fn fetch_checkpoint_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Connection<String, Checkpoint>> {
+        ensure_forward_pagination(&first, &after, &last, &before)?;
+
+        let limit = page_limit(first);
+        let after = parse_cursor(after)?;
+        let stored = self
+            .run_query(move |conn| {
+                let mut query = checkpoints::table.into_boxed();
+                if let Some(after) = after {
+                    query = query.filter(checkpoints::sequence_number.gt(after));
+                }
+                query
+                    .order(checkpoints::sequence_number.asc())
+                    .limit(limit + 1)
+                    .load::<StoredCheckpoint>(conn)
+            })
+            .await?;
+
+        let (stored, has_next_page) = split_page(stored, limit);
+        let epochs = self
+            .fetch_epochs(stored.iter().map(|c| c.epoch).collect())
+            .await?;
+
+        let mut connection = Connection::new(false, has_next_page);
+        for c in stored {
+            let (epoch, end_of_epoch) = match epochs.get(&c.epoch) {
+                Some(e) => (
+                    Some(e.epoch.clone()),
+                    c.end_of_epoch.then(|| e.end_of_epoch.clone()),
+                ),
+                None => (None, None),
+            };
+            connection.edges.push(Edge::new(
+                c.sequence_number.to_string(),
+                Checkpoint {
+                    digest: c.checkpoint_digest,
+                    sequence_number: c.sequence_number as u64,
+                    validator_signature: Some(Base64::from(c.validator_signature.into_bytes())),
+                    previous_checkpoint_digest: c.previous_checkpoint_digest,
+                    live_object_set_digest: None,
+                    network_total_transactions: Some(c.network_total_transactions as u64),
+                    rolling_gas_summary: Some(GasCostSummary {
+                        computation_cost: Some(BigInt::from(c.total_computation_cost as u64)),
+                        storage_cost: Some(BigInt::from(c.total_storage_cost as u64)),
+                        storage_rebate: Some(BigInt::from(c.total_storage_rebate as u64)),
+                        non_refundable_storage_fee: None,
+                    }),
+                    epoch,
+                    end_of_epoch,
+                },
+            ));
+        }
+        Ok(connection)
+    }
+
+    async fn fetch_tx(&self, digest: &str) -> Result<Option<TransactionBlock>> {
+        let digest = NativeTransactionDigest::from_str(digest)?.to_string();
+        let stored = self
+            .run_query(move |conn| {
+                transactions::table
+                    .filter(transactions::transaction_digest.eq(digest))
+                    .first::<StoredTransaction>(conn)
+                    .optional()
+            })
+            .await?;
+
+        let Some(stored) = stored else {
+            return Ok(None);
+        };
+        Ok(self
+            .convert_stored_transactions(vec![stored])
+            .await?
+            .pop()
+            .map(|(_, tx)| tx))
+    }
+
+    async fn fetch_tx_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<Connection<String, TransactionBlock>> {
+        ensure_forward_pagination(&first, &after, &last, &before)?;
+
+        let limit = page_limit(first);
+        let after = parse_cursor(after)?;
+        let filter = filter.unwrap_or_default();
+        if filter.paid_address.is_some() {
+            return Err(Error::UnsupportedFilter("paidAddress".to_string()).extend());
+        }
+        if let (Some(sign), Some(sent)) = (filter.sign_address, filter.sent_address) {
+            if sign != sent {
+                return Err(Error::UnsupportedFilter(
+                    "'signAddress' and 'sentAddress' must be the same address".to_string(),
+                )
+                .extend());
+            }
+        }
+
+        let stored = self
+            .run_query(move |conn| {
+                let mut query = transactions::table.into_boxed();
+                if let Some(after) = after {
+                    query = query.filter(transactions::id.gt(after));
+                }
+                // Sponsors are not indexed as signers, so the signer is matched as the sender.
+                for sender in [filter.sign_address, filter.sent_address]
+                    .into_iter()
+                    .flatten()
+                {
+                    query = query.filter(transactions::sender.eq(address(sender)));
+                }
+                if let Some(checkpoint) = filter.checkpoint {
+                    query = query
+                        .filter(transactions::checkpoint_sequence_number.eq(checkpoint as i64));
+                }
+                query = match filter.kind {
+                    Some(TransactionBlockKindInput::ProgrammableTx) => query
+                        .filter(transactions::transaction_kind.eq(PROGRAMMABLE_TRANSACTION_KIND)),
+                    Some(TransactionBlockKindInput::SystemTx) => query
+                        .filter(transactions::transaction_kind.ne(PROGRAMMABLE_TRANSACTION_KIND)),
+                    None => query,
+                };
+                if let Some(recipient) = filter.recv_address {
+                    query = query.filter(
+                        transactions::transaction_digest.eq_any(
+                            recipients::table
+                                .select(recipients::transaction_digest)
+                                .filter(recipients::recipient.eq(address(recipient))),
+                        ),
+                    );
+                }
+                if let Some(input_object) = filter.input_object {
+                    query = query.filter(
+                        transactions::transaction_digest.eq_any(
+                            input_objects::table
+                                .select(input_objects::transaction_digest)
+                                .filter(input_objects::object_id.eq(object_id(input_object))),
+                        ),
+                    );
+                }
+                if let Some(changed_object) = filter.changed_object {
+                    query = query.filter(
+                        transactions::transaction_digest.eq_any(
+                            changed_objects::table
+                                .select(changed_objects::transaction_digest)
+                                .filter(changed_objects::object_id.eq(object_id(changed_object))),
+                        ),
+                    );
+                }
+                if filter.package.is_some() || filter.module.is_some() || filter.function.is_some()
+                {
+                    let mut calls = move_calls::table
+                        .select(move_calls::transaction_digest)
+                        .into_boxed();
+                    if let Some(package) = filter.package {
+                        calls = calls.filter(move_calls::move_package.eq(object_id(package)));
+                    }
+                    if let Some(module) = filter.module {
+                        calls = calls.filter(move_calls::move_module.eq(module));
+                    }
+                    if let Some(function) = filter.function {
+                        calls = calls.filter(move_calls::move_function.eq(function));
+                    }
+                    query = query.filter(transactions::transaction_digest.eq_any(calls));
+                }
+                query
+                    .order(transactions::id.asc())
+                    .limit(limit + 1)
+                    .load::<StoredTransaction>(conn)
+            })
+            .await?;
+
+        let (stored, has_next_page) = split_page(stored, limit);
+        let mut connection = Connection::new(false, has_next_page);
+        connection.edges.extend(
+            self.convert_stored_transactions(stored)
+                .await?
+                .into_iter()
+                .map(|(id, tx)| Edge::new(id.to_string(), tx)),
+        );
+        Ok(connection)
+    }
+
+    async fn fetch_event_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<EventFilter>,
+    ) -> Result<Connection<String, Event>> {
+        ensure_forward_pagination(&first, &after, &last, &before)?;
+
+        let limit = page_limit(first);
+        let after = parse_cursor(after)?;
+        let filter = filter.unwrap_or_default();
+        if filter.event_field.is_some() {
+            // Only the BCS of events is indexed.
+            return Err(Error::UnsupportedFilter("eventField".to_string()).extend());
+        }
+        if (filter.event_module.is_some() && filter.event_package.is_none())
+            || (filter.event_type.is_some() && filter.event_module.is_none())
+        {
+            return Err(Error::UnsupportedFilter(
+                "'eventType' requires 'eventModule', which requires 'eventPackage'".to_string(),
+            )
+            .extend());
+        }
+        let transaction_digest = filter
+            .transaction_digest
+            .map(|d| NativeTransactionDigest::from_str(&d).map(|d| d.to_string()))
+            .transpose()?;
+        // Event types are indexed with the short form of their package address.
+        let event_type = filter.event_package.map(|package| {
+            let package = NativeObjectID::from(NativeSuiAddress::from(package));
+            let module = filter.event_module.unwrap_or_default();
+            match filter.event_type {
+                Some(type_) => (
+                    format!("0x{}::{module}::{type_}", package.short_str_lossless()),
+                    false,
+                ),
+                None => (
+                    format!(
+                        "{}%",
+                        escape_like(&format!("0x{}::{module}::", package.short_str_lossless()))
+                    ),
+                    true,
+                ),
+            }
+        });
+
+        let stored = self
+            .run_query(move |conn| {
+                let mut query = events::table.into_boxed();
+                if let Some(after) = after {
+                    query = query.filter(events::id.gt(after));
+                }
+                if let Some(sender) = filter.sender {
+                    query = query.filter(events::sender.eq(address(sender)));
+                }
+                if let Some(digest) = transaction_digest {
+                    query = query.filter(events::transaction_digest.eq(digest));
+                }
+                if let Some(package) = filter.emitting_package {
+                    query = query.filter(events::package.eq(object_id(package)));
+                }
+                if let Some(module) = filter.emitting_module {
+                    query = query.filter(events::module.eq(module));
+                }
+                query = match event_type {
+                    Some((pattern, true)) => {
+                        query.filter(events::event_type.like(pattern).escape('\\'))
+                    }
+                    Some((type_, false)) => query.filter(events::event_type.eq(type_)),
+                    None => query,
+                };
+                if let Some(start_time) = filter.start_time {
+                    query = query.filter(events::event_time_ms.ge(start_time.timestamp_ms()));
+                }
+                if let Some(end_time) = filter.end_time {
+                    query = query.filter(events::event_time_ms.lt(end_time.timestamp_ms()));
+                }
+                query
+                    .order(events::id.asc())
+                    .limit(limit + 1)
+                    .load::<StoredEvent>(conn)
+            })
+            .await?;
+
+        let (stored, has_next_page) = split_page(stored, limit);
+        let mut connection = Connection::new(false, has_next_page);
+        for e in stored {
+            let cursor = e.id.unwrap_or_default().to_string();
+            connection
+                .edges
+                .push(Edge::new(cursor, convert_stored_event(e)?));
+        }
+        Ok(connection)
+    }
+
+    async fn fetch_chain_id(&self) -> Result<String> {
+        let digest = self
+            .run_query(|conn| {
+                checkpoints::table
+                    .select(checkpoints::checkpoint_digest)
+                    .filter(checkpoints::sequence_number.eq(0))
+                    .first::<String>(conn)
+            })
+            .await?;
+        let digest = NativeCheckpointDigest::from_str(&digest)
+            .map_err(|e| internal(format!("Invalid genesis checkpoint digest: {e}")))?;
+        Ok(ChainIdentifier::from(digest).to_string())
+    }
+
+    async fn fetch_protocol_config(&self, version: Option<u64>) -> Result<ProtocolConfigs> {
+        self.client.fetch_protocol_config(version).await
+    }
+
+    async fn execute_tx(
+        &self,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+    ) -> Result<ExecutionResult> {
+        self.client.execute_tx(tx_bytes, signatures).await
+    }
+
+    async fn subscribe_events(
+        &self,
+        filter: Option<EventFilter>,
+    ) -> Result<BoxStream<'static, Result<Event>>> {
+        self.client.subscribe_events(filter).await
+    }
+
+    async fn subscribe_txs(
+        &self,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<BoxStream<'static, Result<TransactionBlock>>> {
+        self.client.subscribe_txs(filter).await
+    }
+}
+
+const PROGRAMMABLE_TRANSACTION_KIND: &str = "ProgrammableTransaction";
+
+/// The columns of `objects_history` that make up a [StoredObject].
+const OBJECTS_HISTORY_COLUMNS: (
+    objects_history::epoch,
+    objects_history::checkpoint,
+    objects_history::object_id,
+    objects_history::version,
+    objects_history::object_digest,
+    objects_history::owner_type,
+    objects_history::owner_address,
+    objects_history::initial_shared_version,
+    objects_history::previous_transaction,
+    objects_history::object_type,
+    objects_history::object_status,
+    objects_history::has_public_transfer,
+    objects_history::storage_rebate,
+    objects_history::bcs,
+) = (
+    objects_history::epoch,
+    objects_history::checkpoint,
+    objects_history::object_id,
+    objects_history::version,
+    objects_history::object_digest,
+    objects_history::owner_type,
+    objects_history::owner_address,
+    objects_history::initial_shared_version,
+    objects_history::previous_transaction,
+    objects_history::object_type,
+    objects_history::object_status,
+    objects_history::has_public_transfer,
+    objects_history::storage_rebate,
+    objects_history::bcs,
+);
+
+fn internal(message: String) -> async_graphql::Error {
+    Error::Internal(message).extend()
+}
+
+/// The address of an account, as stored by the indexer.
+fn address(address: SuiAddress) -> String {
+    NativeSuiAddress::from(address).to_string()
+}
+
+/// The ID of an object or package, as stored by the indexer.
+fn object_id(address: SuiAddress) -> String {
+    NativeObjectID::from(NativeSuiAddress::from(address)).to_string()
+}
+
+/// Escapes the `_` and `%` wildcards of a `LIKE` pattern with `\`, so that `value` (e.g. a module
+/// name) only matches itself.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// The number of rows of a page, one more of which is fetched to tell whether there is a next page.
+fn page_limit(first: Option<u64>) -> i64 {
+    first.map_or(DEFAULT_PAGE_SIZE as i64, |first| {
+        i64::try_from(first).unwrap_or(i64::MAX - 1)
+    })
+}
+
+fn split_page<T>(mut rows: Vec<T>, limit: i64) -> (Vec<T>, bool) {
+    let has_next_page = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+    (rows, has_next_page)
+}
+
+/// Parses the cursor of a connection over a table keyed by a sequence number.
+fn parse_cursor(cursor: Option<String>) -> Result<Option<i64>> {
+    cursor
+        .map(|c| {
+            c.parse::<i64>()
+                .map_err(|_| Error::InvalidCursor(format!("Invalid cursor '{c}'")).extend())
+        })
+        .transpose()
+}
+
+fn is_live(o: &StoredObject) -> bool {
+    matches!(
+        o.object_status,
+        ObjectStatus::Created | ObjectStatus::Mutated | ObjectStatus::Unwrapped
+    )
+}
+
+fn convert_stored_object(o: StoredObject) -> Result<Object> {
+    let object = NativeObject::try_from(o)
+        .map_err(|e| internal(format!("Cannot convert the indexed object: {e}")))?;
+    Ok(Object {
+        address: SuiAddress::from_array(**object.id()),
+        version: object.version().value(),
+        digest: object.digest().to_string(),
+        storage_rebate: Some(BigInt::from(object.storage_rebate)),
+        owner: object.owner.get_owner_address().map(SuiAddress::from).ok(),
+        bcs: Some(match &object.data {
+            NativeData::Move(o) => Base64::from(o.contents().to_vec()),
+            NativeData::Package(p) => Base64::from(
+                bcs::to_bytes(p).map_err(|e| internal(format!("Cannot serialize package: {e}")))?,
+            ),
+        }),
+        previous_transaction: Some(TransactionDigest::from_array(
+            object.previous_transaction.into_inner(),
+        )),
+        kind: Some(convert_object_kind(&object.owner)),
+        object_type: Some(NativeObjectType::from(&object)),
+    })
+}
+
+fn convert_stored_event(e: StoredEvent) -> Result<Event> {
+    let package = NativeObjectID::from_str(&e.package)
+        .map_err(|err| internal(format!("Invalid package of event: {err}")))?;
+    let sender = NativeSuiAddress::from_str(&e.sender)
+        .map_err(|err| internal(format!("Invalid sender of event: {err}")))?;
+    Ok(Event {
+        id: ID(format!("{}:{}", e.transaction_digest, e.event_sequence)),
+        sending_package: Some(SuiAddress::from_array(**package)),
+        sending_module: Some(e.module),
+        event_type: Some(e.event_type),
+        sender: Some(Address {
+            address: SuiAddress::from(sender),
+        }),
+        timestamp: e.event_time_ms.and_then(DateTime::from_ms),
+        // The JSON form of events requires the layout of their type, which is not indexed.
+        json: None,
+        bcs: Some(Base64::from(e.event_bcs)),
+    })
+}
+
+fn convert_stored_epoch(
+    epoch: &StoredEpoch,
+    system_state: Option<StoredSystemState>,
+    validators: Vec<StoredValidator>,
+    protocol_configs: Option<ProtocolConfigs>,
+) -> Result<Epoch> {
+    let validators = validators
+        .into_iter()
+        .map(SuiValidatorSummary::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| internal(format!("Cannot convert the indexed validators: {e}")))?;
+    let active_validators = convert_to_validators(validators)?;
+    let big_int = |v: i64| BigInt::from(v as u64);
+
+    Ok(Epoch {
+        epoch_id: epoch.epoch as u64,
+        system_state_version: system_state
+            .as_ref()
+            .map(|s| big_int(s.system_state_version)),
+        protocol_configs,
+        reference_gas_price: epoch.reference_gas_price.map(big_int),
+        system_parameters: system_state.as_ref().map(|s| SystemParameters {
+            duration_ms: Some(big_int(s.epoch_duration_ms)),
+            stake_subsidy_start_epoch: Some(s.stake_subsidy_start_epoch as u64),
+            min_validator_count: None,
+            max_validator_count: None,
+            min_validator_joining_stake: None,
+            validator_low_stake_threshold: None,
+            validator_very_low_stake_threshold: None,
+            validator_low_stake_grace_period: None,
+        }),
+        stake_subsidy: system_state.as_ref().map(|s| StakeSubsidy {
+            balance: Some(big_int(s.stake_subsidy_balance)),
+            distribution_counter: Some(s.stake_subsidy_epoch_counter as u64),
+            current_distribution_amount: Some(big_int(s.stake_subsidy_current_epoch_amount)),
+            period_length: None,
+            decrease_rate: None,
+        }),
+        validator_set: Some(ValidatorSet {
+            total_stake: epoch
+                .total_stake
+                .or(system_state.as_ref().map(|s| s.total_stake))
+                .map(big_int),
+            active_validators: Some(active_validators),
+            pending_removals: system_state
+                .as_ref()
+                .map(|s| s.pending_removals.iter().map(|r| *r as u64).collect()),
+            pending_active_validators_size: system_state
+                .as_ref()
+                .map(|s| s.pending_active_validators_size as u64),
+            stake_pool_mappings_size: system_state
+                .as_ref()
+                .map(|s| s.staking_pool_mappings_size as u64),
+            inactive_pools_size: system_state.as_ref().map(|s| s.inactive_pools_size as u64),
+            validator_candidates_size: system_state
+                .as_ref()
+                .map(|s| s.validator_candidates_size as u64),
+        }),
+        // The split of the storage fund is not indexed.
+        storage_fund: None,
+        safe_mode: system_state.map(|s| SafeMode {
+            enabled: Some(s.safe_mode),
+            gas_summary: None,
+        }),
+        start_timestamp: DateTime::from_ms(epoch.epoch_start_timestamp),
+    })
+}
+
+fn convert_to_end_of_epoch_data(epoch: &StoredEpoch) -> Result<EndOfEpochData> {
+    let new_committee = epoch
+        .next_epoch_committee
+        .iter()
+        .flatten()
+        .zip(epoch.next_epoch_committee_stake.iter().flatten())
+        .map(|(name, stake)| {
+            let name = AuthorityPublicKeyBytes::from_bytes(name)
+                .map_err(|e| internal(format!("Invalid committee member: {e}")))?;
+            Ok(CommitteeMember {
+                authority_name: Some(name.into_concise().to_string()),
+                stake_unit: Some(*stake as u64),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(EndOfEpochData {
+        new_committee: (!new_committee.is_empty()).then_some(new_committee),
+        next_protocol_version: epoch.next_epoch_version.map(|v| v as u64),
+    })
+}
+
+// Tests against a Postgres database, reset by the indexer of a local test cluster.
+#[cfg(all(test, feature = "pg_integration"))]
+mod pg_integration_test {
+    use super::*;
+    use std::env;
+    use std::time::Duration;
+    use sui_indexer::store::{IndexerStore, PgIndexerStore};
+    use sui_indexer::test_utils::start_test_indexer;
+    use sui_indexer::{new_pg_connection_pool, IndexerConfig};
+    use sui_test_transaction_builder::{create_devnet_nft, publish_nfts_package};
+    use test_cluster::{TestCluster, TestClusterBuilder};
+
+    async fn start_test_cluster() -> (TestCluster, PgIndexerStore, PgDataProvider) {
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+
+        let test_cluster = TestClusterBuilder::new().build().await;
+        let config = IndexerConfig {
+            db_url: Some(db_url.clone()),
+            rpc_client_url: test_cluster.rpc_url().to_string(),
+            migrated_methods: IndexerConfig::all_implemented_methods(),
+            reset_db: true,
+            ..Default::default()
+        };
+        let (store, _) = start_test_indexer(config).await.unwrap();
+        let provider = PgDataProvider::new(
+            new_pg_connection_pool(&db_url).unwrap(),
+            test_cluster.sui_client().clone(),
+        );
+        (test_cluster, store, provider)
+    }
+
+    async fn wait_until_transaction_synced_in_checkpoint(
+        store: &PgIndexerStore,
+        digest: NativeTransactionDigest,
+    ) {
+        tokio::time::timeout(Duration::from_secs(60), async {
+            loop {
+                match store.get_transaction_by_digest(&digest.to_string()).await {
+                    Ok(tx) if tx.checkpoint_sequence_number.is_some() => break,
+                    _ => tokio::time::sleep(Duration::from_millis(100)).await,
+                }
+            }
+        })
+        .await
+        .expect("Transaction was not indexed in time");
+    }
+
+    #[tokio::test]
+    async fn test_multi_fetch_obj() {
+        let (test_cluster, store, provider) = start_test_cluster().await;
+        let package_id = publish_nfts_package(&test_cluster.wallet).await.0;
+        let (_, nft_id, digest) = create_devnet_nft(&test_cluster.wallet, package_id).await;
+        wait_until_transaction_synced_in_checkpoint(&store, digest).await;
+
+        let nft = SuiAddress::from_array(**nft_id);
+        let latest = provider.fetch_obj(nft, None).await.unwrap().unwrap();
+        let missing = SuiAddress::from_array(**NativeObjectID::random());
+        let objects = provider
+            .multi_fetch_obj(vec![
+                (nft, latest.version),
+                (missing, latest.version),
+                (nft, latest.version + 1),
+                (nft, latest.version),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(
+            objects,
+            vec![Some(latest.clone()), None, None, Some(latest.clone())]
+        );
+        assert_eq!(
+            provider.fetch_obj(nft, Some(latest.version)).await.unwrap(),
+            Some(latest)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_tx_connection() {
+        let (test_cluster, store, provider) = start_test_cluster().await;
+        let package_id = publish_nfts_package(&test_cluster.wallet).await.0;
+        let (sender, nft_id, digest) = create_devnet_nft(&test_cluster.wallet, package_id).await;
+        wait_until_transaction_synced_in_checkpoint(&store, digest).await;
+        let sender = SuiAddress::from(sender);
+
+        let error = provider
+            .fetch_tx_connection(
+                None,
+                None,
+                None,
+                None,
+                Some(TransactionBlockFilter {
+                    sign_address: Some(sender),
+                    sent_address: Some(SuiAddress::from_array([0; 32])),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message,
+            "Unsupported filter: 'signAddress' and 'sentAddress' must be the same address"
+        );
+
+        let txs = provider
+            .fetch_tx_connection(
+                None,
+                None,
+                None,
+                None,
+                Some(TransactionBlockFilter {
+                    sign_address: Some(sender),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+        let tx = txs
+            .edges
+            .into_iter()
+            .map(|edge| edge.node)
+            .find(|tx| tx.digest.into_array() == digest.into_inner())
+            .unwrap();
+        assert_eq!(tx.sender, Some(Address { address: sender }));
+
+        let gas_input = tx.gas_input.unwrap();
+        assert_eq!(gas_input.gas_payment.unwrap().len(), 1);
+        let effects = tx.effects.unwrap();
+        assert!(effects.gas_effects.unwrap().gas_object.is_some());
+        let nft = SuiAddress::from_array(**nft_id);
+        let created = effects
+            .object_changes
+            .unwrap()
+            .into_iter()
+            .find(|change| change.address == nft)
+            .unwrap();
+        assert_eq!(created.id_created, Some(true));
+        assert!(created.input_state.is_none());
+        assert_eq!(
+            created.output_state,
+            provider.fetch_obj(nft, None).await.unwrap()
+        );
+    }
+}
@@ -2,18 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::extensions::logger::Logger;
+use crate::extensions::query_limits_checker::{Limits, QueryLimitsChecker};
 use crate::extensions::timeout::Timeout;
+use crate::server::pg_data_provider::PgDataProvider;
 use crate::server::sui_sdk_data_provider::SuiClientLoader;
 use crate::{
     server::{
         data_provider::DataProvider,
         version::{check_version_middleware, set_version_middleware},
     },
-    types::query::{Query, SuiGraphQLSchema},
+    types::{
+        mutation::Mutation,
+        query::{Query, SuiGraphQLSchema},
+        subscription::Subscription,
+    },
 };
 use async_graphql::dataloader::DataLoader;
-use async_graphql::{EmptyMutation, EmptySubscription};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse, GraphQLSubscription};
 use axum::middleware;
 use std::default::Default;
 use std::time::Duration;
@@ -26,6 +31,9 @@ pub struct ServerConfig {
     pub port: u16,
     pub host: String,
     pub rpc_url: String,
+    pub ws_url: Option<String>,
+    pub db_url: Option<String>,
+    pub limits: Limits,
 }
 
 impl std::default::Default for ServerConfig {
@@ -34,6 +42,9 @@ impl std::default::Default for ServerConfig {
             port: 8000,
             host: "127.0.0.1".to_string(),
             rpc_url: "https://fullnode.testnet.sui.io:443/".to_string(),
+            ws_url: None,
+            db_url: None,
+            limits: Limits::default(),
         }
     }
 }
@@ -59,6 +70,7 @@ async fn graphiql() -> impl axum::response::IntoResponse {
     axum::response::Html(
         async_graphql::http::GraphiQLSource::build()
             .endpoint("/")
+            .subscription_endpoint("/ws")
             .finish(),
     )
 }
@@ -69,14 +81,26 @@ pub async fn start_example_server(config: Option<ServerConfig>) {
         .with_env()
         .init();
 
-    let sui_sdk_client_v0 = sui_sdk::SuiClientBuilder::default()
+    let mut builder = sui_sdk::SuiClientBuilder::default()
         .request_timeout(RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD)
-        .max_concurrent_requests(MAX_CONCURRENT_REQUESTS)
+        .max_concurrent_requests(MAX_CONCURRENT_REQUESTS);
+    // Subscriptions are forwarded to the RPC server, which only supports them over WebSockets.
+    if let Some(ws_url) = &config.ws_url {
+        builder = builder.ws_url(ws_url);
+    }
+    let sui_sdk_client_v0 = builder
         .build(config.rpc_url.as_str())
         .await
         .expect("Failed to create SuiClient");
 
-    let data_provider: Box<dyn DataProvider> = Box::new(sui_sdk_client_v0.clone());
+    let data_provider: Box<dyn DataProvider> = match &config.db_url {
+        Some(db_url) => {
+            let pool = sui_indexer::new_pg_connection_pool(db_url)
+                .expect("Failed to create the indexer database connection pool");
+            Box::new(PgDataProvider::new(pool, sui_sdk_client_v0.clone()))
+        }
+        None => Box::new(sui_sdk_client_v0.clone()),
+    };
     let data_loader = DataLoader::with_cache(
         SuiClientLoader {
             client: sui_sdk_client_v0,
@@ -85,15 +109,17 @@ pub async fn start_example_server(config: Option<ServerConfig>) {
         async_graphql::dataloader::LruCache::new(DATA_LOADER_LRU_CACHE_SIZE),
     );
     data_loader.enable_all_cache(true);
-    let schema = async_graphql::Schema::build(Query, EmptyMutation, EmptySubscription)
+    let schema = async_graphql::Schema::build(Query, Mutation, Subscription)
         .data(data_provider)
         .data(data_loader)
         .extension(Logger::default())
+        .extension(QueryLimitsChecker::new(config.limits))
         .extension(Timeout::default())
         .finish();
 
     let app = axum::Router::new()
         .route("/", axum::routing::get(graphiql).post(graphql_handler))
+        .route_service("/ws", GraphQLSubscription::new(schema.clone()))
         .layer(axum::extract::Extension(schema))
         .layer(middleware::from_fn(check_version_middleware))
         .layer(middleware::from_fn(set_version_middleware));
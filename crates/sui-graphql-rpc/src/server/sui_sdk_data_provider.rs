@@ -5,7 +5,7 @@
 
 use crate::error::Error;
 use crate::types::address::Address;
-use crate::types::balance::Balance;
+use crate::types::balance::{Balance, BalanceChange};
 use crate::types::base64::Base64;
 use crate::types::big_int::BigInt;
 use crate::types::checkpoint::Checkpoint;
@@ -13,7 +13,10 @@ use crate::types::committee_member::CommitteeMember;
 use crate::types::date_time::DateTime;
 use crate::types::end_of_epoch_data::EndOfEpochData;
 use crate::types::epoch::Epoch;
+use crate::types::event::{Event, EventField, EventFilter};
+use crate::types::mutation::ExecutionResult;
 use crate::types::object::{Object, ObjectFilter, ObjectKind};
+use crate::types::owner::Owner;
 use crate::types::protocol_config::{
     ProtocolConfigAttr, ProtocolConfigFeatureFlag, ProtocolConfigs,
 };
@@ -22,7 +25,18 @@ use crate::types::stake_subsidy::StakeSubsidy;
 use crate::types::storage_fund::StorageFund;
 use crate::types::sui_address::SuiAddress;
 use crate::types::system_parameters::SystemParameters;
-use crate::types::transaction_block::{ExecutionStatus, TransactionBlock, TransactionBlockEffects};
+use crate::types::transaction_block::{
+    ExecutionStatus, ObjectChange, TransactionBlock, TransactionBlockEffects,
+    TransactionBlockFilter, TransactionBlockKindInput,
+};
+use crate::types::transaction_block_kind::{
+    AuthenticatorStateUpdateTransaction, ChangeEpochTransaction,
+    ConsensusCommitPrologueTransaction, GasCoin, GenesisTransaction, Input, MakeMoveVecTransaction,
+    MergeCoinsTransaction, MoveCallTransaction, OwnedOrImmutableInput, ProgrammableTransaction,
+    ProgrammableTransactionBlock, PublishTransaction, PureInput, SharedInput, SplitCoinTransaction,
+    TransactionArgument, TransactionBlockKind, TransactionInput, TransferObjectsTransaction,
+    TxResult, UpgradeTransaction,
+};
 use crate::types::tx_digest::TransactionDigest;
 use crate::types::validator::Validator;
 use crate::types::validator_credentials::ValidatorCredentials;
@@ -34,13 +48,20 @@ use async_graphql::connection::{Connection, Edge};
 use async_graphql::dataloader::*;
 use async_graphql::*;
 use async_trait::async_trait;
-use fastcrypto::traits::EncodeDecodeBase64;
+use fastcrypto::traits::{EncodeDecodeBase64, ToFromBytes};
+use futures::channel::{mpsc, oneshot};
+use futures::stream::BoxStream;
+use futures::{pin_mut, SinkExt, StreamExt};
+use shared_crypto::intent::Intent;
 use std::collections::HashMap;
 use std::str::FromStr;
+use sui_json_rpc::api::QUERY_MAX_RESULT_LIMIT;
 use sui_json_rpc_types::{
-    OwnedObjectRef, SuiExecutionStatus, SuiGasData, SuiObjectDataOptions, SuiObjectResponseQuery,
-    SuiPastObjectResponse, SuiRawData, SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI,
-    SuiTransactionBlockResponseOptions,
+    EventFilter as NativeEventFilter, SuiEvent, SuiExecutionStatus, SuiGetPastObjectRequest,
+    SuiObjectDataOptions, SuiObjectResponseQuery, SuiPastObjectResponse, SuiRawData,
+    SuiRawMovePackage, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionFilter,
 };
 use sui_sdk::types::sui_serde::BigInt as SerdeBigInt;
 use sui_sdk::types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
@@ -48,14 +69,27 @@ use sui_sdk::{
     types::{
         base_types::{ObjectID as NativeObjectID, SuiAddress as NativeSuiAddress},
         digests::TransactionDigest as NativeTransactionDigest,
+        event::EventID as NativeEventID,
         gas::GasCostSummary as NativeGasCostSummary,
+        move_package::MovePackage as NativeMovePackage,
         object::Owner as NativeOwner,
+        parse_sui_struct_tag,
+        quorum_driver_types::ExecuteTransactionRequestType,
+        signature::GenericSignature,
         sui_system_state::sui_system_state_summary::SuiValidatorSummary,
+        transaction::{
+            Argument as NativeArgument, CallArg as NativeCallArg, Command as NativeCommand,
+            ObjectArg as NativeObjectArg, SenderSignedData, Transaction as NativeTransaction,
+            TransactionData as NativeTransactionData, TransactionDataAPI, TransactionExpiration,
+            TransactionKind as NativeTransactionKind,
+        },
+        Identifier,
     },
     SuiClient,
 };
 
-const DEFAULT_PAGE_SIZE: usize = 50;
+pub(crate) const DEFAULT_PAGE_SIZE: usize = 50;
+const SUBSCRIPTION_BUFFER_SIZE: usize = 100;
 
 pub(crate) struct SuiClientLoader {
     pub client: SuiClient,
@@ -70,25 +104,23 @@ impl Loader<TransactionDigest> for SuiClientLoader {
         &self,
         keys: &[TransactionDigest],
     ) -> Result<HashMap<TransactionDigest, Self::Value>, Self::Error> {
-        let mut map = HashMap::new();
         let keys: Vec<_> = keys
             .iter()
             .map(|x| NativeTransactionDigest::new(x.into_array()))
             .collect();
-        for tx in self
+        let txs = self
             .client
             .read_api()
             .multi_get_transactions_with_options(
                 keys,
                 SuiTransactionBlockResponseOptions::full_content(),
             )
+            .await?;
+        Ok(fetch_transaction_blocks(&self.client, txs)
             .await?
-        {
-            let digest = TransactionDigest::from_array(tx.digest.into_inner());
-            let mtx = convert_to_transaction_block(&self.client, tx).await?;
-            map.insert(digest, mtx);
-        }
-        Ok(map)
+            .into_iter()
+            .map(|tx| (tx.digest, tx))
+            .collect())
     }
 }
 
@@ -96,7 +128,7 @@ impl Loader<TransactionDigest> for SuiClientLoader {
 impl DataProvider for SuiClient {
     async fn fetch_obj(&self, address: SuiAddress, version: Option<u64>) -> Result<Option<Object>> {
         let oid: NativeObjectID = address.into_array().as_slice().try_into()?;
-        let opts = SuiObjectDataOptions::full_content();
+        let opts = object_data_options();
 
         let g = match version {
             Some(v) => match self
@@ -118,6 +150,30 @@ impl DataProvider for SuiClient {
         Ok(Some(convert_obj(&g)))
     }
 
+    async fn multi_fetch_obj(&self, keys: Vec<(SuiAddress, u64)>) -> Result<Vec<Option<Object>>> {
+        let mut objects = Vec::with_capacity(keys.len());
+        for chunk in keys.chunks(*QUERY_MAX_RESULT_LIMIT) {
+            let requests = chunk
+                .iter()
+                .map(|(address, version)| {
+                    Ok(SuiGetPastObjectRequest {
+                        object_id: address.into_array().as_slice().try_into()?,
+                        version: (*version).into(),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let responses = self
+                .read_api()
+                .try_multi_get_parsed_past_object(requests, object_data_options())
+                .await?;
+            objects.extend(responses.iter().map(|response| match response {
+                SuiPastObjectResponse::VersionFound(data) => Some(convert_obj(data)),
+                _ => None,
+            }));
+        }
+        Ok(objects)
+    }
+
     async fn fetch_owned_objs(
         &self,
         owner: &SuiAddress,
@@ -131,7 +187,7 @@ impl DataProvider for SuiClient {
 
         let count = first.map(|q| q as usize);
         let native_owner = NativeSuiAddress::from(owner);
-        let query = SuiObjectResponseQuery::new_with_options(SuiObjectDataOptions::full_content());
+        let query = SuiObjectResponseQuery::new_with_options(object_data_options());
 
         let cursor = match after {
             Some(q) => Some(
@@ -278,7 +334,73 @@ impl DataProvider for SuiClient {
             )
             .await?;
 
-        convert_to_transaction_block(self, tx).await.map(Some)
+        Ok(fetch_transaction_blocks(self, vec![tx]).await?.pop())
+    }
+
+    async fn fetch_tx_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<Connection<String, TransactionBlock>> {
+        ensure_forward_pagination(&first, &after, &last, &before)?;
+
+        let count = first.map(|q| q as usize);
+        let cursor = after
+            .map(|q| NativeTransactionDigest::from_str(&q))
+            .transpose()
+            .map_err(|e| Error::InvalidCursor(e.to_string()).extend())?;
+        let query = SuiTransactionBlockResponseQuery::new(
+            filter.map(convert_tx_filter).transpose()?.flatten(),
+            Some(SuiTransactionBlockResponseOptions::full_content()),
+        );
+
+        let pg = self
+            .read_api()
+            .query_transaction_blocks(query, cursor, count, false)
+            .await?;
+
+        let mut connection = Connection::new(false, pg.has_next_page);
+        let cursors: Vec<_> = pg.data.iter().map(|tx| tx.digest.to_string()).collect();
+        connection.edges.extend(
+            cursors
+                .into_iter()
+                .zip(fetch_transaction_blocks(self, pg.data).await?)
+                .map(|(cursor, tx)| Edge::new(cursor, tx)),
+        );
+        Ok(connection)
+    }
+
+    async fn fetch_event_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<EventFilter>,
+    ) -> Result<Connection<String, Event>> {
+        ensure_forward_pagination(&first, &after, &last, &before)?;
+
+        let count = first.map(|q| q as usize);
+        let cursor = after.map(|q| parse_event_cursor(&q)).transpose()?;
+        let filter = filter
+            .map(convert_event_filter)
+            .transpose()?
+            .unwrap_or_else(|| NativeEventFilter::All(vec![]));
+
+        let pg = self
+            .event_api()
+            .query_events(filter, cursor, count, false)
+            .await?;
+
+        let mut connection = Connection::new(false, pg.has_next_page);
+        connection.edges.extend(pg.data.into_iter().map(|e| {
+            let event = convert_to_event(e);
+            Edge::new(event.id.to_string(), event)
+        }));
+        Ok(connection)
     }
 
     async fn fetch_chain_id(&self) -> Result<String> {
@@ -316,6 +438,155 @@ impl DataProvider for SuiClient {
             protocol_version: cfg.protocol_version.as_u64(),
         })
     }
+
+    async fn execute_tx(
+        &self,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+    ) -> Result<ExecutionResult> {
+        let tx_data: NativeTransactionData = bcs::from_bytes(tx_bytes.as_ref()).map_err(|e| {
+            Error::InvalidTransaction(format!("Cannot deserialize transaction data: {e}")).extend()
+        })?;
+        let signatures = signatures
+            .iter()
+            .map(|s| {
+                GenericSignature::from_bytes(s.as_ref()).map_err(|e| {
+                    Error::InvalidTransaction(format!("Cannot deserialize signature: {e}")).extend()
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let tx = NativeTransaction::from_generic_sig_data(
+            tx_data,
+            Intent::sui_transaction(),
+            signatures,
+        );
+
+        let response = match self
+            .quorum_driver_api()
+            .execute_transaction_block(
+                tx,
+                SuiTransactionBlockResponseOptions::full_content(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await
+        {
+            Ok(response) => response,
+            // The transaction could not be finalised, e.g. because it was rejected by the
+            // validators.
+            Err(e) => {
+                return Ok(ExecutionResult {
+                    transaction_block: None,
+                    errors: Some(e.to_string()),
+                })
+            }
+        };
+        let transaction_block = fetch_transaction_blocks(self, vec![response]).await?.pop();
+        let errors = transaction_block
+            .as_ref()
+            .and_then(|tx| tx.effects.as_ref())
+            .and_then(|effects| effects.errors.clone());
+        Ok(ExecutionResult {
+            transaction_block,
+            errors,
+        })
+    }
+
+    async fn subscribe_events(
+        &self,
+        filter: Option<EventFilter>,
+    ) -> Result<BoxStream<'static, Result<Event>>> {
+        let filter = filter
+            .map(convert_event_filter)
+            .transpose()?
+            .unwrap_or_else(|| NativeEventFilter::All(vec![]));
+
+        // The subscription borrows the client, so it is polled by a task of its own that owns a
+        // clone of the client, and forwards the events until the subscriber goes away.
+        let client = self.clone();
+        let (subscribed, subscription_result) = oneshot::channel();
+        let (mut sender, receiver) = mpsc::channel(SUBSCRIPTION_BUFFER_SIZE);
+        tokio::spawn(async move {
+            let events = match client.event_api().subscribe_event(filter).await {
+                Ok(events) => {
+                    let _ = subscribed.send(Ok(()));
+                    events
+                }
+                Err(e) => {
+                    let _ = subscribed.send(Err(e));
+                    return;
+                }
+            };
+            pin_mut!(events);
+            while let Some(event) = events.next().await {
+                let event = event.map(convert_to_event).map_err(Into::into);
+                if sender.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+        subscription_result
+            .await
+            .map_err(|e| Error::Internal(e.to_string()).extend())??;
+        Ok(receiver.boxed())
+    }
+
+    async fn subscribe_txs(
+        &self,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<BoxStream<'static, Result<TransactionBlock>>> {
+        let unsupported = |reason: &str| Error::UnsupportedFilter(reason.to_string()).extend();
+        let filter = match filter.map(convert_tx_filter).transpose()?.flatten() {
+            Some(TransactionFilter::Checkpoint(_)) => {
+                return Err(unsupported(
+                    "transactions cannot be subscribed to by checkpoint",
+                ))
+            }
+            Some(filter) => filter,
+            None => return Err(unsupported("subscribing to transactions requires a filter")),
+        };
+
+        // See `subscribe_events`.
+        let client = self.clone();
+        let (subscribed, subscription_result) = oneshot::channel();
+        let (mut sender, receiver) = mpsc::channel(SUBSCRIPTION_BUFFER_SIZE);
+        tokio::spawn(async move {
+            let effects = match client.read_api().subscribe_transaction(filter).await {
+                Ok(effects) => {
+                    let _ = subscribed.send(Ok(()));
+                    effects
+                }
+                Err(e) => {
+                    let _ = subscribed.send(Err(e));
+                    return;
+                }
+            };
+            pin_mut!(effects);
+            while let Some(effects) = effects.next().await {
+                let tx = match effects {
+                    Ok(effects) => client
+                        .fetch_tx(&effects.transaction_digest().to_string())
+                        .await
+                        .and_then(|tx| {
+                            tx.ok_or_else(|| {
+                                Error::Internal(format!(
+                                    "Transaction {} not found",
+                                    effects.transaction_digest()
+                                ))
+                                .extend()
+                            })
+                        }),
+                    Err(e) => Err(e.into()),
+                };
+                if sender.send(tx).await.is_err() {
+                    break;
+                }
+            }
+        });
+        subscription_result
+            .await
+            .map_err(|e| Error::Internal(e.to_string()).extend())??;
+        Ok(receiver.boxed())
+    }
 }
 
 pub(crate) fn convert_json_rpc_checkpoint(
@@ -388,20 +659,46 @@ fn convert_obj(s: &sui_json_rpc_types::SuiObjectData) -> Object {
             .map(|x| SuiAddress::from_array(x.to_inner()))
             .ok(),
         bcs: s.bcs.as_ref().map(|raw| match raw {
-            SuiRawData::Package(raw_package) => Base64::from(bcs::to_bytes(raw_package).unwrap()),
+            SuiRawData::Package(raw_package) => Base64::from(package_bcs(raw_package)),
             SuiRawData::MoveObject(raw_object) => Base64::from(&raw_object.bcs_bytes),
         }),
         previous_transaction: Some(TransactionDigest::from_array(
             s.previous_transaction.unwrap().into_inner(),
         )),
-        kind: Some(match s.owner.unwrap() {
-            NativeOwner::AddressOwner(_) => ObjectKind::Owned,
-            NativeOwner::ObjectOwner(_) => ObjectKind::Child,
-            NativeOwner::Shared {
-                initial_shared_version: _,
-            } => ObjectKind::Shared,
-            NativeOwner::Immutable => ObjectKind::Immutable,
-        }),
+        kind: Some(convert_object_kind(&s.owner.unwrap())),
+        object_type: s.type_.clone(),
+    }
+}
+
+/// Options to fetch objects with, including their BCS, from which packages are read.
+fn object_data_options() -> SuiObjectDataOptions {
+    SuiObjectDataOptions::full_content().with_bcs()
+}
+
+/// Serializes the package the way it is stored on chain, and in the database of the indexer, for
+/// [Object::move_package] to read it back.
+fn package_bcs(raw_package: &SuiRawMovePackage) -> Vec<u8> {
+    // The package was published, so it is within the maximum package size.
+    let package = NativeMovePackage::new(
+        raw_package.id,
+        raw_package.version,
+        raw_package.module_map.clone(),
+        u64::MAX,
+        raw_package.type_origin_table.clone(),
+        raw_package.linkage_table.clone(),
+    )
+    .unwrap();
+    bcs::to_bytes(&package).unwrap()
+}
+
+pub(crate) fn convert_object_kind(owner: &NativeOwner) -> ObjectKind {
+    match owner {
+        NativeOwner::AddressOwner(_) => ObjectKind::Owned,
+        NativeOwner::ObjectOwner(_) => ObjectKind::Child,
+        NativeOwner::Shared {
+            initial_shared_version: _,
+        } => ObjectKind::Shared,
+        NativeOwner::Immutable => ObjectKind::Immutable,
     }
 }
 
@@ -412,30 +709,6 @@ fn convert_bal(b: sui_json_rpc_types::Balance) -> Balance {
     }
 }
 
-pub(crate) async fn convert_to_gas_input(
-    cl: &SuiClient,
-    gas_data: &SuiGasData,
-) -> Result<GasInput> {
-    let payment_obj_ids: Vec<_> = gas_data.payment.iter().map(|o| o.object_id).collect();
-
-    let obj_responses = cl
-        .read_api()
-        .multi_get_object_with_options(payment_obj_ids, SuiObjectDataOptions::full_content())
-        .await?;
-
-    let payment_objs = obj_responses
-        .iter()
-        .map(|x| convert_obj(x.data.as_ref().unwrap()))
-        .collect();
-
-    Ok(GasInput {
-        gas_sponsor: Some(Address::from(SuiAddress::from(gas_data.owner))),
-        gas_payment: Some(payment_objs),
-        gas_price: Some(BigInt::from(gas_data.price)),
-        gas_budget: Some(BigInt::from(gas_data.budget)),
-    })
-}
-
 pub(crate) fn convert_to_gas_cost_summary(gcs: &NativeGasCostSummary) -> Result<GasCostSummary> {
     Ok(GasCostSummary {
         computation_cost: Some(BigInt::from(gcs.computation_cost)),
@@ -445,26 +718,6 @@ pub(crate) fn convert_to_gas_cost_summary(gcs: &NativeGasCostSummary) -> Result<
     })
 }
 
-pub(crate) async fn convert_to_gas_effects(
-    cl: &SuiClient,
-    gcs: &NativeGasCostSummary,
-    gas_obj_ref: &OwnedObjectRef,
-) -> Result<GasEffects> {
-    let gas_summary = convert_to_gas_cost_summary(gcs)?;
-    let gas_obj = cl
-        .read_api()
-        .get_object_with_options(
-            gas_obj_ref.object_id(),
-            SuiObjectDataOptions::full_content(),
-        )
-        .await?;
-    let gas_object = convert_obj(&gas_obj.data.unwrap());
-    Ok(GasEffects {
-        gas_object: Some(gas_object),
-        gas_summary: Some(gas_summary),
-    })
-}
-
 pub(crate) fn convert_to_epoch(
     gcs: &NativeGasCostSummary,
     system_state: &SuiSystemStateSummary,
@@ -597,42 +850,603 @@ pub(crate) fn convert_to_validators(
     Ok(result)
 }
 
-async fn convert_to_transaction_block(
+/// The epoch with the given ID. Only the current epoch is described in full, by the latest system
+/// state, as past system states cannot be read from the RPC.
+fn convert_epoch_id(
+    epoch_id: u64,
+    gcs: &NativeGasCostSummary,
+    current_epoch: Option<(&SuiSystemStateSummary, &ProtocolConfigs)>,
+) -> Result<Epoch> {
+    match current_epoch {
+        Some((system_state, protocol_configs)) if system_state.epoch == epoch_id => {
+            convert_to_epoch(gcs, system_state, protocol_configs)
+        }
+        _ => Ok(Epoch {
+            epoch_id,
+            system_state_version: None,
+            protocol_configs: None,
+            reference_gas_price: None,
+            system_parameters: None,
+            stake_subsidy: None,
+            validator_set: None,
+            storage_fund: None,
+            safe_mode: None,
+            start_timestamp: None,
+        }),
+    }
+}
+
+/// Converts a page of transactions, reading the latest system state and protocol config once for
+/// the whole page.
+async fn fetch_transaction_blocks(
     cl: &SuiClient,
-    tx: sui_json_rpc_types::SuiTransactionBlockResponse,
-) -> Result<TransactionBlock> {
-    let tx_data = tx.transaction.as_ref().unwrap();
-    let tx_effects = tx.effects.as_ref().unwrap();
-    let sender = *tx_data.data.sender();
-    let gas_effects =
-        convert_to_gas_effects(cl, tx_effects.gas_cost_summary(), tx_effects.gas_object()).await?;
-    let gcs = tx_effects.gas_cost_summary();
+    txs: Vec<SuiTransactionBlockResponse>,
+) -> Result<Vec<TransactionBlock>> {
     let system_state = cl.governance_api().get_latest_sui_system_state().await?;
     let protocol_configs = cl.fetch_protocol_config(None).await?;
-    let epoch = convert_to_epoch(gcs, &system_state, &protocol_configs)?;
-    let txd = TransactionDigest::from_array(tx.digest.into_inner());
-    let expiration = epoch.clone();
-    Ok(TransactionBlock {
-        digest: txd,
-        effects: Some(TransactionBlockEffects {
+    convert_to_transaction_blocks(cl, txs, Some((&system_state, &protocol_configs))).await
+}
+
+/// Converts a page of transactions, fetching the gas coins and the states of the changed objects
+/// of the whole page at once. Objects that cannot be found anymore, e.g. because they were
+/// pruned, are left out.
+async fn convert_to_transaction_blocks(
+    provider: &dyn DataProvider,
+    txs: Vec<SuiTransactionBlockResponse>,
+    current_epoch: Option<(&SuiSystemStateSummary, &ProtocolConfigs)>,
+) -> Result<Vec<TransactionBlock>> {
+    let mut converted = vec![];
+    for tx in txs {
+        let txd = TransactionDigest::from_array(tx.digest.into_inner());
+        let data: SenderSignedData = bcs::from_bytes(&tx.raw_transaction)
+            .map_err(|e| Error::Internal(format!("Cannot deserialize transaction {txd:?}: {e}")))?;
+        let Some(effects) = tx.effects.clone() else {
+            return Err(Error::Internal(format!("Transaction {txd:?} has no effects")).extend());
+        };
+        let changes = changed_objects(&effects);
+        converted.push((tx, txd, data, effects, changes));
+    }
+
+    let gas_object_key = |effects: &SuiTransactionBlockEffects| {
+        let reference = &effects.gas_object().reference;
+        (
+            SuiAddress::from_array(**reference.object_id),
+            reference.version.value(),
+        )
+    };
+    let mut keys = vec![];
+    for (_, _, data, effects, changes) in &converted {
+        keys.extend(
+            data.transaction_data()
+                .gas_data()
+                .payment
+                .iter()
+                .map(|(id, version, _)| (SuiAddress::from_array(***id), version.value())),
+        );
+        keys.push(gas_object_key(effects));
+        keys.extend(changes.iter().flat_map(ChangedObject::keys));
+    }
+    let objects = fetch_objs_by_key(provider, keys).await?;
+
+    let mut result = vec![];
+    for (tx, txd, data, effects, changes) in converted {
+        let tx_data = data.transaction_data();
+        let gas_data = tx_data.gas_data();
+        let gcs = effects.gas_cost_summary();
+        let gas_payment = gas_data
+            .payment
+            .iter()
+            .filter_map(|(id, version, _)| {
+                objects
+                    .get(&(SuiAddress::from_array(***id), version.value()))
+                    .cloned()
+            })
+            .collect();
+        let expiration = match tx_data.expiration() {
+            TransactionExpiration::Epoch(e) => Some(convert_epoch_id(*e, gcs, current_epoch)?),
+            TransactionExpiration::None => None,
+        };
+        let balance_changes = tx.balance_changes.as_ref().map(|changes| {
+            changes
+                .iter()
+                .filter_map(|change| {
+                    let owner = change.owner.get_owner_address().ok()?;
+                    Some(BalanceChange {
+                        owner: Owner {
+                            address: SuiAddress::from(owner),
+                        },
+                        amount: BigInt::from(change.amount),
+                        coin_type: change.coin_type.to_string(),
+                    })
+                })
+                .collect()
+        });
+        result.push(TransactionBlock {
             digest: txd,
-            gas_effects: Some(gas_effects),
-            epoch: Some(epoch),
-            status: Some(match tx_effects.status() {
-                SuiExecutionStatus::Success => ExecutionStatus::Success,
-                SuiExecutionStatus::Failure { error: _ } => ExecutionStatus::Failure,
+            effects: Some(TransactionBlockEffects {
+                digest: txd,
+                gas_effects: Some(GasEffects {
+                    gas_object: objects.get(&gas_object_key(&effects)).cloned(),
+                    gas_summary: Some(convert_to_gas_cost_summary(gcs)?),
+                }),
+                epoch: Some(convert_epoch_id(
+                    effects.executed_epoch(),
+                    gcs,
+                    current_epoch,
+                )?),
+                status: Some(match effects.status() {
+                    SuiExecutionStatus::Success => ExecutionStatus::Success,
+                    SuiExecutionStatus::Failure { error: _ } => ExecutionStatus::Failure,
+                }),
+                errors: match effects.status() {
+                    SuiExecutionStatus::Success => None,
+                    SuiExecutionStatus::Failure { error } => Some(error.clone()),
+                },
+                dependencies: Some(
+                    effects
+                        .dependencies()
+                        .iter()
+                        .map(|d| TransactionDigest::from_array(d.into_inner()))
+                        .collect(),
+                ),
+                object_changes: Some(
+                    changes
+                        .into_iter()
+                        .map(|change| change.into_object_change(&objects))
+                        .collect(),
+                ),
+                balance_changes,
             }),
-            errors: match tx_effects.status() {
-                SuiExecutionStatus::Success => None,
-                SuiExecutionStatus::Failure { error } => Some(error.clone()),
+            sender: Some(Address {
+                address: SuiAddress::from(tx_data.sender()),
+            }),
+            bcs: Some(Base64::from(&tx.raw_transaction)),
+            gas_input: Some(GasInput {
+                gas_sponsor: Some(Address::from(SuiAddress::from(gas_data.owner))),
+                gas_payment: Some(gas_payment),
+                gas_price: Some(BigInt::from(gas_data.price)),
+                gas_budget: Some(BigInt::from(gas_data.budget)),
+            }),
+            kind: Some(convert_to_transaction_block_kind(tx_data.kind())),
+            signatures: Some(
+                data.tx_signatures()
+                    .iter()
+                    .map(|s| Base64::from(s.as_ref().to_vec()))
+                    .collect(),
+            ),
+            expiration,
+        });
+    }
+    Ok(result)
+}
+
+/// An object changed by a transaction, with the versions of its input and output states.
+pub(crate) struct ChangedObject {
+    pub address: SuiAddress,
+    pub input_version: Option<u64>,
+    pub output_version: Option<u64>,
+    pub id_created: bool,
+    pub id_deleted: bool,
+}
+
+impl ChangedObject {
+    /// The versions of the object to fetch for its input and output states.
+    pub(crate) fn keys(&self) -> impl Iterator<Item = (SuiAddress, u64)> + '_ {
+        [self.input_version, self.output_version]
+            .into_iter()
+            .flatten()
+            .map(|version| (self.address, version))
+    }
+
+    pub(crate) fn into_object_change(
+        self,
+        states: &HashMap<(SuiAddress, u64), Object>,
+    ) -> ObjectChange {
+        let state = |version: Option<u64>| {
+            version.and_then(|version| states.get(&(self.address, version)).cloned())
+        };
+        ObjectChange {
+            address: self.address,
+            input_state: state(self.input_version),
+            output_state: state(self.output_version),
+            id_created: Some(self.id_created),
+            id_deleted: Some(self.id_deleted),
+        }
+    }
+}
+
+pub(crate) fn changed_objects(effects: &SuiTransactionBlockEffects) -> Vec<ChangedObject> {
+    let input_versions: HashMap<_, _> = effects.modified_at_versions().into_iter().collect();
+    // (object ID, output version, whether the ID was created, whether the ID was deleted)
+    let changes = effects
+        .created()
+        .iter()
+        .map(|o| {
+            (
+                o.reference.object_id,
+                Some(o.reference.version),
+                true,
+                false,
+            )
+        })
+        .chain(
+            effects
+                .mutated()
+                .iter()
+                .chain(effects.unwrapped())
+                .map(|o| {
+                    (
+                        o.reference.object_id,
+                        Some(o.reference.version),
+                        false,
+                        false,
+                    )
+                }),
+        )
+        .chain(
+            effects
+                .deleted()
+                .iter()
+                .chain(effects.unwrapped_then_deleted())
+                .map(|o| (o.object_id, None, false, true)),
+        )
+        .chain(
+            effects
+                .wrapped()
+                .iter()
+                .map(|o| (o.object_id, None, false, false)),
+        );
+
+    changes
+        .map(
+            |(id, output_version, id_created, id_deleted)| ChangedObject {
+                address: SuiAddress::from_array(**id),
+                input_version: input_versions.get(&id).map(|version| version.value()),
+                output_version: output_version.map(|version| version.value()),
+                id_created,
+                id_deleted,
             },
+        )
+        .collect()
+}
+
+/// Fetches the given versions of objects at once, keyed by address and version. The versions
+/// that cannot be found are left out.
+pub(crate) async fn fetch_objs_by_key(
+    provider: &dyn DataProvider,
+    keys: Vec<(SuiAddress, u64)>,
+) -> Result<HashMap<(SuiAddress, u64), Object>> {
+    let objects = provider.multi_fetch_obj(keys.clone()).await?;
+    Ok(keys
+        .into_iter()
+        .zip(objects)
+        .filter_map(|(key, object)| Some((key, object?)))
+        .collect())
+}
+
+pub(crate) fn convert_to_transaction_block_kind(
+    kind: &NativeTransactionKind,
+) -> TransactionBlockKind {
+    match kind {
+        NativeTransactionKind::ConsensusCommitPrologue(prologue) => {
+            TransactionBlockKind::ConsensusCommitPrologue(ConsensusCommitPrologueTransaction {
+                epoch_id: prologue.epoch,
+                round: prologue.round,
+                timestamp: i64::try_from(prologue.commit_timestamp_ms)
+                    .ok()
+                    .and_then(DateTime::from_ms),
+            })
+        }
+        NativeTransactionKind::Genesis(genesis) => {
+            TransactionBlockKind::Genesis(GenesisTransaction {
+                objects: genesis
+                    .objects
+                    .iter()
+                    .map(|o| SuiAddress::from_array(**o.id()))
+                    .collect(),
+            })
+        }
+        NativeTransactionKind::ChangeEpoch(change) => {
+            TransactionBlockKind::ChangeEpoch(ChangeEpochTransaction {
+                epoch_id: change.epoch,
+                protocol_version: change.protocol_version.as_u64(),
+                timestamp: i64::try_from(change.epoch_start_timestamp_ms)
+                    .ok()
+                    .and_then(DateTime::from_ms),
+                storage_charge: BigInt::from(change.storage_charge),
+                computation_charge: BigInt::from(change.computation_charge),
+                storage_rebate: BigInt::from(change.storage_rebate),
+                non_refundable_storage_fee: BigInt::from(change.non_refundable_storage_fee),
+            })
+        }
+        NativeTransactionKind::AuthenticatorStateUpdate(update) => {
+            TransactionBlockKind::AuthenticatorStateUpdate(AuthenticatorStateUpdateTransaction {
+                epoch_id: update.epoch,
+                round: update.round,
+            })
+        }
+        NativeTransactionKind::ProgrammableTransaction(pt) => {
+            TransactionBlockKind::Programmable(ProgrammableTransactionBlock {
+                inputs: pt.inputs.iter().map(convert_to_transaction_input).collect(),
+                transactions: pt
+                    .commands
+                    .iter()
+                    .map(convert_to_programmable_transaction)
+                    .collect(),
+            })
+        }
+    }
+}
+
+fn convert_to_transaction_input(input: &NativeCallArg) -> TransactionInput {
+    match input {
+        NativeCallArg::Pure(bytes) => TransactionInput::Pure(PureInput {
+            bytes: Base64::from(bytes),
+        }),
+        NativeCallArg::Object(NativeObjectArg::ImmOrOwnedObject((id, version, digest))) => {
+            TransactionInput::OwnedOrImmutable(OwnedOrImmutableInput {
+                address: SuiAddress::from_array(**id),
+                version: version.value(),
+                digest: digest.to_string(),
+            })
+        }
+        NativeCallArg::Object(NativeObjectArg::SharedObject {
+            id,
+            initial_shared_version,
+            mutable,
+        }) => TransactionInput::Shared(SharedInput {
+            address: SuiAddress::from_array(**id),
+            initial_shared_version: initial_shared_version.value(),
+            mutable: *mutable,
+        }),
+    }
+}
+
+fn convert_to_programmable_transaction(command: &NativeCommand) -> ProgrammableTransaction {
+    let arguments = |args: &[NativeArgument]| -> Vec<TransactionArgument> {
+        args.iter().map(convert_to_argument).collect()
+    };
+    let addresses = |ids: &[NativeObjectID]| -> Vec<SuiAddress> {
+        ids.iter().map(|id| SuiAddress::from_array(**id)).collect()
+    };
+    let modules =
+        |modules: &[Vec<u8>]| -> Vec<Base64> { modules.iter().map(Base64::from).collect() };
+    match command {
+        NativeCommand::MoveCall(call) => ProgrammableTransaction::MoveCall(MoveCallTransaction {
+            package: SuiAddress::from_array(**call.package),
+            module: call.module.to_string(),
+            function_name: call.function.to_string(),
+            type_arguments: call.type_arguments.iter().map(|t| t.to_string()).collect(),
+            arguments: arguments(&call.arguments),
+        }),
+        NativeCommand::TransferObjects(objects, address) => {
+            ProgrammableTransaction::TransferObjects(TransferObjectsTransaction {
+                objects: arguments(objects),
+                address: convert_to_argument(address),
+            })
+        }
+        NativeCommand::SplitCoins(coin, amounts) => {
+            ProgrammableTransaction::SplitCoin(SplitCoinTransaction {
+                coin: convert_to_argument(coin),
+                amounts: arguments(amounts),
+            })
+        }
+        NativeCommand::MergeCoins(coin, coins) => {
+            ProgrammableTransaction::MergeCoins(MergeCoinsTransaction {
+                coin: convert_to_argument(coin),
+                coins: arguments(coins),
+            })
+        }
+        NativeCommand::Publish(published, dependencies) => {
+            ProgrammableTransaction::Publish(PublishTransaction {
+                modules: modules(published),
+                dependencies: addresses(dependencies),
+            })
+        }
+        NativeCommand::Upgrade(upgraded, dependencies, current_package, upgrade_ticket) => {
+            ProgrammableTransaction::Upgrade(UpgradeTransaction {
+                modules: modules(upgraded),
+                dependencies: addresses(dependencies),
+                current_package: SuiAddress::from_array(**current_package),
+                upgrade_ticket: convert_to_argument(upgrade_ticket),
+            })
+        }
+        NativeCommand::MakeMoveVec(type_, elements) => {
+            ProgrammableTransaction::MakeMoveVec(MakeMoveVecTransaction {
+                type_: type_.as_ref().map(|t| t.to_string()),
+                elements: arguments(elements),
+            })
+        }
+    }
+}
+
+fn convert_to_argument(argument: &NativeArgument) -> TransactionArgument {
+    match argument {
+        NativeArgument::GasCoin => TransactionArgument::GasCoin(GasCoin { dummy: None }),
+        NativeArgument::Input(ix) => TransactionArgument::Input(Input { ix: *ix }),
+        NativeArgument::Result(cmd) => TransactionArgument::Result(TxResult {
+            cmd: *cmd,
+            ix: None,
         }),
+        NativeArgument::NestedResult(cmd, ix) => TransactionArgument::Result(TxResult {
+            cmd: *cmd,
+            ix: Some(*ix),
+        }),
+    }
+}
+
+/// Converts the filter into a JSON-RPC filter matching the transactions that meet all its
+/// criteria, if it has any. A filter with a single criterion is returned as is.
+fn convert_tx_filter(filter: TransactionBlockFilter) -> Result<Option<TransactionFilter>> {
+    let unsupported = |reason: &str| Error::UnsupportedFilter(reason.to_string()).extend();
+    let object_id = |a: SuiAddress| NativeObjectID::from(NativeSuiAddress::from(a));
+
+    if filter.paid_address.is_some() {
+        return Err(unsupported("paidAddress"));
+    }
+    if (filter.module.is_some() && filter.package.is_none())
+        || (filter.function.is_some() && filter.module.is_none())
+    {
+        return Err(unsupported(
+            "'function' requires 'module', which requires 'package'",
+        ));
+    }
+    let from = match (filter.sign_address, filter.sent_address) {
+        (Some(sign), Some(sent)) if sign != sent => {
+            return Err(unsupported(
+                "'signAddress' and 'sentAddress' must be the same address",
+            ))
+        }
+        (sign, sent) => sign.or(sent).map(NativeSuiAddress::from),
+    };
+    let to = filter.recv_address.map(NativeSuiAddress::from);
+
+    let mut filters = vec![];
+    if let Some(package) = filter.package {
+        filters.push(TransactionFilter::MoveFunction {
+            package: object_id(package),
+            module: filter.module,
+            function: filter.function,
+        });
+    }
+    if let Some(kind) = filter.kind {
+        filters.push(match kind {
+            TransactionBlockKindInput::ProgrammableTx => {
+                TransactionFilter::TransactionKind("ProgrammableTransaction".to_string())
+            }
+            TransactionBlockKindInput::SystemTx => TransactionFilter::TransactionKindIn(
+                [
+                    "ChangeEpoch",
+                    "Genesis",
+                    "ConsensusCommitPrologue",
+                    "AuthenticatorStateUpdate",
+                ]
+                .map(String::from)
+                .to_vec(),
+            ),
+        });
+    }
+    if let Some(checkpoint) = filter.checkpoint {
+        filters.push(TransactionFilter::Checkpoint(checkpoint));
+    }
+    match (from, to) {
+        (Some(from), Some(to)) => filters.push(TransactionFilter::FromAndToAddress { from, to }),
+        (Some(from), None) => filters.push(TransactionFilter::FromAddress(from)),
+        (None, Some(to)) => filters.push(TransactionFilter::ToAddress(to)),
+        (None, None) => {}
+    }
+    if let Some(input_object) = filter.input_object {
+        filters.push(TransactionFilter::InputObject(object_id(input_object)));
+    }
+    if let Some(changed_object) = filter.changed_object {
+        filters.push(TransactionFilter::ChangedObject(object_id(changed_object)));
+    }
+
+    Ok(if filters.len() > 1 {
+        Some(TransactionFilter::All(filters))
+    } else {
+        filters.pop()
+    })
+}
+
+fn convert_to_event(event: SuiEvent) -> Event {
+    Event {
+        id: ID(format!("{}:{}", event.id.tx_digest, event.id.event_seq)),
+        sending_package: Some(SuiAddress::from_array(**event.package_id)),
+        sending_module: Some(event.transaction_module.to_string()),
+        event_type: Some(event.type_.to_string()),
         sender: Some(Address {
-            address: SuiAddress::from_array(sender.to_inner()),
+            address: SuiAddress::from(event.sender),
         }),
-        bcs: Some(Base64::from(&tx.raw_transaction)),
-        gas_input: Some(convert_to_gas_input(cl, tx_data.data.gas_data()).await?),
-        expiration: Some(expiration),
+        timestamp: event.timestamp_ms.and_then(|t| DateTime::from_ms(t as i64)),
+        json: Some(event.parsed_json.to_string()),
+        bcs: Some(Base64::from(event.bcs)),
+    }
+}
+
+/// Converts the filter into a JSON-RPC filter matching the events that meet all its criteria. A
+/// filter with a single criterion is returned as is.
+fn convert_event_filter(filter: EventFilter) -> Result<NativeEventFilter> {
+    let unsupported = |reason: &str| Error::UnsupportedFilter(reason.to_string()).extend();
+    let invalid = |reason: String| Error::InvalidFilter(reason).extend();
+    let object_id = |a: SuiAddress| NativeObjectID::from(NativeSuiAddress::from(a));
+    let identifier = |module: String| {
+        Identifier::new(module.as_str())
+            .map_err(|e| invalid(format!("Invalid module name '{module}': {e}")))
+    };
+
+    if filter.emitting_module.is_some() && filter.emitting_package.is_none() {
+        return Err(unsupported("'emittingModule' requires 'emittingPackage'"));
+    }
+    if filter.event_package.is_some() != filter.event_module.is_some()
+        || (filter.event_type.is_some() && filter.event_module.is_none())
+    {
+        return Err(unsupported(
+            "'eventPackage' and 'eventModule' must be used together, and 'eventType' requires them",
+        ));
+    }
+
+    let mut filters = vec![];
+    if let Some(sender) = filter.sender {
+        filters.push(NativeEventFilter::Sender(sender.into()));
+    }
+    if let Some(digest) = filter.transaction_digest {
+        filters.push(NativeEventFilter::Transaction(
+            NativeTransactionDigest::from_str(&digest)?,
+        ));
+    }
+    if let Some(package) = filter.emitting_package {
+        filters.push(match filter.emitting_module {
+            Some(module) => NativeEventFilter::MoveModule {
+                package: object_id(package),
+                module: identifier(module)?,
+            },
+            None => NativeEventFilter::Package(object_id(package)),
+        });
+    }
+    if let (Some(package), Some(module)) = (filter.event_package, filter.event_module) {
+        filters.push(match filter.event_type {
+            Some(type_) => {
+                let package = NativeSuiAddress::from(package);
+                let tag = format!("{package}::{module}::{type_}");
+                NativeEventFilter::MoveEventType(
+                    parse_sui_struct_tag(&tag)
+                        .map_err(|e| invalid(format!("Invalid event type '{tag}': {e}")))?,
+                )
+            }
+            None => NativeEventFilter::MoveEventModule {
+                package: object_id(package),
+                module: identifier(module)?,
+            },
+        });
+    }
+    if let Some(EventField { path, json }) = filter.event_field {
+        let value = serde_json::from_str(&json)
+            .map_err(|e| invalid(format!("Invalid JSON value of event field: {e}")))?;
+        filters.push(NativeEventFilter::MoveEventField { path, value });
+    }
+    if filter.start_time.is_some() || filter.end_time.is_some() {
+        let timestamp_ms = |t: DateTime| t.timestamp_ms().max(0) as u64;
+        filters.push(NativeEventFilter::TimeRange {
+            start_time: filter.start_time.map_or(0, timestamp_ms),
+            end_time: filter.end_time.map_or(u64::MAX, timestamp_ms),
+        });
+    }
+    Ok(if filters.len() == 1 {
+        filters.remove(0)
+    } else {
+        NativeEventFilter::All(filters)
+    })
+}
+
+/// Parses the cursor of an event, made of the digest of its transaction and its sequence number in
+/// that transaction, as in the IDs of events.
+fn parse_event_cursor(cursor: &str) -> Result<NativeEventID> {
+    let invalid = || Error::InvalidCursor(format!("Invalid event cursor '{cursor}'")).extend();
+    let (digest, seq) = cursor.split_once(':').ok_or_else(invalid)?;
+    Ok(NativeEventID {
+        tx_digest: NativeTransactionDigest::from_str(digest).map_err(|_| invalid())?,
+        event_seq: seq.parse().map_err(|_| invalid())?,
     })
 }
 
@@ -666,7 +1480,7 @@ impl From<&SuiAddress> for NativeSuiAddress {
     }
 }
 
-fn ensure_forward_pagination(
+pub(crate) fn ensure_forward_pagination(
     first: &Option<u64>,
     after: &Option<String>,
     last: &Option<u64>,
@@ -683,3 +1497,555 @@ fn ensure_forward_pagination(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+    use sui_framework::BuiltInFramework;
+    use sui_json_rpc_types::SuiObjectData;
+    use sui_sdk::types::base_types::SequenceNumber;
+    use sui_sdk::types::committee::ProtocolVersion;
+    use sui_sdk::types::digests::ObjectDigest;
+    use sui_sdk::types::effects::TransactionEffects;
+    use sui_sdk::types::execution_status::ExecutionStatus as NativeExecutionStatus;
+    use sui_sdk::types::object::{Data as NativeData, Object as NativeObject};
+    use sui_sdk::types::MOVE_STDLIB_PACKAGE_ID;
+    use sui_test_transaction_builder::{create_devnet_nft, publish_nfts_package};
+    use test_cluster::TestClusterBuilder;
+    use tokio::time::{timeout, Duration};
+
+    /// Serves objects from memory, recording the batches of objects it is asked for.
+    #[derive(Default)]
+    struct MockObjectProvider {
+        objects: BTreeMap<(SuiAddress, u64), Object>,
+        batches: Mutex<Vec<Vec<(SuiAddress, u64)>>>,
+    }
+
+    impl MockObjectProvider {
+        fn insert(&mut self, id: NativeObjectID, version: u64) {
+            let address = SuiAddress::from_array(**id);
+            self.objects.insert(
+                (address, version),
+                Object {
+                    address,
+                    version,
+                    digest: String::new(),
+                    storage_rebate: None,
+                    owner: None,
+                    bcs: None,
+                    previous_transaction: None,
+                    kind: None,
+                    object_type: None,
+                },
+            );
+        }
+    }
+
+    #[async_trait]
+    impl DataProvider for MockObjectProvider {
+        async fn fetch_obj(&self, _: SuiAddress, _: Option<u64>) -> Result<Option<Object>> {
+            panic!("Objects must be fetched in batches")
+        }
+
+        async fn multi_fetch_obj(
+            &self,
+            keys: Vec<(SuiAddress, u64)>,
+        ) -> Result<Vec<Option<Object>>> {
+            self.batches.lock().unwrap().push(keys.clone());
+            Ok(keys
+                .iter()
+                .map(|key| self.objects.get(key).cloned())
+                .collect())
+        }
+
+        async fn fetch_owned_objs(
+            &self,
+            _: &SuiAddress,
+            _: Option<u64>,
+            _: Option<String>,
+            _: Option<u64>,
+            _: Option<String>,
+            _: Option<ObjectFilter>,
+        ) -> Result<Connection<String, Object>> {
+            unimplemented!()
+        }
+
+        async fn fetch_balance(&self, _: &SuiAddress, _: Option<String>) -> Result<Balance> {
+            unimplemented!()
+        }
+
+        async fn fetch_balance_connection(
+            &self,
+            _: &SuiAddress,
+            _: Option<u64>,
+            _: Option<String>,
+            _: Option<u64>,
+            _: Option<String>,
+        ) -> Result<Connection<String, Balance>> {
+            unimplemented!()
+        }
+
+        async fn fetch_checkpoint_connection(
+            &self,
+            _: Option<u64>,
+            _: Option<String>,
+            _: Option<u64>,
+            _: Option<String>,
+        ) -> Result<Connection<String, Checkpoint>> {
+            unimplemented!()
+        }
+
+        async fn fetch_tx(&self, _: &str) -> Result<Option<TransactionBlock>> {
+            unimplemented!()
+        }
+
+        async fn fetch_tx_connection(
+            &self,
+            _: Option<u64>,
+            _: Option<String>,
+            _: Option<u64>,
+            _: Option<String>,
+            _: Option<TransactionBlockFilter>,
+        ) -> Result<Connection<String, TransactionBlock>> {
+            unimplemented!()
+        }
+
+        async fn fetch_event_connection(
+            &self,
+            _: Option<u64>,
+            _: Option<String>,
+            _: Option<u64>,
+            _: Option<String>,
+            _: Option<EventFilter>,
+        ) -> Result<Connection<String, Event>> {
+            unimplemented!()
+        }
+
+        async fn fetch_chain_id(&self) -> Result<String> {
+            unimplemented!()
+        }
+
+        async fn fetch_protocol_config(&self, _: Option<u64>) -> Result<ProtocolConfigs> {
+            unimplemented!()
+        }
+
+        async fn execute_tx(&self, _: Base64, _: Vec<Base64>) -> Result<ExecutionResult> {
+            unimplemented!()
+        }
+
+        async fn subscribe_events(
+            &self,
+            _: Option<EventFilter>,
+        ) -> Result<BoxStream<'static, Result<Event>>> {
+            unimplemented!()
+        }
+
+        async fn subscribe_txs(
+            &self,
+            _: Option<TransactionBlockFilter>,
+        ) -> Result<BoxStream<'static, Result<TransactionBlock>>> {
+            unimplemented!()
+        }
+    }
+
+    fn versions(change: &ObjectChange) -> (Option<u64>, Option<u64>) {
+        (
+            change.input_state.as_ref().map(|o| o.version),
+            change.output_state.as_ref().map(|o| o.version),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_transaction_page_is_fetched_in_one_batch() {
+        let [gas, created, mutated, deleted, wrapped] = [(); 5].map(|_| NativeObjectID::random());
+        let object_ref = |id, version| {
+            (
+                id,
+                SequenceNumber::from_u64(version),
+                ObjectDigest::random(),
+            )
+        };
+        let owner = NativeOwner::AddressOwner(NativeSuiAddress::ZERO);
+        let tx_data = NativeTransactionData::new_transfer_sui(
+            NativeSuiAddress::ZERO,
+            NativeSuiAddress::ZERO,
+            None,
+            object_ref(gas, 1),
+            1_000_000,
+            1_000,
+        );
+        let raw_transaction = bcs::to_bytes(&SenderSignedData::new(
+            tx_data,
+            Intent::sui_transaction(),
+            vec![],
+        ))
+        .unwrap();
+        let response = |executed_epoch| {
+            let digest = NativeTransactionDigest::random();
+            let effects = TransactionEffects::new_from_execution(
+                ProtocolVersion::MAX,
+                NativeExecutionStatus::Success,
+                executed_epoch,
+                NativeGasCostSummary::default(),
+                vec![
+                    (gas, SequenceNumber::from_u64(1)),
+                    (mutated, SequenceNumber::from_u64(1)),
+                    (deleted, SequenceNumber::from_u64(1)),
+                    (wrapped, SequenceNumber::from_u64(1)),
+                ],
+                vec![],
+                digest,
+                vec![(object_ref(created, 2), owner)],
+                vec![(object_ref(mutated, 2), owner), (object_ref(gas, 2), owner)],
+                vec![],
+                vec![object_ref(deleted, 2)],
+                vec![],
+                vec![object_ref(wrapped, 2)],
+                (object_ref(gas, 2), owner),
+                None,
+                vec![],
+            );
+            SuiTransactionBlockResponse {
+                digest,
+                raw_transaction: raw_transaction.clone(),
+                effects: Some(SuiTransactionBlockEffects::try_from(effects).unwrap()),
+                ..Default::default()
+            }
+        };
+
+        // The input versions of the gas coin and of the wrapped object are missing.
+        let mut provider = MockObjectProvider::default();
+        provider.insert(gas, 2);
+        provider.insert(created, 2);
+        provider.insert(mutated, 1);
+        provider.insert(mutated, 2);
+        provider.insert(deleted, 1);
+
+        let txs = convert_to_transaction_blocks(&provider, vec![response(3), response(4)], None)
+            .await
+            .unwrap();
+        let address = |id: NativeObjectID| SuiAddress::from_array(**id);
+        let keys = vec![
+            (address(gas), 1),
+            (address(gas), 2),
+            (address(created), 2),
+            (address(mutated), 1),
+            (address(mutated), 2),
+            (address(gas), 1),
+            (address(gas), 2),
+            (address(deleted), 1),
+            (address(wrapped), 1),
+        ];
+        assert_eq!(
+            provider.batches.into_inner().unwrap(),
+            vec![[keys.clone(), keys].concat()]
+        );
+
+        assert_eq!(txs.len(), 2);
+        let effects = txs[0].effects.as_ref().unwrap();
+        assert_eq!(effects.epoch.as_ref().unwrap().epoch_id, 3);
+        assert_eq!(
+            txs[1]
+                .effects
+                .as_ref()
+                .unwrap()
+                .epoch
+                .as_ref()
+                .unwrap()
+                .epoch_id,
+            4
+        );
+        assert!(txs[0].expiration.is_none());
+        assert_eq!(txs[0].gas_input.as_ref().unwrap().gas_payment, Some(vec![]));
+        let gas_object = effects.gas_effects.as_ref().unwrap().gas_object.as_ref();
+        assert_eq!(
+            gas_object.map(|o| (o.address, o.version)),
+            Some((address(gas), 2))
+        );
+
+        let changes = effects.object_changes.as_ref().unwrap();
+        assert_eq!(changes.len(), 5);
+        assert_eq!(changes[0].address, address(created));
+        assert_eq!(versions(&changes[0]), (None, Some(2)));
+        assert_eq!(changes[0].id_created, Some(true));
+        assert_eq!(changes[1].address, address(mutated));
+        assert_eq!(versions(&changes[1]), (Some(1), Some(2)));
+        assert_eq!(changes[1].id_created, Some(false));
+        assert_eq!(changes[2].address, address(gas));
+        assert_eq!(versions(&changes[2]), (None, Some(2)));
+        assert_eq!(changes[3].address, address(deleted));
+        assert_eq!(versions(&changes[3]), (Some(1), None));
+        assert_eq!(changes[3].id_deleted, Some(true));
+        assert_eq!(changes[4].address, address(wrapped));
+        assert_eq!(versions(&changes[4]), (None, None));
+        assert_eq!(changes[4].id_deleted, Some(false));
+    }
+
+    fn event_filter_error(filter: EventFilter) -> String {
+        convert_event_filter(filter).err().unwrap().message
+    }
+
+    fn tx_filter_error(filter: TransactionBlockFilter) -> String {
+        convert_tx_filter(filter).err().unwrap().message
+    }
+
+    #[test]
+    fn test_event_filter_validation() {
+        let package = Some(SuiAddress::from_array([1; 32]));
+        let module = || Some("devnet_nft".to_string());
+
+        assert_eq!(
+            event_filter_error(EventFilter {
+                emitting_module: module(),
+                ..Default::default()
+            }),
+            "Unsupported filter: 'emittingModule' requires 'emittingPackage'"
+        );
+        let event_type_error = "Unsupported filter: 'eventPackage' and 'eventModule' must be used \
+                                together, and 'eventType' requires them";
+        assert_eq!(
+            event_filter_error(EventFilter {
+                event_package: package,
+                ..Default::default()
+            }),
+            event_type_error
+        );
+        assert_eq!(
+            event_filter_error(EventFilter {
+                event_module: module(),
+                ..Default::default()
+            }),
+            event_type_error
+        );
+        assert_eq!(
+            event_filter_error(EventFilter {
+                event_type: Some("MintNFTEvent".to_string()),
+                ..Default::default()
+            }),
+            event_type_error
+        );
+        assert!(event_filter_error(EventFilter {
+            event_package: package,
+            event_module: Some("0devnet_nft".to_string()),
+            ..Default::default()
+        })
+        .starts_with("Invalid filter: Invalid module name '0devnet_nft'"));
+        assert!(event_filter_error(EventFilter {
+            event_field: Some(EventField {
+                path: "/name".to_string(),
+                json: "{".to_string(),
+            }),
+            ..Default::default()
+        })
+        .starts_with("Invalid filter: Invalid JSON value of event field"));
+
+        // A single criterion is used as is, several are combined.
+        let event_type = convert_event_filter(EventFilter {
+            event_package: package,
+            event_module: module(),
+            event_type: Some("MintNFTEvent".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(matches!(event_type, NativeEventFilter::MoveEventType(_)));
+        let all = convert_event_filter(EventFilter {
+            sender: package,
+            emitting_package: package,
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(matches!(all, NativeEventFilter::All(filters) if filters.len() == 2));
+    }
+
+    #[test]
+    fn test_tx_filter_validation() {
+        let address = |b| Some(SuiAddress::from_array([b; 32]));
+
+        assert_eq!(
+            tx_filter_error(TransactionBlockFilter {
+                paid_address: address(1),
+                ..Default::default()
+            }),
+            "Unsupported filter: paidAddress"
+        );
+        assert_eq!(
+            tx_filter_error(TransactionBlockFilter {
+                function: Some("mint".to_string()),
+                package: address(1),
+                ..Default::default()
+            }),
+            "Unsupported filter: 'function' requires 'module', which requires 'package'"
+        );
+        assert_eq!(
+            tx_filter_error(TransactionBlockFilter {
+                sign_address: address(1),
+                sent_address: address(2),
+                ..Default::default()
+            }),
+            "Unsupported filter: 'signAddress' and 'sentAddress' must be the same address"
+        );
+
+        assert!(matches!(
+            convert_tx_filter(TransactionBlockFilter {
+                sign_address: address(1),
+                recv_address: address(2),
+                ..Default::default()
+            })
+            .unwrap(),
+            Some(TransactionFilter::FromAndToAddress { .. })
+        ));
+        let all = convert_tx_filter(TransactionBlockFilter {
+            sent_address: address(1),
+            checkpoint: Some(1),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(matches!(all, Some(TransactionFilter::All(filters)) if filters.len() == 2));
+        assert!(convert_tx_filter(TransactionBlockFilter::default())
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_execute_tx() {
+        let cluster = TestClusterBuilder::new().build().await;
+        let recipient = cluster.get_address_1();
+        let tx_data = cluster
+            .test_transaction_builder()
+            .await
+            .transfer_sui(Some(1), recipient)
+            .build();
+        let tx = cluster.wallet.sign_transaction(&tx_data);
+        let tx_bytes = || Base64::from(bcs::to_bytes(tx.transaction_data()).unwrap());
+        let signatures = || {
+            tx.tx_signatures()
+                .iter()
+                .map(|s| Base64::from(s.as_ref().to_vec()))
+                .collect::<Vec<_>>()
+        };
+        let client = cluster.sui_client();
+
+        let error = client
+            .execute_tx(Base64::from(vec![0; 4]), signatures())
+            .await
+            .err()
+            .unwrap();
+        assert!(error
+            .message
+            .starts_with("Invalid transaction: Cannot deserialize transaction data"));
+        let error = client
+            .execute_tx(tx_bytes(), vec![Base64::from(vec![0; 4])])
+            .await
+            .err()
+            .unwrap();
+        assert!(error
+            .message
+            .starts_with("Invalid transaction: Cannot deserialize signature"));
+
+        // Transactions the validators refuse to sign are reported as errors of the result.
+        let result = client.execute_tx(tx_bytes(), vec![]).await.unwrap();
+        assert!(result.transaction_block.is_none());
+        assert!(result.errors.is_some());
+
+        let result = client.execute_tx(tx_bytes(), signatures()).await.unwrap();
+        assert_eq!(result.errors, None);
+        let transaction_block = result.transaction_block.unwrap();
+        assert_eq!(
+            transaction_block.digest.into_array(),
+            tx.digest().into_inner()
+        );
+        assert!(transaction_block.effects.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions() {
+        let cluster = TestClusterBuilder::new()
+            .enable_fullnode_events()
+            .build()
+            .await;
+        let client = cluster.sui_client();
+        let (package_id, _, _) = publish_nfts_package(&cluster.wallet).await;
+        let sender = SuiAddress::from(cluster.get_address_0());
+
+        assert_eq!(
+            client.subscribe_txs(None).await.err().unwrap().message,
+            "Unsupported filter: subscribing to transactions requires a filter"
+        );
+        assert_eq!(
+            client
+                .subscribe_txs(Some(TransactionBlockFilter {
+                    checkpoint: Some(1),
+                    ..Default::default()
+                }))
+                .await
+                .err()
+                .unwrap()
+                .message,
+            "Unsupported filter: transactions cannot be subscribed to by checkpoint"
+        );
+
+        let mut events = client
+            .subscribe_events(Some(EventFilter {
+                event_package: Some(SuiAddress::from_array(**package_id)),
+                event_module: Some("devnet_nft".to_string()),
+                event_type: Some("MintNFTEvent".to_string()),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        let mut txs = client
+            .subscribe_txs(Some(TransactionBlockFilter {
+                sent_address: Some(sender),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+
+        let (_, _, digest) = create_devnet_nft(&cluster.wallet, package_id).await;
+
+        let event = timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.sending_module.as_deref(), Some("devnet_nft"));
+        assert!(event
+            .event_type
+            .unwrap()
+            .ends_with("::devnet_nft::MintNFTEvent"));
+        assert_eq!(event.sender, Some(Address { address: sender }));
+        let tx = timeout(Duration::from_secs(5), txs.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx.digest.into_array(), digest.into_inner());
+    }
+
+    #[test]
+    fn test_convert_package() {
+        let package =
+            BuiltInFramework::get_package_by_id(&MOVE_STDLIB_PACKAGE_ID).genesis_move_package();
+        let object = NativeObject::new_package_from_data(
+            NativeData::Package(package.clone()),
+            NativeTransactionDigest::genesis(),
+        );
+        let data = SuiObjectData::try_from((
+            object.compute_object_reference(),
+            object,
+            None,
+            object_data_options(),
+        ))
+        .unwrap();
+
+        let converted = convert_obj(&data).move_package().unwrap().unwrap();
+        assert_eq!(
+            converted.address,
+            SuiAddress::from_array(**MOVE_STDLIB_PACKAGE_ID)
+        );
+        assert_eq!(converted.version, package.version().value());
+        assert_eq!(&converted.modules, package.serialized_module_map());
+    }
+}
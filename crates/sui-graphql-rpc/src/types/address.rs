@@ -34,14 +34,25 @@ pub(crate) enum AddressTransactionBlockRelationship {
 impl Address {
     async fn transaction_block_connection(
         &self,
+        ctx: &Context<'_>,
         first: Option<u64>,
         after: Option<String>,
         last: Option<u64>,
         before: Option<String>,
         relation: Option<AddressTransactionBlockRelationship>,
         filter: Option<TransactionBlockFilter>,
-    ) -> Option<Connection<String, TransactionBlock>> {
-        unimplemented!()
+    ) -> Result<Connection<String, TransactionBlock>> {
+        let mut filter = filter.unwrap_or_default();
+        let address = Some(self.address);
+        match relation.unwrap_or(AddressTransactionBlockRelationship::Sign) {
+            AddressTransactionBlockRelationship::Sign => filter.sign_address = address,
+            AddressTransactionBlockRelationship::Sent => filter.sent_address = address,
+            AddressTransactionBlockRelationship::Recv => filter.recv_address = address,
+            AddressTransactionBlockRelationship::Paid => filter.paid_address = address,
+        }
+        ctx.data_provider()
+            .fetch_tx_connection(first, after, last, before, Some(filter))
+            .await
     }
 
     // =========== Owner interface methods =============
@@ -16,5 +16,5 @@ pub(crate) struct Balance {
 pub(crate) struct BalanceChange {
     pub(crate) owner: Owner,
     pub(crate) amount: BigInt,
-    // pub(crate) coin_type: MoveType,
+    pub(crate) coin_type: String,
 }
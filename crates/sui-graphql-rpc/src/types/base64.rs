@@ -37,6 +37,12 @@ impl FromStr for Base64 {
     }
 }
 
+impl AsRef<[u8]> for Base64 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 impl From<&Vec<u8>> for Base64 {
     fn from(bytes: &Vec<u8>) -> Self {
         Base64(bytes.clone())
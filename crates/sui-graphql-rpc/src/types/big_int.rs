@@ -55,6 +55,12 @@ impl From<u64> for BigInt {
     }
 }
 
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        BigInt::from_str(&value.to_string()).expect("Cannot parse i128 into BigInt")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,6 +37,10 @@ impl DateTime {
             .single()
             .map(Self)
     }
+
+    pub fn timestamp_ms(&self) -> i64 {
+        self.0.timestamp_millis()
+    }
 }
 
 impl FromStr for DateTime {
@@ -0,0 +1,41 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{address::Address, base64::Base64, date_time::DateTime, sui_address::SuiAddress};
+use async_graphql::*;
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct Event {
+    pub id: ID,
+    pub sending_package: Option<SuiAddress>,
+    pub sending_module: Option<String>,
+    pub event_type: Option<String>,
+    pub sender: Option<Address>,
+    pub timestamp: Option<DateTime>,
+    pub json: Option<String>,
+    pub bcs: Option<Base64>,
+}
+
+#[derive(InputObject, Default)]
+pub(crate) struct EventFilter {
+    pub sender: Option<SuiAddress>,
+    pub transaction_digest: Option<String>,
+
+    pub emitting_package: Option<SuiAddress>,
+    pub emitting_module: Option<String>,
+
+    pub event_package: Option<SuiAddress>,
+    pub event_module: Option<String>,
+    pub event_type: Option<String>,
+
+    pub event_field: Option<EventField>,
+
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+}
+
+#[derive(InputObject)]
+pub(crate) struct EventField {
+    pub path: String,
+    pub json: String,
+}
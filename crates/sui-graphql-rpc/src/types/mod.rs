@@ -12,7 +12,13 @@ pub(crate) mod date_time;
 pub(crate) mod display;
 pub(crate) mod end_of_epoch_data;
 pub(crate) mod epoch;
+pub(crate) mod event;
 pub(crate) mod gas;
+pub(crate) mod move_module;
+pub(crate) mod move_object;
+pub(crate) mod move_package;
+pub(crate) mod move_value;
+pub(crate) mod mutation;
 pub(crate) mod name_service;
 pub(crate) mod object;
 pub(crate) mod owner;
@@ -22,9 +28,11 @@ pub(crate) mod safe_mode;
 pub(crate) mod stake;
 pub(crate) mod stake_subsidy;
 pub(crate) mod storage_fund;
+pub(crate) mod subscription;
 pub(crate) mod sui_address;
 pub(crate) mod system_parameters;
 pub(crate) mod transaction_block;
+pub(crate) mod transaction_block_kind;
 pub(crate) mod tx_digest;
 pub(crate) mod validator;
 pub(crate) mod validator_credentials;
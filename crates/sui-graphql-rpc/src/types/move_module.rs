@@ -0,0 +1,303 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use async_graphql::connection::{Connection, Edge};
+use async_graphql::*;
+use move_binary_format::file_format::{Ability, AbilitySet, CompiledModule, Visibility};
+use move_binary_format::file_format_common::VERSION_MAX;
+use move_binary_format::normalized::{
+    Function as NativeFunction, Module as NativeModule, Struct as NativeStruct, Type as NativeType,
+};
+use move_core_types::identifier::IdentStr;
+
+use super::{base64::Base64, move_package::MovePackage, sui_address::SuiAddress};
+use crate::error::Error;
+use crate::server::context_ext::DataProviderContextExt;
+use crate::server::sui_sdk_data_provider::{ensure_forward_pagination, DEFAULT_PAGE_SIZE};
+
+/// The contents of a module are shared with the declarations of its structs and functions,
+/// which refer back to it, so it is cheap to clone.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveModule {
+    pub package: SuiAddress,
+    pub bytes: Arc<[u8]>,
+    pub native: Arc<NativeModule>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveModuleId {
+    pub package: SuiAddress,
+    pub name: String,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveStructDecl {
+    pub module: MoveModule,
+    pub name: String,
+    pub abilities: Vec<MoveAbility>,
+    pub type_parameters: Vec<MoveStructTypeParameterDecl>,
+    pub fields: Vec<MoveFieldDecl>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveStructTypeParameterDecl {
+    pub constraints: Vec<MoveAbility>,
+    pub is_phantom: bool,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveFieldDecl {
+    pub name: String,
+    #[graphql(name = "type")]
+    pub type_: OpenMoveType,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveFunction {
+    pub module: MoveModule,
+    pub name: String,
+    pub visibility: MoveVisibility,
+    pub is_entry: bool,
+    pub type_parameters: Vec<MoveFunctionTypeParameterDecl>,
+    pub parameters: Vec<OpenMoveType>,
+    #[graphql(name = "return")]
+    pub return_: Vec<OpenMoveType>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveFunctionTypeParameterDecl {
+    pub constraints: Vec<MoveAbility>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct OpenMoveType {
+    pub repr: String,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum MoveAbility {
+    Copy,
+    Drop,
+    Store,
+    Key,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum MoveVisibility {
+    Public,
+    Private,
+    Friend,
+}
+
+#[Object]
+impl MoveModule {
+    async fn file_format_version(&self) -> u32 {
+        self.native.file_format_version
+    }
+
+    async fn module_id(&self) -> MoveModuleId {
+        MoveModuleId {
+            package: self.package,
+            name: self.native.name.to_string(),
+        }
+    }
+
+    async fn friends(&self) -> Vec<MoveModuleId> {
+        self.native
+            .friends
+            .iter()
+            .map(|friend| MoveModuleId {
+                package: SuiAddress::from_array(friend.address().into_bytes()),
+                name: friend.name().to_string(),
+            })
+            .collect()
+    }
+
+    #[graphql(name = "struct")]
+    async fn struct_(&self, name: String) -> Option<MoveStructDecl> {
+        let (name, native) = self
+            .native
+            .structs
+            .get_key_value(IdentStr::new(&name).ok()?)?;
+        Some(self.convert_struct(name.to_string(), native))
+    }
+
+    async fn struct_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Connection<String, MoveStructDecl>> {
+        name_connection(
+            &self.native.structs,
+            first,
+            after,
+            last,
+            before,
+            |name, native| Ok(self.convert_struct(name.to_string(), native)),
+        )
+    }
+
+    async fn function(&self, name: String) -> Option<MoveFunction> {
+        let (name, native) = self
+            .native
+            .functions
+            .get_key_value(IdentStr::new(&name).ok()?)?;
+        Some(self.convert_function(name.to_string(), native))
+    }
+
+    async fn function_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Connection<String, MoveFunction>> {
+        name_connection(
+            &self.native.functions,
+            first,
+            after,
+            last,
+            before,
+            |name, native| Ok(self.convert_function(name.to_string(), native)),
+        )
+    }
+
+    async fn bytes(&self) -> Base64 {
+        Base64::from(self.bytes.to_vec())
+    }
+}
+
+#[Object]
+impl MoveModuleId {
+    async fn package(&self, ctx: &Context<'_>) -> Result<Option<MovePackage>> {
+        let Some(object) = ctx.data_provider().fetch_obj(self.package, None).await? else {
+            return Ok(None);
+        };
+        object.move_package()
+    }
+
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl MoveModule {
+    pub(crate) fn new(package: SuiAddress, bytes: &[u8]) -> Result<Self> {
+        let native = normalize_module(bytes)?;
+        Ok(Self {
+            package,
+            bytes: bytes.into(),
+            native: Arc::new(native),
+        })
+    }
+
+    fn convert_struct(&self, name: String, native: &NativeStruct) -> MoveStructDecl {
+        MoveStructDecl {
+            module: self.clone(),
+            name,
+            abilities: convert_abilities(native.abilities),
+            type_parameters: native
+                .type_parameters
+                .iter()
+                .map(|param| MoveStructTypeParameterDecl {
+                    constraints: convert_abilities(param.constraints),
+                    is_phantom: param.is_phantom,
+                })
+                .collect(),
+            fields: native
+                .fields
+                .iter()
+                .map(|field| MoveFieldDecl {
+                    name: field.name.to_string(),
+                    type_: OpenMoveType::from(&field.type_),
+                })
+                .collect(),
+        }
+    }
+
+    fn convert_function(&self, name: String, native: &NativeFunction) -> MoveFunction {
+        MoveFunction {
+            module: self.clone(),
+            name,
+            visibility: match native.visibility {
+                Visibility::Public => MoveVisibility::Public,
+                Visibility::Private => MoveVisibility::Private,
+                Visibility::Friend => MoveVisibility::Friend,
+            },
+            is_entry: native.is_entry,
+            type_parameters: native
+                .type_parameters
+                .iter()
+                .map(|constraints| MoveFunctionTypeParameterDecl {
+                    constraints: convert_abilities(*constraints),
+                })
+                .collect(),
+            parameters: native.parameters.iter().map(OpenMoveType::from).collect(),
+            return_: native.return_.iter().map(OpenMoveType::from).collect(),
+        }
+    }
+}
+
+impl From<&NativeType> for OpenMoveType {
+    fn from(type_: &NativeType) -> Self {
+        OpenMoveType {
+            repr: type_.to_string(),
+        }
+    }
+}
+
+/// Deserializes and normalizes the module in `bytes`.
+pub(crate) fn normalize_module(bytes: &[u8]) -> Result<NativeModule> {
+    let module = CompiledModule::deserialize_with_config(bytes, VERSION_MAX, false)
+        .map_err(|e| Error::Internal(format!("Cannot deserialize module: {e}")).extend())?;
+    Ok(NativeModule::new(&module))
+}
+
+/// Pages forward through `items`, using their names as cursors.
+pub(crate) fn name_connection<K: Ord + Display, V, T: OutputType>(
+    items: &BTreeMap<K, V>,
+    first: Option<u64>,
+    after: Option<String>,
+    last: Option<u64>,
+    before: Option<String>,
+    convert: impl Fn(&K, &V) -> Result<T>,
+) -> Result<Connection<String, T>> {
+    ensure_forward_pagination(&first, &after, &last, &before)?;
+
+    let count = first.unwrap_or(DEFAULT_PAGE_SIZE as u64) as usize;
+    let mut page = items
+        .iter()
+        .filter(|(name, _)| match &after {
+            Some(after) => name.to_string() > *after,
+            None => true,
+        })
+        .peekable();
+
+    let edges: Vec<_> = page
+        .by_ref()
+        .take(count)
+        .map(|(name, item)| Ok(Edge::new(name.to_string(), convert(name, item)?)))
+        .collect::<Result<_>>()?;
+
+    let mut connection = Connection::new(after.is_some(), page.peek().is_some());
+    connection.edges.extend(edges);
+    Ok(connection)
+}
+
+fn convert_abilities(abilities: AbilitySet) -> Vec<MoveAbility> {
+    abilities
+        .into_iter()
+        .map(|ability| match ability {
+            Ability::Copy => MoveAbility::Copy,
+            Ability::Drop => MoveAbility::Drop,
+            Ability::Store => MoveAbility::Store,
+            Ability::Key => MoveAbility::Key,
+        })
+        .collect()
+}
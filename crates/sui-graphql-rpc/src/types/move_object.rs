@@ -0,0 +1,31 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+use move_core_types::language_storage::{StructTag, TypeTag};
+
+use super::{move_value::MoveValue, object::Object};
+use crate::server::context_ext::DataProviderContextExt;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveObject {
+    pub type_: StructTag,
+    pub object: Object,
+}
+
+#[Object]
+impl MoveObject {
+    async fn contents(&self, ctx: &Context<'_>) -> Result<Option<MoveValue>> {
+        let Some(bcs) = &self.object.bcs else {
+            return Ok(None);
+        };
+        let tag = TypeTag::Struct(Box::new(self.type_.clone()));
+        MoveValue::from_bcs(ctx.data_provider(), &tag, bcs.as_ref())
+            .await
+            .map(Some)
+    }
+
+    async fn as_object(&self) -> Object {
+        self.object.clone()
+    }
+}
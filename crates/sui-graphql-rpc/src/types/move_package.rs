@@ -0,0 +1,50 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use async_graphql::connection::Connection;
+use async_graphql::*;
+
+use super::move_module::{name_connection, MoveModule};
+use super::{object::Object, sui_address::SuiAddress};
+use crate::error::Error;
+use crate::server::context_ext::DataProviderContextExt;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MovePackage {
+    pub address: SuiAddress,
+    pub version: u64,
+    pub modules: BTreeMap<String, Vec<u8>>,
+}
+
+#[Object]
+impl MovePackage {
+    async fn module(&self, name: String) -> Result<Option<MoveModule>> {
+        self.modules
+            .get(&name)
+            .map(|bytes| MoveModule::new(self.address, bytes))
+            .transpose()
+    }
+
+    async fn module_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Connection<String, MoveModule>> {
+        name_connection(&self.modules, first, after, last, before, |_, bytes| {
+            MoveModule::new(self.address, bytes)
+        })
+    }
+
+    async fn as_object(&self, ctx: &Context<'_>) -> Result<Object> {
+        ctx.data_provider()
+            .fetch_obj(self.address, Some(self.version))
+            .await?
+            .ok_or_else(|| {
+                Error::Internal(format!("Package not found at version {}", self.version)).extend()
+            })
+    }
+}
@@ -0,0 +1,568 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use async_graphql::*;
+use futures::future::{BoxFuture, FutureExt};
+use move_binary_format::normalized::{
+    Module as NativeModule, Struct as NativeStruct, Type as NativeType,
+};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use move_core_types::value::{
+    MoveFieldLayout, MoveStruct as NativeMoveStruct, MoveStructLayout, MoveTypeLayout,
+    MoveValue as NativeMoveValue,
+};
+use sui_sdk::types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+use super::move_module::normalize_module;
+use super::{base64::Base64, sui_address::SuiAddress};
+use crate::error::Error;
+use crate::server::data_provider::DataProvider;
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveValue {
+    #[graphql(name = "type")]
+    pub type_: MoveType,
+    pub data: MoveData,
+    pub bcs: Base64,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveType {
+    pub repr: String,
+}
+
+#[derive(Union, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum MoveData {
+    Number(MoveNumber),
+    Bool(MoveBool),
+    Address(MoveAddress),
+    Uid(MoveUid),
+    String(MoveString),
+    Vector(MoveVector),
+    Option(MoveOption),
+    Struct(MoveStruct),
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveNumber {
+    pub number: String,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveBool {
+    pub bool: bool,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveAddress {
+    pub address: SuiAddress,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+#[graphql(name = "MoveUID")]
+pub(crate) struct MoveUid {
+    pub uid: SuiAddress,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveString {
+    pub string: String,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveVector {
+    pub elements: Vec<MoveValue>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveOption {
+    pub element: Option<Box<MoveValue>>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveStruct {
+    pub fields: Vec<MoveField>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct MoveField {
+    pub name: String,
+    pub value: MoveValue,
+}
+
+impl MoveValue {
+    /// Deserializes `bcs` as a value of type `tag`, fetching the packages that define the structs
+    /// it contains to find out their layouts.
+    pub(crate) async fn from_bcs(
+        provider: &dyn DataProvider,
+        tag: &TypeTag,
+        bcs: &[u8],
+    ) -> Result<Self> {
+        let mut resolver = LayoutResolver {
+            provider,
+            modules: HashMap::new(),
+        };
+        let layout = resolver.resolve(tag).await?;
+        let value = NativeMoveValue::simple_deserialize(bcs, &layout)
+            .map_err(|e| internal(format!("Cannot deserialize value of type {tag}: {e}")))?;
+        convert_value(value, &layout)
+    }
+}
+
+/// Builds the layouts of types from the definitions of their structs, caching the modules it has
+/// fetched along the way.
+struct LayoutResolver<'p> {
+    provider: &'p dyn DataProvider,
+    modules: HashMap<(AccountAddress, Identifier), NativeModule>,
+}
+
+impl LayoutResolver<'_> {
+    fn resolve<'a>(&'a mut self, tag: &'a TypeTag) -> BoxFuture<'a, Result<MoveTypeLayout>> {
+        async move {
+            Ok(match tag {
+                TypeTag::Bool => MoveTypeLayout::Bool,
+                TypeTag::U8 => MoveTypeLayout::U8,
+                TypeTag::U16 => MoveTypeLayout::U16,
+                TypeTag::U32 => MoveTypeLayout::U32,
+                TypeTag::U64 => MoveTypeLayout::U64,
+                TypeTag::U128 => MoveTypeLayout::U128,
+                TypeTag::U256 => MoveTypeLayout::U256,
+                TypeTag::Address => MoveTypeLayout::Address,
+                TypeTag::Signer => MoveTypeLayout::Signer,
+                TypeTag::Vector(tag) => MoveTypeLayout::Vector(Box::new(self.resolve(tag).await?)),
+                TypeTag::Struct(tag) => MoveTypeLayout::Struct(self.resolve_struct(tag).await?),
+            })
+        }
+        .boxed()
+    }
+
+    async fn resolve_struct(&mut self, tag: &StructTag) -> Result<MoveStructLayout> {
+        let def = self.struct_def(tag).await?;
+        let type_arguments: Vec<_> = tag
+            .type_params
+            .iter()
+            .cloned()
+            .map(NativeType::from)
+            .collect();
+
+        let mut fields = Vec::with_capacity(def.fields.len());
+        for field in def.fields {
+            let field_tag = field
+                .type_
+                .subst(&type_arguments)
+                .into_type_tag()
+                .ok_or_else(|| internal(format!("Field {} of {tag} has no layout", field.name)))?;
+            let layout = self.resolve(&field_tag).await?;
+            fields.push(MoveFieldLayout::new(field.name, layout));
+        }
+
+        Ok(MoveStructLayout::WithTypes {
+            type_: tag.clone(),
+            fields,
+        })
+    }
+
+    async fn struct_def(&mut self, tag: &StructTag) -> Result<NativeStruct> {
+        let key = (tag.address, tag.module.clone());
+        if !self.modules.contains_key(&key) {
+            let package = SuiAddress::from_array(tag.address.into_bytes());
+            let module = self
+                .provider
+                .fetch_obj(package, None)
+                .await?
+                .map(|object| object.move_package())
+                .transpose()?
+                .flatten()
+                .and_then(|package| package.modules.get(tag.module.as_str()).cloned())
+                .ok_or_else(|| internal(format!("Cannot find the module defining {tag}")))?;
+            self.modules.insert(key.clone(), normalize_module(&module)?);
+        }
+
+        self.modules[&key]
+            .structs
+            .get(&tag.name)
+            .cloned()
+            .ok_or_else(|| internal(format!("Cannot find the definition of {tag}")))
+    }
+}
+
+fn convert_value(value: NativeMoveValue, layout: &MoveTypeLayout) -> Result<MoveValue> {
+    let tag: TypeTag = layout
+        .try_into()
+        .map_err(|e| internal(format!("Cannot find the type of a value: {e}")))?;
+    let bcs = value
+        .clone()
+        .undecorate()
+        .simple_serialize()
+        .ok_or_else(|| internal(format!("Cannot serialize value of type {tag}")))?;
+
+    Ok(MoveValue {
+        type_: MoveType {
+            repr: NativeType::from(tag).to_string(),
+        },
+        data: convert_data(value, layout)?,
+        bcs: Base64::from(bcs),
+    })
+}
+
+fn convert_data(value: NativeMoveValue, layout: &MoveTypeLayout) -> Result<MoveData> {
+    let number = |number: String| MoveData::Number(MoveNumber { number });
+    Ok(match (value, layout) {
+        (NativeMoveValue::U8(n), _) => number(n.to_string()),
+        (NativeMoveValue::U16(n), _) => number(n.to_string()),
+        (NativeMoveValue::U32(n), _) => number(n.to_string()),
+        (NativeMoveValue::U64(n), _) => number(n.to_string()),
+        (NativeMoveValue::U128(n), _) => number(n.to_string()),
+        (NativeMoveValue::U256(n), _) => number(n.to_string()),
+        (NativeMoveValue::Bool(bool), _) => MoveData::Bool(MoveBool { bool }),
+        (NativeMoveValue::Address(address) | NativeMoveValue::Signer(address), _) => {
+            MoveData::Address(MoveAddress {
+                address: SuiAddress::from_array(address.into_bytes()),
+            })
+        }
+        (NativeMoveValue::Vector(elements), MoveTypeLayout::Vector(layout)) => {
+            MoveData::Vector(MoveVector {
+                elements: elements
+                    .into_iter()
+                    .map(|element| convert_value(element, layout))
+                    .collect::<Result<_>>()?,
+            })
+        }
+        (
+            NativeMoveValue::Struct(NativeMoveStruct::WithTypes { type_, fields }),
+            MoveTypeLayout::Struct(MoveStructLayout::WithTypes {
+                fields: layouts, ..
+            }),
+        ) => convert_struct(&type_, fields, layouts)?,
+        _ => return Err(internal("Value does not match its layout".to_string())),
+    })
+}
+
+/// Structs from the standard library and framework that have a more natural representation get
+/// it here, and all other structs are represented by their fields.
+fn convert_struct(
+    type_: &StructTag,
+    fields: Vec<(Identifier, NativeMoveValue)>,
+    layouts: &[MoveFieldLayout],
+) -> Result<MoveData> {
+    let is = |address: AccountAddress, module: &str, name: &str| {
+        type_.address == address && type_.module.as_str() == module && type_.name.as_str() == name
+    };
+
+    if is(SUI_FRAMEWORK_ADDRESS, "object", "UID") {
+        return Ok(MoveData::Uid(MoveUid {
+            uid: wrapped_address(type_, &fields)?,
+        }));
+    }
+
+    if is(SUI_FRAMEWORK_ADDRESS, "object", "ID") {
+        return Ok(MoveData::Address(MoveAddress {
+            address: wrapped_address(type_, &fields)?,
+        }));
+    }
+
+    if is(MOVE_STDLIB_ADDRESS, "string", "String") || is(MOVE_STDLIB_ADDRESS, "ascii", "String") {
+        let [(_, NativeMoveValue::Vector(bytes))] = fields.as_slice() else {
+            return Err(malformed(type_));
+        };
+        let bytes = bytes
+            .iter()
+            .map(|byte| match byte {
+                NativeMoveValue::U8(byte) => Ok(*byte),
+                _ => Err(malformed(type_)),
+            })
+            .collect::<Result<_>>()?;
+        return Ok(MoveData::String(MoveString {
+            string: String::from_utf8(bytes).map_err(|_| malformed(type_))?,
+        }));
+    }
+
+    if is(MOVE_STDLIB_ADDRESS, "option", "Option") {
+        let (
+            Some((_, NativeMoveValue::Vector(elements))),
+            [MoveFieldLayout {
+                layout: MoveTypeLayout::Vector(layout),
+                ..
+            }],
+        ) = (fields.into_iter().next(), layouts)
+        else {
+            return Err(malformed(type_));
+        };
+        let element = match elements.into_iter().next() {
+            Some(element) => Some(Box::new(convert_value(element, layout)?)),
+            None => None,
+        };
+        return Ok(MoveData::Option(MoveOption { element }));
+    }
+
+    let fields = fields
+        .into_iter()
+        .zip(layouts)
+        .map(|((name, value), layout)| {
+            Ok(MoveField {
+                name: name.to_string(),
+                value: convert_value(value, &layout.layout)?,
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok(MoveData::Struct(MoveStruct { fields }))
+}
+
+/// The address inside an `ID`, or the `ID` inside a `UID`.
+fn wrapped_address(
+    type_: &StructTag,
+    fields: &[(Identifier, NativeMoveValue)],
+) -> Result<SuiAddress> {
+    match fields {
+        [(_, NativeMoveValue::Address(address))] => {
+            Ok(SuiAddress::from_array(address.into_bytes()))
+        }
+        [(_, NativeMoveValue::Struct(NativeMoveStruct::WithTypes { fields, .. }))] => {
+            wrapped_address(type_, fields)
+        }
+        _ => Err(malformed(type_)),
+    }
+}
+
+fn malformed(type_: &StructTag) -> async_graphql::Error {
+    internal(format!("Malformed value of type {type_}"))
+}
+
+fn internal(message: String) -> async_graphql::Error {
+    Error::Internal(message).extend()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(layout: &MoveTypeLayout, bcs: &[u8]) -> Result<MoveValue> {
+        let value = NativeMoveValue::simple_deserialize(bcs, layout).unwrap();
+        convert_value(value, layout)
+    }
+
+    fn struct_layout(
+        address: AccountAddress,
+        module: &str,
+        name: &str,
+        type_params: Vec<TypeTag>,
+        fields: Vec<(&str, MoveTypeLayout)>,
+    ) -> MoveTypeLayout {
+        MoveTypeLayout::Struct(MoveStructLayout::WithTypes {
+            type_: StructTag {
+                address,
+                module: Identifier::new(module).unwrap(),
+                name: Identifier::new(name).unwrap(),
+                type_params,
+            },
+            fields: fields
+                .into_iter()
+                .map(|(name, layout)| MoveFieldLayout::new(Identifier::new(name).unwrap(), layout))
+                .collect(),
+        })
+    }
+
+    fn id_layout() -> MoveTypeLayout {
+        struct_layout(
+            SUI_FRAMEWORK_ADDRESS,
+            "object",
+            "ID",
+            vec![],
+            vec![("bytes", MoveTypeLayout::Address)],
+        )
+    }
+
+    fn uid_layout() -> MoveTypeLayout {
+        struct_layout(
+            SUI_FRAMEWORK_ADDRESS,
+            "object",
+            "UID",
+            vec![],
+            vec![("id", id_layout())],
+        )
+    }
+
+    fn string_layout(module: &str) -> MoveTypeLayout {
+        struct_layout(
+            MOVE_STDLIB_ADDRESS,
+            module,
+            "String",
+            vec![],
+            vec![(
+                "bytes",
+                MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            )],
+        )
+    }
+
+    fn number(number: &str) -> MoveData {
+        MoveData::Number(MoveNumber {
+            number: number.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_primitives() {
+        let bcs = bcs::to_bytes(&42u64).unwrap();
+        let value = decode(&MoveTypeLayout::U64, &bcs).unwrap();
+        assert_eq!(value.type_.repr, "u64");
+        assert_eq!(value.data, number("42"));
+        assert_eq!(value.bcs, Base64::from(bcs));
+
+        let cases = [
+            (MoveTypeLayout::U8, bcs::to_bytes(&u8::MAX), number("255")),
+            (MoveTypeLayout::U16, bcs::to_bytes(&513u16), number("513")),
+            (
+                MoveTypeLayout::U32,
+                bcs::to_bytes(&70000u32),
+                number("70000"),
+            ),
+            (
+                MoveTypeLayout::U128,
+                bcs::to_bytes(&u128::MAX),
+                number(&u128::MAX.to_string()),
+            ),
+            (
+                MoveTypeLayout::Bool,
+                bcs::to_bytes(&true),
+                MoveData::Bool(MoveBool { bool: true }),
+            ),
+            (
+                MoveTypeLayout::Address,
+                bcs::to_bytes(&AccountAddress::TWO),
+                MoveData::Address(MoveAddress {
+                    address: SuiAddress::from_array(AccountAddress::TWO.into_bytes()),
+                }),
+            ),
+        ];
+        for (layout, bcs, data) in cases {
+            assert_eq!(decode(&layout, &bcs.unwrap()).unwrap().data, data);
+        }
+
+        // u256 is 32 little-endian bytes
+        let mut bcs = vec![0u8; 32];
+        bcs[0] = 7;
+        bcs[1] = 1;
+        let value = decode(&MoveTypeLayout::U256, &bcs).unwrap();
+        assert_eq!(value.data, number("263"));
+    }
+
+    #[test]
+    fn test_vector() {
+        let layout = MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U16));
+        let value = decode(&layout, &bcs::to_bytes(&vec![1u16, 2u16]).unwrap()).unwrap();
+        assert_eq!(value.type_.repr, "vector<u16>");
+        let MoveData::Vector(MoveVector { elements }) = value.data else {
+            panic!("expected a vector");
+        };
+        let elements: Vec<_> = elements.into_iter().map(|element| element.data).collect();
+        assert_eq!(elements, vec![number("1"), number("2")]);
+    }
+
+    #[test]
+    fn test_uid_and_id() {
+        let address = AccountAddress::random();
+        let bcs = bcs::to_bytes(&address).unwrap();
+        let expected = SuiAddress::from_array(address.into_bytes());
+
+        let uid = decode(&uid_layout(), &bcs).unwrap();
+        assert_eq!(uid.data, MoveData::Uid(MoveUid { uid: expected }));
+        assert_eq!(uid.bcs, Base64::from(bcs.clone()));
+
+        let id = decode(&id_layout(), &bcs).unwrap();
+        assert_eq!(
+            id.data,
+            MoveData::Address(MoveAddress { address: expected })
+        );
+    }
+
+    #[test]
+    fn test_strings() {
+        for module in ["string", "ascii"] {
+            let value = decode(&string_layout(module), &bcs::to_bytes("hello").unwrap()).unwrap();
+            assert_eq!(
+                value.data,
+                MoveData::String(MoveString {
+                    string: "hello".to_string()
+                })
+            );
+        }
+
+        // Strings that are not valid UTF-8 are rejected
+        let bcs = bcs::to_bytes(&vec![0xffu8]).unwrap();
+        assert!(decode(&string_layout("string"), &bcs).is_err());
+    }
+
+    #[test]
+    fn test_option() {
+        let layout = struct_layout(
+            MOVE_STDLIB_ADDRESS,
+            "option",
+            "Option",
+            vec![TypeTag::U64],
+            vec![("vec", MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U64)))],
+        );
+
+        let some = decode(&layout, &bcs::to_bytes(&vec![5u64]).unwrap()).unwrap();
+        assert_eq!(some.type_.repr, "0x1::option::Option<u64>");
+        let five = decode(&MoveTypeLayout::U64, &bcs::to_bytes(&5u64).unwrap()).unwrap();
+        assert_eq!(
+            some.data,
+            MoveData::Option(MoveOption {
+                element: Some(Box::new(five))
+            })
+        );
+
+        let none = decode(&layout, &bcs::to_bytes(&Vec::<u64>::new()).unwrap()).unwrap();
+        assert_eq!(none.data, MoveData::Option(MoveOption { element: None }));
+    }
+
+    #[test]
+    fn test_struct() {
+        let layout = struct_layout(
+            AccountAddress::from_hex_literal("0x42").unwrap(),
+            "m",
+            "S",
+            vec![],
+            vec![
+                ("id", uid_layout()),
+                ("balance", MoveTypeLayout::U64),
+                ("name", string_layout("string")),
+            ],
+        );
+        let address = AccountAddress::random();
+        let bcs = bcs::to_bytes(&(address, 100u64, "coin")).unwrap();
+
+        let value = decode(&layout, &bcs).unwrap();
+        assert_eq!(value.bcs, Base64::from(bcs));
+        let MoveData::Struct(MoveStruct { fields }) = value.data else {
+            panic!("expected a struct");
+        };
+        let fields: Vec<_> = fields
+            .into_iter()
+            .map(|field| (field.name, field.value.data))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (
+                    "id".to_string(),
+                    MoveData::Uid(MoveUid {
+                        uid: SuiAddress::from_array(address.into_bytes())
+                    })
+                ),
+                ("balance".to_string(), number("100")),
+                (
+                    "name".to_string(),
+                    MoveData::String(MoveString {
+                        string: "coin".to_string()
+                    })
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+
+use super::{base64::Base64, transaction_block::TransactionBlock};
+use crate::server::context_ext::DataProviderContextExt;
+
+pub(crate) struct Mutation;
+
+#[Object]
+impl Mutation {
+    async fn execute_transaction_block(
+        &self,
+        ctx: &Context<'_>,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+    ) -> Result<ExecutionResult> {
+        ctx.data_provider().execute_tx(tx_bytes, signatures).await
+    }
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct ExecutionResult {
+    pub transaction_block: Option<TransactionBlock>,
+    pub errors: Option<String>,
+}
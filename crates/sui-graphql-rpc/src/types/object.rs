@@ -3,14 +3,19 @@
 
 use async_graphql::dataloader::{DataLoader, LruCache};
 use async_graphql::{connection::Connection, *};
+use sui_sdk::types::base_types::ObjectType as NativeObjectType;
+use sui_sdk::types::move_package::MovePackage as NativeMovePackage;
 
 use super::big_int::BigInt;
+use super::move_object::MoveObject;
+use super::move_package::MovePackage;
 use super::name_service::NameService;
 use super::tx_digest::TransactionDigest;
 use super::{
     balance::Balance, coin::Coin, owner::Owner, stake::Stake, sui_address::SuiAddress,
     transaction_block::TransactionBlock,
 };
+use crate::error::Error;
 use crate::server::sui_sdk_data_provider::SuiClientLoader;
 use crate::{server::context_ext::DataProviderContextExt, types::base64::Base64};
 
@@ -24,6 +29,7 @@ pub(crate) struct Object {
     pub bcs: Option<Base64>,
     pub previous_transaction: Option<TransactionDigest>,
     pub kind: Option<ObjectKind>,
+    pub object_type: Option<NativeObjectType>,
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
@@ -51,6 +57,22 @@ pub(crate) struct ObjectKey {
     version: u64,
 }
 
+impl Object {
+    /// The package stored in this object, if it is one.
+    pub(crate) fn move_package(&self) -> Result<Option<MovePackage>> {
+        let (Some(NativeObjectType::Package), Some(bcs)) = (&self.object_type, &self.bcs) else {
+            return Ok(None);
+        };
+        let package: NativeMovePackage = bcs::from_bytes(bcs.as_ref())
+            .map_err(|e| Error::Internal(format!("Cannot deserialize package: {e}")).extend())?;
+        Ok(Some(MovePackage {
+            address: self.address,
+            version: self.version,
+            modules: package.serialized_module_map().clone(),
+        }))
+    }
+}
+
 #[allow(unreachable_code)]
 #[allow(unused_variables)]
 #[Object]
@@ -91,6 +113,20 @@ impl Object {
         self.owner.as_ref().map(|q| Owner { address: *q })
     }
 
+    async fn as_move_object(&self) -> Option<MoveObject> {
+        match &self.object_type {
+            Some(NativeObjectType::Struct(type_)) => Some(MoveObject {
+                type_: type_.clone().into(),
+                object: self.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    async fn as_move_package(&self) -> Result<Option<MovePackage>> {
+        self.move_package()
+    }
+
     // =========== Owner interface methods =============
 
     pub async fn location(&self) -> SuiAddress {
@@ -4,13 +4,21 @@
 use async_graphql::{connection::Connection, *};
 
 use super::{
-    address::Address, checkpoint::Checkpoint, object::Object, owner::ObjectOwner,
-    protocol_config::ProtocolConfigs, sui_address::SuiAddress,
+    address::Address,
+    checkpoint::Checkpoint,
+    event::{Event, EventFilter},
+    mutation::Mutation,
+    object::Object,
+    owner::ObjectOwner,
+    protocol_config::ProtocolConfigs,
+    subscription::Subscription,
+    sui_address::SuiAddress,
+    transaction_block::{TransactionBlock, TransactionBlockFilter},
 };
 use crate::server::context_ext::DataProviderContextExt;
 
 pub(crate) struct Query;
-pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, EmptyMutation, EmptySubscription>;
+pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, Subscription>;
 
 #[allow(unreachable_code)]
 #[allow(unused_variables)]
@@ -40,6 +48,14 @@ impl Query {
         Some(Address { address })
     }
 
+    async fn transaction_block(
+        &self,
+        ctx: &Context<'_>,
+        digest: String,
+    ) -> Result<Option<TransactionBlock>> {
+        ctx.data_provider().fetch_tx(&digest).await
+    }
+
     async fn checkpoint_connection(
         &self,
         ctx: &Context<'_>,
@@ -53,6 +69,34 @@ impl Query {
             .await
     }
 
+    async fn transaction_block_connection(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<Connection<String, TransactionBlock>> {
+        ctx.data_provider()
+            .fetch_tx_connection(first, after, last, before, filter)
+            .await
+    }
+
+    async fn event_connection(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<EventFilter>,
+    ) -> Result<Connection<String, Event>> {
+        ctx.data_provider()
+            .fetch_event_connection(first, after, last, before, filter)
+            .await
+    }
+
     async fn protocol_config(
         &self,
         ctx: &Context<'_>,
@@ -0,0 +1,32 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+use futures::Stream;
+
+use super::{
+    event::{Event, EventFilter},
+    transaction_block::{TransactionBlock, TransactionBlockFilter},
+};
+use crate::server::context_ext::DataProviderContextExt;
+
+pub(crate) struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EventFilter>,
+    ) -> Result<impl Stream<Item = Result<Event>>> {
+        ctx.data_provider().subscribe_events(filter).await
+    }
+
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<impl Stream<Item = Result<TransactionBlock>>> {
+        ctx.data_provider().subscribe_txs(filter).await
+    }
+}
@@ -3,10 +3,13 @@
 
 use super::{
     address::Address,
+    balance::BalanceChange,
     base64::Base64,
     epoch::Epoch,
     gas::{GasEffects, GasInput},
+    object::Object,
     sui_address::SuiAddress,
+    transaction_block_kind::TransactionBlockKind,
     tx_digest::TransactionDigest,
 };
 use async_graphql::*;
@@ -18,6 +21,8 @@ pub(crate) struct TransactionBlock {
     pub sender: Option<Address>,
     pub bcs: Option<Base64>,
     pub gas_input: Option<GasInput>,
+    pub kind: Option<TransactionBlockKind>,
+    pub signatures: Option<Vec<Base64>>,
     pub expiration: Option<Epoch>,
 }
 
@@ -28,16 +33,24 @@ pub(crate) struct TransactionBlockEffects {
     pub epoch: Option<Epoch>,
     pub status: Option<ExecutionStatus>,
     pub errors: Option<String>,
+    pub dependencies: Option<Vec<TransactionDigest>>,
+    pub object_changes: Option<Vec<ObjectChange>>,
+    pub balance_changes: Option<Vec<BalanceChange>>,
     // pub transaction_block: TransactionBlock,
-    // pub dependencies: Vec<TransactionBlock>,
     // pub lamport_version: Option<u64>,
     // pub object_reads: Vec<Object>,
-    // pub object_changes: Vec<ObjectChange>,
-    // pub balance_changes: Vec<BalanceChange>,
-    // pub epoch: Epoch
     // pub checkpoint: Checkpoint
 }
 
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct ObjectChange {
+    pub address: SuiAddress,
+    pub input_state: Option<Object>,
+    pub output_state: Option<Object>,
+    pub id_created: Option<bool>,
+    pub id_deleted: Option<bool>,
+}
+
 #[derive(Enum, Copy, Clone, Eq, PartialEq)]
 pub(crate) enum TransactionBlockKindInput {
     ProgrammableTx,
@@ -50,20 +63,22 @@ pub enum ExecutionStatus {
     Failure,
 }
 
-#[derive(InputObject)]
+#[derive(InputObject, Default)]
 pub(crate) struct TransactionBlockFilter {
-    package: Option<SuiAddress>,
-    module: Option<String>,
-    function: Option<String>,
+    pub package: Option<SuiAddress>,
+    pub module: Option<String>,
+    pub function: Option<String>,
 
-    kind: Option<TransactionBlockKindInput>,
-    checkpoint: Option<u64>,
+    pub kind: Option<TransactionBlockKindInput>,
+    pub checkpoint: Option<u64>,
 
-    sign_address: Option<SuiAddress>,
-    sent_address: Option<SuiAddress>,
-    recv_address: Option<SuiAddress>,
-    paid_address: Option<SuiAddress>,
+    /// Only the sender is matched as a signer, sponsors are not. Filtering by both `signAddress`
+    /// and `sentAddress` requires them to be the same address.
+    pub sign_address: Option<SuiAddress>,
+    pub sent_address: Option<SuiAddress>,
+    pub recv_address: Option<SuiAddress>,
+    pub paid_address: Option<SuiAddress>,
 
-    input_object: Option<SuiAddress>,
-    changed_object: Option<SuiAddress>,
+    pub input_object: Option<SuiAddress>,
+    pub changed_object: Option<SuiAddress>,
 }
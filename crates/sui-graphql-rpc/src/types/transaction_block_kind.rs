@@ -0,0 +1,160 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{base64::Base64, big_int::BigInt, date_time::DateTime, sui_address::SuiAddress};
+use async_graphql::*;
+
+#[derive(Union, Clone, Eq, PartialEq)]
+pub(crate) enum TransactionBlockKind {
+    ConsensusCommitPrologue(ConsensusCommitPrologueTransaction),
+    Genesis(GenesisTransaction),
+    ChangeEpoch(ChangeEpochTransaction),
+    AuthenticatorStateUpdate(AuthenticatorStateUpdateTransaction),
+    Programmable(ProgrammableTransactionBlock),
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct ConsensusCommitPrologueTransaction {
+    pub epoch_id: u64,
+    pub round: u64,
+    pub timestamp: Option<DateTime>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct GenesisTransaction {
+    pub objects: Vec<SuiAddress>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct ChangeEpochTransaction {
+    pub epoch_id: u64,
+    pub protocol_version: u64,
+    pub timestamp: Option<DateTime>,
+    pub storage_charge: BigInt,
+    pub computation_charge: BigInt,
+    pub storage_rebate: BigInt,
+    pub non_refundable_storage_fee: BigInt,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct AuthenticatorStateUpdateTransaction {
+    pub epoch_id: u64,
+    pub round: u64,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct ProgrammableTransactionBlock {
+    pub inputs: Vec<TransactionInput>,
+    pub transactions: Vec<ProgrammableTransaction>,
+}
+
+#[derive(Union, Clone, Eq, PartialEq)]
+pub(crate) enum TransactionInput {
+    OwnedOrImmutable(OwnedOrImmutableInput),
+    Shared(SharedInput),
+    Pure(PureInput),
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct OwnedOrImmutableInput {
+    pub address: SuiAddress,
+    pub version: u64,
+    pub digest: String,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct SharedInput {
+    pub address: SuiAddress,
+    pub initial_shared_version: u64,
+    pub mutable: bool,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct PureInput {
+    pub bytes: Base64,
+}
+
+#[derive(Union, Clone, Eq, PartialEq)]
+pub(crate) enum TransactionArgument {
+    GasCoin(GasCoin),
+    Input(Input),
+    Result(TxResult),
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct GasCoin {
+    // GraphQL objects need at least one field.
+    #[graphql(name = "_")]
+    pub dummy: Option<bool>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct Input {
+    pub ix: u16,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+#[graphql(name = "Result")]
+pub(crate) struct TxResult {
+    pub cmd: u16,
+    pub ix: Option<u16>,
+}
+
+#[derive(Union, Clone, Eq, PartialEq)]
+pub(crate) enum ProgrammableTransaction {
+    MoveCall(MoveCallTransaction),
+    TransferObjects(TransferObjectsTransaction),
+    SplitCoin(SplitCoinTransaction),
+    MergeCoins(MergeCoinsTransaction),
+    Publish(PublishTransaction),
+    Upgrade(UpgradeTransaction),
+    MakeMoveVec(MakeMoveVecTransaction),
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct MoveCallTransaction {
+    pub package: SuiAddress,
+    pub module: String,
+    pub function_name: String,
+    pub type_arguments: Vec<String>,
+    pub arguments: Vec<TransactionArgument>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct TransferObjectsTransaction {
+    pub objects: Vec<TransactionArgument>,
+    pub address: TransactionArgument,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct SplitCoinTransaction {
+    pub coin: TransactionArgument,
+    pub amounts: Vec<TransactionArgument>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct MergeCoinsTransaction {
+    pub coin: TransactionArgument,
+    pub coins: Vec<TransactionArgument>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct PublishTransaction {
+    pub modules: Vec<Base64>,
+    pub dependencies: Vec<SuiAddress>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct UpgradeTransaction {
+    pub modules: Vec<Base64>,
+    pub dependencies: Vec<SuiAddress>,
+    pub current_package: SuiAddress,
+    pub upgrade_ticket: TransactionArgument,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct MakeMoveVecTransaction {
+    #[graphql(name = "type")]
+    pub type_: Option<String>,
+    pub elements: Vec<TransactionArgument>,
+}
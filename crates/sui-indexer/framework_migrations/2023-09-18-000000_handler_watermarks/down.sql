@@ -0,0 +1 @@
+DROP TABLE IF EXISTS handler_watermarks;
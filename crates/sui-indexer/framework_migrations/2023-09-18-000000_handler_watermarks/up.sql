@@ -0,0 +1,5 @@
+CREATE TABLE handler_watermarks
+(
+    handler_name               TEXT   PRIMARY KEY,
+    checkpoint_sequence_number BIGINT NOT NULL
+);
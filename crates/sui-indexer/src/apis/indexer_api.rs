@@ -235,6 +235,24 @@ impl<S: IndexerStore> IndexerApi<S> {
                     )
                     .await
             }
+            Some(
+                filter @ (TransactionFilter::All(_)
+                | TransactionFilter::Any(_)
+                | TransactionFilter::Not(_)),
+            ) => {
+                let indexer_seq_number = self
+                    .state
+                    .get_transaction_sequence_by_digest(cursor_str, is_descending)
+                    .await?;
+                self.state
+                    .get_transaction_page_by_filter(
+                        filter,
+                        indexer_seq_number,
+                        limit + 1,
+                        is_descending,
+                    )
+                    .await
+            }
         }?;
 
         let has_next_page = tx_vec_from_db.len() > limit;
@@ -1,16 +1,28 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
+
+use anyhow::{anyhow, bail, Result};
+use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use super::fetcher::CheckpointFetcher;
+use super::runner::RegisteredHandler;
+use super::watermarks::Watermarks;
 use super::Handler;
+use crate::PgConnectionPool;
 
+/// Builds an indexer that downloads checkpoints from a fullnode's REST API and feeds them to a set
+/// of handlers. See the [module documentation](super) for an example.
 pub struct IndexerBuilder {
     rest_url: Option<String>,
-    handlers: Vec<Box<dyn Handler>>,
+    /// Each handler, along with the checkpoint it backfills from if it tracks its own watermark.
+    handlers: Vec<(Box<dyn Handler>, Option<CheckpointSequenceNumber>)>,
     last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
     checkpoint_buffer_size: usize,
+    pg_pool: Option<PgConnectionPool>,
+    migrations: Vec<EmbeddedMigrations>,
 }
 
 impl IndexerBuilder {
@@ -23,6 +35,8 @@ impl IndexerBuilder {
             handlers: Vec::new(),
             last_downloaded_checkpoint: None,
             checkpoint_buffer_size: Self::DEFAULT_CHECKPOINT_BUFFER_SIZE,
+            pg_pool: None,
+            migrations: Vec::new(),
         }
     }
 
@@ -31,8 +45,35 @@ impl IndexerBuilder {
         self
     }
 
+    /// Registers a handler that sees every checkpoint after `last_downloaded_checkpoint`.
     pub fn handler<T: Handler + 'static>(mut self, handler: T) -> Self {
-        self.handlers.push(Box::new(handler));
+        self.handlers.push((Box::new(handler), None));
+        self
+    }
+
+    /// Registers a handler that tracks its own watermark, keyed by its name. The first time it is
+    /// run it backfills from `backfill_from`, and afterwards it resumes from the checkpoint after
+    /// its watermark, independently of the other handlers. Requires a `pg_pool`.
+    pub fn custom_handler<T: Handler + 'static>(
+        mut self,
+        handler: T,
+        backfill_from: CheckpointSequenceNumber,
+    ) -> Self {
+        self.handlers.push((Box::new(handler), Some(backfill_from)));
+        self
+    }
+
+    /// The database that handler watermarks are kept in, and that `migrations` are run against.
+    pub fn pg_pool(mut self, pg_pool: PgConnectionPool) -> Self {
+        self.pg_pool = Some(pg_pool);
+        self
+    }
+
+    /// Migrations for the tables written by custom handlers, typically created with
+    /// `diesel_migrations::embed_migrations!`. Pending migrations are run before indexing starts.
+    /// Requires a `pg_pool`.
+    pub fn migrations(mut self, migrations: EmbeddedMigrations) -> Self {
+        self.migrations.push(migrations);
         self
     }
 
@@ -50,6 +91,36 @@ impl IndexerBuilder {
     }
 
     pub async fn run(self) {
+        assert!(!self.handlers.is_empty());
+
+        let watermarks = self.pg_pool.as_ref().map(|pool| {
+            let mut conn = pool
+                .get()
+                .expect("Failed to get a Postgres connection to run migrations");
+            for migrations in std::iter::once(super::watermarks::MIGRATIONS).chain(self.migrations)
+            {
+                conn.run_pending_migrations(migrations)
+                    .expect("Failed to run pending migrations");
+            }
+            Watermarks::new(pool.clone())
+        });
+
+        let default_first_checkpoint = self
+            .last_downloaded_checkpoint
+            .map(|checkpoint| checkpoint.saturating_add(1))
+            .unwrap_or(0);
+        let handlers =
+            register_handlers(self.handlers, default_first_checkpoint, watermarks.as_ref())
+                .await
+                .expect("Failed to register handlers");
+
+        // Download from the earliest checkpoint that any handler still needs.
+        let last_downloaded_checkpoint = handlers
+            .iter()
+            .map(|registered| registered.first_checkpoint)
+            .min()
+            .and_then(|first_checkpoint| first_checkpoint.checked_sub(1));
+
         let (downloaded_checkpoint_data_sender, downloaded_checkpoint_data_receiver) =
             mysten_metrics::metered_channel::channel(
                 self.checkpoint_buffer_size,
@@ -64,19 +135,148 @@ impl IndexerBuilder {
         let rest_api_url = format!("{}/rest", self.rest_url.unwrap());
         let fetcher = CheckpointFetcher::new(
             sui_rest_api::Client::new(rest_api_url),
-            self.last_downloaded_checkpoint,
+            last_downloaded_checkpoint,
             downloaded_checkpoint_data_sender,
         );
         mysten_metrics::spawn_monitored_task!(fetcher.run());
 
-        assert!(!self.handlers.is_empty());
-
         super::runner::run(
             mysten_metrics::metered_channel::ReceiverStream::new(
                 downloaded_checkpoint_data_receiver,
             ),
-            self.handlers,
+            handlers,
+            watermarks,
         )
         .await;
     }
 }
+
+/// Works out the first checkpoint each handler should see: the one after its watermark if it has
+/// one, otherwise the one it backfills from, or `default_first_checkpoint` for handlers that don't
+/// track a watermark.
+async fn register_handlers(
+    handlers: Vec<(Box<dyn Handler>, Option<CheckpointSequenceNumber>)>,
+    default_first_checkpoint: CheckpointSequenceNumber,
+    watermarks: Option<&Watermarks>,
+) -> Result<Vec<RegisteredHandler>> {
+    let mut names = HashSet::new();
+    let mut registered = Vec::with_capacity(handlers.len());
+    for (handler, backfill_from) in handlers {
+        if !names.insert(handler.name().to_owned()) {
+            bail!(
+                "Handler names must be unique, found {} twice",
+                handler.name()
+            );
+        }
+
+        let first_checkpoint = match backfill_from {
+            None => default_first_checkpoint,
+            Some(backfill_from) => watermarks
+                .ok_or_else(|| {
+                    anyhow!("Custom handlers require a Postgres pool to keep watermarks in")
+                })?
+                .get(handler.name())
+                .await?
+                .map(|checkpoint| checkpoint.saturating_add(1))
+                .unwrap_or(backfill_from),
+        };
+        registered.push(RegisteredHandler {
+            handler,
+            first_checkpoint,
+            track_watermark: backfill_from.is_some(),
+        });
+    }
+    Ok(registered)
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_rest_api::CheckpointData;
+
+    use super::*;
+
+    struct NoopHandler(&'static str);
+
+    #[async_trait::async_trait]
+    impl Handler for NoopHandler {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        async fn process_checkpoint(&mut self, _checkpoint_data: &CheckpointData) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn handlers(
+        handlers: Vec<(&'static str, Option<CheckpointSequenceNumber>)>,
+    ) -> Vec<(Box<dyn Handler>, Option<CheckpointSequenceNumber>)> {
+        handlers
+            .into_iter()
+            .map(|(name, backfill_from)| {
+                (
+                    Box::new(NoopHandler(name)) as Box<dyn Handler>,
+                    backfill_from,
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_handler_names_are_rejected() {
+        let result = register_handlers(
+            handlers(vec![("objects", None), ("objects", None)]),
+            0,
+            None,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "pg_integration")]
+    #[tokio::test]
+    async fn test_custom_handlers_resume_from_watermark() {
+        use diesel::RunQueryDsl;
+
+        let pg_host = std::env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = std::env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = std::env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let pool = crate::new_pg_connection_pool(&db_url).unwrap();
+
+        let mut conn = pool.get().unwrap();
+        conn.run_pending_migrations(crate::framework::watermarks::MIGRATIONS)
+            .unwrap();
+        diesel::sql_query("DELETE FROM handler_watermarks")
+            .execute(&mut conn)
+            .unwrap();
+
+        let watermarks = Watermarks::new(pool);
+        watermarks.set("resumed", 41).await.unwrap();
+
+        let registered = register_handlers(
+            handlers(vec![
+                ("resumed", Some(0)),
+                ("backfilled", Some(7)),
+                ("plain", None),
+            ]),
+            3,
+            Some(&watermarks),
+        )
+        .await
+        .unwrap();
+
+        let first_checkpoints: Vec<_> = registered
+            .iter()
+            .map(|r| (r.handler.name(), r.first_checkpoint, r.track_watermark))
+            .collect();
+        assert_eq!(
+            first_checkpoints,
+            vec![
+                ("resumed", 42, true),
+                ("backfilled", 7, true),
+                ("plain", 3, false)
+            ]
+        );
+    }
+}
@@ -10,6 +10,7 @@ pub struct CheckpointFetcher {
     client: Client,
     last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
     highest_known_checkpoint: CheckpointSequenceNumber,
+    range_requests_supported: bool,
     sender: mysten_metrics::metered_channel::Sender<CheckpointData>,
 }
 
@@ -26,6 +27,7 @@ impl CheckpointFetcher {
             client,
             last_downloaded_checkpoint,
             highest_known_checkpoint: 0,
+            range_requests_supported: true,
             sender,
         }
     }
@@ -62,36 +64,89 @@ impl CheckpointFetcher {
         use futures::StreamExt;
         use tap::Pipe;
 
-        let checkpoint_range = self
-            .last_downloaded_checkpoint
-            .map(|i| i.saturating_add(1))
-            .unwrap_or(0)..=self.highest_known_checkpoint;
+        // When far behind, catch up by streaming ranges of checkpoints instead of requesting them
+        // one at a time.
+        if self.range_requests_supported
+            && self
+                .highest_known_checkpoint
+                .saturating_sub(self.next_checkpoint())
+                >= Self::CHECKPOINT_DOWNLOAD_CONCURRENCY as u64
+        {
+            self.stream_checkpoints().await?;
+        }
+
+        let checkpoint_range = self.next_checkpoint()..=self.highest_known_checkpoint;
 
         if !checkpoint_range.is_empty() {
             info!("Starting download of checkpoints {checkpoint_range:?}");
         }
 
+        let client = self.client.clone();
         let mut checkpoint_stream = checkpoint_range
-            .map(|next| self.client.get_full_checkpoint(next))
+            .map(|next| client.get_full_checkpoint(next))
             .pipe(futures::stream::iter)
             .buffered(Self::CHECKPOINT_DOWNLOAD_CONCURRENCY);
 
         while let Some(maybe_checkpoint) = checkpoint_stream.next().await {
-            let checkpoint = maybe_checkpoint?;
-            self.last_downloaded_checkpoint =
-                Some(*checkpoint.checkpoint_summary.sequence_number());
-
-            info!(
-                checkpoint = checkpoint.checkpoint_summary.sequence_number(),
-                "successfully downloaded checkpoint"
-            );
-
-            self.sender
-                .send(checkpoint)
-                .await
-                .expect("channel shouldn't be closed");
+            self.handle_checkpoint(maybe_checkpoint?).await;
         }
 
         Ok(())
     }
+
+    /// Downloads checkpoints through range requests until caught up with the highest known
+    /// checkpoint, or until the server turns out not to support them.
+    async fn stream_checkpoints(&mut self) -> Result<()> {
+        use futures::StreamExt;
+
+        let client = self.client.clone();
+        loop {
+            let start = self.next_checkpoint();
+            if start > self.highest_known_checkpoint {
+                return Ok(());
+            }
+
+            info!("Starting streamed download of checkpoints from {start}");
+            let end = self.highest_known_checkpoint.saturating_add(1);
+            let Some(checkpoint_stream) =
+                client.get_full_checkpoint_range(start..end, true).await?
+            else {
+                warn!("fullnode does not support checkpoint range requests, falling back to fetching checkpoints individually");
+                self.range_requests_supported = false;
+                return Ok(());
+            };
+            futures::pin_mut!(checkpoint_stream);
+
+            let mut downloaded_any = false;
+            while let Some(maybe_checkpoint) = checkpoint_stream.next().await {
+                self.handle_checkpoint(maybe_checkpoint?).await;
+                downloaded_any = true;
+            }
+
+            // The server has no more checkpoints for us right now.
+            if !downloaded_any {
+                return Ok(());
+            }
+        }
+    }
+
+    fn next_checkpoint(&self) -> CheckpointSequenceNumber {
+        self.last_downloaded_checkpoint
+            .map(|i| i.saturating_add(1))
+            .unwrap_or(0)
+    }
+
+    async fn handle_checkpoint(&mut self, checkpoint: CheckpointData) {
+        self.last_downloaded_checkpoint = Some(*checkpoint.checkpoint_summary.sequence_number());
+
+        info!(
+            checkpoint = checkpoint.checkpoint_summary.sequence_number(),
+            "successfully downloaded checkpoint"
+        );
+
+        self.sender
+            .send(checkpoint)
+            .await
+            .expect("channel shouldn't be closed");
+    }
 }
@@ -7,7 +7,12 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 #[async_trait::async_trait]
 pub trait Handler: Send {
+    /// Identifies the handler, and its watermark if it tracks one, so it must be unique among the
+    /// handlers of an indexer.
     fn name(&self) -> &str;
+    /// Called for each checkpoint in order. Handlers that track a watermark may see the
+    /// checkpoint after their watermark more than once, because the watermark is not updated
+    /// atomically with their writes, so they should process checkpoints idempotently.
     async fn process_checkpoint(&mut self, checkpoint_data: &CheckpointData) -> Result<()>;
 }
 
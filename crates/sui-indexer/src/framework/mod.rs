@@ -1,10 +1,41 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+//! A framework for indexing checkpoints downloaded from a fullnode's REST API.
+//!
+//! Downstream crates can index their own tables in the same process as the built-in handlers by
+//! implementing [`Handler`] and registering it with [`IndexerBuilder::custom_handler`]. Each
+//! custom handler keeps its own watermark, so it can be added to an indexer that is already
+//! running and backfill from any checkpoint without affecting the other handlers. The tables a
+//! handler writes to are created by its own diesel migrations, which the builder runs before
+//! indexing starts. Besides those, the builder only adds the `handler_watermarks` table to the
+//! database:
+//!
+//! ```ignore
+//! const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+//!
+//! IndexerBuilder::new()
+//!     .rest_url("http://127.0.0.1:9000")
+//!     .pg_pool(pool.clone())
+//!     .migrations(MIGRATIONS)
+//!     .custom_handler(ListingsHandler::new(pool), /* backfill_from */ 1_000_000)
+//!     .run()
+//!     .await;
+//! ```
+//!
+//! Watermarks are updated after a handler returns from [`Handler::process_checkpoint`], not in
+//! the same transaction as its writes, so handlers must tolerate processing the checkpoint after
+//! their watermark again when they resume.
+//!
+//! Note that `utils::reset_database` can only revert the indexer's own migrations, so databases
+//! with custom migrations need to be reset with `drop_all`.
+
 mod builder;
 mod fetcher;
 pub mod interface;
 mod runner;
+mod watermarks;
 
 pub use builder::IndexerBuilder;
 pub use interface::Handler;
+pub use watermarks::Watermarks;
@@ -2,21 +2,50 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use sui_rest_api::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use super::interface::Handler;
+use super::watermarks::Watermarks;
 
-pub async fn run<S>(mut stream: S, mut handlers: Vec<Box<dyn Handler>>)
-where
+/// A handler along with the first checkpoint it should see, and whether the checkpoints it
+/// processes should be recorded in its watermark.
+pub(crate) struct RegisteredHandler {
+    pub handler: Box<dyn Handler>,
+    pub first_checkpoint: CheckpointSequenceNumber,
+    pub track_watermark: bool,
+}
+
+pub(crate) async fn run<S>(
+    mut stream: S,
+    mut handlers: Vec<RegisteredHandler>,
+    watermarks: Option<Watermarks>,
+) where
     S: futures::Stream<Item = CheckpointData> + std::marker::Unpin,
 {
     use futures::StreamExt;
 
     while let Some(checkpoint) = stream.next().await {
+        let sequence_number = *checkpoint.checkpoint_summary.sequence_number();
         //TODO create tracing spans for processing
         futures::future::join_all(
             handlers
                 .iter_mut()
-                .map(|handler| async { handler.process_checkpoint(&checkpoint).await.unwrap() }),
+                // Handlers that start later than others skip the checkpoints before their start.
+                .filter(|registered| registered.first_checkpoint <= sequence_number)
+                .map(|registered| async {
+                    registered
+                        .handler
+                        .process_checkpoint(&checkpoint)
+                        .await
+                        .unwrap();
+
+                    if let (true, Some(watermarks)) = (registered.track_watermark, &watermarks) {
+                        watermarks
+                            .set(registered.handler.name(), sequence_number)
+                            .await
+                            .unwrap();
+                    }
+                }),
         )
         .await;
     }
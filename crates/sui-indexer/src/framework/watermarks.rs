@@ -0,0 +1,79 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Result};
+use diesel::upsert::excluded;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::PgConnectionPool;
+
+/// Creates the `handler_watermarks` table. These are kept apart from the indexer's own migrations
+/// so that running custom handlers against a database only adds this one table to it.
+pub(crate) const MIGRATIONS: EmbeddedMigrations = embed_migrations!("framework_migrations");
+
+diesel::table! {
+    handler_watermarks (handler_name) {
+        handler_name -> Text,
+        checkpoint_sequence_number -> Int8,
+    }
+}
+
+/// The last checkpoint processed by each handler, kept in the `handler_watermarks` table so that
+/// handlers can resume, or start backfilling, independently of each other.
+///
+/// Watermarks are written after a handler has processed a checkpoint, separately from whatever
+/// the handler wrote, so a handler that is interrupted in between will see that checkpoint again
+/// when it resumes.
+#[derive(Clone)]
+pub struct Watermarks {
+    pool: PgConnectionPool,
+}
+
+impl Watermarks {
+    pub fn new(pool: PgConnectionPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get(&self, handler_name: &str) -> Result<Option<CheckpointSequenceNumber>> {
+        let pool = self.pool.clone();
+        let handler_name = handler_name.to_owned();
+        let watermark: Option<i64> = tokio::task::spawn_blocking(move || {
+            handler_watermarks::table
+                .filter(handler_watermarks::handler_name.eq(handler_name))
+                .select(handler_watermarks::checkpoint_sequence_number)
+                .first(&mut pool.get()?)
+                .optional()
+                .map_err(|e| anyhow!("Failed reading handler watermark: {e}"))
+        })
+        .await??;
+        Ok(watermark.map(|checkpoint| checkpoint as CheckpointSequenceNumber))
+    }
+
+    pub async fn set(
+        &self,
+        handler_name: &str,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<()> {
+        let pool = self.pool.clone();
+        let handler_name = handler_name.to_owned();
+        tokio::task::spawn_blocking(move || {
+            diesel::insert_into(handler_watermarks::table)
+                .values((
+                    handler_watermarks::handler_name.eq(handler_name),
+                    handler_watermarks::checkpoint_sequence_number.eq(checkpoint as i64),
+                ))
+                .on_conflict(handler_watermarks::handler_name)
+                .do_update()
+                .set(
+                    handler_watermarks::checkpoint_sequence_number
+                        .eq(excluded(handler_watermarks::checkpoint_sequence_number)),
+                )
+                .execute(&mut pool.get()?)
+                .map_err(|e| anyhow!("Failed writing handler watermark: {e}"))
+        })
+        .await??;
+        Ok(())
+    }
+}
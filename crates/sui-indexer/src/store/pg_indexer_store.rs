@@ -8,9 +8,9 @@ use std::sync::Arc;
 use anyhow::anyhow;
 use async_trait::async_trait;
 use cached::proc_macro::once;
-use diesel::dsl::{count, max};
+use diesel::dsl::{count, max, sql};
 use diesel::pg::PgConnection;
-use diesel::sql_types::{BigInt, VarChar};
+use diesel::sql_types::{BigInt, Bool, VarChar};
 use diesel::upsert::excluded;
 use diesel::ExpressionMethods;
 use diesel::{OptionalExtension, QueryableByName};
@@ -24,7 +24,7 @@ use tracing::info;
 
 use sui_json_rpc_types::{
     CheckpointId, EpochInfo, EventFilter, EventPage, MoveCallMetrics, MoveFunctionName,
-    NetworkMetrics, SuiEvent, SuiObjectDataFilter,
+    NetworkMetrics, SuiEvent, SuiObjectDataFilter, TransactionFilter,
 };
 use sui_json_rpc_types::{
     SuiTransactionBlock, SuiTransactionBlockEffects, SuiTransactionBlockEvents,
@@ -385,38 +385,8 @@ impl PgIndexerStore {
         descending_order: bool,
     ) -> Result<EventPage, IndexerError> {
         let mut boxed_query = events::table.into_boxed();
-        match query {
-            EventFilter::All(..) => {}
-            EventFilter::Transaction(digest) => {
-                boxed_query =
-                    boxed_query.filter(events::dsl::transaction_digest.eq(digest.base58_encode()));
-            }
-            EventFilter::MoveModule { package, module } => {
-                boxed_query = boxed_query
-                    .filter(events::dsl::package.eq(package.to_string()))
-                    .filter(events::dsl::module.eq(module.to_string()));
-            }
-            EventFilter::MoveEventType(struct_name) => {
-                boxed_query =
-                    boxed_query.filter(events::dsl::event_type.eq(struct_name.to_string()));
-            }
-            EventFilter::Sender(sender) => {
-                boxed_query = boxed_query.filter(events::dsl::sender.eq(sender.to_string()));
-            }
-            EventFilter::TimeRange {
-                start_time,
-                end_time,
-            } => {
-                boxed_query = boxed_query
-                    .filter(events::dsl::event_time_ms.ge(start_time as i64))
-                    .filter(events::dsl::event_time_ms.lt(end_time as i64));
-            }
-            // TODO: Implement EventFilter to SQL
-            _ => {
-                return Err(IndexerError::NotSupportedError(format!(
-                    "Filter type [{query:?}] not supported by the Indexer."
-                )))
-            }
+        if !matches!(&query, EventFilter::All(filters) if filters.is_empty()) {
+            boxed_query = boxed_query.filter(sql::<Bool>(&event_filter_condition(&query)?));
         }
 
         let mut page_limit = limit.unwrap_or(MAX_EVENT_PAGE_SIZE);
@@ -1201,6 +1171,38 @@ impl PgIndexerStore {
         self.multi_get_transactions_by_digests(&tx_digests)
     }
 
+    fn get_transaction_page_by_filter(
+        &self,
+        filter: TransactionFilter,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        let sql_query = format!(
+            "SELECT transaction_digest as digest_name FROM transactions
+             WHERE {} {}
+             ORDER BY id {} LIMIT {}",
+            transaction_filter_condition(&filter)?,
+            if let Some(start_sequence) = start_sequence {
+                if is_descending {
+                    format!("AND id < {}", start_sequence)
+                } else {
+                    format!("AND id > {}", start_sequence)
+                }
+            } else {
+                "".to_string()
+            },
+            if is_descending { "DESC" } else { "ASC" },
+            limit
+        );
+        let tx_digests: Vec<String> = read_only_blocking!(&self.blocking_cp, |conn| diesel::sql_query(sql_query).load(conn))
+                .context(&format!("Failed reading transaction digests by filter {filter:?} with start_sequence {start_sequence:?} and limit {limit}"))?
+                .into_iter()
+                .map(|table: TempDigestTable| table.digest_name)
+                .collect();
+        self.multi_get_transactions_by_digests(&tx_digests)
+    }
+
     fn get_network_metrics(&self) -> Result<NetworkMetrics, IndexerError> {
         get_network_metrics_cached(&self.blocking_cp)
     }
@@ -2202,6 +2204,19 @@ impl IndexerStore for PgIndexerStore {
         .await
     }
 
+    async fn get_transaction_page_by_filter(
+        &self,
+        filter: TransactionFilter,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        self.spawn_blocking(move |this| {
+            this.get_transaction_page_by_filter(filter, start_sequence, limit, is_descending)
+        })
+        .await
+    }
+
     async fn get_transaction_sequence_by_digest(
         &self,
         tx_digest: Option<String>,
@@ -2630,3 +2645,271 @@ fn get_network_metrics_cached(cp: &PgConnectionPool) -> Result<NetworkMetrics, I
     .get_result::<DBNetworkMetrics>(conn))?;
     Ok(metrics.into())
 }
+
+/// Compiles a transaction filter into a condition on the `transactions` table.
+fn transaction_filter_condition(filter: &TransactionFilter) -> Result<String, IndexerError> {
+    let recipient = |address: &SuiAddress| {
+        format!(
+            "transaction_digest IN (SELECT transaction_digest FROM recipients WHERE recipient = '{}')",
+            address
+        )
+    };
+    Ok(match filter {
+        TransactionFilter::Checkpoint(sequence_number) => {
+            format!("checkpoint_sequence_number = {}", sequence_number)
+        }
+        TransactionFilter::MoveFunction {
+            package,
+            module,
+            function,
+        } => {
+            let mut condition = format!(
+                "transaction_digest IN (SELECT transaction_digest FROM move_calls WHERE move_package = '{}'",
+                package
+            );
+            // module and function are user-controlled, so only accept valid Move identifiers,
+            // which are safe to use as-is in the query.
+            for (column, name) in [("move_module", module), ("move_function", function)] {
+                if let Some(name) = name {
+                    let name = Identifier::new(name.as_str())
+                        .map_err(|e| IndexerError::InvalidArgumentError(e.to_string()))?;
+                    condition.push_str(&format!(" AND {column} = '{name}'"));
+                }
+            }
+            condition.push(')');
+            condition
+        }
+        TransactionFilter::InputObject(object_id) => format!(
+            "transaction_digest IN (SELECT transaction_digest FROM input_objects WHERE object_id = '{}')",
+            object_id
+        ),
+        TransactionFilter::ChangedObject(object_id) => format!(
+            "transaction_digest IN (SELECT transaction_digest FROM changed_objects WHERE object_id = '{}')",
+            object_id
+        ),
+        TransactionFilter::FromAddress(address) => format!("sender = '{}'", address),
+        TransactionFilter::ToAddress(address) => recipient(address),
+        TransactionFilter::FromAndToAddress { from, to } => {
+            format!("(sender = '{}' AND {})", from, recipient(to))
+        }
+        TransactionFilter::FromOrToAddress { addr } => {
+            format!("(sender = '{}' OR {})", addr, recipient(addr))
+        }
+        TransactionFilter::TransactionKind(kind) => {
+            format!("transaction_kind = {}", sql_string(kind))
+        }
+        TransactionFilter::TransactionKindIn(kinds) if kinds.is_empty() => "FALSE".to_string(),
+        TransactionFilter::TransactionKindIn(kinds) => format!(
+            "transaction_kind IN ({})",
+            kinds
+                .iter()
+                .map(|kind| sql_string(kind))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        TransactionFilter::All(filters) => combine_conditions(
+            filters.iter().map(transaction_filter_condition),
+            "AND",
+            "TRUE",
+        )?,
+        TransactionFilter::Any(filters) => combine_conditions(
+            filters.iter().map(transaction_filter_condition),
+            "OR",
+            "FALSE",
+        )?,
+        TransactionFilter::Not(filter) => format!("NOT ({})", transaction_filter_condition(filter)?),
+    })
+}
+
+/// Compiles an event filter into a condition on the `events` table.
+fn event_filter_condition(filter: &EventFilter) -> Result<String, IndexerError> {
+    Ok(match filter {
+        EventFilter::Sender(sender) => format!("sender = '{}'", sender),
+        EventFilter::Transaction(digest) => {
+            format!("transaction_digest = '{}'", digest.base58_encode())
+        }
+        EventFilter::Package(package) => format!("package = '{}'", package),
+        EventFilter::MoveModule { package, module } => {
+            format!("(package = '{}' AND module = '{}')", package, module)
+        }
+        EventFilter::MoveEventType(struct_name) => {
+            format!("event_type = {}", sql_string(&struct_name.to_string()))
+        }
+        // Event types are indexed with the short form of their package address.
+        EventFilter::MoveEventModule { package, module } => format!(
+            "event_type LIKE {}",
+            sql_prefix_pattern(&format!("0x{}::{}::", package.short_str_lossless(), module))
+        ),
+        EventFilter::TimeRange {
+            start_time,
+            end_time,
+        } => format!(
+            "(event_time_ms >= {} AND event_time_ms < {})",
+            start_time, end_time
+        ),
+        EventFilter::All(filters) => {
+            combine_conditions(filters.iter().map(event_filter_condition), "AND", "TRUE")?
+        }
+        EventFilter::Any(filters) => {
+            combine_conditions(filters.iter().map(event_filter_condition), "OR", "FALSE")?
+        }
+        EventFilter::And(f1, f2) => format!(
+            "({} AND {})",
+            event_filter_condition(f1)?,
+            event_filter_condition(f2)?
+        ),
+        EventFilter::Or(f1, f2) => format!(
+            "({} OR {})",
+            event_filter_condition(f1)?,
+            event_filter_condition(f2)?
+        ),
+        EventFilter::Not(filter) => format!("NOT ({})", event_filter_condition(filter)?),
+        EventFilter::MoveEventField { .. } => {
+            return Err(IndexerError::NotSupportedError(format!(
+                "Filter type [{filter:?}] not supported by the Indexer."
+            )))
+        }
+    })
+}
+
+/// Joins `conditions` with `operator`, or returns `identity` if there are none.
+fn combine_conditions(
+    conditions: impl Iterator<Item = Result<String, IndexerError>>,
+    operator: &str,
+    identity: &str,
+) -> Result<String, IndexerError> {
+    let conditions: Vec<_> = conditions.collect::<Result<_, _>>()?;
+    Ok(if conditions.is_empty() {
+        identity.to_string()
+    } else {
+        format!("({})", conditions.join(&format!(" {operator} ")))
+    })
+}
+
+/// Quotes a user-provided string for use in a query.
+fn sql_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Builds a `LIKE` pattern matching the strings that start with `prefix`, escaping the `_` and `%`
+/// wildcards it may contain (e.g. in module names).
+fn sql_prefix_pattern(prefix: &str) -> String {
+    let escaped = prefix
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("{} ESCAPE '\\'", sql_string(&format!("{escaped}%")))
+}
+
+#[cfg(test)]
+mod test {
+    use move_core_types::identifier::Identifier;
+    use sui_json_rpc_types::{EventFilter, TransactionFilter};
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::parse_sui_struct_tag;
+
+    use super::{event_filter_condition, transaction_filter_condition};
+    use crate::errors::IndexerError;
+
+    #[test]
+    fn test_transaction_filter_condition() {
+        let sender = SuiAddress::random_for_testing_only();
+        let object = ObjectID::random();
+
+        let filter = TransactionFilter::All(vec![
+            TransactionFilter::FromAddress(sender),
+            TransactionFilter::Not(Box::new(TransactionFilter::InputObject(object))),
+        ]);
+        assert_eq!(
+            transaction_filter_condition(&filter).unwrap(),
+            format!(
+                "(sender = '{sender}' AND NOT (transaction_digest IN (SELECT transaction_digest FROM input_objects WHERE object_id = '{object}')))"
+            )
+        );
+
+        let filter = TransactionFilter::Any(vec![
+            TransactionFilter::TransactionKind("Genesis'".to_string()),
+            TransactionFilter::MoveFunction {
+                package: object,
+                module: Some("coin".to_string()),
+                function: None,
+            },
+        ]);
+        assert_eq!(
+            transaction_filter_condition(&filter).unwrap(),
+            format!(
+                "(transaction_kind = 'Genesis''' OR transaction_digest IN (SELECT transaction_digest FROM move_calls WHERE move_package = '{object}' AND move_module = 'coin'))"
+            )
+        );
+
+        assert_eq!(
+            transaction_filter_condition(&TransactionFilter::All(vec![])).unwrap(),
+            "TRUE"
+        );
+        assert_eq!(
+            transaction_filter_condition(&TransactionFilter::Any(vec![])).unwrap(),
+            "FALSE"
+        );
+
+        let filter = TransactionFilter::Not(Box::new(TransactionFilter::MoveFunction {
+            package: object,
+            module: Some("coin'; DROP TABLE transactions; --".to_string()),
+            function: None,
+        }));
+        assert!(matches!(
+            transaction_filter_condition(&filter),
+            Err(IndexerError::InvalidArgumentError(_))
+        ));
+    }
+
+    #[test]
+    fn test_event_filter_condition() {
+        let sender = SuiAddress::random_for_testing_only();
+        let struct_tag = parse_sui_struct_tag("0x2::coin::CoinMetadata<0x2::sui::SUI>").unwrap();
+
+        let filter = EventFilter::Or(
+            Box::new(EventFilter::Sender(sender)),
+            Box::new(EventFilter::TimeRange {
+                start_time: 1,
+                end_time: 2,
+            }),
+        );
+        assert_eq!(
+            event_filter_condition(&filter).unwrap(),
+            format!("(sender = '{sender}' OR (event_time_ms >= 1 AND event_time_ms < 2))")
+        );
+
+        let filter = EventFilter::All(vec![
+            EventFilter::MoveEventType(struct_tag.clone()),
+            EventFilter::Not(Box::new(EventFilter::MoveEventModule {
+                package: ObjectID::from_single_byte(2),
+                module: Identifier::new("coin").unwrap(),
+            })),
+        ]);
+        assert_eq!(
+            event_filter_condition(&filter).unwrap(),
+            format!(
+                "(event_type = '{struct_tag}' AND NOT (event_type LIKE '0x2::coin::%' ESCAPE '\\'))"
+            )
+        );
+
+        // wildcards in module names only match themselves
+        let filter = EventFilter::MoveEventModule {
+            package: ObjectID::from_single_byte(2),
+            module: Identifier::new("dynamic_field").unwrap(),
+        };
+        assert_eq!(
+            event_filter_condition(&filter).unwrap(),
+            "event_type LIKE '0x2::dynamic\\_field::%' ESCAPE '\\'"
+        );
+
+        let filter = EventFilter::Any(vec![EventFilter::MoveEventField {
+            path: "/name".to_string(),
+            value: serde_json::Value::Null,
+        }]);
+        assert!(matches!(
+            event_filter_condition(&filter),
+            Err(IndexerError::NotSupportedError(_))
+        ));
+    }
+}
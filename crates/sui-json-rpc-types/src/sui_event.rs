@@ -179,6 +179,8 @@ pub enum EventFilter {
     Any(Vec<EventFilter>),
     And(Box<EventFilter>, Box<EventFilter>),
     Or(Box<EventFilter>, Box<EventFilter>),
+    /// Return events that do not match the given filter.
+    Not(Box<EventFilter>),
 }
 
 impl EventFilter {
@@ -201,6 +203,7 @@ impl EventFilter {
             EventFilter::Or(f1, f2) => {
                 EventFilter::Any(vec![*(*f1).clone(), *(*f2).clone()]).matches(item)
             }
+            EventFilter::Not(f) => !f.matches(item),
             EventFilter::Transaction(digest) => digest == &item.id.tx_digest,
 
             EventFilter::TimeRange {
@@ -1754,6 +1754,12 @@ pub enum TransactionFilter {
     TransactionKind(String),
     /// Query transactions of any given kind in the input.
     TransactionKindIn(Vec<String>),
+    /// Query transactions that match all of the given filters.
+    All(Vec<TransactionFilter>),
+    /// Query transactions that match any of the given filters.
+    Any(Vec<TransactionFilter>),
+    /// Query transactions that do not match the given filter.
+    Not(Box<TransactionFilter>),
 }
 
 impl Filter<EffectsWithInput> for TransactionFilter {
@@ -1794,6 +1800,9 @@ impl Filter<EffectsWithInput> for TransactionFilter {
             TransactionFilter::TransactionKindIn(kinds) => {
                 kinds.contains(&item.input.kind().to_string())
             }
+            TransactionFilter::All(filters) => filters.iter().all(|f| f.matches(item)),
+            TransactionFilter::Any(filters) => filters.iter().any(|f| f.matches(item)),
+            TransactionFilter::Not(filter) => !filter.matches(item),
             // these filters are not supported, rpc will reject these filters on subscription
             TransactionFilter::Checkpoint(_) => false,
             TransactionFilter::FromOrToAddress { addr: _ } => false,
@@ -104,7 +104,7 @@ pub trait StateRead: Send + Sync {
         cursor: Option<EventID>,
         limit: usize,
         descending: bool,
-    ) -> StateReadResult<Vec<SuiEvent>>;
+    ) -> StateReadResult<(Vec<SuiEvent>, Option<EventID>)>;
 
     // transaction_execution_api
     #[allow(clippy::type_complexity)]
@@ -144,7 +144,7 @@ pub trait StateRead: Send + Sync {
         cursor: Option<TransactionDigest>,
         limit: Option<usize>,
         reverse: bool,
-    ) -> StateReadResult<Vec<TransactionDigest>>;
+    ) -> StateReadResult<(Vec<TransactionDigest>, Option<TransactionDigest>)>;
 
     fn get_dynamic_field_object_id(
         &self,
@@ -320,7 +320,7 @@ impl StateRead for AuthorityState {
         cursor: Option<EventID>,
         limit: usize,
         descending: bool,
-    ) -> StateReadResult<Vec<SuiEvent>> {
+    ) -> StateReadResult<(Vec<SuiEvent>, Option<EventID>)> {
         Ok(self
             .query_events(kv_store, query, cursor, limit, descending)
             .await?)
@@ -374,7 +374,7 @@ impl StateRead for AuthorityState {
         cursor: Option<TransactionDigest>,
         limit: Option<usize>,
         reverse: bool,
-    ) -> StateReadResult<Vec<TransactionDigest>> {
+    ) -> StateReadResult<(Vec<TransactionDigest>, Option<TransactionDigest>)> {
         Ok(self
             .get_transactions(kv_store, filter, cursor, limit, reverse)
             .await?)
@@ -215,7 +215,7 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
             let opts = query.options.unwrap_or_default();
 
             // Retrieve 1 extra item for next cursor
-            let mut digests = self
+            let (mut digests, scan_cursor) = self
                 .state
                 .get_transactions(
                     &self.transaction_kv_store,
@@ -227,10 +227,11 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
                 .await
                 .map_err(Error::from)?;
 
-            // extract next cursor
-            let has_next_page = digests.len() > limit;
+            // extract next cursor, which is where the scan stopped if it ran out of candidates
+            // to check before filling the page
+            let has_next_page = digests.len() > limit || scan_cursor.is_some();
             digests.truncate(limit);
-            let next_cursor = digests.last().cloned().map_or(cursor, Some);
+            let next_cursor = scan_cursor.or(digests.last().cloned()).or(cursor);
 
             let data: Vec<SuiTransactionBlockResponse> = if opts.only_digest() {
                 digests
@@ -270,7 +271,7 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
             let limit = cap_page_limit(limit);
             self.metrics.query_events_limit.report(limit as u64);
             // Retrieve 1 extra item for next cursor
-            let mut data = self
+            let (mut data, scan_cursor) = self
                 .state
                 .query_events(
                     &self.transaction_kv_store,
@@ -281,9 +282,11 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
                 )
                 .await
                 .map_err(Error::from)?;
-            let has_next_page = data.len() > limit;
+            let has_next_page = data.len() > limit || scan_cursor.is_some();
             data.truncate(limit);
-            let next_cursor = data.last().map_or(cursor, |e| Some(e.id.clone()));
+            let next_cursor = scan_cursor
+                .or_else(|| data.last().map(|e| e.id.clone()))
+                .or(cursor);
             self.metrics
                 .query_events_result_size
                 .report(data.len() as u64);
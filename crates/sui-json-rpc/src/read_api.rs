@@ -19,6 +19,7 @@ use tracing::{debug, error, info, instrument, warn};
 
 use mysten_metrics::spawn_monitored_task;
 use sui_core::authority::AuthorityState;
+use sui_core::historical_object_reader::HistoricalObjectReader;
 use sui_json_rpc_types::{
     BalanceChange, Checkpoint, CheckpointId, CheckpointPage, DisplayFieldsResponse, EventFilter,
     ObjectChange, ProtocolConfigResponse, SuiEvent, SuiGetPastObjectRequest, SuiMoveStruct,
@@ -65,6 +66,8 @@ pub struct ReadApi {
     pub state: Arc<dyn StateRead>,
     pub transaction_kv_store: Arc<TransactionKeyValueStore>,
     pub metrics: Arc<JsonRpcMetrics>,
+    /// Reconstructs past object versions that were pruned from the node's store.
+    pub historical_object_reader: Option<Arc<HistoricalObjectReader>>,
 }
 
 // Internal data structure to make it easy to work with data returned from
@@ -106,9 +109,18 @@ impl ReadApi {
             state,
             transaction_kv_store,
             metrics,
+            historical_object_reader: None,
         }
     }
 
+    pub fn with_historical_object_reader(
+        mut self,
+        historical_object_reader: Arc<HistoricalObjectReader>,
+    ) -> Self {
+        self.historical_object_reader = Some(historical_object_reader);
+        self
+    }
+
     async fn get_checkpoint_internal(&self, id: CheckpointId) -> Result<Checkpoint, Error> {
         Ok(match id {
             CheckpointId::SequenceNumber(seq) => {
@@ -583,6 +595,21 @@ impl ReadApiServer for ReadApi {
                 error!("Failed to call try_get_past_object for object: {object_id:?} version: {version:?} with error: {e:?}");
                 Error::from(e)
             })}).await.map_err(Error::from)??;
+            let past_read = match (past_read, &self.historical_object_reader) {
+                (PastObjectRead::VersionNotFound(id, seq_num), Some(reader)) => {
+                    match reader.get_past_object(id, seq_num).await {
+                        Ok(Some((object_ref, o, layout))) => {
+                            PastObjectRead::VersionFound(object_ref, o, layout)
+                        }
+                        Ok(None) => PastObjectRead::VersionNotFound(id, seq_num),
+                        Err(e) => {
+                            warn!("Failed to reconstruct object: {id:?} version: {seq_num:?} with error: {e:?}");
+                            PastObjectRead::VersionNotFound(id, seq_num)
+                        }
+                    }
+                }
+                (past_read, _) => past_read,
+            };
             let options = options.unwrap_or_default();
             match past_read {
                 PastObjectRead::ObjectNotExists(id) => {
@@ -1119,7 +1146,7 @@ async fn get_display_object_by_type(
     object_type: &StructTag,
     // TODO: add query version support
 ) -> Result<Option<DisplayVersionUpdatedEvent>, ObjectDisplayError> {
-    let mut events = fullnode_api
+    let (mut events, _) = fullnode_api
         .state
         .query_events(
             kv_store,
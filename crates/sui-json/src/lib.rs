@@ -821,14 +821,18 @@ fn resolve_call_arg(
 fn resolve_call_args(
     view: &BinaryIndexedView,
     type_args: &[TypeTag],
-    json_args: &[SuiJsonValue],
+    json_args: &[Option<SuiJsonValue>],
     parameter_types: &[SignatureToken],
-) -> Result<Vec<ResolvedCallArg>, anyhow::Error> {
+) -> Result<Vec<Option<ResolvedCallArg>>, anyhow::Error> {
     json_args
         .iter()
         .zip(parameter_types)
         .enumerate()
-        .map(|(idx, (arg, param))| resolve_call_arg(view, type_args, idx, arg, param))
+        .map(|(idx, (arg, param))| {
+            arg.as_ref()
+                .map(|arg| resolve_call_arg(view, type_args, idx, arg, param))
+                .transpose()
+        })
         .collect()
 }
 
@@ -841,6 +845,29 @@ pub fn resolve_move_function_args(
     type_args: &[TypeTag],
     combined_args_json: Vec<SuiJsonValue>,
 ) -> Result<Vec<(ResolvedCallArg, SignatureToken)>, anyhow::Error> {
+    let call_args = resolve_partial_move_function_args(
+        package,
+        module_ident,
+        function,
+        type_args,
+        combined_args_json.into_iter().map(Some).collect(),
+    )?;
+    Ok(call_args
+        .into_iter()
+        .map(|(arg, expected_type)| (arg.expect("All args are given as JSON"), expected_type))
+        .collect())
+}
+
+/// Same as [resolve_move_function_args], except that args given as `None` are left unresolved.
+/// Programmable transactions use this for args that are results of earlier commands, which are
+/// only known once the transaction is executed.
+pub fn resolve_partial_move_function_args(
+    package: &MovePackage,
+    module_ident: Identifier,
+    function: Identifier,
+    type_args: &[TypeTag],
+    combined_args_json: Vec<Option<SuiJsonValue>>,
+) -> Result<Vec<(Option<ResolvedCallArg>, SignatureToken)>, anyhow::Error> {
     // Extract the expected function signature
     let module = package.deserialize_module(&module_ident, VERSION_MAX, true)?;
     let function_str = function.as_ident_str();
@@ -2,10 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
+use crate::remote_signer::{
+    send_request, RemoteKey, RemoteSignerConfig, RemoteSignerRequest, RemoteSignerResponse,
+};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
 use anyhow::anyhow;
+use argon2::{Algorithm, Argon2, Params, Version};
 use bip32::DerivationPath;
 use bip39::{Language, Mnemonic, Seed};
-use rand::{rngs::StdRng, SeedableRng};
+use fastcrypto::encoding::{Base64, Encoding};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use shared_crypto::intent::{Intent, IntentMessage};
 use std::collections::BTreeMap;
@@ -15,10 +22,12 @@ use std::fs;
 use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::{
     enum_dispatch, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme, SuiKeyPair,
+    SuiSignature,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -26,7 +35,22 @@ use sui_types::crypto::{
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    Encrypted(EncryptedFileKeystore),
+    Remote(RemoteKeystore),
+}
+
+impl Keystore {
+    /// Open the keystore file at `path`, as an [EncryptedFileKeystore] if the file holds
+    /// encrypted keys, or as a plain [FileBasedKeystore] otherwise.
+    pub fn from_path(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        Ok(if EncryptedFileKeystore::is_encrypted(path) {
+            Keystore::Encrypted(EncryptedFileKeystore::new(path)?)
+        } else {
+            Keystore::File(FileBasedKeystore::new(path)?)
+        })
+    }
 }
+
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
     fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error>;
@@ -91,6 +115,16 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::Encrypted(file) => {
+                writeln!(writer, "Keystore Type : Encrypted")?;
+                write!(writer, "Keystore Path : {:?}", file.path)?;
+                write!(f, "{}", writer)
+            }
+            Keystore::Remote(remote) => {
+                writeln!(writer, "Keystore Type : Remote")?;
+                write!(writer, "Keystore Endpoint : {}", remote.config.endpoint)?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -179,8 +213,16 @@ impl FileBasedKeystore {
                 File::open(path)
                     .map_err(|e| anyhow!("Can't open FileBasedKeystore from {:?}: {e}", path))?,
             );
-            let kp_strings: Vec<String> = serde_json::from_reader(reader)
-                .map_err(|e| anyhow!("Can't deserialize FileBasedKeystore from {:?}: {e}", path))?;
+            let kp_strings: Vec<String> = serde_json::from_reader(reader).map_err(|e| {
+                if EncryptedFileKeystore::is_encrypted(path) {
+                    anyhow!(
+                        "Keystore at {:?} is encrypted, open it as an Encrypted keystore",
+                        path
+                    )
+                } else {
+                    anyhow!("Can't deserialize FileBasedKeystore from {:?}: {e}", path)
+                }
+            })?;
             kp_strings
                 .iter()
                 .map(|kpstr| {
@@ -203,6 +245,10 @@ impl FileBasedKeystore {
         self.path = Some(path.to_path_buf());
     }
 
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
     pub fn save(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
             let store = serde_json::to_string_pretty(
@@ -223,6 +269,564 @@ impl FileBasedKeystore {
     }
 }
 
+/// Environment variable consulted for the passphrase of an [EncryptedFileKeystore] before
+/// falling back to an interactive prompt.
+pub const SUI_KEYSTORE_PASSPHRASE_ENV: &str = "SUI_KEYSTORE_PASSPHRASE";
+
+const ENCRYPTED_KEYSTORE_VERSION: u8 = 1;
+const ENCRYPTION_KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+const SALT_LENGTH: usize = 16;
+
+/// Argon2id parameters used to derive the AES-256-GCM key from the keystore passphrase.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Base64 encoded random salt.
+    pub salt: String,
+    /// Memory size in KiB.
+    pub m_cost: u32,
+    /// Number of iterations.
+    pub t_cost: u32,
+    /// Degree of parallelism.
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        let mut salt = [0u8; SALT_LENGTH];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self {
+            salt: Base64::encode(salt),
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl KdfParams {
+    fn derive_key(&self, passphrase: &str) -> Result<[u8; ENCRYPTION_KEY_LENGTH], anyhow::Error> {
+        let salt = Base64::decode(&self.salt)
+            .map_err(|e| anyhow!("Invalid keystore salt encoding: {:?}", e))?;
+        let params = Params::new(
+            self.m_cost,
+            self.t_cost,
+            self.p_cost,
+            Some(ENCRYPTION_KEY_LENGTH),
+        )
+        .map_err(|e| anyhow!("Invalid keystore KDF parameters: {e}"))?;
+        let mut key = [0u8; ENCRYPTION_KEY_LENGTH];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| anyhow!("Cannot derive keystore encryption key: {e}"))?;
+        Ok(key)
+    }
+}
+
+/// A private key sealed with AES-256-GCM. The public key is kept in the clear, so that
+/// addresses can be listed without the passphrase, and is bound to the ciphertext as
+/// associated data.
+#[derive(Clone, Serialize, Deserialize)]
+struct EncryptedKey {
+    /// Base64 encoded `flag || pubkey`.
+    public_key: String,
+    /// Base64 encoded AES-GCM nonce.
+    nonce: String,
+    /// Base64 encoded encryption of `flag || privkey`.
+    ciphertext: String,
+}
+
+impl EncryptedKey {
+    fn seal(keypair: &SuiKeyPair, cipher: &Aes256Gcm) -> Result<Self, anyhow::Error> {
+        let public_key = keypair.public().encode_base64();
+        let mut nonce = [0u8; NONCE_LENGTH];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let plaintext = Base64::decode(&keypair.encode_base64())
+            .map_err(|e| anyhow!("Cannot encode keypair: {:?}", e))?;
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: public_key.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("Cannot encrypt keypair"))?;
+        Ok(Self {
+            public_key,
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    fn open(&self, cipher: &Aes256Gcm) -> Result<SuiKeyPair, anyhow::Error> {
+        let nonce =
+            Base64::decode(&self.nonce).map_err(|e| anyhow!("Invalid nonce encoding: {:?}", e))?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(anyhow!("Invalid nonce length {}", nonce.len()));
+        }
+        let ciphertext = Base64::decode(&self.ciphertext)
+            .map_err(|e| anyhow!("Invalid ciphertext encoding: {:?}", e))?;
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: self.public_key.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("Incorrect passphrase or corrupted keystore"))?;
+        let keypair = SuiKeyPair::decode_base64(&Base64::encode(plaintext))
+            .map_err(|e| anyhow!("Invalid decrypted keypair: {e}"))?;
+        if keypair.public().encode_base64() != self.public_key {
+            return Err(anyhow!("Decrypted keypair does not match its public key"));
+        }
+        Ok(keypair)
+    }
+}
+
+/// On-disk layout of an [EncryptedFileKeystore].
+#[derive(Serialize, Deserialize)]
+struct EncryptedKeystoreFile {
+    version: u8,
+    kdf: KdfParams,
+    keys: Vec<EncryptedKey>,
+}
+
+/// Decrypted state of an [EncryptedFileKeystore], populated on first use of a private key.
+struct UnlockedKeys {
+    encryption_key: [u8; ENCRYPTION_KEY_LENGTH],
+    keys: BTreeMap<SuiAddress, SuiKeyPair>,
+}
+
+impl UnlockedKeys {
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.encryption_key))
+    }
+}
+
+/// A file based keystore which encrypts every private key at rest with a key derived from a
+/// passphrase (Argon2id + AES-256-GCM). Public keys are readable without the passphrase; the
+/// private keys are only decrypted the first time one is needed, using the passphrase set with
+/// [EncryptedFileKeystore::set_passphrase], the `SUI_KEYSTORE_PASSPHRASE` environment variable
+/// or an interactive prompt, in that order.
+#[derive(Default)]
+pub struct EncryptedFileKeystore {
+    kdf: KdfParams,
+    encrypted_keys: BTreeMap<SuiAddress, EncryptedKey>,
+    public_keys: BTreeMap<SuiAddress, PublicKey>,
+    path: Option<PathBuf>,
+    passphrase: Option<String>,
+    unlocked: OnceLock<UnlockedKeys>,
+}
+
+impl Serialize for EncryptedFileKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(
+            self.path
+                .as_ref()
+                .unwrap_or(&PathBuf::default())
+                .to_str()
+                .unwrap_or(""),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for EncryptedFileKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        EncryptedFileKeystore::new(&PathBuf::from(String::deserialize(deserializer)?))
+            .map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for EncryptedFileKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        Ok(Signature::new_hashed(
+            msg,
+            self.get_key(address)
+                .map_err(signature::Error::from_source)?,
+        ))
+    }
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        Ok(Signature::new_secure(
+            &IntentMessage::new(intent, msg),
+            self.get_key(address)
+                .map_err(signature::Error::from_source)?,
+        ))
+    }
+
+    fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        self.unlock()?;
+        let unlocked = self
+            .unlocked
+            .get_mut()
+            .expect("Keystore must be unlocked after unlock()");
+        let public_key = keypair.public();
+        let address: SuiAddress = (&public_key).into();
+        let encrypted = EncryptedKey::seal(&keypair, &unlocked.cipher())?;
+        unlocked.keys.insert(address, keypair);
+        self.encrypted_keys.insert(address, encrypted);
+        self.public_keys.insert(address, public_key);
+        self.save()?;
+        Ok(())
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.public_keys.values().cloned().collect()
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        if !self.encrypted_keys.contains_key(address) {
+            return Err(anyhow!("Cannot find key for address: [{address}]"));
+        }
+        match self.unlock()?.keys.get(address) {
+            Some(key) => Ok(key),
+            None => Err(anyhow!("Cannot find key for address: [{address}]")),
+        }
+    }
+}
+
+impl EncryptedFileKeystore {
+    pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        let mut keystore = Self {
+            path: Some(path.to_path_buf()),
+            ..Default::default()
+        };
+        if path.exists() {
+            let reader =
+                BufReader::new(File::open(path).map_err(|e| {
+                    anyhow!("Can't open EncryptedFileKeystore from {:?}: {e}", path)
+                })?);
+            let file: EncryptedKeystoreFile = serde_json::from_reader(reader).map_err(|e| {
+                anyhow!(
+                    "Can't deserialize EncryptedFileKeystore from {:?}: {e}",
+                    path
+                )
+            })?;
+            if file.version != ENCRYPTED_KEYSTORE_VERSION {
+                return Err(anyhow!(
+                    "Unsupported EncryptedFileKeystore version {} in {:?}",
+                    file.version,
+                    path
+                ));
+            }
+            keystore.kdf = file.kdf;
+            for key in file.keys {
+                let public_key = PublicKey::decode_base64(&key.public_key)
+                    .map_err(|e| anyhow!("Invalid public key {:#?} in {:?}", e, path))?;
+                let address: SuiAddress = (&public_key).into();
+                keystore.public_keys.insert(address, public_key);
+                keystore.encrypted_keys.insert(address, key);
+            }
+        }
+        Ok(keystore)
+    }
+
+    /// Encrypt all keys of a plain [FileBasedKeystore], with `passphrase` if given or else with
+    /// a new passphrase read from the environment or prompted for. The result is not persisted
+    /// until a path is set and [EncryptedFileKeystore::save] is called.
+    pub fn from_file_keystore(
+        keystore: &FileBasedKeystore,
+        passphrase: Option<&str>,
+    ) -> Result<Self, anyhow::Error> {
+        let mut encrypted = Self::default();
+        if let Some(passphrase) = passphrase {
+            encrypted.set_passphrase(passphrase);
+        }
+        encrypted.unlock()?;
+        for keypair in keystore.key_pairs() {
+            // SuiKeyPair is not Clone, round trip through its encoding to copy it.
+            let keypair = SuiKeyPair::decode_base64(&keypair.encode_base64())
+                .map_err(|e| anyhow!("Invalid keypair in keystore: {e}"))?;
+            encrypted.add_key(keypair)?;
+        }
+        Ok(encrypted)
+    }
+
+    /// Returns true if the file at `path` exists and holds an encrypted keystore.
+    pub fn is_encrypted(path: &Path) -> bool {
+        File::open(path)
+            .ok()
+            .and_then(|file| {
+                serde_json::from_reader::<_, EncryptedKeystoreFile>(BufReader::new(file)).ok()
+            })
+            .is_some()
+    }
+
+    pub fn set_path(&mut self, path: &Path) {
+        self.path = Some(path.to_path_buf());
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Use `passphrase` to unlock the keystore instead of reading it from the environment or
+    /// prompting for it.
+    pub fn set_passphrase(&mut self, passphrase: &str) {
+        self.passphrase = Some(passphrase.to_string());
+    }
+
+    /// Returns true if the private keys have already been decrypted.
+    pub fn is_unlocked(&self) -> bool {
+        self.unlocked.get().is_some()
+    }
+
+    /// Decrypt the private keys, failing if the passphrase is wrong. Subsequent calls are free.
+    pub fn unlock(&self) -> Result<(), anyhow::Error> {
+        self.unlocked().map(|_| ())
+    }
+
+    fn unlocked(&self) -> Result<&UnlockedKeys, anyhow::Error> {
+        if let Some(unlocked) = self.unlocked.get() {
+            return Ok(unlocked);
+        }
+        let passphrase = self.read_passphrase()?;
+        let encryption_key = self.kdf.derive_key(&passphrase)?;
+        let mut unlocked = UnlockedKeys {
+            encryption_key,
+            keys: BTreeMap::new(),
+        };
+        let cipher = unlocked.cipher();
+        for (address, key) in &self.encrypted_keys {
+            unlocked.keys.insert(*address, key.open(&cipher)?);
+        }
+        // Another thread may have unlocked the keystore concurrently, with the same result.
+        let _ = self.unlocked.set(unlocked);
+        Ok(self.unlocked.get().expect("Keystore was just unlocked"))
+    }
+
+    fn read_passphrase(&self) -> Result<String, anyhow::Error> {
+        if let Some(passphrase) = &self.passphrase {
+            return Ok(passphrase.clone());
+        }
+        if let Ok(passphrase) = std::env::var(SUI_KEYSTORE_PASSPHRASE_ENV) {
+            return Ok(passphrase);
+        }
+        let location = self
+            .path
+            .as_ref()
+            .map(|p| format!(" for {:?}", p))
+            .unwrap_or_default();
+        if self.encrypted_keys.is_empty() {
+            let passphrase =
+                rpassword::prompt_password(format!("New keystore passphrase{location}: "))?;
+            let confirmation = rpassword::prompt_password("Confirm passphrase: ")?;
+            if passphrase != confirmation {
+                return Err(anyhow!("Passphrases do not match"));
+            }
+            Ok(passphrase)
+        } else {
+            Ok(rpassword::prompt_password(format!(
+                "Keystore passphrase{location}: "
+            ))?)
+        }
+    }
+
+    /// Write the keystore to its path. The file is replaced atomically so that an interrupted
+    /// save never leaves a partially written keystore behind.
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        if let Some(path) = &self.path {
+            let store = serde_json::to_string_pretty(&EncryptedKeystoreFile {
+                version: ENCRYPTED_KEYSTORE_VERSION,
+                kdf: self.kdf.clone(),
+                keys: self.encrypted_keys.values().cloned().collect(),
+            })?;
+            let tmp_path = path.with_extension("tmp");
+            fs::write(&tmp_path, store)?;
+            fs::rename(&tmp_path, path)?;
+        }
+        Ok(())
+    }
+}
+
+/// A keystore whose private keys are held by an external signing daemon (e.g. backed by an HSM
+/// or an air-gapped machine), see [crate::remote_signer] for the protocol. Only public keys
+/// are known locally; every signature is requested from the daemon and checked before use.
+/// The keys are fetched from the daemon the first time they are needed, so loading a config
+/// that refers to a remote keystore does not require the daemon to be reachable.
+pub struct RemoteKeystore {
+    config: RemoteSignerConfig,
+    keys: OnceLock<RemoteKeys>,
+}
+
+type RemoteKeys = BTreeMap<SuiAddress, (PublicKey, RemoteKey)>;
+
+impl Serialize for RemoteKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.config.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RemoteKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(RemoteKeystore::new(RemoteSignerConfig::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
+impl AccountKeystore for RemoteKeystore {
+    fn sign_hashed(
+        &self,
+        address: &SuiAddress,
+        _msg: &[u8],
+    ) -> Result<Signature, signature::Error> {
+        Err(signature::Error::from_source(format!(
+            "Remote signer only signs intent messages, cannot sign a raw hash for address: [{address}]"
+        )))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        self.request_signature(address, &IntentMessage::new(intent, msg))
+            .map_err(signature::Error::from_source)
+    }
+
+    fn add_key(&mut self, _keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        Err(anyhow!(
+            "Cannot add keys to a remote signer, import them into the signing daemon instead"
+        ))
+    }
+
+    /// Returns no keys if they cannot be fetched from the signing daemon, use
+    /// [RemoteKeystore::refresh_keys] to find out why.
+    fn keys(&self) -> Vec<PublicKey> {
+        self.remote_keys()
+            .map(|keys| keys.values().map(|(key, _)| key.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        Err(anyhow!(
+            "The private key for address [{address}] is held by the remote signer and cannot be exported"
+        ))
+    }
+}
+
+impl RemoteKeystore {
+    /// A keystore for the signing daemon described by `config`. The daemon is not contacted
+    /// until its keys are needed.
+    pub fn new(config: RemoteSignerConfig) -> Self {
+        Self {
+            config,
+            keys: OnceLock::new(),
+        }
+    }
+
+    pub fn config(&self) -> &RemoteSignerConfig {
+        &self.config
+    }
+
+    /// Re-fetch the list of keys and their policies from the signing daemon.
+    pub fn refresh_keys(&mut self) -> Result<(), anyhow::Error> {
+        self.keys = OnceLock::from(self.fetch_keys()?);
+        Ok(())
+    }
+
+    fn remote_keys(&self) -> Result<&RemoteKeys, anyhow::Error> {
+        if let Some(keys) = self.keys.get() {
+            return Ok(keys);
+        }
+        let keys = self.fetch_keys()?;
+        // Another thread may have fetched the keys concurrently, either answer is fine.
+        Ok(self.keys.get_or_init(|| keys))
+    }
+
+    fn fetch_keys(&self) -> Result<RemoteKeys, anyhow::Error> {
+        let keys = match send_request(&self.config, &RemoteSignerRequest::ListKeys)? {
+            RemoteSignerResponse::Keys(keys) => keys,
+            RemoteSignerResponse::Error(e) => {
+                return Err(anyhow!("Remote signer failed to list keys: {e}"))
+            }
+            response => {
+                return Err(anyhow!(
+                    "Unexpected remote signer response to list keys: {:?}",
+                    response
+                ))
+            }
+        };
+        keys.into_iter()
+            .map(|key| {
+                let public_key = key.public_key()?;
+                let address: SuiAddress = (&public_key).into();
+                Ok((address, (public_key, key)))
+            })
+            .collect()
+    }
+
+    fn request_signature<T>(
+        &self,
+        address: &SuiAddress,
+        intent_msg: &IntentMessage<T>,
+    ) -> Result<Signature, anyhow::Error>
+    where
+        T: Serialize,
+    {
+        let (_, key) = self
+            .remote_keys()?
+            .get(address)
+            .ok_or_else(|| anyhow!("Cannot find key for address: [{address}]"))?;
+        // Fail early instead of asking the daemon for a signature it is going to refuse.
+        if !key.allows(intent_msg.intent.scope) {
+            return Err(anyhow!(
+                "Remote signer policy does not allow {:?} signatures for address: [{address}]",
+                intent_msg.intent.scope
+            ));
+        }
+        let request = RemoteSignerRequest::sign(
+            *address,
+            intent_msg.intent.clone(),
+            &bcs::to_bytes(&intent_msg.value)?,
+        );
+        let signature = match send_request(&self.config, &request)? {
+            RemoteSignerResponse::Signature(signature) => Signature::decode_base64(&signature)
+                .map_err(|e| anyhow!("Invalid signature from remote signer: {e}"))?,
+            RemoteSignerResponse::Error(e) => {
+                return Err(anyhow!("Remote signer refused to sign: {e}"))
+            }
+            response => {
+                return Err(anyhow!(
+                    "Unexpected remote signer response to sign: {:?}",
+                    response
+                ))
+            }
+        };
+        signature
+            .verify_secure(intent_msg, *address, signature.scheme())
+            .map_err(|e| anyhow!("Remote signer returned an invalid signature: {e}"))?;
+        Ok(signature)
+    }
+}
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct InMemKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
@@ -0,0 +1,232 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Wire protocol spoken between a [crate::keystore::RemoteKeystore] and an external signing
+//! daemon. Each connection carries a single request and its response, both encoded as one line
+//! of JSON. Daemons listening on HTTP receive the same JSON as the body of a `POST` instead.
+//! The daemon owns the private keys and decides, per address, which intents it is willing to
+//! sign.
+
+use anyhow::anyhow;
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentScope};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey};
+
+const UNIX_SCHEME: &str = "unix://";
+const TCP_SCHEME: &str = "tcp://";
+const HTTP_SCHEME: &str = "http://";
+
+fn default_timeout_ms() -> u64 {
+    30_000
+}
+
+/// Location of a signing daemon, stored in `client.yaml` for a remote keystore.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RemoteSignerConfig {
+    /// `unix:///path/to/signer.sock`, `tcp://host:port` or `http://host:port/path`.
+    pub endpoint: String,
+    /// How long to wait for the daemon to answer a request. Signing may require a human
+    /// to approve the request on the device, so this defaults to 30 seconds.
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+impl RemoteSignerConfig {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            timeout_ms: default_timeout_ms(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum RemoteSignerRequest {
+    /// List the keys held by the daemon.
+    ListKeys,
+    /// Sign the intent message `intent || message` with the key of `address`.
+    Sign {
+        address: SuiAddress,
+        intent: Intent,
+        /// Base64 encoded BCS bytes of the message, e.g. a `TransactionData`.
+        message: String,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteSignerResponse {
+    Keys(Vec<RemoteKey>),
+    /// Base64 encoded `flag || signature || pubkey`.
+    Signature(String),
+    Error(String),
+}
+
+/// A key held by the signing daemon, with the policy the daemon applies to it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemoteKey {
+    /// Base64 encoded `flag || pubkey`.
+    pub public_key: String,
+    /// Intent scopes the daemon signs for with this key, any scope if empty.
+    #[serde(default)]
+    pub allowed_scopes: Vec<IntentScope>,
+}
+
+impl RemoteKey {
+    pub fn new(public_key: &PublicKey, allowed_scopes: Vec<IntentScope>) -> Self {
+        Self {
+            public_key: public_key.encode_base64(),
+            allowed_scopes,
+        }
+    }
+
+    pub fn public_key(&self) -> Result<PublicKey, anyhow::Error> {
+        PublicKey::decode_base64(&self.public_key)
+            .map_err(|e| anyhow!("Invalid public key from remote signer: {e}"))
+    }
+
+    pub fn allows(&self, scope: IntentScope) -> bool {
+        self.allowed_scopes.is_empty() || self.allowed_scopes.contains(&scope)
+    }
+}
+
+impl RemoteSignerRequest {
+    pub fn sign(address: SuiAddress, intent: Intent, message: &[u8]) -> Self {
+        Self::Sign {
+            address,
+            intent,
+            message: Base64::encode(message),
+        }
+    }
+}
+
+/// Send `request` to the daemon at `config.endpoint` and wait for its response.
+pub fn send_request(
+    config: &RemoteSignerConfig,
+    request: &RemoteSignerRequest,
+) -> Result<RemoteSignerResponse, anyhow::Error> {
+    let timeout = Some(Duration::from_millis(config.timeout_ms));
+    let connect_error =
+        |e: std::io::Error| anyhow!("Cannot connect to remote signer {}: {e}", config.endpoint);
+    if let Some(address) = config.endpoint.strip_prefix(TCP_SCHEME) {
+        let stream = TcpStream::connect(address).map_err(connect_error)?;
+        stream.set_read_timeout(timeout)?;
+        stream.set_write_timeout(timeout)?;
+        round_trip(stream, request)
+    } else if let Some(url) = config.endpoint.strip_prefix(HTTP_SCHEME) {
+        let (host, path) = match url.find('/') {
+            Some(i) => url.split_at(i),
+            None => (url, "/"),
+        };
+        let stream = TcpStream::connect(host).map_err(connect_error)?;
+        stream.set_read_timeout(timeout)?;
+        stream.set_write_timeout(timeout)?;
+        http_round_trip(stream, host, path, request)
+    } else if let Some(path) = config.endpoint.strip_prefix(UNIX_SCHEME) {
+        #[cfg(unix)]
+        {
+            let stream = std::os::unix::net::UnixStream::connect(path).map_err(connect_error)?;
+            stream.set_read_timeout(timeout)?;
+            stream.set_write_timeout(timeout)?;
+            round_trip(stream, request)
+        }
+        #[cfg(not(unix))]
+        {
+            Err(anyhow!(
+                "Unix socket endpoint {path} is not supported on this platform"
+            ))
+        }
+    } else {
+        Err(anyhow!(
+            "Invalid remote signer endpoint {}, expected {UNIX_SCHEME}<path>, {TCP_SCHEME}<host:port> or {HTTP_SCHEME}<host:port>/<path>",
+            config.endpoint
+        ))
+    }
+}
+
+fn round_trip<S: Read + Write>(
+    mut stream: S,
+    request: &RemoteSignerRequest,
+) -> Result<RemoteSignerResponse, anyhow::Error> {
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+    stream.flush()?;
+
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response)?;
+    if response.is_empty() {
+        return Err(anyhow!(
+            "Remote signer closed the connection without answering"
+        ));
+    }
+    serde_json::from_str(&response).map_err(|e| anyhow!("Invalid remote signer response: {e}"))
+}
+
+/// `POST` the request to `path` with HTTP/1.1 and read the response until the daemon closes the
+/// connection. Only meant for a daemon on a trusted network, the exchange is not encrypted.
+fn http_round_trip<S: Read + Write>(
+    mut stream: S,
+    host: &str,
+    path: &str,
+    request: &RemoteSignerRequest,
+) -> Result<RemoteSignerResponse, anyhow::Error> {
+    let body = serde_json::to_string(request)?;
+    write!(
+        stream,
+        "POST {path} HTTP/1.1\r\nHost: {host}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| anyhow!("Invalid HTTP response from remote signer"))?;
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .ok_or_else(|| anyhow!("Invalid HTTP response from remote signer"))?;
+    if status != "200" {
+        return Err(anyhow!(
+            "Remote signer answered with HTTP status {status}: {body}"
+        ));
+    }
+    if lines.any(|line| {
+        line.to_ascii_lowercase()
+            .starts_with("transfer-encoding: chunked")
+    }) {
+        return Err(anyhow!(
+            "Remote signer answered with a chunked response, which is not supported"
+        ));
+    }
+    serde_json::from_str(body).map_err(|e| anyhow!("Invalid remote signer response: {e}"))
+}
+
+/// Read a single request from a connection accepted by a signing daemon.
+pub fn read_request<S: Read>(stream: S) -> Result<RemoteSignerRequest, anyhow::Error> {
+    let mut request = String::new();
+    BufReader::new(stream).read_line(&mut request)?;
+    serde_json::from_str(&request).map_err(|e| anyhow!("Invalid remote signer request: {e}"))
+}
+
+/// Write the response to a request read with [read_request].
+pub fn write_response<S: Write>(
+    mut stream: S,
+    response: &RemoteSignerResponse,
+) -> Result<(), anyhow::Error> {
+    let mut line = serde_json::to_string(response)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+    Ok(stream.flush()?)
+}
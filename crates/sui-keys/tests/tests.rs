@@ -5,8 +5,19 @@ use std::str::FromStr;
 use fastcrypto::hash::HashFunction;
 use tempfile::TempDir;
 
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
+use fastcrypto::encoding::{Base64, Encoding};
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope};
+use sui_keys::keystore::{
+    AccountKeystore, EncryptedFileKeystore, FileBasedKeystore, InMemKeystore, Keystore,
+    RemoteKeystore,
+};
+use sui_keys::remote_signer::{
+    read_request, write_response, RemoteKey, RemoteSignerConfig, RemoteSignerRequest,
+    RemoteSignerResponse,
+};
+use sui_types::crypto::{
+    DefaultHash, EncodeDecodeBase64, SignatureScheme, SuiSignature, SuiSignatureInner,
+};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -69,3 +80,247 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+#[test]
+fn encrypted_keystore_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = EncryptedFileKeystore::new(&keystore_path)?;
+    keystore.set_passphrase("correct horse battery staple");
+    let (address, _, _) =
+        keystore.generate_and_add_new_key(SignatureScheme::ED25519, None, None)?;
+
+    // Private keys are not stored in the clear.
+    let content = std::fs::read_to_string(&keystore_path)?;
+    let secret = keystore.get_key(&address)?.encode_base64();
+    assert!(!content.contains(&secret));
+
+    // Addresses are available without the passphrase.
+    let mut reopened = EncryptedFileKeystore::new(&keystore_path)?;
+    assert_eq!(reopened.addresses(), vec![address]);
+    assert!(!reopened.is_unlocked());
+
+    let mut wrong = EncryptedFileKeystore::new(&keystore_path)?;
+    wrong.set_passphrase("wrong passphrase");
+    assert!(wrong
+        .sign_secure(&address, &"msg", Intent::sui_transaction())
+        .is_err());
+    assert!(!wrong.is_unlocked());
+
+    reopened.set_passphrase("correct horse battery staple");
+    reopened.sign_secure(&address, &"msg", Intent::sui_transaction())?;
+    assert!(reopened.is_unlocked());
+    assert_eq!(
+        reopened.get_key(&address)?.public(),
+        keystore.get_key(&address)?.public()
+    );
+    Ok(())
+}
+
+#[test]
+fn encrypted_keystore_migration_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut file_keystore = FileBasedKeystore::new(&keystore_path)?;
+    file_keystore.generate_and_add_new_key(SignatureScheme::ED25519, None, None)?;
+    file_keystore.generate_and_add_new_key(SignatureScheme::Secp256k1, None, None)?;
+
+    let mut encrypted = EncryptedFileKeystore::from_file_keystore(&file_keystore, Some("pass"))?;
+    encrypted.set_path(&keystore_path);
+    encrypted.save()?;
+    assert_eq!(encrypted.addresses(), file_keystore.addresses());
+
+    assert!(EncryptedFileKeystore::is_encrypted(&keystore_path));
+    assert!(FileBasedKeystore::new(&keystore_path).is_err());
+    let keystore = Keystore::from_path(&keystore_path)?;
+    assert!(matches!(keystore, Keystore::Encrypted(_)));
+    assert_eq!(keystore.addresses(), file_keystore.addresses());
+    assert!(keystore.to_string().contains("Encrypted"));
+    Ok(())
+}
+
+/// Answers `request` the way a signing daemon holding the keys of `keystore` would, signing
+/// only intents within `allowed_scopes`.
+fn mock_signer_response(
+    keystore: &InMemKeystore,
+    allowed_scopes: &[IntentScope],
+    request: RemoteSignerRequest,
+) -> RemoteSignerResponse {
+    match request {
+        RemoteSignerRequest::ListKeys => RemoteSignerResponse::Keys(
+            keystore
+                .keys()
+                .iter()
+                .map(|pk| RemoteKey::new(pk, allowed_scopes.to_vec()))
+                .collect(),
+        ),
+        RemoteSignerRequest::Sign {
+            address,
+            intent,
+            message,
+        } => {
+            if allowed_scopes.contains(&intent.scope) {
+                let mut intent_msg = bcs::to_bytes(&intent).unwrap();
+                intent_msg.extend(Base64::decode(&message).unwrap());
+                let mut hasher = DefaultHash::default();
+                hasher.update(&intent_msg);
+                match keystore.sign_hashed(&address, &hasher.finalize().digest) {
+                    Ok(signature) => RemoteSignerResponse::Signature(signature.encode_base64()),
+                    Err(e) => RemoteSignerResponse::Error(e.to_string()),
+                }
+            } else {
+                RemoteSignerResponse::Error("intent not allowed".to_string())
+            }
+        }
+    }
+}
+
+/// Stands in for a signing daemon: serves the keys of `keystore` on a Unix socket.
+#[cfg(unix)]
+fn spawn_mock_signer(
+    socket: &std::path::Path,
+    keystore: InMemKeystore,
+    allowed_scopes: Vec<IntentScope>,
+) {
+    let listener = std::os::unix::net::UnixListener::bind(socket).unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = stream.unwrap();
+            let request = read_request(&stream).unwrap();
+            let response = mock_signer_response(&keystore, &allowed_scopes, request);
+            write_response(&stream, &response).unwrap();
+        }
+    });
+}
+
+/// Stands in for a signing daemon listening on HTTP, returns the address it listens on.
+fn spawn_mock_http_signer(
+    keystore: InMemKeystore,
+    allowed_scopes: Vec<IntentScope>,
+) -> std::net::SocketAddr {
+    use std::io::{BufRead, BufReader, Read, Write};
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let request = serde_json::from_slice(&body).unwrap();
+            let response =
+                serde_json::to_string(&mock_signer_response(&keystore, &allowed_scopes, request))
+                    .unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{response}",
+                response.len()
+            )
+            .unwrap();
+        }
+    });
+    address
+}
+
+#[cfg(unix)]
+#[test]
+fn remote_keystore_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let socket = temp_dir.path().join("signer.sock");
+    let signer_keys = InMemKeystore::new_insecure_for_tests(2);
+    let addresses = signer_keys.addresses();
+    spawn_mock_signer(&socket, signer_keys, vec![IntentScope::TransactionData]);
+
+    let config = RemoteSignerConfig::new(format!("unix://{}", socket.display()));
+    let mut keystore = Keystore::from(RemoteKeystore::new(config));
+    assert_eq!(keystore.addresses(), addresses);
+    assert!(keystore.to_string().contains("signer.sock"));
+
+    let signature = keystore.sign_secure(&addresses[1], &"tx", Intent::sui_transaction())?;
+    signature.verify_secure(
+        &IntentMessage::new(Intent::sui_transaction(), "tx"),
+        addresses[1],
+        signature.scheme(),
+    )?;
+
+    // Intents outside of the policy of the key are refused.
+    let personal_message = Intent::sui_app(IntentScope::PersonalMessage);
+    assert!(keystore
+        .sign_secure(&addresses[1], &"msg", personal_message)
+        .is_err());
+    assert!(keystore
+        .sign_secure(
+            &SuiAddress::random_for_testing_only(),
+            &"tx",
+            Intent::sui_transaction()
+        )
+        .is_err());
+
+    // Private keys never leave the signer.
+    assert!(keystore.get_key(&addresses[0]).is_err());
+    assert!(keystore.sign_hashed(&addresses[0], b"digest").is_err());
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None)
+        .is_err());
+
+    // The keystore is persisted as its signer config and reconnects when loaded.
+    let reloaded: Keystore = serde_json::from_str(&serde_json::to_string(&keystore)?)?;
+    assert_eq!(reloaded.addresses(), addresses);
+    Ok(())
+}
+
+#[test]
+fn remote_keystore_http_test() -> Result<(), anyhow::Error> {
+    let signer_keys = InMemKeystore::new_insecure_for_tests(2);
+    let addresses = signer_keys.addresses();
+    let address = spawn_mock_http_signer(signer_keys, vec![IntentScope::TransactionData]);
+
+    let config = RemoteSignerConfig::new(format!("http://{address}/sign"));
+    let keystore = Keystore::from(RemoteKeystore::new(config));
+    assert_eq!(keystore.addresses(), addresses);
+
+    let signature = keystore.sign_secure(&addresses[0], &"tx", Intent::sui_transaction())?;
+    signature.verify_secure(
+        &IntentMessage::new(Intent::sui_transaction(), "tx"),
+        addresses[0],
+        signature.scheme(),
+    )?;
+    assert!(keystore
+        .sign_secure(
+            &addresses[0],
+            &"msg",
+            Intent::sui_app(IntentScope::PersonalMessage)
+        )
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn remote_keystore_loads_without_signer_test() -> Result<(), anyhow::Error> {
+    // Nothing listens on this endpoint: loading the config must not contact the signer, only
+    // using the keys does.
+    let config = RemoteSignerConfig::new("tcp://127.0.0.1:1");
+    let mut keystore: RemoteKeystore = serde_json::from_str(&serde_json::to_string(&config)?)?;
+    assert_eq!(keystore.config(), &config);
+    assert!(keystore.keys().is_empty());
+    assert!(keystore
+        .sign_secure(
+            &SuiAddress::random_for_testing_only(),
+            &"tx",
+            Intent::sui_transaction()
+        )
+        .is_err());
+    assert!(keystore.refresh_keys().is_err());
+    Ok(())
+}
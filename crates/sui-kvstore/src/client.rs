@@ -7,10 +7,17 @@ use aws_sdk_dynamodb::config::{Credentials, Region};
 use aws_sdk_dynamodb::primitives::Blob;
 use aws_sdk_dynamodb::types::{AttributeValue, PutRequest, WriteRequest};
 use aws_sdk_s3 as s3;
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use object_store::DynObjectStore;
 use serde::Serialize;
 use std::borrow::Borrow;
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use sui_config::node::TransactionKeyValueStoreWriteConfig;
+use sui_storage::object_store::util::put;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_storage::object_store_key_value_store::value_path;
 
 #[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
 pub enum KVTable {
@@ -23,7 +30,22 @@ pub enum KVTable {
     State,
 }
 
+impl KVTable {
+    fn type_name(self) -> &'static str {
+        match self {
+            KVTable::Transactions => "tx",
+            KVTable::Effects => "fx",
+            KVTable::Events => "ev",
+            KVTable::State => "state",
+            KVTable::CheckpointContent => "cc",
+            KVTable::CheckpointSummary => "cs",
+            KVTable::TransactionToCheckpoint => "tx2c",
+        }
+    }
+}
+
 const UPLOAD_PROGRESS_KEY: [u8; 1] = [0];
+const MAX_CONCURRENT_UPLOADS: usize = 16;
 
 #[async_trait]
 pub trait KVWriteClient {
@@ -48,6 +70,7 @@ pub trait KVWriteClient {
     }
 }
 
+#[derive(Clone)]
 pub struct DynamoDbClient {
     dynamo_client: dynamodb::Client,
     s3_client: s3::Client,
@@ -78,19 +101,6 @@ impl DynamoDbClient {
             bucket_name: config.bucket_name.clone(),
         }
     }
-
-    fn type_name(table: KVTable) -> String {
-        match table {
-            KVTable::Transactions => "tx",
-            KVTable::Effects => "fx",
-            KVTable::Events => "ev",
-            KVTable::State => "state",
-            KVTable::CheckpointContent => "cc",
-            KVTable::CheckpointSummary => "cs",
-            KVTable::TransactionToCheckpoint => "tx2c",
-        }
-        .to_string()
-    }
 }
 
 #[async_trait]
@@ -111,7 +121,7 @@ impl KVWriteClient for DynamoDbClient {
                 .set_put_request(Some(
                     PutRequest::builder()
                         .item("digest", AttributeValue::B(Blob::new(digest)))
-                        .item("type", AttributeValue::S(Self::type_name(table)))
+                        .item("type", AttributeValue::S(table.type_name().to_string()))
                         .item(
                             "bcs",
                             AttributeValue::B(Blob::new(bcs::to_bytes(value.borrow())?)),
@@ -186,3 +196,230 @@ impl KVWriteClient for DynamoDbClient {
         Ok(())
     }
 }
+
+/// Writes every value to `{encoded key}/{item type}` in an object store, which can be a local
+/// directory. This is the layout read by `ObjectStoreKVStore` and served by the http key value
+/// store, so no AWS services are needed to offload historical data.
+#[derive(Clone)]
+pub struct ObjectStoreClient {
+    store: Arc<DynObjectStore>,
+}
+
+impl ObjectStoreClient {
+    pub fn new(config: &ObjectStoreConfig) -> anyhow::Result<Self> {
+        Ok(Self {
+            store: config.make()?,
+        })
+    }
+
+    async fn put(&self, table: KVTable, key: &[u8], bytes: Vec<u8>) -> anyhow::Result<()> {
+        let path = value_path(&base64_url::encode(key), table.type_name());
+        put(&path, Bytes::from(bytes), self.store.clone()).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl KVWriteClient for ObjectStoreClient {
+    async fn multi_set<V: Serialize>(
+        &mut self,
+        table: KVTable,
+        values: impl IntoIterator<Item = (Vec<u8>, V)> + std::marker::Send,
+    ) -> anyhow::Result<()> {
+        let mut items = vec![];
+        let mut seen = HashSet::new();
+        for (digest, value) in values {
+            if !seen.insert(digest.clone()) {
+                continue;
+            }
+            items.push((digest, bcs::to_bytes(value.borrow())?));
+        }
+        let this = &*self;
+        futures::stream::iter(items)
+            .map(|(digest, bytes)| async move { this.put(table, &digest, bytes).await })
+            .buffer_unordered(MAX_CONCURRENT_UPLOADS)
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(())
+    }
+
+    async fn get_state(&self) -> anyhow::Result<Option<u64>> {
+        let path = value_path(
+            &base64_url::encode(&UPLOAD_PROGRESS_KEY),
+            KVTable::State.type_name(),
+        );
+        match self.store.get(&path).await {
+            Ok(result) => Ok(Some(bcs::from_bytes(&result.bytes().await?)?)),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn update_state(&mut self, value: u64) -> anyhow::Result<()> {
+        self.put(KVTable::State, &UPLOAD_PROGRESS_KEY, bcs::to_bytes(&value)?)
+            .await
+    }
+
+    async fn upload_blob<V: Serialize + std::marker::Send>(
+        &mut self,
+        table: KVTable,
+        key: Vec<u8>,
+        value: V,
+    ) -> anyhow::Result<()> {
+        self.put(table, &key, bcs::to_bytes(value.borrow())?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::iter::repeat;
+    use sui_storage::http_key_value_store::TaggedKey;
+    use sui_storage::key_value_store_metrics::KeyValueStoreMetrics;
+    use sui_storage::object_store::ObjectStoreType;
+    use sui_storage::object_store_key_value_store::ObjectStoreKVStore;
+    use sui_types::base_types::ExecutionDigests;
+    use sui_types::committee::Committee;
+    use sui_types::crypto::KeypairTraits;
+    use sui_types::digests::TransactionDigest;
+    use sui_types::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
+    use sui_types::event::Event;
+    use sui_types::messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, SignedCheckpointSummary,
+    };
+    use sui_types::utils::create_fake_transaction;
+
+    fn checkpoint(sequence_number: u64) -> (CertifiedCheckpointSummary, CheckpointContents) {
+        let contents = CheckpointContents::new_with_causally_ordered_transactions(
+            [ExecutionDigests::random()].into_iter(),
+        );
+        let (committee, keys) = Committee::new_simple_test_committee();
+        let summary = CheckpointSummary::new(
+            committee.epoch,
+            sequence_number,
+            0,
+            &contents,
+            None,
+            Default::default(),
+            None,
+            0,
+        );
+        let signatures: Vec<_> = keys
+            .iter()
+            .map(|key| {
+                SignedCheckpointSummary::sign(committee.epoch, &summary, key, key.public().into())
+            })
+            .collect();
+        let summary = CertifiedCheckpointSummary::new(summary, signatures, &committee).unwrap();
+        (summary, contents)
+    }
+
+    #[tokio::test]
+    async fn test_object_store_kv_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let mut client = ObjectStoreClient::new(&config).unwrap();
+
+        let tx = create_fake_transaction();
+        let fx = TransactionEffects::new_with_tx(&tx);
+        let events = TransactionEvents {
+            data: vec![Event::random_for_testing()],
+        };
+        let (summary, contents) = checkpoint(7);
+        let tx_key = tx.digest().into_inner().to_vec();
+        client
+            .multi_set(KVTable::Transactions, [(tx_key.clone(), &tx)])
+            .await
+            .unwrap();
+        client
+            .multi_set(KVTable::Effects, [(tx_key.clone(), &fx)])
+            .await
+            .unwrap();
+        client
+            .multi_set(
+                KVTable::Events,
+                [(events.digest().into_inner().to_vec(), &events)],
+            )
+            .await
+            .unwrap();
+        client
+            .multi_set(KVTable::TransactionToCheckpoint, [(tx_key, 7u64)])
+            .await
+            .unwrap();
+        let sequence_number_key = bcs::to_bytes(&TaggedKey::CheckpointSequenceNumber(7)).unwrap();
+        client
+            .multi_set(
+                KVTable::CheckpointSummary,
+                [
+                    sequence_number_key.clone(),
+                    summary.digest().into_inner().to_vec(),
+                ]
+                .into_iter()
+                .zip(repeat(&summary)),
+            )
+            .await
+            .unwrap();
+        client
+            .multi_set(
+                KVTable::CheckpointContent,
+                [
+                    sequence_number_key,
+                    summary.content_digest.into_inner().to_vec(),
+                ]
+                .into_iter()
+                .zip(repeat(&contents)),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(client.get_state().await.unwrap(), None);
+        client.update_state(7).await.unwrap();
+        assert_eq!(client.get_state().await.unwrap(), Some(7));
+
+        let store =
+            ObjectStoreKVStore::new_kv(&config, KeyValueStoreMetrics::new_for_tests()).unwrap();
+
+        let result = store
+            .multi_get(
+                &[*tx.digest(), TransactionDigest::random()],
+                &[*fx.transaction_digest()],
+                &[events.digest()],
+            )
+            .await;
+        assert_eq!(
+            result.unwrap(),
+            (
+                vec![Some(tx.clone()), None],
+                vec![Some(fx)],
+                vec![Some(events)]
+            )
+        );
+
+        let (summaries, contents_by_seq, summaries_by_digest, contents_by_digest) = store
+            .multi_get_checkpoints(
+                &[7, 8],
+                &[7, 8],
+                &[*summary.digest()],
+                &[summary.content_digest],
+            )
+            .await
+            .unwrap();
+        assert_eq!(summaries[0].as_ref().unwrap().data(), summary.data());
+        assert!(summaries[1].is_none());
+        assert_eq!(contents_by_seq, vec![Some(contents.clone()), None]);
+        assert_eq!(
+            summaries_by_digest[0].as_ref().unwrap().data(),
+            summary.data()
+        );
+        assert_eq!(contents_by_digest, vec![Some(contents)]);
+
+        let result = store
+            .deprecated_get_transaction_checkpoint(*tx.digest())
+            .await;
+        assert_eq!(result.unwrap(), Some(7));
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::client::{DynamoDbClient, KVTable, KVWriteClient};
+use crate::client::{DynamoDbClient, KVTable, KVWriteClient, ObjectStoreClient};
 use anyhow::{anyhow, Result};
 use mysten_metrics::spawn_monitored_task;
 use prometheus::{register_int_gauge_with_registry, IntGauge, Registry};
@@ -47,22 +47,35 @@ pub async fn setup_key_value_store_uploader(
     let (sender, receiver) = oneshot::channel();
     let metrics = KVStoreMetrics::new(registry);
 
-    spawn_monitored_task!(async move {
-        upload_to_kv_store(store, receiver, config, metrics)
-            .await
-            .expect("Upload failed to key value store")
-    });
+    if let Some(object_store_config) = &config.object_store_config {
+        let client = ObjectStoreClient::new(object_store_config)?;
+        spawn_monitored_task!(async move {
+            upload_to_kv_store(store, receiver, config, client, metrics)
+                .await
+                .expect("Upload failed to key value store")
+        });
+    } else {
+        let client = DynamoDbClient::new(&config).await;
+        spawn_monitored_task!(async move {
+            upload_to_kv_store(store, receiver, config, client, metrics)
+                .await
+                .expect("Upload failed to key value store")
+        });
+    }
     Ok(Some(sender))
 }
 
-async fn upload_to_kv_store(
+async fn upload_to_kv_store<C>(
     store: RocksDbStore,
     mut receiver: oneshot::Receiver<()>,
     config: TransactionKeyValueStoreWriteConfig,
+    mut client: C,
     metrics: KVStoreMetrics,
-) -> Result<()> {
+) -> Result<()>
+where
+    C: KVWriteClient + Clone + Send + Sync + 'static,
+{
     let mut updates: HashSet<u64> = HashSet::new();
-    let mut client = DynamoDbClient::new(&config).await;
     let mut checkpoint_number = client
         .get_state()
         .await
@@ -79,6 +92,7 @@ async fn upload_to_kv_store(
     for shard_id in 0..config.concurrency {
         let cloned_store = store.clone();
         let cloned_config = config.clone();
+        let cloned_client = client.clone();
         let cloned_progress_sender = progress_sender.clone();
         let (term_sender, term_receiver) = oneshot::channel();
         child_handles.push(term_sender);
@@ -88,6 +102,7 @@ async fn upload_to_kv_store(
                 checkpoint_number,
                 cloned_store,
                 cloned_config,
+                cloned_client,
                 cloned_progress_sender,
                 term_receiver,
             )
@@ -117,19 +132,20 @@ async fn upload_to_kv_store(
     Ok(())
 }
 
-pub async fn uploader<S>(
+pub async fn uploader<S, C>(
     shard_id: u64,
     mut checkpoint_number: CheckpointSequenceNumber,
     store: S,
     config: TransactionKeyValueStoreWriteConfig,
+    mut client: C,
     progress_sender: mpsc::Sender<u64>,
     mut receiver: oneshot::Receiver<()>,
 ) -> Result<()>
 where
     S: ReadStore + Send,
     <S as ReadStore>::Error: Send,
+    C: KVWriteClient + Send,
 {
-    let mut client = DynamoDbClient::new(&config).await;
     while receiver.try_recv().is_err() {
         if let Some(checkpoint_summary) = store
             .get_checkpoint_by_sequence_number(checkpoint_number + shard_id)
@@ -0,0 +1,635 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generation of typed Rust bindings for a Move package. The bindings contain a Rust struct for
+//! every struct of the package, serializing to the same BCS bytes as its Move counterpart, and a
+//! function for every public or entry function of the package that appends a call to it to a
+//! `ProgrammableTransactionBuilder`. Structs of dependencies are generated as well when they are
+//! used by the package, except for a few well known types mapped to their Rust versions.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use anyhow::{anyhow, bail};
+use move_binary_format::{
+    file_format::Visibility,
+    normalized::{self, Type},
+};
+use move_compiler::compiled_unit::CompiledUnitEnum;
+use move_core_types::language_storage::ModuleId;
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+use move_symbol_pool::Symbol;
+use sui_types::{base_types::ObjectID, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+use crate::CompiledPackage;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+    "try", "type", "unsafe", "use", "where", "while", "yield", "abstract", "become", "do", "final",
+    "macro", "override", "priv", "typeof", "unsized", "virtual",
+];
+
+/// Keywords that cannot be used as raw identifiers.
+const RESERVED_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+const HEADER: &str = r#"/// An argument of a generated function taking a pure (BCS encoded) value: either the value
+/// itself, added to the inputs of the transaction, or the result of an earlier command.
+pub enum PureArg<T> {
+    Value(T),
+    Argument(::sui_types::transaction::Argument),
+}
+
+impl<T> From<T> for PureArg<T> {
+    fn from(value: T) -> Self {
+        PureArg::Value(value)
+    }
+}
+
+fn pure<T: ::serde::Serialize>(
+    builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+    arg: impl Into<PureArg<T>>,
+) -> ::anyhow::Result<::sui_types::transaction::Argument> {
+    match arg.into() {
+        PureArg::Value(value) => builder.pure(value),
+        PureArg::Argument(argument) => Ok(argument),
+    }
+}
+"#;
+
+/// Generate the Rust bindings of the root package of `package`, calling its functions at
+/// `package_id`. The bindings are returned as the source of a single Rust module.
+pub fn generate_bindings(
+    package: &CompiledPackage,
+    package_id: ObjectID,
+) -> anyhow::Result<String> {
+    BindingsGenerator::new(package).generate(package_id)
+}
+
+struct ModuleInfo {
+    package: Symbol,
+    module: normalized::Module,
+}
+
+struct BindingsGenerator {
+    root_package: Symbol,
+    modules: BTreeMap<ModuleId, ModuleInfo>,
+}
+
+impl BindingsGenerator {
+    fn new(package: &CompiledPackage) -> Self {
+        let root_package = package.package.compiled_package_info.package_name;
+        let root_modules = package.get_modules().map(|m| (root_package, m));
+        let dependent_modules = package
+            .package
+            .deps_compiled_units
+            .iter()
+            .map(|(name, unit)| match &unit.unit {
+                CompiledUnitEnum::Module(m) => (*name, &m.module),
+                CompiledUnitEnum::Script(_) => unimplemented!("Scripts not supported in Sui Move"),
+            });
+        let modules = root_modules
+            .chain(dependent_modules)
+            .map(|(package, m)| {
+                let module = normalized::Module::new(m);
+                let id = ModuleId::new(module.address, module.name.clone());
+                (id, ModuleInfo { package, module })
+            })
+            .collect();
+        Self {
+            root_package,
+            modules,
+        }
+    }
+
+    fn generate(&self, package_id: ObjectID) -> anyhow::Result<String> {
+        // Structs of dependencies to generate, because they are used by the root package.
+        let mut dependent_structs = BTreeSet::new();
+        for info in self.root_modules() {
+            for s in info.module.structs.values() {
+                for field in &s.fields {
+                    self.collect_structs(&field.type_, &mut dependent_structs)?;
+                }
+            }
+            for (_, f) in Self::callable_functions(&info.module) {
+                for ty in Self::call_parameters(f) {
+                    self.collect_structs(ty, &mut dependent_structs)?;
+                }
+            }
+        }
+
+        let mut out = String::new();
+        writeln!(
+            out,
+            "// Rust bindings of the Move package {}, generated by `sui move generate-bindings`.",
+            self.root_package
+        )?;
+        writeln!(
+            out,
+            "// They require the `anyhow`, `serde` and `sui-types` crates, and `move-core-types` for `u256`."
+        )?;
+        writeln!(out)?;
+        out.push_str(HEADER);
+
+        writeln!(out)?;
+        writeln!(out, "pub mod {} {{", package_ident(self.root_package))?;
+        writeln!(
+            out,
+            "    /// ID the functions of the package are called at, `{package_id}`."
+        )?;
+        writeln!(
+            out,
+            "    pub const PACKAGE_ID: ::sui_types::base_types::ObjectID = ::sui_types::base_types::ObjectID::new({:?});",
+            package_id.into_bytes()
+        )?;
+        for info in self.root_modules() {
+            let structs = info.module.structs.keys().collect::<Vec<_>>();
+            self.write_module(&mut out, info, &structs, true)?;
+        }
+        writeln!(out, "}}")?;
+
+        let mut dependencies: BTreeMap<Symbol, BTreeMap<&ModuleId, Vec<&Identifier>>> =
+            BTreeMap::new();
+        for (id, name) in &dependent_structs {
+            let package = self.module(id)?.package;
+            dependencies
+                .entry(package)
+                .or_default()
+                .entry(id)
+                .or_default()
+                .push(name);
+        }
+        for (package, modules) in dependencies {
+            writeln!(out)?;
+            writeln!(out, "pub mod {} {{", package_ident(package))?;
+            for (id, structs) in modules {
+                self.write_module(&mut out, self.module(id)?, &structs, false)?;
+            }
+            writeln!(out, "}}")?;
+        }
+        Ok(out)
+    }
+
+    fn root_modules(&self) -> impl Iterator<Item = &ModuleInfo> {
+        self.modules
+            .values()
+            .filter(|info| info.package == self.root_package)
+    }
+
+    fn module(&self, id: &ModuleId) -> anyhow::Result<&ModuleInfo> {
+        self.modules
+            .get(id)
+            .ok_or_else(|| anyhow!("Module {id} not found in package or its dependencies"))
+    }
+
+    fn struct_def(
+        &self,
+        address: &AccountAddress,
+        module: &Identifier,
+        name: &Identifier,
+    ) -> anyhow::Result<&normalized::Struct> {
+        let id = ModuleId::new(*address, module.clone());
+        self.module(&id)?
+            .module
+            .structs
+            .get(name)
+            .ok_or_else(|| anyhow!("Struct {name} not found in module {id}"))
+    }
+
+    /// The functions of a module that can be called from a programmable transaction.
+    fn callable_functions(
+        module: &normalized::Module,
+    ) -> impl Iterator<Item = (&Identifier, &normalized::Function)> {
+        module
+            .functions
+            .iter()
+            .filter(|(_, f)| f.visibility == Visibility::Public || f.is_entry)
+    }
+
+    /// The parameters of `f` given by a programmable transaction, i.e. omitting the `TxContext`
+    /// provided by the runtime.
+    fn call_parameters(f: &normalized::Function) -> &[Type] {
+        match f.parameters.split_last() {
+            Some((last, parameters)) if is_tx_context(last) => parameters,
+            _ => &f.parameters,
+        }
+    }
+
+    /// Add the structs of dependencies used by `ty`, and the structs they use in turn, to
+    /// `structs`.
+    fn collect_structs(
+        &self,
+        ty: &Type,
+        structs: &mut BTreeSet<(ModuleId, Identifier)>,
+    ) -> anyhow::Result<()> {
+        match ty {
+            Type::Vector(ty) | Type::Reference(ty) | Type::MutableReference(ty) => {
+                self.collect_structs(ty, structs)
+            }
+            Type::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => {
+                for ty in self.rust_type_arguments(address, module, name, type_arguments)? {
+                    self.collect_structs(ty, structs)?;
+                }
+                if known_type(address, module, name).is_some() {
+                    return Ok(());
+                }
+                let id = ModuleId::new(*address, module.clone());
+                if self.module(&id)?.package == self.root_package
+                    || !structs.insert((id, name.clone()))
+                {
+                    return Ok(());
+                }
+                for field in &self.struct_def(address, module, name)?.fields {
+                    self.collect_structs(&field.type_, structs)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The type arguments of a struct that are kept in its Rust version: phantom type parameters
+    /// are dropped, as they do not contribute to the layout of the struct.
+    fn rust_type_arguments<'t>(
+        &self,
+        address: &AccountAddress,
+        module: &Identifier,
+        name: &Identifier,
+        type_arguments: &'t [Type],
+    ) -> anyhow::Result<Vec<&'t Type>> {
+        if let Some(known) = known_type(address, module, name) {
+            return Ok(if known.generic {
+                type_arguments.iter().collect()
+            } else {
+                vec![]
+            });
+        }
+        let def = self.struct_def(address, module, name)?;
+        Ok(def
+            .type_parameters
+            .iter()
+            .zip(type_arguments)
+            .filter(|(param, _)| !param.is_phantom)
+            .map(|(_, ty)| ty)
+            .collect())
+    }
+
+    fn write_module(
+        &self,
+        out: &mut String,
+        info: &ModuleInfo,
+        structs: &[&Identifier],
+        with_functions: bool,
+    ) -> anyhow::Result<()> {
+        let module = &info.module;
+        let id = ModuleId::new(module.address, module.name.clone());
+        writeln!(out)?;
+        writeln!(out, "    #[allow(dead_code, clippy::too_many_arguments)]")?;
+        writeln!(out, "    pub mod {} {{", rust_ident(module.name.as_str()))?;
+
+        for name in structs {
+            let def = &module.structs[*name];
+            let type_parameters = def
+                .type_parameters
+                .iter()
+                .enumerate()
+                .filter(|(_, param)| !param.is_phantom)
+                .map(|(i, _)| format!("T{i}"))
+                .collect::<Vec<_>>();
+            writeln!(out)?;
+            writeln!(
+                out,
+                "        /// Rust version of the Move `{}::{}::{}` type.",
+                module.address.to_hex_literal(),
+                module.name,
+                name
+            )?;
+            writeln!(
+                out,
+                "        #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]"
+            )?;
+            writeln!(
+                out,
+                "        pub struct {}{} {{",
+                rust_ident(name.as_str()),
+                generics(&type_parameters)
+            )?;
+            for field in &def.fields {
+                writeln!(
+                    out,
+                    "            pub {}: {},",
+                    rust_ident(field.name.as_str()),
+                    self.rust_type(&field.type_, &id)?
+                )?;
+            }
+            writeln!(out, "        }}")?;
+        }
+
+        if with_functions {
+            for (name, f) in Self::callable_functions(module) {
+                self.write_function(out, &id, name, f)?;
+            }
+        }
+        writeln!(out, "    }}")?;
+        Ok(())
+    }
+
+    fn write_function(
+        &self,
+        out: &mut String,
+        id: &ModuleId,
+        name: &Identifier,
+        f: &normalized::Function,
+    ) -> anyhow::Result<()> {
+        let type_parameters = (0..f.type_parameters.len())
+            .map(|i| format!("T{i}"))
+            .collect::<Vec<_>>();
+        let move_parameters = f
+            .parameters
+            .iter()
+            .map(|ty| ty.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut parameters = vec![String::from(
+            "builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder",
+        )];
+        if !f.type_parameters.is_empty() {
+            parameters.push(format!(
+                "type_args: [::sui_types::TypeTag; {}]",
+                f.type_parameters.len()
+            ));
+        }
+        let mut arguments = vec![];
+        for (i, ty) in Self::call_parameters(f).iter().enumerate() {
+            let value = match ty {
+                Type::Reference(ty) | Type::MutableReference(ty) => &**ty,
+                ty => ty,
+            };
+            if is_pure(value) {
+                parameters.push(format!(
+                    "arg{i}: impl Into<super::super::PureArg<{}>>",
+                    self.rust_type(value, id)?
+                ));
+                arguments.push(format!("super::super::pure(builder, arg{i})?"));
+            } else {
+                parameters.push(format!("arg{i}: ::sui_types::transaction::Argument"));
+                arguments.push(format!("arg{i}"));
+            }
+        }
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "        /// Call `{}::{}{}({})`.",
+            id.name(),
+            name,
+            generics(&type_parameters),
+            move_parameters
+        )?;
+        writeln!(out, "        pub fn {}(", rust_ident(name.as_str()))?;
+        for parameter in parameters {
+            writeln!(out, "            {parameter},")?;
+        }
+        writeln!(
+            out,
+            "        ) -> ::anyhow::Result<::sui_types::transaction::Argument> {{"
+        )?;
+        writeln!(
+            out,
+            "            let arguments = vec![{}];",
+            arguments.join(", ")
+        )?;
+        writeln!(out, "            Ok(builder.programmable_move_call(")?;
+        writeln!(out, "                super::PACKAGE_ID,")?;
+        writeln!(
+            out,
+            "                ::sui_types::Identifier::new({:?})?,",
+            id.name().as_str()
+        )?;
+        writeln!(
+            out,
+            "                ::sui_types::Identifier::new({:?})?,",
+            name.as_str()
+        )?;
+        if f.type_parameters.is_empty() {
+            writeln!(out, "                vec![],")?;
+        } else {
+            writeln!(out, "                type_args.to_vec(),")?;
+        }
+        writeln!(out, "                arguments,")?;
+        writeln!(out, "            ))")?;
+        writeln!(out, "        }}")?;
+        Ok(())
+    }
+
+    /// The Rust version of `ty`, as used in module `current`.
+    fn rust_type(&self, ty: &Type, current: &ModuleId) -> anyhow::Result<String> {
+        Ok(match ty {
+            Type::Bool => "bool".to_string(),
+            Type::U8 => "u8".to_string(),
+            Type::U16 => "u16".to_string(),
+            Type::U32 => "u32".to_string(),
+            Type::U64 => "u64".to_string(),
+            Type::U128 => "u128".to_string(),
+            Type::U256 => "::move_core_types::u256::U256".to_string(),
+            Type::Address => "::sui_types::base_types::SuiAddress".to_string(),
+            Type::Vector(ty) => format!("::std::vec::Vec<{}>", self.rust_type(ty, current)?),
+            Type::TypeParameter(i) => format!("T{i}"),
+            Type::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => {
+                let type_arguments = self
+                    .rust_type_arguments(address, module, name, type_arguments)?
+                    .into_iter()
+                    .map(|ty| self.rust_type(ty, current))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                let path = if let Some(known) = known_type(address, module, name) {
+                    known.rust_path.to_string()
+                } else {
+                    let id = ModuleId::new(*address, module.clone());
+                    if &id == current {
+                        rust_ident(name.as_str())
+                    } else {
+                        format!(
+                            "super::super::{}::{}::{}",
+                            package_ident(self.module(&id)?.package),
+                            rust_ident(module.as_str()),
+                            rust_ident(name.as_str())
+                        )
+                    }
+                };
+                format!("{path}{}", generics(&type_arguments))
+            }
+            Type::Signer | Type::Reference(_) | Type::MutableReference(_) => {
+                bail!("Type {ty} has no Rust version")
+            }
+        })
+    }
+}
+
+/// A Move type with an existing Rust version.
+struct KnownType {
+    address: AccountAddress,
+    module: &'static str,
+    name: &'static str,
+    rust_path: &'static str,
+    /// Whether the Rust version keeps the type argument of the Move type.
+    generic: bool,
+}
+
+const KNOWN_TYPES: &[KnownType] = &[
+    KnownType {
+        address: MOVE_STDLIB_ADDRESS,
+        module: "string",
+        name: "String",
+        rust_path: "::std::string::String",
+        generic: false,
+    },
+    KnownType {
+        address: MOVE_STDLIB_ADDRESS,
+        module: "ascii",
+        name: "String",
+        rust_path: "::std::string::String",
+        generic: false,
+    },
+    KnownType {
+        address: MOVE_STDLIB_ADDRESS,
+        module: "option",
+        name: "Option",
+        rust_path: "::std::option::Option",
+        generic: true,
+    },
+    KnownType {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: "object",
+        name: "UID",
+        rust_path: "::sui_types::id::UID",
+        generic: false,
+    },
+    KnownType {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: "object",
+        name: "ID",
+        rust_path: "::sui_types::id::ID",
+        generic: false,
+    },
+    KnownType {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: "balance",
+        name: "Balance",
+        rust_path: "::sui_types::balance::Balance",
+        generic: false,
+    },
+    KnownType {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: "coin",
+        name: "Coin",
+        rust_path: "::sui_types::coin::Coin",
+        generic: false,
+    },
+];
+
+fn known_type(
+    address: &AccountAddress,
+    module: &Identifier,
+    name: &Identifier,
+) -> Option<&'static KnownType> {
+    KNOWN_TYPES.iter().find(|known| {
+        &known.address == address && module.as_str() == known.module && name.as_str() == known.name
+    })
+}
+
+/// Whether values of `ty` can be passed to a Move call as pure arguments.
+fn is_pure(ty: &Type) -> bool {
+    match ty {
+        Type::Bool
+        | Type::U8
+        | Type::U16
+        | Type::U32
+        | Type::U64
+        | Type::U128
+        | Type::U256
+        | Type::Address => true,
+        Type::Vector(ty) => is_pure(ty),
+        Type::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => match (address, module.as_str(), name.as_str()) {
+            (&MOVE_STDLIB_ADDRESS, "string" | "ascii", "String")
+            | (&SUI_FRAMEWORK_ADDRESS, "object", "ID") => true,
+            (&MOVE_STDLIB_ADDRESS, "option", "Option") => type_arguments.iter().all(is_pure),
+            _ => false,
+        },
+        Type::Signer | Type::TypeParameter(_) | Type::Reference(_) | Type::MutableReference(_) => {
+            false
+        }
+    }
+}
+
+fn is_tx_context(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(ty) | Type::MutableReference(ty) => matches!(
+            &**ty,
+            Type::Struct { address, module, name, .. }
+                if address == &SUI_FRAMEWORK_ADDRESS
+                    && module.as_str() == "tx_context"
+                    && name.as_str() == "TxContext"
+        ),
+        _ => false,
+    }
+}
+
+fn generics(arguments: &[String]) -> String {
+    if arguments.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", arguments.join(", "))
+    }
+}
+
+fn rust_ident(name: &str) -> String {
+    if RESERVED_KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else if RUST_KEYWORDS.contains(&name) {
+        format!("r#{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+/// The snake case name of the module holding the bindings of a package, e.g. `move_stdlib` for
+/// `MoveStdlib`.
+fn package_ident(package: Symbol) -> String {
+    let chars = package.as_str().chars().collect::<Vec<_>>();
+    let mut ident = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let after_lower =
+                i > 0 && (chars[i - 1].is_ascii_lowercase() || chars[i - 1].is_ascii_digit());
+            let before_lower = i > 0
+                && chars[i - 1].is_ascii_uppercase()
+                && chars.get(i + 1).map_or(false, |c| c.is_ascii_lowercase());
+            if after_lower || before_lower {
+                ident.push('_');
+            }
+            ident.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            ident.push(*c);
+        } else {
+            ident.push('_');
+        }
+    }
+    rust_ident(&ident)
+}
@@ -64,6 +64,7 @@ use crate::linters::{
 #[path = "unit_tests/build_tests.rs"]
 mod build_tests;
 
+pub mod bindings;
 pub mod linters;
 
 /// Wrapper around the core Move `CompiledPackage` with some Sui-specific traits and info
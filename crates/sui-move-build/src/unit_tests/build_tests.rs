@@ -3,7 +3,9 @@
 
 use std::path::Path;
 
-use crate::BuildConfig;
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+use crate::{bindings::generate_bindings, BuildConfig};
 
 #[test]
 fn generate_struct_layouts() {
@@ -28,3 +30,41 @@ fn generate_struct_layouts() {
         "0000000000000000000000000000000000000000000000000000000000000002::tx_context::TxContext"
     ));
 }
+
+#[test]
+fn generate_bindings() {
+    // build the Sui framework and generate its bindings to make sure nothing crashes
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+        .join("sui-framework")
+        .join("packages")
+        .join("sui-framework");
+    let pkg = BuildConfig::new_for_testing().build(path).unwrap();
+    let bindings = generate_bindings(&pkg, SUI_FRAMEWORK_ADDRESS.into()).unwrap();
+    // check for a couple of modules, structs and functions that aren't likely to go away
+    assert!(bindings.contains("pub mod sui {"));
+    assert!(bindings.contains("pub mod coin {"));
+    assert!(bindings.contains("pub struct TreasuryCap {"));
+    assert!(bindings.contains("pub fn split("));
+    // known types are mapped to their existing Rust versions
+    assert!(bindings.contains("pub balance: ::sui_types::balance::Balance,"));
+    // dependencies only contain the structs used by the package
+    assert!(bindings.contains("pub mod move_stdlib {"));
+    assert!(bindings.contains("pub struct TypeName {"));
+    assert!(!bindings.contains("pub fn utf8("));
+
+    // the bindings must compile against the crates they refer to
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sui_framework_bindings.rs");
+    std::fs::write(
+        &path,
+        format!("#![allow(dead_code)]\n\nmod bindings {{\n{bindings}}}\n\nfn main() {{}}\n"),
+    )
+    .unwrap();
+    let cases = trybuild::TestCases::new();
+    cases.pass(&path);
+    // the test cases are compiled when dropped, while the file still exists
+    drop(cases);
+}
@@ -0,0 +1,56 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::build::resolve_lock_file_path;
+use anyhow::bail;
+use clap::Parser;
+use move_cli::base;
+use move_package::BuildConfig as MoveBuildConfig;
+use std::{fs, path::PathBuf};
+use sui_move_build::{bindings::generate_bindings, BuildConfig};
+use sui_types::base_types::ObjectID;
+
+#[derive(Parser)]
+#[group(id = "sui-move-generate-bindings")]
+pub struct GenerateBindings {
+    /// File to write the generated Rust bindings to, printed to stdout if not provided
+    #[clap(long, short = 'o')]
+    pub output: Option<PathBuf>,
+    /// ID of the package the generated functions call, the `published-at` address of the
+    /// package manifest if not provided
+    #[clap(long)]
+    pub package_id: Option<ObjectID>,
+}
+
+impl GenerateBindings {
+    pub fn execute(
+        &self,
+        path: Option<PathBuf>,
+        build_config: MoveBuildConfig,
+    ) -> anyhow::Result<()> {
+        let rerooted_path = base::reroot_path(path.clone())?;
+        let build_config = resolve_lock_file_path(build_config, path)?;
+        let pkg = BuildConfig {
+            config: build_config,
+            run_bytecode_verifier: false,
+            print_diags_to_stderr: true,
+            lint: false,
+        }
+        .build(rerooted_path)?;
+
+        let package_id = match (self.package_id, &pkg.published_at) {
+            (Some(package_id), _) => package_id,
+            (None, Ok(published_at)) => *published_at,
+            (None, Err(_)) => bail!(
+                "Package {} has no valid published-at address, pass the ID to call it at with --package-id",
+                pkg.package.compiled_package_info.package_name
+            ),
+        };
+        let bindings = generate_bindings(&pkg, package_id)?;
+        match &self.output {
+            Some(output) => fs::write(output, bindings)?,
+            None => print!("{bindings}"),
+        }
+        Ok(())
+    }
+}
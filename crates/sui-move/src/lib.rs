@@ -15,6 +15,8 @@ pub mod build;
 pub mod coverage;
 #[cfg(feature = "disassemble")]
 pub mod disassemble;
+#[cfg(feature = "generate_bindings")]
+pub mod generate_bindings;
 pub mod new;
 #[cfg(feature = "prove")]
 pub mod prove;
@@ -29,6 +31,8 @@ pub enum Command {
     Coverage(coverage::Coverage),
     #[cfg(feature = "disassemble")]
     Disassemble(disassemble::Disassemble),
+    #[cfg(feature = "generate_bindings")]
+    GenerateBindings(generate_bindings::GenerateBindings),
     New(new::New),
     #[cfg(feature = "prove")]
     Prove(prove::Prover),
@@ -55,6 +59,8 @@ pub fn execute_move_command(
         Command::Coverage(c) => c.execute(package_path, build_config),
         #[cfg(feature = "disassemble")]
         Command::Disassemble(c) => c.execute(package_path, build_config),
+        #[cfg(feature = "generate_bindings")]
+        Command::GenerateBindings(c) => c.execute(package_path, build_config),
         Command::New(c) => c.execute(package_path),
         #[cfg(feature = "prove")]
         Command::Prove(c) => c.execute(package_path, build_config),
@@ -67,11 +67,13 @@ use sui_core::consensus_adapter::{
 };
 use sui_core::consensus_handler::ConsensusHandler;
 use sui_core::consensus_validator::{SuiTxValidator, SuiTxValidatorMetrics};
+use sui_core::db_backup_handler::DBBackupHandler;
 use sui_core::db_checkpoint_handler::DBCheckpointHandler;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_core::epoch::data_removal::EpochDataRemover;
 use sui_core::epoch::epoch_metrics::EpochMetrics;
 use sui_core::epoch::reconfiguration::ReconfigurationInitiator;
+use sui_core::historical_object_reader::HistoricalObjectReader;
 use sui_core::module_cache_metrics::ResolverMetrics;
 use sui_core::narwhal_manager::{NarwhalConfiguration, NarwhalManager, NarwhalManagerMetrics};
 use sui_core::signature_verifier::SignatureVerifierMetrics;
@@ -103,6 +105,7 @@ use sui_storage::{
     http_key_value_store::HttpKVStore,
     key_value_store::{FallbackTransactionKVStore, TransactionKeyValueStore},
     key_value_store_metrics::KeyValueStoreMetrics,
+    object_store_key_value_store::ObjectStoreKVStore,
 };
 use sui_storage::{FileCompression, IndexStore, StorageFormat};
 use sui_types::base_types::{AuthorityName, EpochId};
@@ -167,6 +170,7 @@ pub struct SuiNode {
     trusted_peer_change_tx: watch::Sender<TrustedPeerChangeEvent>,
 
     _db_checkpoint_handle: Option<tokio::sync::broadcast::Sender<()>>,
+    _db_backup_handle: Option<tokio::sync::broadcast::Sender<()>>,
 
     #[cfg(msim)]
     sim_state: SimState,
@@ -488,7 +492,7 @@ impl SuiNode {
             config.certificate_deny_config.clone(),
             config.indirect_objects_threshold,
             config.state_debug_dump_config.clone(),
-            archive_readers,
+            archive_readers.clone(),
         )
         .await;
         // ensure genesis txn was executed
@@ -509,6 +513,9 @@ impl SuiNode {
                 .unwrap();
         }
 
+        // Start backing up the databases to remote store
+        let db_backup_handle = Self::start_db_backup(&config, state.clone(), &prometheus_registry)?;
+
         if config
             .expensive_safety_check_config
             .enable_secondary_index_checks()
@@ -540,6 +547,7 @@ impl SuiNode {
             &transaction_orchestrator.clone(),
             &config,
             &prometheus_registry,
+            archive_readers,
             custom_rpc_runtime,
         )?;
 
@@ -612,6 +620,7 @@ impl SuiNode {
             trusted_peer_change_tx,
 
             _db_checkpoint_handle: db_checkpoint_handle,
+            _db_backup_handle: db_backup_handle,
 
             #[cfg(msim)]
             sim_state: SimState {
@@ -779,6 +788,25 @@ impl SuiNode {
         }
     }
 
+    fn start_db_backup(
+        config: &NodeConfig,
+        state: Arc<AuthorityState>,
+        prometheus_registry: &Registry,
+    ) -> Result<Option<tokio::sync::broadcast::Sender<()>>> {
+        match &config.db_backup_config {
+            Some(db_backup_config) => {
+                let handler = DBBackupHandler::new(
+                    state,
+                    db_backup_config,
+                    config.db_backup_staging_path(),
+                    prometheus_registry,
+                )?;
+                Ok(Some(DBBackupHandler::start(handler)))
+            }
+            None => Ok(None),
+        }
+    }
+
     fn create_p2p_network(
         config: &NodeConfig,
         state_sync_store: RocksDbStore,
@@ -1497,6 +1525,18 @@ fn build_kv_store(
     let metrics = KeyValueStoreMetrics::new(registry);
     let db_store = TransactionKeyValueStore::new("rocksdb", metrics.clone(), state.clone());
 
+    if let Some(object_store_config) = &config.transaction_kv_store_read_config.object_store_config
+    {
+        let object_store = ObjectStoreKVStore::new_kv(object_store_config, metrics.clone())?;
+        info!("using local key-value store with fallback to object store key-value store");
+        return Ok(Arc::new(FallbackTransactionKVStore::new_kv(
+            db_store,
+            object_store,
+            metrics,
+            "json_rpc_fallback",
+        )));
+    }
+
     let base_url = &config.transaction_kv_store_read_config.base_url;
 
     if base_url.is_empty() {
@@ -1536,6 +1576,7 @@ pub fn build_http_server(
     transaction_orchestrator: &Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
     config: &NodeConfig,
     prometheus_registry: &Registry,
+    archive_readers: ArchiveReaderBalancer,
     _custom_runtime: Option<Handle>,
 ) -> Result<Option<tokio::task::JoinHandle<()>>> {
     // Validators do not expose these APIs
@@ -1551,11 +1592,17 @@ pub fn build_http_server(
         let kv_store = build_kv_store(&state, config, prometheus_registry)?;
 
         let metrics = Arc::new(JsonRpcMetrics::new(prometheus_registry));
-        server.register_module(ReadApi::new(
-            state.clone(),
-            kv_store.clone(),
-            metrics.clone(),
-        ))?;
+        let mut read_api = ReadApi::new(state.clone(), kv_store.clone(), metrics.clone());
+        if let Some(historical_object_read_config) = &config.historical_object_read_config {
+            read_api =
+                read_api.with_historical_object_reader(Arc::new(HistoricalObjectReader::new(
+                    state.clone(),
+                    kv_store.clone(),
+                    archive_readers,
+                    historical_object_read_config,
+                )));
+        }
+        server.register_module(read_api)?;
         server.register_module(CoinReadApi::new(
             state.clone(),
             kv_store.clone(),
@@ -1590,7 +1637,7 @@ pub fn build_http_server(
     router = router.merge(json_rpc_router);
 
     if config.enable_experimental_rest_api {
-        let rest_router = sui_rest_api::rest_router(state);
+        let rest_router = sui_rest_api::rest_router(state, transaction_orchestrator.clone());
         router = router.nest("/rest", rest_router);
     }
 
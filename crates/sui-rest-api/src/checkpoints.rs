@@ -8,9 +8,12 @@ use std::{
 
 use anyhow::Result;
 use axum::{
-    extract::{Path, State},
+    body::StreamBody,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
     Json, TypedHeader,
 };
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use sui_core::authority::AuthorityState;
 use sui_types::{
@@ -28,6 +31,15 @@ use crate::{headers::Accept, AppError, Bcs};
 pub const GET_LATEST_CHECKPOINT_PATH: &str = "/checkpoints";
 pub const GET_CHECKPOINT_PATH: &str = "/checkpoints/:checkpoint";
 pub const GET_FULL_CHECKPOINT_PATH: &str = "/checkpoints/:checkpoint/full";
+pub const GET_FULL_CHECKPOINT_RANGE_PATH: &str = "/checkpoints/range";
+
+/// The most checkpoints that are streamed in response to a single range request.
+pub const MAX_CHECKPOINT_RANGE_SIZE: u64 = 1000;
+
+const FRAME_HEADER_LEN: usize = std::mem::size_of::<u32>();
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+/// How many checkpoints of a range are read from the database ahead of the client.
+const CHECKPOINT_RANGE_READ_CONCURRENCY: usize = 4;
 
 pub async fn get_full_checkpoint(
     //TODO support digest as well as sequence number
@@ -36,9 +48,16 @@ pub async fn get_full_checkpoint(
     State(state): State<Arc<AuthorityState>>,
 ) -> Result<Bcs<CheckpointData>, AppError> {
     if accept.as_str() != crate::APPLICATION_BCS {
-        return Err(AppError(anyhow::anyhow!("invalid accept type")));
+        return Err(anyhow::anyhow!("invalid accept type").into());
     }
 
+    Ok(Bcs(load_full_checkpoint(&state, checkpoint_id)?))
+}
+
+fn load_full_checkpoint(
+    state: &AuthorityState,
+    checkpoint_id: CheckpointSequenceNumber,
+) -> Result<CheckpointData> {
     let verified_summary = state.get_verified_checkpoint_by_sequence_number(checkpoint_id)?;
     let checkpoint_contents = state.get_checkpoint_contents(verified_summary.content_digest)?;
 
@@ -159,11 +178,123 @@ pub async fn get_full_checkpoint(
         full_transactions.push(full_transaction);
     }
 
-    Ok(Bcs(CheckpointData {
+    Ok(CheckpointData {
         checkpoint_summary: verified_summary.into(),
         checkpoint_contents,
         transactions: full_transactions,
-    }))
+    })
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct CheckpointRange {
+    /// The first checkpoint in the range.
+    pub start: CheckpointSequenceNumber,
+    /// The checkpoint after the last one in the range.
+    pub end: CheckpointSequenceNumber,
+}
+
+/// Streams the full contents of the checkpoints in `[start, end)` as length-delimited BCS frames,
+/// optionally compressed with zstd. The range is truncated to `MAX_CHECKPOINT_RANGE_SIZE`
+/// checkpoints and to the latest checkpoint known to this node, so clients should keep requesting
+/// from the checkpoint after the last one they receive.
+pub async fn get_full_checkpoint_range(
+    Query(range): Query<CheckpointRange>,
+    accept: Option<TypedHeader<Accept>>,
+    State(state): State<Arc<AuthorityState>>,
+) -> Result<Response, AppError> {
+    let compressed = negotiate_stream_format(accept.as_ref().map(|TypedHeader(a)| a.as_str()))
+        .ok_or_else(|| AppError::from(anyhow::anyhow!("invalid accept type")))?;
+    let content_type = if compressed {
+        crate::APPLICATION_BCS_STREAM_ZSTD
+    } else {
+        crate::APPLICATION_BCS_STREAM
+    };
+
+    let latest = state.get_latest_checkpoint_sequence_number()?;
+    let end = range
+        .end
+        .min(range.start.saturating_add(MAX_CHECKPOINT_RANGE_SIZE))
+        .min(latest.saturating_add(1));
+
+    // Reading a checkpoint blocks on the database, so do it off of the async runtime.
+    let frames = futures::stream::iter(range.start..end)
+        .map(move |checkpoint_id| {
+            let state = state.clone();
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    let checkpoint = load_full_checkpoint(&state, checkpoint_id)?;
+                    encode_frame(&checkpoint, compressed)
+                })
+                .await?
+            }
+        })
+        .buffered(CHECKPOINT_RANGE_READ_CONCURRENCY);
+
+    Ok((
+        [(
+            axum::http::header::CONTENT_TYPE,
+            axum::http::HeaderValue::from_static(content_type),
+        )],
+        StreamBody::new(frames),
+    )
+        .into_response())
+}
+
+/// Picks the stream format to respond with given the value of an `Accept` header, returning
+/// whether frames should be compressed, or `None` if no acceptable format is supported. Media
+/// ranges are considered in the order they are listed, ignoring quality values, and a missing
+/// header or a wildcard selects the uncompressed format.
+fn negotiate_stream_format(accept: Option<&str>) -> Option<bool> {
+    let Some(accept) = accept else {
+        return Some(false);
+    };
+
+    accept.split(',').find_map(|media_range| {
+        let media_type = media_range.split(';').next()?.trim().to_ascii_lowercase();
+        match media_type.as_str() {
+            crate::APPLICATION_BCS_STREAM_ZSTD => Some(true),
+            crate::APPLICATION_BCS_STREAM | "application/*" | "*/*" => Some(false),
+            _ => None,
+        }
+    })
+}
+
+/// Serializes `checkpoint` as BCS, optionally compresses it, and prefixes it with its length as a
+/// big-endian `u32`.
+fn encode_frame(checkpoint: &CheckpointData, compressed: bool) -> Result<Vec<u8>> {
+    let mut payload = bcs::to_bytes(checkpoint)?;
+    if compressed {
+        payload = zstd::encode_all(payload.as_slice(), ZSTD_COMPRESSION_LEVEL)?;
+    }
+
+    let len = u32::try_from(payload.len())?;
+    let mut frame = Vec::with_capacity(FRAME_HEADER_LEN + payload.len());
+    frame.extend_from_slice(&len.to_be_bytes());
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+/// Removes the first complete frame from the front of `buf` and decodes it, or returns `None` if
+/// `buf` does not hold a complete frame yet.
+pub(crate) fn decode_frame(buf: &mut Vec<u8>, compressed: bool) -> Result<Option<CheckpointData>> {
+    let Some(header) = buf.get(..FRAME_HEADER_LEN) else {
+        return Ok(None);
+    };
+    let len = u32::from_be_bytes(header.try_into()?) as usize;
+    if buf.len() < FRAME_HEADER_LEN + len {
+        return Ok(None);
+    }
+
+    let frame: Vec<u8> = buf
+        .drain(..FRAME_HEADER_LEN + len)
+        .skip(FRAME_HEADER_LEN)
+        .collect();
+    let checkpoint = if compressed {
+        bcs::from_bytes(&zstd::decode_all(frame.as_slice())?)?
+    } else {
+        bcs::from_bytes(&frame)?
+    };
+    Ok(Some(checkpoint))
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -204,3 +335,79 @@ pub async fn get_checkpoint(
     let verified_summary = state.get_verified_checkpoint_by_sequence_number(checkpoint_id)?;
     Ok(Json(verified_summary.into()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::{ExecutionDigests, ObjectID};
+    use sui_types::committee::Committee;
+    use sui_types::utils::{create_fake_transaction, mock_certified_checkpoint};
+
+    fn checkpoint(sequence_number: CheckpointSequenceNumber) -> CheckpointData {
+        let (committee, keys) = Committee::new_simple_test_committee();
+        CheckpointData {
+            checkpoint_summary: mock_certified_checkpoint(keys.iter(), committee, sequence_number),
+            checkpoint_contents: CheckpointContents::new_with_causally_ordered_transactions(
+                [ExecutionDigests::random()].into_iter(),
+            ),
+            transactions: vec![CheckpointTransaction {
+                transaction: create_fake_transaction(),
+                effects: TransactionEffects::default(),
+                events: None,
+                input_objects: vec![Object::immutable_with_id_for_testing(ObjectID::random())],
+                output_objects: vec![],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_frame_round_trip() {
+        for compressed in [false, true] {
+            let checkpoints = [checkpoint(1), checkpoint(2)];
+            let mut stream = Vec::new();
+            for checkpoint in &checkpoints {
+                stream.extend(encode_frame(checkpoint, compressed).unwrap());
+            }
+
+            // Feed the stream in small chunks, as it would arrive over the network.
+            let mut buf = Vec::new();
+            let mut decoded = Vec::new();
+            for chunk in stream.chunks(7) {
+                buf.extend_from_slice(chunk);
+                while let Some(checkpoint) = decode_frame(&mut buf, compressed).unwrap() {
+                    decoded.push(checkpoint);
+                }
+            }
+
+            assert!(buf.is_empty());
+            assert_eq!(decoded.len(), checkpoints.len());
+            for (expected, actual) in checkpoints.iter().zip(&decoded) {
+                assert_eq!(
+                    bcs::to_bytes(expected).unwrap(),
+                    bcs::to_bytes(actual).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_negotiate_stream_format() {
+        assert_eq!(negotiate_stream_format(None), Some(false));
+        assert_eq!(
+            negotiate_stream_format(Some(crate::APPLICATION_BCS_STREAM_ZSTD)),
+            Some(true)
+        );
+        assert_eq!(
+            negotiate_stream_format(Some(
+                "application/json, application/x-bcs-stream;q=0.9, */*;q=0.1"
+            )),
+            Some(false)
+        );
+        assert_eq!(
+            negotiate_stream_format(Some("Application/X-BCS-Stream+ZSTD; q=1")),
+            Some(true)
+        );
+        assert_eq!(negotiate_stream_format(Some("*/*")), Some(false));
+        assert_eq!(negotiate_stream_format(Some(crate::APPLICATION_BCS)), None);
+    }
+}
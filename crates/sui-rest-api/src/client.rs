@@ -1,12 +1,21 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::ops::Range;
+
 use anyhow::Result;
+use futures::Stream;
 use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::digests::TransactionDigest;
 use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
 use sui_types::object::Object;
+use sui_types::quorum_driver_types::ExecuteTransactionResponse;
+use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
+use sui_types::transaction::Transaction;
 
-use crate::checkpoints::CheckpointData;
+use crate::checkpoints::{decode_frame, CheckpointData};
+use crate::transactions::TransactionResponse;
 
 #[derive(Clone)]
 pub struct Client {
@@ -44,49 +53,206 @@ impl Client {
             self.base_url
         );
 
-        let bytes = self
+        self.get_bcs(url).await
+    }
+
+    /// Streams the full contents of the checkpoints in `range`. The server may end the stream
+    /// early, either because the range is longer than `MAX_CHECKPOINT_RANGE_SIZE` or because it
+    /// has not seen all of the checkpoints yet. Returns `None` if the server does not support
+    /// range requests.
+    pub async fn get_full_checkpoint_range(
+        &self,
+        range: Range<CheckpointSequenceNumber>,
+        compressed: bool,
+    ) -> Result<Option<impl Stream<Item = Result<CheckpointData>>>> {
+        let url = format!(
+            "{}/checkpoints/range?start={}&end={}",
+            self.base_url, range.start, range.end
+        );
+        let accept = if compressed {
+            crate::APPLICATION_BCS_STREAM_ZSTD
+        } else {
+            crate::APPLICATION_BCS_STREAM
+        };
+
+        let response = self
             .inner
             .get(url)
-            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .header(reqwest::header::ACCEPT, accept)
             .send()
-            .await?
-            .bytes()
             .await?;
 
-        bcs::from_bytes(&bytes).map_err(Into::into)
+        // Servers without the range endpoint route the request to the single checkpoint endpoint,
+        // which rejects `range` as a sequence number.
+        if matches!(
+            response.status(),
+            reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::BAD_REQUEST
+        ) {
+            return Ok(None);
+        }
+        let response = response.error_for_status()?;
+
+        let stream = futures::stream::try_unfold(
+            (response, Vec::new()),
+            move |(mut response, mut buf)| async move {
+                loop {
+                    if let Some(checkpoint) = decode_frame(&mut buf, compressed)? {
+                        return Ok(Some((checkpoint, (response, buf))));
+                    }
+                    match response.chunk().await? {
+                        Some(chunk) => buf.extend_from_slice(&chunk),
+                        None if buf.is_empty() => return Ok(None),
+                        None => anyhow::bail!("checkpoint stream ended part way through a frame"),
+                    }
+                }
+            },
+        );
+        Ok(Some(stream))
     }
 
     pub async fn get_object(&self, object_id: ObjectID) -> Result<Object> {
         let url = format!("{}/objects/{object_id}", self.base_url);
 
+        self.get_bcs(url).await
+    }
+
+    pub async fn get_object_with_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Object> {
+        let url = format!("{}/objects/{object_id}/version/{version}", self.base_url);
+
+        self.get_bcs(url).await
+    }
+
+    pub async fn get_transaction(
+        &self,
+        transaction_digest: &TransactionDigest,
+    ) -> Result<TransactionResponse> {
+        let url = format!("{}/transactions/{transaction_digest}", self.base_url);
+
+        self.get_bcs(url).await
+    }
+
+    pub async fn execute_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<ExecuteTransactionResponse> {
+        let url = format!("{}/transactions", self.base_url);
+        let body = bcs::to_bytes(transaction)?;
+
         let bytes = self
             .inner
-            .get(url)
+            .post(url)
             .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .header(reqwest::header::CONTENT_TYPE, crate::APPLICATION_BCS)
+            .body(body)
             .send()
             .await?
+            .error_for_status()?
             .bytes()
             .await?;
 
         bcs::from_bytes(&bytes).map_err(Into::into)
     }
 
-    pub async fn get_object_with_version(
-        &self,
-        object_id: ObjectID,
-        version: SequenceNumber,
-    ) -> Result<Object> {
-        let url = format!("{}/objects/{object_id}/version/{version}", self.base_url);
+    pub async fn get_latest_committee(&self) -> Result<Committee> {
+        let url = format!("{}/committee", self.base_url);
 
+        self.get_bcs(url).await
+    }
+
+    pub async fn get_committee(&self, epoch: EpochId) -> Result<Committee> {
+        let url = format!("{}/committee/{epoch}", self.base_url);
+
+        self.get_bcs(url).await
+    }
+
+    pub async fn get_system_state_summary(&self) -> Result<SuiSystemStateSummary> {
+        let url = format!("{}/system", self.base_url);
+        let system_state = self
+            .inner
+            .get(url)
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_JSON)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(system_state)
+    }
+
+    async fn get_bcs<T: serde::de::DeserializeOwned>(&self, url: String) -> Result<T> {
         let bytes = self
             .inner
             .get(url)
             .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
             .send()
             .await?
+            .error_for_status()?
             .bytes()
             .await?;
 
         bcs::from_bytes(&bytes).map_err(Into::into)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transactions::tests::state_with_transaction;
+    use std::sync::Arc;
+    use sui_core::authority::AuthorityState;
+    use sui_types::utils::create_fake_transaction;
+
+    fn serve(state: Arc<AuthorityState>) -> Client {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = crate::rest_router(state, None);
+        tokio::spawn(
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service()),
+        );
+        Client::new(url)
+    }
+
+    fn status(err: &anyhow::Error) -> Option<reqwest::StatusCode> {
+        err.downcast_ref::<reqwest::Error>()
+            .and_then(|err| err.status())
+    }
+
+    #[tokio::test]
+    async fn test_client() {
+        let (state, digest) = state_with_transaction().await;
+        let client = serve(state.clone());
+
+        let transaction = client.get_transaction(&digest).await.unwrap();
+        assert_eq!(transaction.digest, digest);
+        let err = client
+            .get_transaction(&TransactionDigest::random())
+            .await
+            .unwrap_err();
+        assert_eq!(status(&err), Some(reqwest::StatusCode::NOT_FOUND));
+
+        // The node serving the API does not execute transactions
+        let err = client
+            .execute_transaction(&create_fake_transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(status(&err), Some(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+
+        let committee = state.clone_committee_for_testing();
+        assert_eq!(client.get_latest_committee().await.unwrap(), committee);
+        assert_eq!(
+            client.get_committee(committee.epoch).await.unwrap(),
+            committee
+        );
+        let err = client.get_committee(committee.epoch + 1).await.unwrap_err();
+        assert_eq!(status(&err), Some(reqwest::StatusCode::NOT_FOUND));
+
+        let system_state = client.get_system_state_summary().await.unwrap();
+        assert_eq!(system_state.epoch, committee.epoch);
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    TypedHeader,
+};
+use sui_core::authority::AuthorityState;
+use sui_types::{committee::Committee, committee::EpochId};
+
+use crate::{headers::Accept, AppError, ResponseContent};
+
+pub const GET_LATEST_COMMITTEE_PATH: &str = "/committee";
+pub const GET_COMMITTEE_PATH: &str = "/committee/:epoch";
+
+pub async fn get_latest_committee(
+    accept: Option<TypedHeader<Accept>>,
+    State(state): State<Arc<AuthorityState>>,
+) -> Result<ResponseContent<Committee>, AppError> {
+    let committee = state
+        .load_epoch_store_one_call_per_task()
+        .committee()
+        .as_ref()
+        .clone();
+
+    Ok(ResponseContent::new(accept, committee))
+}
+
+pub async fn get_committee(
+    Path(epoch): Path<EpochId>,
+    accept: Option<TypedHeader<Accept>>,
+    State(state): State<Arc<AuthorityState>>,
+) -> Result<ResponseContent<Committee>, AppError> {
+    let committee = state
+        .committee_store()
+        .get_committee(&epoch)?
+        .ok_or_else(|| AppError::not_found("committee"))?;
+
+    Ok(ResponseContent::new(accept, committee.as_ref().clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use sui_core::authority::test_authority_builder::TestAuthorityBuilder;
+
+    #[tokio::test]
+    async fn test_get_committee() {
+        let state = TestAuthorityBuilder::new().build().await;
+        let expected = state.clone_committee_for_testing();
+
+        let response =
+            get_latest_committee(Some(TypedHeader(Accept::bcs())), State(state.clone())).await;
+        let Ok(ResponseContent::Bcs(committee)) = response else {
+            panic!("expected a BCS response");
+        };
+        assert_eq!(committee, expected);
+
+        let response = get_committee(Path(expected.epoch), None, State(state.clone())).await;
+        let Ok(ResponseContent::Json(committee)) = response else {
+            panic!("expected a JSON response");
+        };
+        assert_eq!(committee, expected);
+
+        let response = get_committee(Path(expected.epoch + 1), None, State(state))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -1,15 +1,33 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use axum::{http::StatusCode, routing::get, Router};
+use std::sync::Arc;
+
+use axum::{
+    async_trait,
+    body::{Bytes, HttpBody},
+    extract::{FromRef, FromRequest},
+    http::{Request, StatusCode},
+    routing::{get, post},
+    BoxError, Router, TypedHeader,
+};
+use sui_core::authority::AuthorityState;
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 
 mod checkpoints;
 mod client;
+mod committee;
 pub mod headers;
 mod objects;
+mod system;
+mod transactions;
 
-pub use checkpoints::{CheckpointData, CheckpointTransaction};
+pub use checkpoints::{
+    CheckpointData, CheckpointRange, CheckpointTransaction, MAX_CHECKPOINT_RANGE_SIZE,
+};
 pub use client::Client;
+pub use transactions::TransactionResponse;
 
 async fn health_check() -> StatusCode {
     StatusCode::OK
@@ -20,6 +38,8 @@ pub struct Bcs<T>(pub T);
 pub const TEXT_PLAIN_UTF_8: &str = "text/plain; charset=utf-8";
 pub const APPLICATION_BCS: &str = "application/bcs";
 pub const APPLICATION_JSON: &str = "application/json";
+pub const APPLICATION_BCS_STREAM: &str = "application/x-bcs-stream";
+pub const APPLICATION_BCS_STREAM_ZSTD: &str = "application/x-bcs-stream+zstd";
 
 impl<T> axum::response::IntoResponse for Bcs<T>
 where
@@ -48,13 +68,99 @@ where
     }
 }
 
-pub fn rest_router(state: std::sync::Arc<sui_core::authority::AuthorityState>) -> Router {
+#[async_trait]
+impl<T, S, B> FromRequest<S, B> for Bcs<T>
+where
+    T: serde::de::DeserializeOwned,
+    S: Send + Sync,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        let is_bcs = req
+            .headers()
+            .get(axum::http::header::CONTENT_TYPE)
+            .is_some_and(|value| value.as_bytes() == APPLICATION_BCS.as_bytes());
+        if !is_bcs {
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!("expected content type {APPLICATION_BCS}"),
+            ));
+        }
+
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(|e| (e.status(), e.to_string()))?;
+        bcs::from_bytes(&bytes)
+            .map(Self)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))
+    }
+}
+
+/// A response that is encoded as BCS or JSON, depending on the request's `Accept` header. JSON is
+/// used when the client does not ask for BCS.
+pub enum ResponseContent<T> {
+    Bcs(T),
+    Json(T),
+}
+
+impl<T> ResponseContent<T> {
+    pub fn new(accept: Option<TypedHeader<headers::Accept>>, inner: T) -> Self {
+        match accept {
+            Some(TypedHeader(accept)) if accept.as_str() == APPLICATION_BCS => Self::Bcs(inner),
+            _ => Self::Json(inner),
+        }
+    }
+}
+
+impl<T> axum::response::IntoResponse for ResponseContent<T>
+where
+    T: serde::Serialize,
+{
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ResponseContent::Bcs(inner) => Bcs(inner).into_response(),
+            ResponseContent::Json(inner) => axum::Json(inner).into_response(),
+        }
+    }
+}
+
+/// State shared by all of the routes. Handlers extract the parts they need through `FromRef`.
+#[derive(Clone)]
+pub struct RestState {
+    state: Arc<AuthorityState>,
+    transaction_orchestrator: Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
+}
+
+impl FromRef<RestState> for Arc<AuthorityState> {
+    fn from_ref(state: &RestState) -> Self {
+        state.state.clone()
+    }
+}
+
+impl FromRef<RestState> for Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>> {
+    fn from_ref(state: &RestState) -> Self {
+        state.transaction_orchestrator.clone()
+    }
+}
+
+pub fn rest_router(
+    state: Arc<AuthorityState>,
+    transaction_orchestrator: Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
+) -> Router {
     Router::new()
         .route("/", get(health_check))
         .route(
             checkpoints::GET_FULL_CHECKPOINT_PATH,
             get(checkpoints::get_full_checkpoint),
         )
+        .route(
+            checkpoints::GET_FULL_CHECKPOINT_RANGE_PATH,
+            get(checkpoints::get_full_checkpoint_range),
+        )
         .route(
             checkpoints::GET_CHECKPOINT_PATH,
             get(checkpoints::get_checkpoint),
@@ -68,14 +174,32 @@ pub fn rest_router(state: std::sync::Arc<sui_core::authority::AuthorityState>) -
             objects::GET_OBJECT_WITH_VERSION_PATH,
             get(objects::get_object_with_version),
         )
-        .with_state(state)
+        .route(
+            transactions::GET_TRANSACTION_PATH,
+            get(transactions::get_transaction),
+        )
+        .route(
+            transactions::POST_EXECUTE_TRANSACTION_PATH,
+            post(transactions::execute_transaction),
+        )
+        .route(
+            committee::GET_LATEST_COMMITTEE_PATH,
+            get(committee::get_latest_committee),
+        )
+        .route(committee::GET_COMMITTEE_PATH, get(committee::get_committee))
+        .route(system::GET_SYSTEM_STATE_PATH, get(system::get_system_state))
+        .with_state(RestState {
+            state,
+            transaction_orchestrator,
+        })
 }
 
 pub async fn start_service(
     socket_address: std::net::SocketAddr,
-    state: std::sync::Arc<sui_core::authority::AuthorityState>,
+    state: Arc<AuthorityState>,
+    transaction_orchestrator: Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
 ) {
-    let app = rest_router(state);
+    let app = rest_router(state, transaction_orchestrator);
 
     axum::Server::bind(&socket_address)
         .serve(app.into_make_service())
@@ -83,17 +207,24 @@ pub async fn start_service(
         .unwrap();
 }
 
-// Make our own error that wraps `anyhow::Error`.
-pub struct AppError(anyhow::Error);
+/// An error response: the status code to respond with and the error that caused it. Errors that
+/// are converted with `?` are internal errors.
+pub struct AppError(StatusCode, anyhow::Error);
+
+impl AppError {
+    pub fn new(status: StatusCode, error: impl Into<anyhow::Error>) -> Self {
+        Self(status, error.into())
+    }
+
+    pub fn not_found(what: &str) -> Self {
+        Self(StatusCode::NOT_FOUND, anyhow::anyhow!("{what} not found"))
+    }
+}
 
 // Tell axum how to convert `AppError` into a response.
 impl axum::response::IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Something went wrong: {}", self.0),
-        )
-            .into_response()
+        (self.0, format!("Something went wrong: {}", self.1)).into_response()
     }
 }
 
@@ -104,6 +235,6 @@ where
     E: Into<anyhow::Error>,
 {
     fn from(err: E) -> Self {
-        Self(err.into())
+        Self(StatusCode::INTERNAL_SERVER_ERROR, err.into())
     }
 }
@@ -22,7 +22,7 @@ pub async fn get_object(
     let object = state
         .database
         .get_object(&object_id)?
-        .ok_or_else(|| anyhow::anyhow!("object not found"))?;
+        .ok_or_else(|| AppError::not_found("object"))?;
 
     Ok(Bcs(object))
 }
@@ -36,7 +36,7 @@ pub async fn get_object_with_version(
     let object = state
         .database
         .get_object_by_key(&object_id, version)?
-        .ok_or_else(|| anyhow::anyhow!("object not found"))?;
+        .ok_or_else(|| AppError::not_found("object"))?;
 
     Ok(Bcs(object))
 }
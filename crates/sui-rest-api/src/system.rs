@@ -0,0 +1,25 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use axum::{extract::State, Json};
+use sui_core::authority::AuthorityState;
+use sui_types::sui_system_state::{
+    sui_system_state_summary::SuiSystemStateSummary, SuiSystemStateTrait,
+};
+
+use crate::AppError;
+
+pub const GET_SYSTEM_STATE_PATH: &str = "/system";
+
+pub async fn get_system_state(
+    State(state): State<Arc<AuthorityState>>,
+) -> Result<Json<SuiSystemStateSummary>, AppError> {
+    let system_state = state
+        .database
+        .get_sui_system_state_object()?
+        .into_sui_system_state_summary();
+
+    Ok(Json(system_state))
+}
@@ -0,0 +1,190 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    TypedHeader,
+};
+use serde::{Deserialize, Serialize};
+use sui_core::authority::AuthorityState;
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::transaction_orchestrator::TransactiondOrchestrator;
+use sui_types::{
+    digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    quorum_driver_types::{
+        ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
+    },
+    transaction::Transaction,
+};
+
+use crate::{headers::Accept, AppError, Bcs, ResponseContent};
+
+pub const GET_TRANSACTION_PATH: &str = "/transactions/:transaction";
+
+pub async fn get_transaction(
+    Path(transaction_digest): Path<TransactionDigest>,
+    accept: Option<TypedHeader<Accept>>,
+    State(state): State<Arc<AuthorityState>>,
+) -> Result<ResponseContent<TransactionResponse>, AppError> {
+    let transaction = state
+        .database
+        .get_transaction_block(&transaction_digest)?
+        .ok_or_else(|| AppError::not_found("transaction"))?;
+    let effects = state
+        .database
+        .get_executed_effects(&transaction_digest)?
+        .ok_or_else(|| AppError::not_found("effects"))?;
+    let events = effects
+        .events_digest()
+        .map(|event_digest| state.get_transaction_events(event_digest))
+        .transpose()?;
+
+    let response = TransactionResponse {
+        digest: transaction_digest,
+        transaction: transaction.into(),
+        effects,
+        events,
+    };
+
+    Ok(ResponseContent::new(accept, response))
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionResponse {
+    pub digest: TransactionDigest,
+    /// The signed Transaction
+    pub transaction: Transaction,
+    /// The effects produced by executing this transaction
+    pub effects: TransactionEffects,
+    /// The events, if any, emitted by this transaction during execution
+    pub events: Option<TransactionEvents>,
+}
+
+pub const POST_EXECUTE_TRANSACTION_PATH: &str = "/transactions";
+
+/// Submits a signed transaction, encoded as BCS, for execution and waits for its effects to be
+/// certified.
+pub async fn execute_transaction(
+    State(transaction_orchestrator): State<
+        Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
+    >,
+    accept: Option<TypedHeader<Accept>>,
+    Bcs(transaction): Bcs<Transaction>,
+) -> Result<ResponseContent<ExecuteTransactionResponse>, AppError> {
+    let transaction_orchestrator = transaction_orchestrator.ok_or_else(|| {
+        AppError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            anyhow::anyhow!("transaction execution is not enabled on this node"),
+        )
+    })?;
+
+    let response = transaction_orchestrator
+        .execute_transaction_block(ExecuteTransactionRequest {
+            transaction,
+            request_type: ExecuteTransactionRequestType::WaitForEffectsCert,
+        })
+        .await?;
+
+    Ok(ResponseContent::new(accept, response))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+    use sui_core::authority::authority_test_utils::{
+        init_state_with_ids, init_transfer_transaction, send_and_confirm_transaction,
+    };
+    use sui_core::authority::test_authority_builder::TestAuthorityBuilder;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::transaction::TEST_ONLY_GAS_UNIT_FOR_TRANSFER;
+    use sui_types::utils::create_fake_transaction;
+
+    /// Builds an authority that has executed a single transfer, and returns the transfer's digest.
+    pub(crate) async fn state_with_transaction() -> (Arc<AuthorityState>, TransactionDigest) {
+        let (sender, key): (_, AccountKeyPair) = get_key_pair();
+        let (object_id, gas_id) = (ObjectID::random(), ObjectID::random());
+        let state = init_state_with_ids([(sender, object_id), (sender, gas_id)]).await;
+        let mut object_refs = vec![];
+        for id in [object_id, gas_id] {
+            let object = state.get_object(&id).await.unwrap().unwrap();
+            object_refs.push(object.compute_object_reference());
+        }
+        let rgp = state.reference_gas_price_for_testing().unwrap();
+        let transaction = init_transfer_transaction(
+            &state,
+            sender,
+            &key,
+            SuiAddress::random_for_testing_only(),
+            object_refs[0],
+            object_refs[1],
+            rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+            rgp,
+        );
+        let digest = *transaction.digest();
+        send_and_confirm_transaction(&state, transaction.into())
+            .await
+            .unwrap();
+        (state, digest)
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction() {
+        let (state, digest) = state_with_transaction().await;
+
+        let response = get_transaction(
+            Path(digest),
+            Some(TypedHeader(Accept::bcs())),
+            State(state.clone()),
+        )
+        .await;
+        let Ok(ResponseContent::Bcs(transaction)) = response else {
+            panic!("expected a BCS response");
+        };
+        assert_eq!(transaction.digest, digest);
+        assert_eq!(*transaction.effects.transaction_digest(), digest);
+
+        // JSON is the default
+        for accept in [None, Some(TypedHeader(Accept::json()))] {
+            let response = get_transaction(Path(digest), accept, State(state.clone()))
+                .await
+                .into_response();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.headers()[axum::http::header::CONTENT_TYPE],
+                crate::APPLICATION_JSON
+            );
+        }
+
+        let response =
+            get_transaction(Path(digest), Some(TypedHeader(Accept::bcs())), State(state))
+                .await
+                .into_response();
+        assert_eq!(
+            response.headers()[axum::http::header::CONTENT_TYPE],
+            crate::APPLICATION_BCS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_missing_transaction() {
+        let state = TestAuthorityBuilder::new().build().await;
+        let response = get_transaction(Path(TransactionDigest::random()), None, State(state))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_disabled() {
+        let response = execute_transaction(State(None), None, Bcs(create_fake_transaction()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -0,0 +1,497 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Management of the SUI coins of an address submitting many transactions concurrently.
+//!
+//! The [CoinManager] keeps a pool of gas coins so that concurrent transactions do not contend on
+//! a single gas coin, and hands each of them out to one caller at a time: a coin is locked while
+//! a transaction using it as gas is in flight, which prevents two transactions from being signed
+//! with the same version of the coin (equivocation). Periodically, it merges dust coins and
+//! splits new gas coins off the largest coin of the address to refill the pool.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use shared_crypto::intent::Intent;
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiObjectDataOptions, SuiTransactionBlockEffects,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
+};
+use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::gas_coin::GasCoin;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::transaction::{Argument, Command, ObjectArg, Transaction, TransactionData};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::{SuiClient, SUI_COIN_TYPE};
+
+/// How long a caller waiting for a stale coin waits before reading the coins again.
+const STALE_COIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug)]
+pub struct CoinManagerConfig {
+    /// Number of gas coins to keep, besides the largest coin of the address which new gas coins
+    /// are split from.
+    pub gas_pool_size: usize,
+    /// Balance of the gas coins split to refill the pool.
+    pub gas_coin_balance: u64,
+    /// Coins with a smaller balance are merged into the largest coin of the address.
+    pub dust_threshold: u64,
+    /// Maximum number of dust coins merged by a single transaction.
+    pub max_merged_coins: usize,
+    /// Gas budget of the transactions merging and splitting coins.
+    pub gas_budget: u64,
+    /// Interval between two rounds of maintenance of the background task.
+    pub maintenance_interval: Duration,
+}
+
+impl Default for CoinManagerConfig {
+    fn default() -> Self {
+        Self {
+            gas_pool_size: 16,
+            gas_coin_balance: 1_000_000_000,
+            dust_threshold: 10_000_000,
+            max_merged_coins: 256,
+            gas_budget: 50_000_000,
+            maintenance_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CoinStatus {
+    /// The coin can be handed out.
+    Available,
+    /// The coin is used by a transaction in flight.
+    Locked,
+    /// The coin was used by a transaction whose outcome is unknown: it is not handed out again
+    /// before the network reports it at `min_version` or later, as the full node may not have
+    /// caught up with the transaction yet. A coin whose transaction never executes stays stale.
+    Stale { min_version: SequenceNumber },
+}
+
+#[derive(Clone, Debug)]
+struct ManagedCoin {
+    object_ref: ObjectRef,
+    balance: u64,
+    status: CoinStatus,
+}
+
+/// Outcome of a round of maintenance.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MaintenanceReport {
+    /// Number of dust coins merged.
+    pub merged: usize,
+    /// Number of gas coins split.
+    pub split: usize,
+    /// The transaction merging and splitting coins, if one was needed.
+    pub digest: Option<TransactionDigest>,
+}
+
+pub struct CoinManager {
+    client: SuiClient,
+    keystore: Arc<Keystore>,
+    address: SuiAddress,
+    config: CoinManagerConfig,
+    coins: Mutex<BTreeMap<ObjectID, ManagedCoin>>,
+    coin_released: Notify,
+    /// Held while merging and splitting coins, so that rounds of maintenance do not overlap.
+    maintenance: tokio::sync::Mutex<()>,
+}
+
+/// A gas coin handed out by a [CoinManager], locked until the lease is released. Dropping the
+/// lease without releasing it leaves the coin unused until the network reports a newer version of
+/// it, which never happens if no transaction used it, so a lease should be released with the
+/// effects of its transaction, or with `None` if no transaction was submitted.
+pub struct GasLease {
+    manager: Arc<CoinManager>,
+    object_ref: ObjectRef,
+    balance: u64,
+    released: bool,
+}
+
+impl CoinManager {
+    /// Create a manager of the SUI coins of `address`, signing its own transactions with the key
+    /// of `address` in `keystore`. Coins are loaded with [CoinManager::refresh].
+    pub fn new(
+        client: SuiClient,
+        keystore: Arc<Keystore>,
+        address: SuiAddress,
+        config: CoinManagerConfig,
+    ) -> Self {
+        Self {
+            client,
+            keystore,
+            address,
+            config,
+            coins: Default::default(),
+            coin_released: Notify::new(),
+            maintenance: Default::default(),
+        }
+    }
+
+    pub fn address(&self) -> SuiAddress {
+        self.address
+    }
+
+    /// Read the SUI coins of the address from the network. Coins locked by a transaction in
+    /// flight are kept locked; stale coins become available again once the network reports a
+    /// version written by their last transaction.
+    pub async fn refresh(&self) -> Result<(), anyhow::Error> {
+        // Every page is read before the pool is updated, a coin missing from a page that failed
+        // to load must not be dropped.
+        let mut latest = vec![];
+        let mut cursor = None;
+        loop {
+            let page = self
+                .client
+                .coin_read_api()
+                .get_coins(self.address, Some(SUI_COIN_TYPE.to_string()), cursor, None)
+                .await?;
+            latest.extend(page.data);
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        let mut coins = self.coins.lock().unwrap();
+        let mut refreshed = BTreeMap::new();
+        for coin in latest {
+            let managed = match coins.remove(&coin.coin_object_id) {
+                Some(managed) if managed.status == CoinStatus::Locked => managed,
+                Some(managed)
+                    if matches!(
+                        managed.status,
+                        CoinStatus::Stale { min_version } if coin.version < min_version
+                    ) =>
+                {
+                    managed
+                }
+                _ => ManagedCoin {
+                    object_ref: coin.object_ref(),
+                    balance: coin.balance,
+                    status: CoinStatus::Available,
+                },
+            };
+            refreshed.insert(coin.coin_object_id, managed);
+        }
+        // Coins spent by a transaction in flight may already be gone from the network.
+        refreshed.extend(
+            coins
+                .iter()
+                .filter(|(_, coin)| coin.status == CoinStatus::Locked)
+                .map(|(id, coin)| (*id, coin.clone())),
+        );
+        *coins = refreshed;
+        drop(coins);
+        self.coin_released.notify_waiters();
+        Ok(())
+    }
+
+    /// Hand out a gas coin of the pool with a balance of at least `budget`, waiting for one to be
+    /// released if all of them are in use.
+    pub async fn acquire_gas(self: &Arc<Self>, budget: u64) -> Result<GasLease, anyhow::Error> {
+        loop {
+            // Created before looking at the coins, so that releases in between are not missed.
+            let released = self.coin_released.notified();
+            let has_stale = {
+                let mut coins = self.coins.lock().unwrap();
+                // The reserve is only handed out if no other coin can cover the budget, now or
+                // once released.
+                let reserve = Self::reserve(&coins).filter(|reserve| {
+                    coins
+                        .values()
+                        .any(|coin| coin.object_ref.0 != *reserve && coin.balance >= budget)
+                });
+                // The smallest sufficient coin, keeping the larger ones for larger budgets.
+                let candidate = coins
+                    .values_mut()
+                    .filter(|coin| coin.status == CoinStatus::Available)
+                    .filter(|coin| coin.balance >= budget)
+                    .filter(|coin| Some(coin.object_ref.0) != reserve)
+                    .min_by_key(|coin| coin.balance);
+                if let Some(coin) = candidate {
+                    coin.status = CoinStatus::Locked;
+                    return Ok(GasLease {
+                        manager: self.clone(),
+                        object_ref: coin.object_ref,
+                        balance: coin.balance,
+                        released: false,
+                    });
+                }
+                let mut pending = coins
+                    .values()
+                    .filter(|coin| coin.status != CoinStatus::Available)
+                    .filter(|coin| coin.balance >= budget);
+                let has_stale = pending
+                    .clone()
+                    .any(|coin| matches!(coin.status, CoinStatus::Stale { .. }));
+                if pending.next().is_none() {
+                    bail!(
+                        "No gas coin of {} has a balance of at least {budget}",
+                        self.address
+                    );
+                }
+                has_stale
+            };
+            if has_stale {
+                // Wait for a coin to be released, or for the full node to catch up with the
+                // transactions of the stale coins.
+                let _ = tokio::time::timeout(STALE_COIN_POLL_INTERVAL, released).await;
+                self.refresh().await?;
+            } else {
+                released.await;
+            }
+        }
+    }
+
+    /// Merge dust coins and split gas coins until the pool has `gas_pool_size` coins, in a single
+    /// transaction paid by the largest coin of the address.
+    pub async fn maintain(&self) -> Result<MaintenanceReport, anyhow::Error> {
+        let _maintenance = self.maintenance.lock().await;
+        self.refresh().await?;
+        let gas_price = self
+            .client
+            .governance_api()
+            .get_reference_gas_price()
+            .await?;
+
+        // The transaction is signed before the coins are locked, so that the coins only become
+        // stale if it may have been submitted.
+        let (reserve, dust, split, transaction) = {
+            let mut coins = self.coins.lock().unwrap();
+            let Some(reserve_id) = Self::reserve(&coins) else {
+                bail!("Address {} has no available SUI coin", self.address);
+            };
+            let reserve = coins[&reserve_id].clone();
+            let dust = coins
+                .values()
+                .filter(|coin| coin.object_ref.0 != reserve_id)
+                .filter(|coin| coin.status == CoinStatus::Available)
+                .filter(|coin| coin.balance < self.config.dust_threshold)
+                .take(self.config.max_merged_coins)
+                .map(|coin| coin.object_ref)
+                .collect::<Vec<_>>();
+            let pool_size = coins
+                .values()
+                .filter(|coin| coin.object_ref.0 != reserve_id)
+                .filter(|coin| coin.balance >= self.config.dust_threshold)
+                .count();
+            let missing = self.config.gas_pool_size.saturating_sub(pool_size);
+            let affordable = reserve.balance.saturating_sub(self.config.gas_budget)
+                / self.config.gas_coin_balance.max(1);
+            let split = missing.min(affordable as usize);
+            if dust.is_empty() && split == 0 {
+                return Ok(MaintenanceReport::default());
+            }
+            let transaction =
+                self.merge_and_split_transaction(reserve.object_ref, &dust, split, gas_price)?;
+            for id in dust.iter().map(|coin| coin.0).chain([reserve_id]) {
+                coins.get_mut(&id).unwrap().status = CoinStatus::Locked;
+            }
+            (reserve, dust, split, transaction)
+        };
+
+        let result = self.execute(transaction).await;
+        {
+            // The coins are read again below, whatever the outcome of the transaction.
+            let mut coins = self.coins.lock().unwrap();
+            for id in dust.iter().map(|coin| coin.0).chain([reserve.object_ref.0]) {
+                if let Some(coin) = coins.get_mut(&id) {
+                    coin.status = CoinStatus::Stale {
+                        min_version: coin.object_ref.1.next(),
+                    };
+                }
+            }
+        }
+        self.refresh().await?;
+        let digest = result?;
+        info!(
+            "Merged {} dust coins and split {split} gas coins of {} in transaction {digest}",
+            dust.len(),
+            self.address
+        );
+        Ok(MaintenanceReport {
+            merged: dust.len(),
+            split,
+            digest: Some(digest),
+        })
+    }
+
+    /// Run [CoinManager::maintain] every `maintenance_interval` in a background task.
+    pub fn start(self: &Arc<Self>) -> JoinHandle<()> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(manager.config.maintenance_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = manager.maintain().await {
+                    warn!("Coin maintenance of {} failed: {e}", manager.address);
+                }
+            }
+        })
+    }
+
+    fn merge_and_split_transaction(
+        &self,
+        reserve: ObjectRef,
+        dust: &[ObjectRef],
+        split: usize,
+        gas_price: u64,
+    ) -> Result<Transaction, anyhow::Error> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        if !dust.is_empty() {
+            let coins = dust
+                .iter()
+                .map(|coin| builder.obj(ObjectArg::ImmOrOwnedObject(*coin)))
+                .collect::<Result<Vec<_>, _>>()?;
+            builder.command(Command::MergeCoins(Argument::GasCoin, coins));
+        }
+        if split > 0 {
+            builder.pay_sui(
+                vec![self.address; split],
+                vec![self.config.gas_coin_balance; split],
+            )?;
+        }
+        let tx_data = TransactionData::new_programmable(
+            self.address,
+            vec![reserve],
+            builder.finish(),
+            self.config.gas_budget,
+            gas_price,
+        );
+        let signature =
+            self.keystore
+                .sign_secure(&self.address, &tx_data, Intent::sui_transaction())?;
+        Ok(Transaction::from_data(
+            tx_data,
+            Intent::sui_transaction(),
+            vec![signature],
+        ))
+    }
+
+    async fn execute(&self, transaction: Transaction) -> Result<TransactionDigest, anyhow::Error> {
+        let response = self
+            .client
+            .quorum_driver_api()
+            .execute_transaction_block(
+                transaction,
+                SuiTransactionBlockResponseOptions::new().with_effects(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await?;
+        let effects = response
+            .effects
+            .ok_or_else(|| anyhow!("Effects from SuiTransactionBlockResult should not be empty"))?;
+        if let SuiExecutionStatus::Failure { error } = effects.status() {
+            bail!("Coin maintenance transaction failed: {error}");
+        }
+        Ok(*effects.transaction_digest())
+    }
+
+    /// The largest available coin, used to pay for maintenance and to split gas coins from.
+    fn reserve(coins: &BTreeMap<ObjectID, ManagedCoin>) -> Option<ObjectID> {
+        coins
+            .values()
+            .filter(|coin| coin.status == CoinStatus::Available)
+            .max_by_key(|coin| coin.balance)
+            .map(|coin| coin.object_ref.0)
+    }
+
+    fn update(&self, object_ref: ObjectRef, balance: u64, status: CoinStatus) {
+        let mut coins = self.coins.lock().unwrap();
+        coins.insert(
+            object_ref.0,
+            ManagedCoin {
+                object_ref,
+                balance,
+                status,
+            },
+        );
+        drop(coins);
+        self.coin_released.notify_waiters();
+    }
+}
+
+impl GasLease {
+    pub fn object_ref(&self) -> ObjectRef {
+        self.object_ref
+    }
+
+    pub fn balance(&self) -> u64 {
+        self.balance
+    }
+
+    /// Return the coin to the pool. `effects` are the effects of the transaction the coin paid
+    /// for, or `None` if no transaction using the coin was submitted.
+    pub async fn release(
+        mut self,
+        effects: Option<&SuiTransactionBlockEffects>,
+    ) -> Result<(), anyhow::Error> {
+        self.released = true;
+        let Some(effects) = effects else {
+            self.manager
+                .update(self.object_ref, self.balance, CoinStatus::Available);
+            return Ok(());
+        };
+        let object_ref = effects.gas_object().reference.to_object_ref();
+        let object = self
+            .manager
+            .client
+            .read_api()
+            .get_object_with_options(object_ref.0, SuiObjectDataOptions::full_content())
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|response| Ok(response.into_object()?));
+        match object {
+            Ok(object) if object.object_ref() == object_ref => match GasCoin::try_from(&object) {
+                Ok(coin) => self
+                    .manager
+                    .update(object_ref, coin.value(), CoinStatus::Available),
+                Err(e) => {
+                    // The coin is marked as released, it must not stay locked by this lease.
+                    self.manager.update(
+                        object_ref,
+                        self.balance,
+                        CoinStatus::Stale {
+                            min_version: object_ref.1,
+                        },
+                    );
+                    return Err(e.into());
+                }
+            },
+            // The full node has not caught up with the transaction yet.
+            _ => self.manager.update(
+                object_ref,
+                self.balance,
+                CoinStatus::Stale {
+                    min_version: object_ref.1,
+                },
+            ),
+        }
+        Ok(())
+    }
+}
+
+impl Drop for GasLease {
+    fn drop(&mut self) {
+        // The transaction of the lease may still be in flight, the leased version of the coin
+        // must not be handed out again.
+        if !self.released {
+            self.manager.update(
+                self.object_ref,
+                self.balance,
+                CoinStatus::Stale {
+                    min_version: self.object_ref.1.next(),
+                },
+            );
+        }
+    }
+}
@@ -21,6 +21,8 @@ pub enum Error {
     TransactionConfirmationError(TransactionDigest, jsonrpsee::core::Error),
     #[error("Failed to confirm tx status for {0:?} within {1} seconds.")]
     FailToConfirmTransactionStatus(TransactionDigest, u64),
+    #[error("Transaction {0:?} was rejected: {1}")]
+    TransactionRejected(TransactionDigest, jsonrpsee::core::Error),
     #[error("Data error: {0}")]
     DataError(String),
     #[error("Client/Server api version mismatch, client api version : {client_version}, server api version : {server_version}")]
@@ -100,8 +100,10 @@ use crate::apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriverApi, ReadApi
 use crate::error::{Error, SuiRpcResult};
 
 pub mod apis;
+pub mod coin_manager;
 pub mod error;
 pub mod sui_client_config;
+pub mod transaction_executor;
 pub mod wallet_context;
 pub const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
 pub const SUI_LOCAL_NETWORK_URL: &str = "http://127.0.0.1:9000";
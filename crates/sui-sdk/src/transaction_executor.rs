@@ -0,0 +1,230 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Execution of signed transactions until their outcome is known.
+//!
+//! A failed call to `execute_transaction_block` of the [QuorumDriverApi](crate::apis::QuorumDriverApi)
+//! does not mean that the transaction was not committed: the request may have timed out after
+//! the transaction was certified, or an earlier submission may have succeeded. The
+//! [TransactionExecutor] resolves this by looking the digest of the transaction up on the full
+//! node, and by only ever resubmitting the same signed transaction. Since the digest, and so the
+//! versions of the objects the transaction uses, never change, a resubmission cannot equivocate.
+
+use std::time::{Duration, Instant};
+
+use jsonrpsee::core::Error as RpcError;
+use jsonrpsee::types::error::{CallError, INTERNAL_ERROR_CODE};
+use sui_json_rpc::error::TRANSIENT_ERROR_CODE;
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions,
+};
+use sui_types::digests::TransactionDigest;
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::transaction::Transaction;
+use tracing::{debug, warn};
+
+use crate::error::{Error, SuiRpcResult};
+use crate::SuiClient;
+
+#[derive(Clone, Debug)]
+pub struct TransactionExecutorConfig {
+    /// Maximum number of submissions of a transaction.
+    pub max_attempts: u32,
+    /// Delay before the first resubmission, doubled after each attempt.
+    pub retry_interval: Duration,
+    /// Upper bound of the delay between two submissions.
+    pub max_retry_interval: Duration,
+    /// Interval between two lookups of the transaction while waiting to resubmit it.
+    pub poll_interval: Duration,
+    /// Time after which the outcome of the transaction is given up on.
+    pub timeout: Duration,
+}
+
+impl Default for TransactionExecutorConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            retry_interval: Duration::from_secs(1),
+            max_retry_interval: Duration::from_secs(10),
+            poll_interval: Duration::from_millis(500),
+            timeout: Duration::from_secs(120),
+        }
+    }
+}
+
+/// The final status of a committed transaction.
+#[derive(Debug, Clone)]
+pub enum TransactionOutcome {
+    /// The transaction was committed and executed successfully.
+    Committed(SuiTransactionBlockResponse),
+    /// The transaction was committed but its execution failed, e.g. because of a Move abort. Gas
+    /// was charged and the versions of its objects were bumped.
+    Failed {
+        response: SuiTransactionBlockResponse,
+        error: String,
+    },
+}
+
+impl TransactionOutcome {
+    fn new(response: SuiTransactionBlockResponse) -> SuiRpcResult<Self> {
+        let effects = response.effects.as_ref().ok_or_else(|| {
+            Error::DataError(format!(
+                "Response of transaction {:?} has no effects",
+                response.digest
+            ))
+        })?;
+        Ok(match effects.status().clone() {
+            SuiExecutionStatus::Success => Self::Committed(response),
+            SuiExecutionStatus::Failure { error } => Self::Failed { response, error },
+        })
+    }
+
+    pub fn response(&self) -> &SuiTransactionBlockResponse {
+        match self {
+            Self::Committed(response) | Self::Failed { response, .. } => response,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Committed(_))
+    }
+}
+
+/// Submits signed transactions and retries them on transient failures until they are committed,
+/// rejected, or the timeout elapses.
+#[derive(Clone)]
+pub struct TransactionExecutor {
+    client: SuiClient,
+    config: TransactionExecutorConfig,
+}
+
+impl TransactionExecutor {
+    pub fn new(client: SuiClient, config: TransactionExecutorConfig) -> Self {
+        Self { client, config }
+    }
+
+    /// Execute `tx` and return its outcome once it is committed. Effects are always part of the
+    /// returned response, whatever the `options`.
+    ///
+    /// Returns [Error::TransactionRejected] if the validators refused the transaction, in which
+    /// case it was not committed and its objects are not locked by it anymore, and
+    /// [Error::FailToConfirmTransactionStatus] if its outcome is still unknown after the timeout.
+    /// In the latter case, the transaction may still be committed later on, so the objects it
+    /// uses must not be used by another transaction until it is looked up again.
+    pub async fn execute(
+        &self,
+        tx: Transaction,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> SuiRpcResult<TransactionOutcome> {
+        let options = options.with_effects();
+        let digest = *tx.digest();
+        let start = Instant::now();
+        let deadline = start + self.config.timeout;
+        let mut retry_interval = self.config.retry_interval;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let error = match self
+                .client
+                .quorum_driver_api()
+                .execute_transaction_block(
+                    tx.clone(),
+                    options.clone(),
+                    Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+                )
+                .await
+            {
+                Ok(response) => return TransactionOutcome::new(response),
+                Err(error) => error,
+            };
+
+            if !is_retriable(&error) {
+                // An earlier submission may have been committed, in which case the validators
+                // reject this one, e.g. because its objects are locked by the transaction itself.
+                if let Some(response) = self.get_transaction(digest, &options).await {
+                    return TransactionOutcome::new(response);
+                }
+                return Err(match error {
+                    Error::RpcError(error) => Error::TransactionRejected(digest, error),
+                    error => error,
+                });
+            }
+            warn!("Attempt {attempt} to execute transaction {digest:?} failed: {error}");
+
+            let resubmit_at = deadline.min(Instant::now() + retry_interval);
+            if let Some(response) = self
+                .wait_for_transaction(digest, &options, resubmit_at)
+                .await
+            {
+                return TransactionOutcome::new(response);
+            }
+            if attempt >= self.config.max_attempts || Instant::now() >= deadline {
+                return Err(Error::FailToConfirmTransactionStatus(
+                    digest,
+                    start.elapsed().as_secs(),
+                ));
+            }
+            retry_interval = (retry_interval * 2).min(self.config.max_retry_interval);
+        }
+    }
+
+    /// Look the transaction up until it is found or `until` is reached.
+    async fn wait_for_transaction(
+        &self,
+        digest: TransactionDigest,
+        options: &SuiTransactionBlockResponseOptions,
+        until: Instant,
+    ) -> Option<SuiTransactionBlockResponse> {
+        loop {
+            if let Some(response) = self.get_transaction(digest, options).await {
+                return Some(response);
+            }
+            let now = Instant::now();
+            if now >= until {
+                return None;
+            }
+            tokio::time::sleep(self.config.poll_interval.min(until - now)).await;
+        }
+    }
+
+    async fn get_transaction(
+        &self,
+        digest: TransactionDigest,
+        options: &SuiTransactionBlockResponseOptions,
+    ) -> Option<SuiTransactionBlockResponse> {
+        match self
+            .client
+            .read_api()
+            .get_transaction_with_options(digest, options.clone())
+            .await
+        {
+            Ok(response) if response.effects.is_some() => Some(response),
+            Ok(_) => None,
+            // The full node returns an error for transactions it has not executed yet.
+            Err(e) => {
+                debug!("Transaction {digest:?} not found: {e}");
+                None
+            }
+        }
+    }
+}
+
+/// Whether the transaction may still be committed after `error`, so that it is worth waiting for
+/// it and submitting it again. Errors returned by the validators about the transaction itself,
+/// such as invalid signatures, objects locked by another transaction or insufficient gas, are
+/// final.
+fn is_retriable(error: &Error) -> bool {
+    match error {
+        Error::RpcError(RpcError::Call(CallError::Custom(error))) => {
+            error.code() == TRANSIENT_ERROR_CODE || error.code() == INTERNAL_ERROR_CODE
+        }
+        // The outcome of the request is unknown.
+        Error::RpcError(RpcError::Transport(_))
+        | Error::RpcError(RpcError::RequestTimeout)
+        | Error::RpcError(RpcError::RestartNeeded(_)) => true,
+        // The transaction was certified but not executed by the full node yet.
+        Error::FailToConfirmTransactionStatus(..) => true,
+        _ => false,
+    }
+}
@@ -15,6 +15,8 @@ use sui_json_rpc_types::{
     SuiObjectResponseQuery, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_keys::keystore::AccountKeystore;
+use sui_transaction_builder::offline::OfflineDataReader;
+use sui_transaction_builder::{DataReader, TransactionBuilder};
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
 use sui_types::gas_coin::GasCoin;
 use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI};
@@ -26,6 +28,7 @@ pub struct WalletContext {
     request_timeout: Option<std::time::Duration>,
     client: Arc<RwLock<Option<SuiClient>>>,
     max_concurrent_requests: Option<u64>,
+    offline: Option<Arc<OfflineDataReader>>,
 }
 
 impl WalletContext {
@@ -47,15 +50,46 @@ impl WalletContext {
             request_timeout,
             client: Default::default(),
             max_concurrent_requests,
+            offline: None,
         };
         Ok(context)
     }
 
+    /// Build transactions from `snapshot` rather than from the state of a full node. An offline
+    /// context cannot connect to a full node, so transactions can be built and signed but not
+    /// executed.
+    pub fn with_offline_snapshot(mut self, snapshot: OfflineDataReader) -> Self {
+        self.offline = Some(Arc::new(snapshot));
+        self
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline.is_some()
+    }
+
+    /// The snapshot transactions are built from, if the context is offline.
+    pub fn offline_snapshot(&self) -> Option<&OfflineDataReader> {
+        self.offline.as_deref()
+    }
+
+    /// The transaction builder of the full node client, or of the offline snapshot if any.
+    pub async fn transaction_builder(&self) -> Result<TransactionBuilder, anyhow::Error> {
+        Ok(match &self.offline {
+            Some(offline) => TransactionBuilder::new(offline.clone()),
+            None => self.get_client().await?.transaction_builder().clone(),
+        })
+    }
+
     pub fn get_addresses(&self) -> Vec<SuiAddress> {
         self.config.keystore.addresses()
     }
 
     pub async fn get_client(&self) -> Result<SuiClient, anyhow::Error> {
+        if self.offline.is_some() {
+            return Err(anyhow!(
+                "Cannot connect to a full node when building transactions from an offline snapshot"
+            ));
+        }
         let read = self.client.read().await;
 
         Ok(if let Some(client) = read.as_ref() {
@@ -152,6 +186,9 @@ impl WalletContext {
     }
 
     pub async fn get_object_owner(&self, id: &ObjectID) -> Result<SuiAddress, anyhow::Error> {
+        if let Some(offline) = &self.offline {
+            return Ok(offline.get_object(id)?.owner.get_owner_address()?);
+        }
         let client = self.get_client().await?;
         let object = client
             .read_api()
@@ -273,6 +310,9 @@ impl WalletContext {
     }
 
     pub async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
+        if let Some(offline) = &self.offline {
+            return offline.get_reference_gas_price().await;
+        }
         let client = self.get_client().await?;
         let gas_price = client.governance_api().get_reference_gas_price().await?;
         Ok(gas_price)
@@ -307,6 +347,12 @@ impl WalletContext {
         &self,
         tx: Transaction,
     ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        if self.is_offline() {
+            return Err(anyhow!(
+                "Cannot execute a transaction built from an offline snapshot, serialize it with \
+                 --serialize-signed-transaction and execute it later with `sui client execute-signed-tx`"
+            ));
+        }
         let client = self.get_client().await?;
         Ok(client
             .quorum_driver_api()
@@ -3,6 +3,7 @@
 
 use anyhow::{anyhow, Result};
 use byteorder::ReadBytesExt;
+use clap::ValueEnum;
 use integer_encoding::{VarInt, VarIntReader};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use serde::de::DeserializeOwned;
@@ -13,7 +14,7 @@ use std::marker::PhantomData;
 pub const MAX_VARINT_LENGTH: usize = 10;
 pub const BLOB_ENCODING_BYTES: usize = 1;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive, ValueEnum)]
 #[repr(u8)]
 pub enum BlobEncoding {
     Bcs = 1,
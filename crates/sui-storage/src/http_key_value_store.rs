@@ -27,7 +27,10 @@ use tap::TapFallible;
 use tracing::{error, info, trace, warn};
 use url::Url;
 
-use crate::key_value_store::{TransactionKeyValueStore, TransactionKeyValueStoreTrait};
+use crate::key_value_store::{
+    KVStoreCheckpointData, KVStoreTransactionData, TransactionKeyValueStore,
+    TransactionKeyValueStoreTrait,
+};
 use crate::key_value_store_metrics::KeyValueStoreMetrics;
 
 pub struct HttpKVStore {
@@ -85,7 +88,7 @@ enum Value {
     TxToCheckpoint(CheckpointSequenceNumber),
 }
 
-fn key_to_path_elements(key: &Key) -> SuiResult<(String, &'static str)> {
+pub(crate) fn key_to_path_elements(key: &Key) -> SuiResult<(String, &'static str)> {
     match key {
         Key::Tx(digest) => Ok((encode_digest(digest), "tx")),
         Key::Fx(digest) => Ok((encode_digest(digest), "fx")),
@@ -147,18 +150,23 @@ impl HttpKVStore {
             .into_sui_result()?;
         Uri::from_str(joined.as_str()).into_sui_result()
     }
+}
 
-    async fn multi_fetch(&self, uris: Vec<Key>) -> Vec<SuiResult<Option<Bytes>>> {
-        let uris_vec = uris.to_vec();
-        let fetches = stream::iter(
-            uris_vec
-                .into_iter()
-                .enumerate()
-                .map(|(_i, uri)| self.fetch(uri)),
-        );
-        fetches.buffered(uris.len()).collect::<Vec<_>>().await
+/// Source of the raw bytes stored under a key. Key value stores share the key layout and the
+/// decoding of values, and only differ in how the bytes are fetched.
+#[async_trait]
+pub(crate) trait KeyFetcher: Send + Sync {
+    async fn fetch(&self, key: Key) -> SuiResult<Option<Bytes>>;
+
+    async fn multi_fetch(&self, keys: Vec<Key>) -> Vec<SuiResult<Option<Bytes>>> {
+        let num_keys = keys.len();
+        let fetches = stream::iter(keys.into_iter().map(|key| self.fetch(key)));
+        fetches.buffered(num_keys).collect::<Vec<_>>().await
     }
+}
 
+#[async_trait]
+impl KeyFetcher for HttpKVStore {
     async fn fetch(&self, key: Key) -> SuiResult<Option<Bytes>> {
         let uri = self.get_url(&key)?;
         trace!("fetching uri: {}", uri);
@@ -244,6 +252,172 @@ where
     })
 }
 
+// Fetch and decode the values of transactions, effects and events, for any key value store
+// built on a `KeyFetcher`.
+pub(crate) async fn multi_get(
+    fetcher: &impl KeyFetcher,
+    transactions: &[TransactionDigest],
+    effects: &[TransactionDigest],
+    events: &[TransactionEventsDigest],
+) -> SuiResult<KVStoreTransactionData> {
+    let num_txns = transactions.len();
+    let num_effects = effects.len();
+    let num_events = events.len();
+
+    let keys = transactions
+        .iter()
+        .map(|tx| Key::Tx(*tx))
+        .chain(effects.iter().map(|fx| Key::Fx(*fx)))
+        .chain(events.iter().map(|events| Key::Events(*events)))
+        .collect::<Vec<_>>();
+
+    let fetches = fetcher.multi_fetch(keys).await;
+    let txn_slice = fetches[..num_txns].to_vec();
+    let fx_slice = fetches[num_txns..num_txns + num_effects].to_vec();
+    let events_slice = fetches[num_txns + num_effects..].to_vec();
+
+    let txn_results = txn_slice
+        .iter()
+        .take(num_txns)
+        .zip(transactions.iter())
+        .map(map_fetch)
+        .map(|maybe_bytes| {
+            maybe_bytes.and_then(|(bytes, digest)| {
+                deser_check_digest(digest, bytes, |tx: &Transaction| *tx.digest())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let fx_results = fx_slice
+        .iter()
+        .take(num_effects)
+        .zip(effects.iter())
+        .map(map_fetch)
+        .map(|maybe_bytes| {
+            maybe_bytes.and_then(|(bytes, digest)| {
+                deser_check_digest(digest, bytes, |fx: &TransactionEffects| {
+                    *fx.transaction_digest()
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let events_results = events_slice
+        .iter()
+        .take(num_events)
+        .zip(events.iter())
+        .map(map_fetch)
+        .map(|maybe_bytes| {
+            maybe_bytes.and_then(|(bytes, digest)| {
+                deser_check_digest(digest, bytes, |events: &TransactionEvents| events.digest())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok((txn_results, fx_results, events_results))
+}
+
+pub(crate) async fn multi_get_checkpoints(
+    fetcher: &impl KeyFetcher,
+    checkpoint_summaries: &[CheckpointSequenceNumber],
+    checkpoint_contents: &[CheckpointSequenceNumber],
+    checkpoint_summaries_by_digest: &[CheckpointDigest],
+    checkpoint_contents_by_digest: &[CheckpointContentsDigest],
+) -> SuiResult<KVStoreCheckpointData> {
+    let keys = checkpoint_summaries
+        .iter()
+        .map(|cp| Key::CheckpointSummary(*cp))
+        .chain(
+            checkpoint_contents
+                .iter()
+                .map(|cp| Key::CheckpointContents(*cp)),
+        )
+        .chain(
+            checkpoint_summaries_by_digest
+                .iter()
+                .map(|cp| Key::CheckpointSummaryByDigest(*cp)),
+        )
+        .chain(
+            checkpoint_contents_by_digest
+                .iter()
+                .map(|cp| Key::CheckpointContentsByDigest(*cp)),
+        )
+        .collect::<Vec<_>>();
+
+    let summaries_len = checkpoint_summaries.len();
+    let contents_len = checkpoint_contents.len();
+    let summaries_by_digest_len = checkpoint_summaries_by_digest.len();
+    let contents_by_digest_len = checkpoint_contents_by_digest.len();
+
+    let fetches = fetcher.multi_fetch(keys).await;
+
+    let input_slices = [
+        summaries_len,
+        contents_len,
+        summaries_by_digest_len,
+        contents_by_digest_len,
+    ];
+
+    let result_slices = multi_split_slice(&fetches, &input_slices);
+
+    let summaries_results = result_slices[0]
+        .iter()
+        .zip(checkpoint_summaries.iter())
+        .map(map_fetch)
+        .map(|maybe_bytes| {
+            maybe_bytes.and_then(|(bytes, seq)| deser::<_, CertifiedCheckpointSummary>(seq, bytes))
+        })
+        .collect::<Vec<_>>();
+
+    let contents_results = result_slices[1]
+        .iter()
+        .zip(checkpoint_contents.iter())
+        .map(map_fetch)
+        .map(|maybe_bytes| {
+            maybe_bytes.and_then(|(bytes, seq)| deser::<_, CheckpointContents>(seq, bytes))
+        })
+        .collect::<Vec<_>>();
+
+    let summaries_by_digest_results = result_slices[2]
+        .iter()
+        .zip(checkpoint_summaries_by_digest.iter())
+        .map(map_fetch)
+        .map(|maybe_bytes| {
+            maybe_bytes.and_then(|(bytes, digest)| {
+                deser_check_digest(digest, bytes, |s: &CertifiedCheckpointSummary| *s.digest())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let contents_by_digest_results = result_slices[3]
+        .iter()
+        .zip(checkpoint_contents_by_digest.iter())
+        .map(map_fetch)
+        .map(|maybe_bytes| {
+            maybe_bytes.and_then(|(bytes, digest)| {
+                deser_check_digest(digest, bytes, |c: &CheckpointContents| *c.digest())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok((
+        summaries_results,
+        contents_results,
+        summaries_by_digest_results,
+        contents_by_digest_results,
+    ))
+}
+
+pub(crate) async fn get_transaction_checkpoint(
+    fetcher: &impl KeyFetcher,
+    digest: TransactionDigest,
+) -> SuiResult<Option<CheckpointSequenceNumber>> {
+    let key = Key::TxToCheckpoint(digest);
+    fetcher.fetch(key).await.map(|maybe| {
+        maybe.and_then(|bytes| deser::<_, CheckpointSequenceNumber>(&key, bytes.as_ref()))
+    })
+}
+
 #[async_trait]
 impl TransactionKeyValueStoreTrait for HttpKVStore {
     async fn multi_get(
@@ -251,66 +425,8 @@ impl TransactionKeyValueStoreTrait for HttpKVStore {
         transactions: &[TransactionDigest],
         effects: &[TransactionDigest],
         events: &[TransactionEventsDigest],
-    ) -> SuiResult<(
-        Vec<Option<Transaction>>,
-        Vec<Option<TransactionEffects>>,
-        Vec<Option<TransactionEvents>>,
-    )> {
-        let num_txns = transactions.len();
-        let num_effects = effects.len();
-        let num_events = events.len();
-
-        let keys = transactions
-            .iter()
-            .map(|tx| Key::Tx(*tx))
-            .chain(effects.iter().map(|fx| Key::Fx(*fx)))
-            .chain(events.iter().map(|events| Key::Events(*events)))
-            .collect::<Vec<_>>();
-
-        let fetches = self.multi_fetch(keys).await;
-        let txn_slice = fetches[..num_txns].to_vec();
-        let fx_slice = fetches[num_txns..num_txns + num_effects].to_vec();
-        let events_slice = fetches[num_txns + num_effects..].to_vec();
-
-        let txn_results = txn_slice
-            .iter()
-            .take(num_txns)
-            .zip(transactions.iter())
-            .map(map_fetch)
-            .map(|maybe_bytes| {
-                maybe_bytes.and_then(|(bytes, digest)| {
-                    deser_check_digest(digest, bytes, |tx: &Transaction| *tx.digest())
-                })
-            })
-            .collect::<Vec<_>>();
-
-        let fx_results = fx_slice
-            .iter()
-            .take(num_effects)
-            .zip(effects.iter())
-            .map(map_fetch)
-            .map(|maybe_bytes| {
-                maybe_bytes.and_then(|(bytes, digest)| {
-                    deser_check_digest(digest, bytes, |fx: &TransactionEffects| {
-                        *fx.transaction_digest()
-                    })
-                })
-            })
-            .collect::<Vec<_>>();
-
-        let events_results = events_slice
-            .iter()
-            .take(num_events)
-            .zip(events.iter())
-            .map(map_fetch)
-            .map(|maybe_bytes| {
-                maybe_bytes.and_then(|(bytes, digest)| {
-                    deser_check_digest(digest, bytes, |events: &TransactionEvents| events.digest())
-                })
-            })
-            .collect::<Vec<_>>();
-
-        Ok((txn_results, fx_results, events_results))
+    ) -> SuiResult<KVStoreTransactionData> {
+        multi_get(self, transactions, effects, events).await
     }
 
     async fn multi_get_checkpoints(
@@ -319,104 +435,21 @@ impl TransactionKeyValueStoreTrait for HttpKVStore {
         checkpoint_contents: &[CheckpointSequenceNumber],
         checkpoint_summaries_by_digest: &[CheckpointDigest],
         checkpoint_contents_by_digest: &[CheckpointContentsDigest],
-    ) -> SuiResult<(
-        Vec<Option<CertifiedCheckpointSummary>>,
-        Vec<Option<CheckpointContents>>,
-        Vec<Option<CertifiedCheckpointSummary>>,
-        Vec<Option<CheckpointContents>>,
-    )> {
-        let keys = checkpoint_summaries
-            .iter()
-            .map(|cp| Key::CheckpointSummary(*cp))
-            .chain(
-                checkpoint_contents
-                    .iter()
-                    .map(|cp| Key::CheckpointContents(*cp)),
-            )
-            .chain(
-                checkpoint_summaries_by_digest
-                    .iter()
-                    .map(|cp| Key::CheckpointSummaryByDigest(*cp)),
-            )
-            .chain(
-                checkpoint_contents_by_digest
-                    .iter()
-                    .map(|cp| Key::CheckpointContentsByDigest(*cp)),
-            )
-            .collect::<Vec<_>>();
-
-        let summaries_len = checkpoint_summaries.len();
-        let contents_len = checkpoint_contents.len();
-        let summaries_by_digest_len = checkpoint_summaries_by_digest.len();
-        let contents_by_digest_len = checkpoint_contents_by_digest.len();
-
-        let fetches = self.multi_fetch(keys).await;
-
-        let input_slices = [
-            summaries_len,
-            contents_len,
-            summaries_by_digest_len,
-            contents_by_digest_len,
-        ];
-
-        let result_slices = multi_split_slice(&fetches, &input_slices);
-
-        let summaries_results = result_slices[0]
-            .iter()
-            .zip(checkpoint_summaries.iter())
-            .map(map_fetch)
-            .map(|maybe_bytes| {
-                maybe_bytes
-                    .and_then(|(bytes, seq)| deser::<_, CertifiedCheckpointSummary>(seq, bytes))
-            })
-            .collect::<Vec<_>>();
-
-        let contents_results = result_slices[1]
-            .iter()
-            .zip(checkpoint_contents.iter())
-            .map(map_fetch)
-            .map(|maybe_bytes| {
-                maybe_bytes.and_then(|(bytes, seq)| deser::<_, CheckpointContents>(seq, bytes))
-            })
-            .collect::<Vec<_>>();
-
-        let summaries_by_digest_results = result_slices[2]
-            .iter()
-            .zip(checkpoint_summaries_by_digest.iter())
-            .map(map_fetch)
-            .map(|maybe_bytes| {
-                maybe_bytes.and_then(|(bytes, digest)| {
-                    deser_check_digest(digest, bytes, |s: &CertifiedCheckpointSummary| *s.digest())
-                })
-            })
-            .collect::<Vec<_>>();
-
-        let contents_by_digest_results = result_slices[3]
-            .iter()
-            .zip(checkpoint_contents_by_digest.iter())
-            .map(map_fetch)
-            .map(|maybe_bytes| {
-                maybe_bytes.and_then(|(bytes, digest)| {
-                    deser_check_digest(digest, bytes, |c: &CheckpointContents| *c.digest())
-                })
-            })
-            .collect::<Vec<_>>();
-
-        Ok((
-            summaries_results,
-            contents_results,
-            summaries_by_digest_results,
-            contents_by_digest_results,
-        ))
+    ) -> SuiResult<KVStoreCheckpointData> {
+        multi_get_checkpoints(
+            self,
+            checkpoint_summaries,
+            checkpoint_contents,
+            checkpoint_summaries_by_digest,
+            checkpoint_contents_by_digest,
+        )
+        .await
     }
 
     async fn deprecated_get_transaction_checkpoint(
         &self,
         digest: TransactionDigest,
     ) -> SuiResult<Option<CheckpointSequenceNumber>> {
-        let key = Key::TxToCheckpoint(digest);
-        self.fetch(key).await.map(|maybe| {
-            maybe.and_then(|bytes| deser::<_, CheckpointSequenceNumber>(&key, bytes.as_ref()))
-        })
+        get_transaction_checkpoint(self, digest).await
     }
 }
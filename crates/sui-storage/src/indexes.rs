@@ -20,7 +20,7 @@ use tokio::sync::OwnedMutexGuard;
 
 use crate::mutex_table::MutexTable;
 use crate::sharded_lru::ShardedLruCache;
-use sui_json_rpc_types::{SuiObjectDataFilter, TransactionFilter};
+use sui_json_rpc_types::{EventFilter, SuiObjectDataFilter, TransactionFilter};
 use sui_types::base_types::{
     ObjectDigest, ObjectID, SequenceNumber, SuiAddress, TransactionDigest, TxSequenceNumber,
 };
@@ -29,6 +29,7 @@ use sui_types::digests::TransactionEventsDigest;
 use sui_types::dynamic_field::{self, DynamicFieldInfo};
 use sui_types::effects::TransactionEvents;
 use sui_types::error::{SuiError, SuiResult, UserInputError};
+use sui_types::event::EventID;
 use sui_types::inner_temporary_store::TxCoins;
 use sui_types::object::{Object, Owner};
 use sui_types::parse_sui_struct_tag;
@@ -47,10 +48,14 @@ type DynamicFieldKey = (ObjectID, ObjectID);
 type EventId = (TxSequenceNumber, usize);
 type EventIndex = (TransactionEventsDigest, TransactionDigest, u64);
 type AllBalance = HashMap<TypeTag, TotalBalance>;
+/// Entries of an index, keyed by their position in the order of transactions or events.
+type SequencedIter<'a, S, V> = Box<dyn Iterator<Item = (S, V)> + 'a>;
 
 pub const MAX_TX_RANGE_SIZE: u64 = 4096;
 
 pub const MAX_GET_OWNED_OBJECT_SIZE: usize = 256;
+/// Maximum number of candidates checked against a compound filter for a single page of results.
+pub const MAX_FILTER_CANDIDATES_PER_PAGE: usize = 10_000;
 const ENV_VAR_COIN_INDEX_BLOCK_CACHE_SIZE_MB: &str = "COIN_INDEX_BLOCK_CACHE_MB";
 const ENV_VAR_DISABLE_INDEX_CACHE: &str = "DISABLE_INDEX_CACHE";
 const ENV_VAR_INVALIDATE_INSTEAD_OF_UPDATE: &str = "INVALIDATE_INSTEAD_OF_UPDATE";
@@ -646,13 +651,16 @@ impl IndexStore {
         self.next_sequence_number.load(Ordering::SeqCst) + 1
     }
 
+    /// Returns the transactions matching `filter`. For compound filters, the scan may stop before
+    /// `limit` transactions are found, in which case the cursor to resume it from is returned as
+    /// well (see `get_transactions_by_filter`).
     pub fn get_transactions(
         &self,
         filter: Option<TransactionFilter>,
         cursor: Option<TransactionDigest>,
         limit: Option<usize>,
         reverse: bool,
-    ) -> SuiResult<Vec<TransactionDigest>> {
+    ) -> SuiResult<(Vec<TransactionDigest>, Option<TransactionDigest>)> {
         // Lookup TransactionDigest sequence number,
         let cursor = if let Some(cursor) = cursor {
             Some(
@@ -662,31 +670,40 @@ impl IndexStore {
         } else {
             None
         };
-        match filter {
+        let digests = match filter {
             Some(TransactionFilter::MoveFunction {
                 package,
                 module,
                 function,
-            }) => Ok(self.get_transactions_by_move_function(
+            }) => self.get_transactions_by_move_function(
                 package, module, function, cursor, limit, reverse,
-            )?),
+            )?,
             Some(TransactionFilter::InputObject(object_id)) => {
-                Ok(self.get_transactions_by_input_object(object_id, cursor, limit, reverse)?)
+                self.get_transactions_by_input_object(object_id, cursor, limit, reverse)?
             }
             Some(TransactionFilter::ChangedObject(object_id)) => {
-                Ok(self.get_transactions_by_mutated_object(object_id, cursor, limit, reverse)?)
+                self.get_transactions_by_mutated_object(object_id, cursor, limit, reverse)?
             }
             Some(TransactionFilter::FromAddress(address)) => {
-                Ok(self.get_transactions_from_addr(address, cursor, limit, reverse)?)
+                self.get_transactions_from_addr(address, cursor, limit, reverse)?
             }
             Some(TransactionFilter::ToAddress(address)) => {
-                Ok(self.get_transactions_to_addr(address, cursor, limit, reverse)?)
+                self.get_transactions_to_addr(address, cursor, limit, reverse)?
             }
+            Some(
+                filter @ (TransactionFilter::FromAndToAddress { .. }
+                | TransactionFilter::FromOrToAddress { .. }
+                | TransactionFilter::All(_)
+                | TransactionFilter::Any(_)
+                | TransactionFilter::Not(_)),
+            ) => return self.get_transactions_by_filter(&filter, cursor, limit, reverse),
             // NOTE: filter via checkpoint sequence number is implemented in
             // `get_transactions` of authority.rs.
-            Some(_) => Err(SuiError::UserInputError {
-                error: UserInputError::Unsupported(format!("{:?}", filter)),
-            }),
+            Some(_) => {
+                return Err(SuiError::UserInputError {
+                    error: UserInputError::Unsupported(format!("{:?}", filter)),
+                })
+            }
             None => {
                 let iter = self.tables.transaction_order.unbounded_iter();
 
@@ -697,9 +714,9 @@ impl IndexStore {
                         .skip(usize::from(cursor.is_some()))
                         .map(|(_, digest)| digest);
                     if let Some(limit) = limit {
-                        Ok(iter.take(limit).collect())
+                        iter.take(limit).collect()
                     } else {
-                        Ok(iter.collect())
+                        iter.collect()
                     }
                 } else {
                     let iter = iter
@@ -707,13 +724,14 @@ impl IndexStore {
                         .skip(usize::from(cursor.is_some()))
                         .map(|(_, digest)| digest);
                     if let Some(limit) = limit {
-                        Ok(iter.take(limit).collect())
+                        iter.take(limit).collect()
                     } else {
-                        Ok(iter.collect())
+                        iter.collect()
                     }
                 }
             }
-        }
+        };
+        Ok((digests, None))
     }
 
     /// Return loaded child objects table for a tx
@@ -796,6 +814,22 @@ impl IndexStore {
         )
     }
 
+    /// Return the first transaction at or after `sequence` that created or mutated the object.
+    pub fn get_next_transaction_by_mutated_object(
+        &self,
+        mutated_object: ObjectID,
+        sequence: TxSequenceNumber,
+    ) -> SuiResult<Option<(TxSequenceNumber, TransactionDigest)>> {
+        Ok(self
+            .tables
+            .transactions_by_mutated_object_id
+            .unbounded_iter()
+            .skip_to(&(mutated_object, sequence))?
+            .next()
+            .filter(|((id, _), _)| *id == mutated_object)
+            .map(|((_, sequence), digest)| (sequence, digest)))
+    }
+
     pub fn get_transactions_from_addr(
         &self,
         addr: SuiAddress,
@@ -1125,6 +1159,426 @@ impl IndexStore {
         })
     }
 
+    /// Returns the transactions matching a filter that combines other filters, by walking the
+    /// index of the first sub-filter of an `All` that has one (or the merged indexes of every
+    /// sub-filter of an `Any`) and checking each candidate transaction against the whole filter.
+    /// Filters that cannot be narrowed down with an index, such as a bare `Not`, are rejected
+    /// rather than scanning every transaction.
+    ///
+    /// At most `MAX_FILTER_CANDIDATES_PER_PAGE` candidates are checked. If there are more, the
+    /// digest of the last transaction checked is returned along with the matches found so far, as
+    /// the exclusive cursor to resume the scan from.
+    pub fn get_transactions_by_filter(
+        &self,
+        filter: &TransactionFilter,
+        cursor: Option<TxSequenceNumber>,
+        limit: Option<usize>,
+        reverse: bool,
+    ) -> SuiResult<(Vec<TransactionDigest>, Option<TransactionDigest>)> {
+        self.scan_transactions_by_filter(
+            filter,
+            cursor,
+            limit,
+            reverse,
+            MAX_FILTER_CANDIDATES_PER_PAGE,
+        )
+    }
+
+    fn scan_transactions_by_filter(
+        &self,
+        filter: &TransactionFilter,
+        cursor: Option<TxSequenceNumber>,
+        limit: Option<usize>,
+        reverse: bool,
+        max_candidates: usize,
+    ) -> SuiResult<(Vec<TransactionDigest>, Option<TransactionDigest>)> {
+        check_transaction_filter(filter)?;
+
+        let start = cursor.unwrap_or(if reverse {
+            TxSequenceNumber::MAX
+        } else {
+            TxSequenceNumber::MIN
+        });
+        let candidates = self
+            .transaction_candidates(filter, start, reverse)?
+            .ok_or_else(|| unindexed_combination(filter))?;
+
+        let mut digests = vec![];
+        let mut last_checked = None;
+        // skip the cursor, which is exclusive
+        for (checked, (sequence, digest)) in candidates
+            .skip_while(|(sequence, _)| Some(*sequence) == cursor)
+            .enumerate()
+        {
+            if limit.is_some_and(|limit| digests.len() >= limit) {
+                break;
+            }
+            if checked == max_candidates {
+                return Ok((digests, last_checked));
+            }
+            if self.transaction_matches(filter, sequence)? {
+                digests.push(digest);
+            }
+            last_checked = Some(digest);
+        }
+        Ok((digests, None))
+    }
+
+    /// A superset of the transactions matching `filter`, in order, or `None` if there is no index
+    /// to narrow them down with.
+    fn transaction_candidates(
+        &self,
+        filter: &TransactionFilter,
+        start: TxSequenceNumber,
+        reverse: bool,
+    ) -> SuiResult<Option<SequencedIter<'_, TxSequenceNumber, TransactionDigest>>> {
+        let tables = &self.tables;
+        Ok(Some(match filter {
+            TransactionFilter::FromAddress(address)
+            | TransactionFilter::FromAndToAddress { from: address, .. } => {
+                index_iter(&tables.transactions_from_addr, *address, start, reverse)?
+            }
+            TransactionFilter::ToAddress(address) => {
+                index_iter(&tables.transactions_to_addr, *address, start, reverse)?
+            }
+            TransactionFilter::FromOrToAddress { addr } => merge_by_sequence(
+                vec![
+                    index_iter(&tables.transactions_from_addr, *addr, start, reverse)?,
+                    index_iter(&tables.transactions_to_addr, *addr, start, reverse)?,
+                ],
+                reverse,
+            ),
+            TransactionFilter::InputObject(object_id) => index_iter(
+                &tables.transactions_by_input_object_id,
+                *object_id,
+                start,
+                reverse,
+            )?,
+            TransactionFilter::ChangedObject(object_id) => index_iter(
+                &tables.transactions_by_mutated_object_id,
+                *object_id,
+                start,
+                reverse,
+            )?,
+            TransactionFilter::MoveFunction {
+                package,
+                module: Some(module),
+                function: Some(function),
+            } => {
+                let (package, module, function) = (*package, module.clone(), function.clone());
+                let key = (package, module.clone(), function.clone(), start);
+                let iter = tables.transactions_by_move_function.unbounded_iter();
+                let iter: SequencedIter<'_, _, _> = if reverse {
+                    Box::new(iter.skip_prior_to(&key)?.reverse())
+                } else {
+                    Box::new(iter.skip_to(&key)?)
+                };
+                Box::new(
+                    iter.take_while(move |((p, m, f, _), _)| {
+                        *p == package && *m == module && *f == function
+                    })
+                    .map(|((_, _, _, sequence), digest)| (sequence, digest)),
+                )
+            }
+            TransactionFilter::All(filters) => {
+                for filter in filters {
+                    if let Some(candidates) = self.transaction_candidates(filter, start, reverse)? {
+                        return Ok(Some(candidates));
+                    }
+                }
+                return Ok(None);
+            }
+            TransactionFilter::Any(filters) => {
+                let mut candidates = vec![];
+                for filter in filters {
+                    match self.transaction_candidates(filter, start, reverse)? {
+                        Some(iter) => candidates.push(iter),
+                        None => return Ok(None),
+                    }
+                }
+                merge_by_sequence(candidates, reverse)
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    fn transaction_matches(
+        &self,
+        filter: &TransactionFilter,
+        sequence: TxSequenceNumber,
+    ) -> SuiResult<bool> {
+        let tables = &self.tables;
+        Ok(match filter {
+            TransactionFilter::FromAddress(address) => tables
+                .transactions_from_addr
+                .contains_key(&(*address, sequence))?,
+            TransactionFilter::ToAddress(address) => tables
+                .transactions_to_addr
+                .contains_key(&(*address, sequence))?,
+            TransactionFilter::FromAndToAddress { from, to } => {
+                tables
+                    .transactions_from_addr
+                    .contains_key(&(*from, sequence))?
+                    && tables.transactions_to_addr.contains_key(&(*to, sequence))?
+            }
+            TransactionFilter::FromOrToAddress { addr } => {
+                tables
+                    .transactions_from_addr
+                    .contains_key(&(*addr, sequence))?
+                    || tables
+                        .transactions_to_addr
+                        .contains_key(&(*addr, sequence))?
+            }
+            TransactionFilter::InputObject(object_id) => tables
+                .transactions_by_input_object_id
+                .contains_key(&(*object_id, sequence))?,
+            TransactionFilter::ChangedObject(object_id) => tables
+                .transactions_by_mutated_object_id
+                .contains_key(&(*object_id, sequence))?,
+            TransactionFilter::MoveFunction {
+                package,
+                module: Some(module),
+                function: Some(function),
+            } => tables.transactions_by_move_function.contains_key(&(
+                *package,
+                module.clone(),
+                function.clone(),
+                sequence,
+            ))?,
+            TransactionFilter::All(filters) => {
+                for filter in filters {
+                    if !self.transaction_matches(filter, sequence)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            TransactionFilter::Any(filters) => {
+                for filter in filters {
+                    if self.transaction_matches(filter, sequence)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            TransactionFilter::Not(filter) => !self.transaction_matches(filter, sequence)?,
+            TransactionFilter::MoveFunction { .. }
+            | TransactionFilter::Checkpoint(_)
+            | TransactionFilter::TransactionKind(_)
+            | TransactionFilter::TransactionKindIn(_) => {
+                return Err(unsupported_combination(filter))
+            }
+        })
+    }
+
+    /// Returns the events matching a filter that combines other filters, starting from (and
+    /// including) the event at `(tx_seq, event_seq)`. As for transactions, filters that cannot be
+    /// narrowed down with an index are rejected, and at most `MAX_FILTER_CANDIDATES_PER_PAGE`
+    /// candidates are checked: if there are more, the id of the last event checked is returned as
+    /// the exclusive cursor to resume the scan from.
+    #[allow(clippy::type_complexity)]
+    pub fn events_by_filter(
+        &self,
+        filter: &EventFilter,
+        tx_seq: TxSequenceNumber,
+        event_seq: usize,
+        limit: usize,
+        descending: bool,
+    ) -> SuiResult<(
+        Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>,
+        Option<EventID>,
+    )> {
+        self.scan_events_by_filter(
+            filter,
+            tx_seq,
+            event_seq,
+            limit,
+            descending,
+            MAX_FILTER_CANDIDATES_PER_PAGE,
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn scan_events_by_filter(
+        &self,
+        filter: &EventFilter,
+        tx_seq: TxSequenceNumber,
+        event_seq: usize,
+        limit: usize,
+        descending: bool,
+        max_candidates: usize,
+    ) -> SuiResult<(
+        Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>,
+        Option<EventID>,
+    )> {
+        check_event_filter(filter)?;
+
+        let start = (tx_seq, event_seq);
+        let candidates = self
+            .event_candidates(filter, start, descending)?
+            .ok_or_else(|| unindexed_combination(filter))?;
+
+        let mut events = vec![];
+        let mut last_checked = None;
+        for (checked, (id, (digest, tx_digest, time))) in candidates.enumerate() {
+            if events.len() >= limit {
+                break;
+            }
+            if checked == max_candidates {
+                return Ok((events, last_checked));
+            }
+            if self.event_matches(filter, id)? {
+                events.push((digest, tx_digest, id.1, time));
+            }
+            last_checked = Some(EventID {
+                tx_digest,
+                event_seq: id.1 as u64,
+            });
+        }
+        Ok((events, None))
+    }
+
+    /// A superset of the events matching `filter`, in order, or `None` if there is no index to
+    /// narrow them down with.
+    fn event_candidates(
+        &self,
+        filter: &EventFilter,
+        start: EventId,
+        descending: bool,
+    ) -> SuiResult<Option<SequencedIter<'_, EventId, EventIndex>>> {
+        let tables = &self.tables;
+        Ok(Some(match filter {
+            EventFilter::Transaction(digest) => {
+                let seq = self
+                    .get_transaction_seq(digest)?
+                    .ok_or(SuiError::TransactionNotFound { digest: *digest })?;
+                let start = if descending {
+                    min(start, (seq, usize::MAX))
+                } else {
+                    max(start, (seq, 0))
+                };
+                Box::new(
+                    self.event_order_iter(start, descending)?
+                        .take_while(move |((tx, _), _)| *tx == seq),
+                )
+            }
+            EventFilter::MoveModule { package, module } => index_iter(
+                &tables.event_by_move_module,
+                ModuleId::new((*package).into(), module.clone()),
+                start,
+                descending,
+            )?,
+            EventFilter::MoveEventType(struct_name) => index_iter(
+                &tables.event_by_move_event,
+                struct_name.clone(),
+                start,
+                descending,
+            )?,
+            EventFilter::MoveEventModule { package, module } => index_iter(
+                &tables.event_by_event_module,
+                ModuleId::new((*package).into(), module.clone()),
+                start,
+                descending,
+            )?,
+            EventFilter::Sender(sender) => {
+                index_iter(&tables.event_by_sender, *sender, start, descending)?
+            }
+            EventFilter::All(filters) => {
+                for filter in filters {
+                    if let Some(candidates) = self.event_candidates(filter, start, descending)? {
+                        return Ok(Some(candidates));
+                    }
+                }
+                return Ok(None);
+            }
+            EventFilter::And(f1, f2) => match self.event_candidates(f1, start, descending)? {
+                Some(candidates) => candidates,
+                None => return self.event_candidates(f2, start, descending),
+            },
+            EventFilter::Any(filters) => {
+                let mut candidates = vec![];
+                for filter in filters {
+                    match self.event_candidates(filter, start, descending)? {
+                        Some(iter) => candidates.push(iter),
+                        None => return Ok(None),
+                    }
+                }
+                merge_by_sequence(candidates, descending)
+            }
+            EventFilter::Or(f1, f2) => {
+                let (Some(c1), Some(c2)) = (
+                    self.event_candidates(f1, start, descending)?,
+                    self.event_candidates(f2, start, descending)?,
+                ) else {
+                    return Ok(None);
+                };
+                merge_by_sequence(vec![c1, c2], descending)
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    fn event_matches(&self, filter: &EventFilter, id: EventId) -> SuiResult<bool> {
+        let tables = &self.tables;
+        Ok(match filter {
+            EventFilter::Transaction(digest) => self.get_transaction_seq(digest)? == Some(id.0),
+            EventFilter::MoveModule { package, module } => tables
+                .event_by_move_module
+                .contains_key(&(ModuleId::new((*package).into(), module.clone()), id))?,
+            EventFilter::MoveEventType(struct_name) => tables
+                .event_by_move_event
+                .contains_key(&(struct_name.clone(), id))?,
+            EventFilter::MoveEventModule { package, module } => tables
+                .event_by_event_module
+                .contains_key(&(ModuleId::new((*package).into(), module.clone()), id))?,
+            EventFilter::Sender(sender) => tables.event_by_sender.contains_key(&(*sender, id))?,
+            EventFilter::TimeRange {
+                start_time,
+                end_time,
+            } => tables
+                .event_order
+                .get(&id)?
+                .is_some_and(|(_, _, time)| *start_time <= time && time < *end_time),
+            EventFilter::All(filters) => {
+                for filter in filters {
+                    if !self.event_matches(filter, id)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            EventFilter::Any(filters) => {
+                for filter in filters {
+                    if self.event_matches(filter, id)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            EventFilter::And(f1, f2) => {
+                self.event_matches(f1, id)? && self.event_matches(f2, id)?
+            }
+            EventFilter::Or(f1, f2) => self.event_matches(f1, id)? || self.event_matches(f2, id)?,
+            EventFilter::Not(filter) => !self.event_matches(filter, id)?,
+            EventFilter::Package(_) | EventFilter::MoveEventField { .. } => {
+                return Err(unsupported_combination(filter))
+            }
+        })
+    }
+
+    fn event_order_iter(
+        &self,
+        start: EventId,
+        descending: bool,
+    ) -> SuiResult<SequencedIter<'_, EventId, EventIndex>> {
+        let iter = self.tables.event_order.unbounded_iter();
+        Ok(if descending {
+            Box::new(iter.skip_prior_to(&start)?.reverse())
+        } else {
+            Box::new(iter.skip_to(&start)?)
+        })
+    }
+
     pub fn get_dynamic_fields_iterator(
         &self,
         object: ObjectID,
@@ -1577,17 +2031,124 @@ impl IndexStore {
     }
 }
 
+/// Iterates over the entries of `index` under `key`, in order of their sequence numbers, starting
+/// from (and including) `start`.
+fn index_iter<'a, K, S, V>(
+    index: &'a DBMap<(K, S), V>,
+    key: K,
+    start: S,
+    reverse: bool,
+) -> SuiResult<SequencedIter<'a, S, V>>
+where
+    K: Clone + PartialEq + Serialize + DeserializeOwned + 'a,
+    S: Serialize + DeserializeOwned + 'a,
+    V: Serialize + DeserializeOwned + 'a,
+{
+    let iter = index.unbounded_iter();
+    let iter: SequencedIter<'a, (K, S), V> = if reverse {
+        Box::new(iter.skip_prior_to(&(key.clone(), start))?.reverse())
+    } else {
+        Box::new(iter.skip_to(&(key.clone(), start))?)
+    };
+    Ok(Box::new(
+        iter.take_while(move |((k, _), _)| *k == key)
+            .map(|((_, sequence), value)| (sequence, value)),
+    ))
+}
+
+/// Merges iterators that are each ordered by sequence number, dropping duplicates.
+fn merge_by_sequence<'a, S: Ord + 'a, V: 'a>(
+    iters: Vec<SequencedIter<'a, S, V>>,
+    reverse: bool,
+) -> SequencedIter<'a, S, V> {
+    Box::new(
+        iters
+            .into_iter()
+            .kmerge_by(move |a, b| if reverse { a.0 > b.0 } else { a.0 < b.0 })
+            .dedup_by(|a, b| a.0 == b.0),
+    )
+}
+
+/// Checks up-front that every filter inside a combination can be evaluated against the indexes,
+/// so that queries fail the same way regardless of the data they run over.
+fn check_transaction_filter(filter: &TransactionFilter) -> SuiResult<()> {
+    match filter {
+        TransactionFilter::All(filters) | TransactionFilter::Any(filters) => {
+            filters.iter().try_for_each(check_transaction_filter)
+        }
+        TransactionFilter::Not(filter) => check_transaction_filter(filter),
+        TransactionFilter::MoveFunction {
+            module: Some(_),
+            function: Some(_),
+            ..
+        }
+        | TransactionFilter::FromAddress(_)
+        | TransactionFilter::ToAddress(_)
+        | TransactionFilter::FromAndToAddress { .. }
+        | TransactionFilter::FromOrToAddress { .. }
+        | TransactionFilter::InputObject(_)
+        | TransactionFilter::ChangedObject(_) => Ok(()),
+        TransactionFilter::MoveFunction { .. }
+        | TransactionFilter::Checkpoint(_)
+        | TransactionFilter::TransactionKind(_)
+        | TransactionFilter::TransactionKindIn(_) => Err(unsupported_combination(filter)),
+    }
+}
+
+fn check_event_filter(filter: &EventFilter) -> SuiResult<()> {
+    match filter {
+        EventFilter::All(filters) | EventFilter::Any(filters) => {
+            filters.iter().try_for_each(check_event_filter)
+        }
+        EventFilter::And(f1, f2) | EventFilter::Or(f1, f2) => {
+            check_event_filter(f1)?;
+            check_event_filter(f2)
+        }
+        EventFilter::Not(filter) => check_event_filter(filter),
+        EventFilter::Transaction(_)
+        | EventFilter::MoveModule { .. }
+        | EventFilter::MoveEventType(_)
+        | EventFilter::MoveEventModule { .. }
+        | EventFilter::Sender(_)
+        | EventFilter::TimeRange { .. } => Ok(()),
+        EventFilter::Package(_) | EventFilter::MoveEventField { .. } => {
+            Err(unsupported_combination(filter))
+        }
+    }
+}
+
+fn unindexed_combination(filter: &impl std::fmt::Debug) -> SuiError {
+    SuiError::UserInputError {
+        error: UserInputError::Unsupported(format!(
+            "{filter:?} requires an indexed filter that every result matches, such as a sender"
+        )),
+    }
+}
+
+fn unsupported_combination(filter: &impl std::fmt::Debug) -> SuiError {
+    SuiError::UserInputError {
+        error: UserInputError::Unsupported(format!(
+            "{filter:?} cannot be combined with other filters"
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::indexes::ObjectIndexChanges;
     use crate::IndexStore;
     use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::StructTag;
     use prometheus::Registry;
     use std::collections::BTreeMap;
     use std::env::temp_dir;
-    use sui_types::base_types::{ObjectInfo, ObjectType, SuiAddress};
+    use sui_json_rpc_types::{EventFilter, TransactionFilter};
+    use sui_types::base_types::{ObjectID, ObjectInfo, ObjectType, SuiAddress};
     use sui_types::digests::TransactionDigest;
     use sui_types::effects::TransactionEvents;
+    use sui_types::error::SuiError;
+    use sui_types::event::{Event, EventID};
     use sui_types::gas_coin::GAS;
     use sui_types::object;
     use sui_types::object::Owner;
@@ -1729,4 +2290,224 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_compound_transaction_filters() -> anyhow::Result<()> {
+        let dir = tempfile::TempDir::new()?;
+        let index_store = IndexStore::new(dir.path().to_path_buf(), &Registry::default(), None);
+        let alice: SuiAddress = AccountAddress::random().into();
+        let bob: SuiAddress = AccountAddress::random().into();
+        let (x, y) = (ObjectID::random(), ObjectID::random());
+
+        let mut digests = vec![];
+        for (sender, input) in [(alice, x), (alice, y), (bob, x)] {
+            let digest = TransactionDigest::random();
+            index_store
+                .index_tx(
+                    sender,
+                    vec![input].into_iter(),
+                    vec![].into_iter(),
+                    vec![].into_iter(),
+                    &TransactionEvents { data: vec![] },
+                    ObjectIndexChanges {
+                        deleted_owners: vec![],
+                        deleted_dynamic_fields: vec![],
+                        new_owners: vec![],
+                        new_dynamic_fields: vec![],
+                    },
+                    &digest,
+                    1234,
+                    None,
+                    &BTreeMap::new(),
+                )
+                .await?;
+            digests.push(digest);
+        }
+
+        let query = |filter| {
+            index_store
+                .get_transactions(Some(filter), None, None, false)
+                .map(|(digests, _)| digests)
+        };
+
+        let all = TransactionFilter::All(vec![
+            TransactionFilter::FromAddress(alice),
+            TransactionFilter::InputObject(x),
+        ]);
+        assert_eq!(query(all)?, vec![digests[0]]);
+
+        let any = TransactionFilter::Any(vec![
+            TransactionFilter::InputObject(y),
+            TransactionFilter::FromAddress(bob),
+        ]);
+        assert_eq!(query(any)?, vec![digests[1], digests[2]]);
+
+        let not = TransactionFilter::All(vec![
+            TransactionFilter::InputObject(x),
+            TransactionFilter::Not(Box::new(TransactionFilter::FromAddress(alice))),
+        ]);
+        assert_eq!(query(not)?, vec![digests[2]]);
+
+        // paging in reverse from a cursor skips the cursor itself
+        let any = TransactionFilter::Any(vec![
+            TransactionFilter::InputObject(x),
+            TransactionFilter::InputObject(y),
+        ]);
+        let page = index_store.get_transactions(Some(any), Some(digests[2]), Some(1), true)?;
+        assert_eq!(page, (vec![digests[1]], None));
+
+        // a scan that runs out of candidates to check returns where to resume it from
+        let not = TransactionFilter::All(vec![
+            TransactionFilter::InputObject(x),
+            TransactionFilter::Not(Box::new(TransactionFilter::FromAddress(alice))),
+        ]);
+        let page = index_store.scan_transactions_by_filter(&not, None, None, false, 1)?;
+        assert_eq!(page, (vec![], Some(digests[0])));
+        let cursor = index_store.get_transaction_seq(&digests[0])?;
+        let page = index_store.scan_transactions_by_filter(&not, cursor, None, false, 1)?;
+        assert_eq!(page, (vec![digests[2]], None));
+
+        // combinations without an indexed filter that every result matches are rejected
+        for unindexed in [
+            TransactionFilter::Not(Box::new(TransactionFilter::FromAddress(alice))),
+            TransactionFilter::All(vec![]),
+            TransactionFilter::Any(vec![
+                TransactionFilter::InputObject(x),
+                TransactionFilter::Not(Box::new(TransactionFilter::FromAddress(alice))),
+            ]),
+        ] {
+            assert!(matches!(
+                query(unindexed),
+                Err(SuiError::UserInputError { .. })
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_compound_event_filters() -> anyhow::Result<()> {
+        let dir = tempfile::TempDir::new()?;
+        let index_store = IndexStore::new(dir.path().to_path_buf(), &Registry::default(), None);
+        let alice: SuiAddress = AccountAddress::random().into();
+        let bob: SuiAddress = AccountAddress::random().into();
+        let package = ObjectID::random();
+        let struct_tag = |module: &str, name: &str| StructTag {
+            address: AccountAddress::TWO,
+            module: Identifier::new(module).unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        };
+
+        let mut digests = vec![];
+        for (sender, module, type_, timestamp_ms) in [
+            (alice, "m1", struct_tag("a", "A"), 1000),
+            (alice, "m2", struct_tag("b", "B"), 2000),
+            (bob, "m1", struct_tag("a", "A"), 3000),
+        ] {
+            let digest = TransactionDigest::random();
+            let event = Event {
+                package_id: package,
+                transaction_module: Identifier::new(module)?,
+                sender,
+                type_,
+                contents: vec![],
+            };
+            index_store
+                .index_tx(
+                    sender,
+                    vec![].into_iter(),
+                    vec![].into_iter(),
+                    vec![].into_iter(),
+                    &TransactionEvents { data: vec![event] },
+                    ObjectIndexChanges {
+                        deleted_owners: vec![],
+                        deleted_dynamic_fields: vec![],
+                        new_owners: vec![],
+                        new_dynamic_fields: vec![],
+                    },
+                    &digest,
+                    timestamp_ms,
+                    None,
+                    &BTreeMap::new(),
+                )
+                .await?;
+            digests.push(digest);
+        }
+
+        let query = |filter| {
+            index_store
+                .events_by_filter(&filter, 0, 0, 10, false)
+                .map(|(events, _)| {
+                    events
+                        .into_iter()
+                        .map(|(_, digest, _, _)| digest)
+                        .collect::<Vec<_>>()
+                })
+        };
+
+        let all = EventFilter::All(vec![
+            EventFilter::Sender(alice),
+            EventFilter::MoveEventType(struct_tag("a", "A")),
+        ]);
+        assert_eq!(query(all)?, vec![digests[0]]);
+
+        let any = EventFilter::Any(vec![
+            EventFilter::Sender(bob),
+            EventFilter::MoveModule {
+                package,
+                module: Identifier::new("m2")?,
+            },
+        ]);
+        assert_eq!(query(any)?, vec![digests[1], digests[2]]);
+
+        let and = EventFilter::And(
+            Box::new(EventFilter::TimeRange {
+                start_time: 1500,
+                end_time: 4000,
+            }),
+            Box::new(EventFilter::Sender(alice)),
+        );
+        assert_eq!(query(and)?, vec![digests[1]]);
+
+        let not = EventFilter::And(
+            Box::new(EventFilter::MoveEventType(struct_tag("a", "A"))),
+            Box::new(EventFilter::Not(Box::new(EventFilter::Sender(alice)))),
+        );
+        assert_eq!(query(not.clone())?, vec![digests[2]]);
+
+        // a scan that runs out of candidates to check returns where to resume it from
+        let (events, cursor) = index_store.scan_events_by_filter(&not, 0, 0, 10, false, 1)?;
+        assert!(events.is_empty());
+        assert_eq!(
+            cursor,
+            Some(EventID {
+                tx_digest: digests[0],
+                event_seq: 0
+            })
+        );
+        let tx_seq = index_store.get_transaction_seq(&digests[0])?.unwrap();
+        let (events, cursor) = index_store.scan_events_by_filter(&not, tx_seq, 0, 10, false, 2)?;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].1, digests[2]);
+        assert_eq!(cursor, None);
+
+        // combinations without an indexed filter that every result matches are rejected
+        let time_range = || EventFilter::TimeRange {
+            start_time: 0,
+            end_time: u64::MAX,
+        };
+        for unindexed in [
+            EventFilter::Not(Box::new(EventFilter::Sender(alice))),
+            EventFilter::All(vec![time_range()]),
+            EventFilter::Any(vec![EventFilter::Sender(alice), time_range()]),
+        ] {
+            assert!(matches!(
+                query(unindexed),
+                Err(SuiError::UserInputError { .. })
+            ));
+        }
+
+        Ok(())
+    }
 }
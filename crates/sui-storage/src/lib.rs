@@ -8,6 +8,7 @@ use crate::blob::BlobIter;
 use anyhow::{anyhow, Result};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use bytes::{Buf, Bytes};
+use clap::ValueEnum;
 use fastcrypto::hash::{HashFunction, Sha3_256};
 pub use indexes::{IndexStore, IndexStoreTables};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -27,6 +28,7 @@ pub mod key_value_store;
 pub mod key_value_store_metrics;
 pub mod mutex_table;
 pub mod object_store;
+pub mod object_store_key_value_store;
 pub mod package_object_cache;
 pub mod sharded_lru;
 pub mod write_path_pending_tx_log;
@@ -42,7 +44,16 @@ pub enum StorageFormat {
 }
 
 #[derive(
-    Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, TryFromPrimitive, IntoPrimitive,
+    Copy,
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    TryFromPrimitive,
+    IntoPrimitive,
+    ValueEnum,
 )]
 #[repr(u8)]
 pub enum FileCompression {
@@ -0,0 +1,119 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use std::sync::Arc;
+use sui_types::{
+    digests::{
+        CheckpointContentsDigest, CheckpointDigest, TransactionDigest, TransactionEventsDigest,
+    },
+    error::{SuiError, SuiResult},
+    messages_checkpoint::CheckpointSequenceNumber,
+};
+use tracing::{info, trace};
+
+use crate::http_key_value_store::{
+    get_transaction_checkpoint, key_to_path_elements, multi_get, multi_get_checkpoints, Key,
+    KeyFetcher,
+};
+use crate::key_value_store::{
+    KVStoreCheckpointData, KVStoreTransactionData, TransactionKeyValueStore,
+    TransactionKeyValueStoreTrait,
+};
+use crate::key_value_store_metrics::KeyValueStoreMetrics;
+use crate::object_store::ObjectStoreConfig;
+
+/// A key value store reading from any object store, including a local directory.
+/// Values are stored with the same layout served by the http key value store,
+/// `{encoded key}/{item type}`, so a store written by the `sui-kvstore` uploader can be read
+/// directly or served over http.
+pub struct ObjectStoreKVStore {
+    store: Arc<DynObjectStore>,
+}
+
+/// The location of a value in the store, from its encoded key and item type.
+pub fn value_path(encoded_key: &str, item_type: &str) -> Path {
+    Path::from(format!("{}/{}", encoded_key, item_type))
+}
+
+impl ObjectStoreKVStore {
+    pub fn new_kv(
+        config: &ObjectStoreConfig,
+        metrics: Arc<KeyValueStoreMetrics>,
+    ) -> SuiResult<TransactionKeyValueStore> {
+        let inner = Arc::new(Self::new(config)?);
+        Ok(TransactionKeyValueStore::new(
+            "object_store",
+            metrics,
+            inner,
+        ))
+    }
+
+    pub fn new(config: &ObjectStoreConfig) -> SuiResult<Self> {
+        info!(
+            "creating ObjectStoreKVStore with store: {:?}",
+            config.object_store
+        );
+        let store = config
+            .make()
+            .map_err(|e| SuiError::GenericStorageError(e.to_string()))?;
+        Ok(Self { store })
+    }
+}
+
+#[async_trait]
+impl KeyFetcher for ObjectStoreKVStore {
+    async fn fetch(&self, key: Key) -> SuiResult<Option<Bytes>> {
+        let (encoded_key, item_type) = key_to_path_elements(&key)?;
+        let path = value_path(&encoded_key, item_type);
+        trace!("fetching path: {}", path);
+        match self.store.get(&path).await {
+            Ok(result) => result
+                .bytes()
+                .await
+                .map(Some)
+                .map_err(|e| SuiError::GenericStorageError(e.to_string())),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(SuiError::GenericStorageError(e.to_string())),
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionKeyValueStoreTrait for ObjectStoreKVStore {
+    async fn multi_get(
+        &self,
+        transactions: &[TransactionDigest],
+        effects: &[TransactionDigest],
+        events: &[TransactionEventsDigest],
+    ) -> SuiResult<KVStoreTransactionData> {
+        multi_get(self, transactions, effects, events).await
+    }
+
+    async fn multi_get_checkpoints(
+        &self,
+        checkpoint_summaries: &[CheckpointSequenceNumber],
+        checkpoint_contents: &[CheckpointSequenceNumber],
+        checkpoint_summaries_by_digest: &[CheckpointDigest],
+        checkpoint_contents_by_digest: &[CheckpointContentsDigest],
+    ) -> SuiResult<KVStoreCheckpointData> {
+        multi_get_checkpoints(
+            self,
+            checkpoint_summaries,
+            checkpoint_contents,
+            checkpoint_summaries_by_digest,
+            checkpoint_contents_by_digest,
+        )
+        .await
+    }
+
+    async fn deprecated_get_transaction_checkpoint(
+        &self,
+        digest: TransactionDigest,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        get_transaction_checkpoint(self, digest).await
+    }
+}
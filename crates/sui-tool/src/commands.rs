@@ -3,9 +3,10 @@
 
 use crate::{
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
-    download_db_snapshot, get_object, get_transaction_block, make_clients,
-    restore_from_db_checkpoint, state_sync_from_archive, verify_archive,
-    verify_archive_by_checksum, ConciseObjectOutput, GroupedObjectOutput, VerboseObjectOutput,
+    download_db_snapshot, get_object, get_transaction_block, list_db_backups, make_clients,
+    rechunk_archive, restore_from_db_backup, restore_from_db_checkpoint, state_sync_from_archive,
+    verify_archive, verify_archive_by_checksum, ConciseObjectOutput, GroupedObjectOutput,
+    VerboseObjectOutput,
 };
 use anyhow::{anyhow, Result};
 use std::env;
@@ -20,7 +21,9 @@ use clap::*;
 use fastcrypto::encoding::Encoding;
 use sui_config::Config;
 use sui_core::authority_aggregator::AuthorityAggregatorBuilder;
+use sui_storage::blob::BlobEncoding;
 use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+use sui_storage::FileCompression;
 use sui_types::messages_checkpoint::{
     CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber,
 };
@@ -152,6 +155,28 @@ pub enum ToolCommand {
         download_concurrency: usize,
     },
 
+    /// Tool to copy the archive store into a new archive store with files of the target size
+    #[command(name = "rechunk-archive")]
+    RechunkArchive {
+        #[command(flatten)]
+        object_store_config: ObjectStoreConfig,
+        /// Path to a yaml file with the object store config of the new archive
+        #[arg(long = "destination-config")]
+        destination_config: PathBuf,
+        /// Local directory to stage files in before they are uploaded to the new archive
+        #[arg(long = "staging-dir")]
+        staging_dir: PathBuf,
+        /// Size in bytes of a file's contents before compression at which it is cut
+        #[arg(long = "target-file-size", default_value_t = 1024 * 1024 * 1024)]
+        target_file_size: usize,
+        #[arg(long = "file-compression", value_enum, default_value_t = FileCompression::Zstd)]
+        file_compression: FileCompression,
+        #[arg(long = "blob-encoding", value_enum, default_value_t = BlobEncoding::Bcs)]
+        blob_encoding: BlobEncoding,
+        #[arg(default_value_t = 5)]
+        download_concurrency: usize,
+    },
+
     #[command(name = "dump-validators")]
     DumpValidators {
         #[arg(long = "genesis")]
@@ -201,6 +226,27 @@ pub enum ToolCommand {
         db_checkpoint_path: PathBuf,
     },
 
+    /// List the incremental backups of the databases in the backup store
+    #[command(name = "list-db-backups")]
+    ListDBBackups {
+        #[command(flatten)]
+        object_store_config: ObjectStoreConfig,
+    },
+
+    /// Restore the databases from an incremental backup: the backup with the given id, the
+    /// latest backup taken at or before the given checkpoint, or else the latest backup
+    #[command(name = "restore-db-backup")]
+    RestoreFromDBBackup {
+        #[arg(long = "config-path")]
+        config_path: PathBuf,
+        #[command(flatten)]
+        object_store_config: ObjectStoreConfig,
+        #[arg(long = "backup-id", conflicts_with = "checkpoint")]
+        backup_id: Option<u64>,
+        #[arg(long = "checkpoint")]
+        checkpoint: Option<CheckpointSequenceNumber>,
+    },
+
     #[clap(name = "download-db-snapshot")]
     DownloadDBSnapshot {
         #[clap(long = "epoch")]
@@ -413,6 +459,20 @@ impl ToolCommand {
                 let config = sui_config::NodeConfig::load(config_path)?;
                 restore_from_db_checkpoint(&config, &db_checkpoint_path).await?;
             }
+            ToolCommand::ListDBBackups {
+                object_store_config,
+            } => {
+                list_db_backups(object_store_config).await?;
+            }
+            ToolCommand::RestoreFromDBBackup {
+                config_path,
+                object_store_config,
+                backup_id,
+                checkpoint,
+            } => {
+                let config = sui_config::NodeConfig::load(config_path)?;
+                restore_from_db_backup(&config, object_store_config, backup_id, checkpoint).await?;
+            }
             ToolCommand::DownloadDBSnapshot {
                 epoch,
                 genesis,
@@ -566,6 +626,26 @@ impl ToolCommand {
             } => {
                 verify_archive_by_checksum(object_store_config, download_concurrency).await?;
             }
+            ToolCommand::RechunkArchive {
+                object_store_config,
+                destination_config,
+                staging_dir,
+                target_file_size,
+                file_compression,
+                blob_encoding,
+                download_concurrency,
+            } => {
+                rechunk_archive(
+                    object_store_config,
+                    &destination_config,
+                    staging_dir,
+                    target_file_size,
+                    file_compression,
+                    blob_encoding,
+                    download_concurrency,
+                )
+                .await?;
+            }
             ToolCommand::SignTransaction {
                 genesis,
                 sender_signed_data,
@@ -86,6 +86,36 @@ pub fn table_summary(
     .map_err(|err| anyhow!(err.to_string()))
 }
 
+/// Upgrades the values of a versioned table to the current schema version. The node must not be
+/// running, as the tables are opened for writing.
+pub fn migrate_table(
+    store_name: StoreName,
+    epoch: Option<EpochId>,
+    db_path: PathBuf,
+    table_name: &str,
+) -> anyhow::Result<u64> {
+    match store_name {
+        StoreName::Validator => {
+            let epoch_tables = AuthorityEpochTables::describe_tables();
+            if epoch_tables.contains_key(table_name) {
+                let epoch = epoch.ok_or_else(|| anyhow!("--epoch is required"))?;
+                AuthorityEpochTables::open(epoch, &db_path, None).migrate_table(table_name)
+            } else {
+                AuthorityPerpetualTables::open(&db_path, None).migrate_table(table_name)
+            }
+        }
+        StoreName::Index => {
+            IndexStoreTables::open_tables_read_write(db_path, MetricConf::default(), None, None)
+                .migrate_table(table_name)
+        }
+        StoreName::Epoch => {
+            CommitteeStoreTables::open_tables_read_write(db_path, MetricConf::default(), None, None)
+                .migrate_table(table_name)
+        }
+    }
+    .map_err(|err| anyhow!(err.to_string()))
+}
+
 pub fn print_table_metadata(
     store_name: StoreName,
     epoch: Option<EpochId>,
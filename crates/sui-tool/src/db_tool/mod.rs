@@ -3,7 +3,9 @@
 
 use self::db_dump::{dump_table, duplicate_objects_summary, list_tables, table_summary, StoreName};
 use self::index_search::{search_index, SearchRange};
-use crate::db_tool::db_dump::{compact, print_table_metadata, prune_checkpoints, prune_objects};
+use crate::db_tool::db_dump::{
+    compact, migrate_table, print_table_metadata, prune_checkpoints, prune_objects,
+};
 use anyhow::{anyhow, bail};
 use clap::Parser;
 use narwhal_storage::NodeStorage;
@@ -43,6 +45,7 @@ pub enum DbToolCommand {
     Compact,
     PruneObjects,
     PruneCheckpoints,
+    MigrateTable(MigrateTableOptions),
 }
 
 #[derive(Parser)]
@@ -91,6 +94,20 @@ pub struct Options {
     epoch: Option<EpochId>,
 }
 
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct MigrateTableOptions {
+    /// The type of store holding the table
+    #[arg(long = "store", short = 's', value_enum)]
+    store_name: StoreName,
+    /// The name of the table to migrate. Its values must be versioned
+    #[arg(long = "table-name", short = 't')]
+    table_name: String,
+    /// The epoch to use when loading AuthorityEpochTables.
+    #[arg(long = "epoch", short = 'e')]
+    epoch: Option<EpochId>,
+}
+
 #[derive(Parser)]
 #[command(rename_all = "kebab-case")]
 pub struct PrintConsensusCommitOptions {
@@ -192,6 +209,11 @@ pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> an
         DbToolCommand::Compact => compact(db_path),
         DbToolCommand::PruneObjects => prune_objects(db_path).await,
         DbToolCommand::PruneCheckpoints => prune_checkpoints(db_path).await,
+        DbToolCommand::MigrateTable(d) => {
+            let migrated = migrate_table(d.store_name, d.epoch, db_path, &d.table_name)?;
+            println!("Upgraded {} values of table {}", migrated, d.table_name);
+            Ok(())
+        }
         DbToolCommand::IndexSearchKeyRange(rg) => {
             let res = search_index(
                 db_path,
@@ -36,11 +36,14 @@ use sui_config::node::ArchiveReaderConfig;
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::authority::AuthorityStore;
 use sui_core::checkpoints::CheckpointStore;
+use sui_core::db_backup_handler::{verify_restored_backup, BackupStore};
 use sui_core::db_checkpoint_handler::SUCCESS_MARKER;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_core::storage::RocksDbStore;
+use sui_storage::blob::BlobEncoding;
 use sui_storage::object_store::util::{copy_file, get_path};
 use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+use sui_storage::FileCompression;
 use sui_types::messages_grpc::{
     ObjectInfoRequest, ObjectInfoRequestKind, ObjectInfoResponse, TransactionInfoRequest,
     TransactionStatus,
@@ -622,6 +625,77 @@ pub async fn restore_from_db_checkpoint(
     Ok(())
 }
 
+pub async fn list_db_backups(object_store_config: ObjectStoreConfig) -> Result<(), anyhow::Error> {
+    let manifest = BackupStore::new(&object_store_config)?
+        .read_manifest()
+        .await?;
+    for chain in manifest.chains.iter() {
+        println!("Chain {}:", chain.id);
+        for backup in chain.backups.iter() {
+            println!(
+                "  Backup {}: epoch {}, checkpoint {} ({}), taken at {} ms, {} files, {} bytes",
+                backup.id,
+                backup.epoch,
+                backup.checkpoint,
+                backup.checkpoint_digest,
+                backup.timestamp_ms,
+                backup.files.len(),
+                backup.files.iter().map(|file| file.size).sum::<u64>()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Restores the node's databases from the backup with the given id or, if no id is given, from
+/// the latest backup taken at or before `checkpoint`, or from the latest backup. The restored
+/// databases are checked to hold the checkpoint the backup was taken at before they are moved
+/// to the db path.
+pub async fn restore_from_db_backup(
+    config: &NodeConfig,
+    object_store_config: ObjectStoreConfig,
+    backup_id: Option<u64>,
+    checkpoint: Option<u64>,
+) -> Result<(), anyhow::Error> {
+    let db_path = config.db_path();
+    if db_path.exists() {
+        return Err(anyhow!(
+            "Db path {} already exists, move it away before restoring",
+            db_path.display()
+        ));
+    }
+    let backup_store = BackupStore::new(&object_store_config)?;
+    let manifest = backup_store.read_manifest().await?;
+    let backup = manifest
+        .find_backup(backup_id, checkpoint)
+        .ok_or_else(|| anyhow!("No backup matches the given backup id or checkpoint"))?;
+    info!(
+        "Restoring backup {} taken at checkpoint {}",
+        backup.id, backup.checkpoint
+    );
+
+    // The suffix is appended to the file name, as the db path may already have an extension
+    let mut restore_file_name = db_path
+        .file_name()
+        .ok_or_else(|| anyhow!("Db path {} has no file name", db_path.display()))?
+        .to_os_string();
+    restore_file_name.push(".restore");
+    let restore_path = db_path.with_file_name(restore_file_name);
+    if restore_path.exists() {
+        fs::remove_dir_all(&restore_path)?;
+    }
+    backup_store.restore(backup, &restore_path).await?;
+    verify_restored_backup(backup, &restore_path)?;
+    fs::rename(&restore_path, &db_path)?;
+    println!(
+        "Restored backup {} at checkpoint {} to {}",
+        backup.id,
+        backup.checkpoint,
+        db_path.display()
+    );
+    Ok(())
+}
+
 pub async fn download_db_snapshot(
     path: &Path,
     epoch: u32,
@@ -949,6 +1023,34 @@ pub async fn verify_archive_by_checksum(
     verify_archive_with_checksums(remote_store_config, concurrency).await
 }
 
+pub async fn rechunk_archive(
+    remote_store_config: ObjectStoreConfig,
+    destination_config: &Path,
+    staging_dir: PathBuf,
+    target_file_size: usize,
+    file_compression: FileCompression,
+    blob_encoding: BlobEncoding,
+    concurrency: usize,
+) -> Result<()> {
+    let destination_store_config: ObjectStoreConfig =
+        serde_yaml::from_reader(fs::File::open(destination_config)?)?;
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(staging_dir),
+        ..Default::default()
+    };
+    sui_archival::rechunk_archive(
+        remote_store_config,
+        local_store_config,
+        destination_store_config,
+        file_compression,
+        blob_encoding,
+        target_file_size,
+        concurrency,
+    )
+    .await
+}
+
 pub async fn state_sync_from_archive(
     path: &Path,
     genesis: &Path,
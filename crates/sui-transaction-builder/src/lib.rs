@@ -13,7 +13,7 @@ use move_binary_format::file_format::SignatureToken;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 
-use sui_json::{resolve_move_function_args, ResolvedCallArg, SuiJsonValue};
+use sui_json::{resolve_partial_move_function_args, ResolvedCallArg, SuiJsonValue};
 use sui_json_rpc_types::{
     RPCTransactionRequestParams, SuiData, SuiObjectDataOptions, SuiObjectResponse, SuiRawData,
     SuiTypeTag,
@@ -28,10 +28,13 @@ use sui_types::object::{Object, Owner};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{
-    Argument, CallArg, Command, InputObjectKind, ObjectArg, TransactionData, TransactionKind,
+    Argument, CallArg, Command, InputObjectKind, ObjectArg, ProgrammableTransaction,
+    TransactionData, TransactionDataAPI, TransactionKind,
 };
 use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
+pub mod offline;
+
 #[async_trait]
 pub trait DataReader {
     async fn get_owned_objects(
@@ -49,6 +52,28 @@ pub trait DataReader {
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
 }
 
+/// The address passed as `argument` of `pt`, if it is a pure input.
+fn pure_address(pt: &ProgrammableTransaction, argument: &Argument) -> Option<SuiAddress> {
+    let Argument::Input(index) = argument else {
+        return None;
+    };
+    let CallArg::Pure(bytes) = pt.inputs.get(*index as usize)? else {
+        return None;
+    };
+    bcs::from_bytes(bytes).ok()
+}
+
+/// An argument of a Move call in a programmable transaction.
+#[derive(Debug, Clone)]
+pub enum ProgrammableMoveCallArg {
+    /// A value that is checked against the signature of the function, like the args of
+    /// [TransactionBuilder::move_call].
+    Json(SuiJsonValue),
+    /// An argument that is already part of the transaction, e.g. the gas coin or the result of
+    /// an earlier command.
+    Argument(Argument),
+}
+
 #[derive(Clone)]
 pub struct TransactionBuilder(Arc<dyn DataReader + Sync + Send>);
 
@@ -300,6 +325,32 @@ impl TransactionBuilder {
         type_args: Vec<SuiTypeTag>,
         call_args: Vec<SuiJsonValue>,
     ) -> anyhow::Result<()> {
+        self.programmable_move_call(
+            builder,
+            package,
+            module,
+            function,
+            type_args,
+            call_args
+                .into_iter()
+                .map(ProgrammableMoveCallArg::Json)
+                .collect(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Add a Move call to `builder` and return its result. Unlike [Self::single_move_call], the
+    /// args may refer to the results of earlier commands of the transaction.
+    pub async fn programmable_move_call(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<SuiTypeTag>,
+        call_args: Vec<ProgrammableMoveCallArg>,
+    ) -> anyhow::Result<Argument> {
         let module = Identifier::from_str(module)?;
         let function = Identifier::from_str(function)?;
 
@@ -309,15 +360,169 @@ impl TransactionBuilder {
             .collect::<Result<Vec<_>, _>>()?;
 
         let call_args = self
-            .resolve_and_checks_json_args(
-                builder, package, &module, &function, &type_args, call_args,
-            )
+            .resolve_and_checks_args(builder, package, &module, &function, &type_args, call_args)
             .await?;
 
-        builder.command(Command::move_call(
+        Ok(builder.command(Command::move_call(
             package, module, function, type_args, call_args,
-        ));
-        Ok(())
+        )))
+    }
+
+    /// Add `object_id` as an input of `builder`, as a shared object if it is shared.
+    pub async fn object_input(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        object_id: ObjectID,
+        is_mutable_ref: bool,
+    ) -> anyhow::Result<Argument> {
+        let obj_arg = self
+            .get_object_arg(object_id, &mut BTreeMap::new(), is_mutable_ref)
+            .await?;
+        builder.obj(obj_arg)
+    }
+
+    /// Wrap a programmable transaction into a [TransactionData], selecting a gas coin that is
+    /// not one of its inputs if `gas` is not given.
+    pub async fn programmable_transaction(
+        &self,
+        signer: SuiAddress,
+        pt: ProgrammableTransaction,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        self.programmable_transaction_allow_sponsor(signer, pt, gas, gas_budget, signer)
+            .await
+    }
+
+    /// Same as [Self::programmable_transaction], except that the gas is paid by `sponsor`: the
+    /// gas coin is selected among the coins of the sponsor, and the transaction has to be signed
+    /// by both the sender and the sponsor. The commands cannot move value out of the gas coin to
+    /// anyone but the sponsor.
+    pub async fn programmable_transaction_allow_sponsor(
+        &self,
+        signer: SuiAddress,
+        pt: ProgrammableTransaction,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+        sponsor: SuiAddress,
+    ) -> anyhow::Result<TransactionData> {
+        // The gas coin belongs to the sponsor, the sender must not be able to spend it.
+        if sponsor != signer {
+            ensure!(
+                !self.moves_gas_coin_value(&pt, sponsor).await?,
+                "A sponsored transaction cannot move value out of the gas coin of its sponsor"
+            );
+        }
+        let input_objects = pt
+            .input_objects()?
+            .iter()
+            .flat_map(|obj| match obj {
+                InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
+                _ => None,
+            })
+            .collect();
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas = self
+            .select_gas(sponsor, gas, gas_budget, input_objects, gas_price)
+            .await?;
+
+        Ok(TransactionData::new_programmable_allow_sponsor(
+            signer,
+            vec![gas],
+            pt,
+            gas_budget,
+            gas_price,
+            sponsor,
+        ))
+    }
+
+    /// Make `sponsor` pay the gas of `tx_data`, replacing its gas payment with a coin of the
+    /// sponsor. Only programmable transactions that do not move value out of the gas coin can be
+    /// sponsored.
+    pub async fn sponsor_transaction(
+        &self,
+        tx_data: TransactionData,
+        sponsor: SuiAddress,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> anyhow::Result<TransactionData> {
+        let sender = tx_data.sender();
+        let gas_budget = gas_budget.unwrap_or_else(|| tx_data.gas_budget());
+        let TransactionKind::ProgrammableTransaction(pt) = tx_data.into_kind() else {
+            bail!("Only programmable transactions can be sponsored");
+        };
+        self.programmable_transaction_allow_sponsor(sender, pt, gas, gas_budget, sponsor)
+            .await
+    }
+
+    /// Returns true if a command of `pt` can move value out of the gas coin to an address other
+    /// than `gas_owner`: transferring the gas coin, or a coin split off from it, to another
+    /// address, merging either into another coin, or passing either to a Move function by value
+    /// or by mutable reference.
+    async fn moves_gas_coin_value(
+        &self,
+        pt: &ProgrammableTransaction,
+        gas_owner: SuiAddress,
+    ) -> Result<bool, anyhow::Error> {
+        // Whether the result of each command holds value taken from the gas coin
+        let mut from_gas = Vec::with_capacity(pt.commands.len());
+        let is_gas = |from_gas: &Vec<bool>, argument: &Argument| match argument {
+            Argument::GasCoin => true,
+            Argument::Result(index) | Argument::NestedResult(index, _) => {
+                from_gas.get(*index as usize).copied().unwrap_or(false)
+            }
+            Argument::Input(_) => false,
+        };
+        for command in &pt.commands {
+            let result_from_gas = match command {
+                Command::SplitCoins(coin, _) => is_gas(&from_gas, coin),
+                Command::MakeMoveVec(_, elements) => {
+                    elements.iter().any(|element| is_gas(&from_gas, element))
+                }
+                Command::MergeCoins(coin, coins) => {
+                    if !is_gas(&from_gas, coin) && coins.iter().any(|c| is_gas(&from_gas, c)) {
+                        return Ok(true);
+                    }
+                    false
+                }
+                Command::TransferObjects(objects, recipient) => {
+                    if objects.iter().any(|object| is_gas(&from_gas, object))
+                        && pure_address(pt, recipient) != Some(gas_owner)
+                    {
+                        return Ok(true);
+                    }
+                    false
+                }
+                Command::MoveCall(call) => {
+                    if call.arguments.iter().any(|arg| is_gas(&from_gas, arg)) {
+                        let package = self.get_move_package(call.package).await?;
+                        let parameters = resolve_partial_move_function_args(
+                            &package,
+                            call.module.clone(),
+                            call.function.clone(),
+                            &call.type_arguments,
+                            call.arguments.iter().map(|_| None).collect(),
+                        )?;
+                        // Only an immutable reference leaves the coin untouched
+                        if call
+                            .arguments
+                            .iter()
+                            .zip(parameters)
+                            .any(|(arg, (_, param))| {
+                                is_gas(&from_gas, arg)
+                                    && !matches!(param, SignatureToken::Reference(_))
+                            })
+                        {
+                            return Ok(true);
+                        }
+                    }
+                    false
+                }
+                Command::Publish(..) | Command::Upgrade(..) => false,
+            };
+            from_gas.push(result_from_gas);
+        }
+        Ok(false)
     }
 
     async fn get_object_arg(
@@ -349,15 +554,7 @@ impl TransactionBuilder {
         })
     }
 
-    async fn resolve_and_checks_json_args(
-        &self,
-        builder: &mut ProgrammableTransactionBuilder,
-        package_id: ObjectID,
-        module: &Identifier,
-        function: &Identifier,
-        type_args: &[TypeTag],
-        json_args: Vec<SuiJsonValue>,
-    ) -> Result<Vec<Argument>, anyhow::Error> {
+    async fn get_move_package(&self, package_id: ObjectID) -> Result<MovePackage, anyhow::Error> {
         let object = self
             .0
             .get_object_with_options(package_id, SuiObjectDataOptions::bcs_lossless())
@@ -366,16 +563,35 @@ impl TransactionBuilder {
         let Some(SuiRawData::Package(package)) = object.bcs else {
             bail!("Bcs field in object [{}] is missing or not a package.", package_id);
         };
-        let package: MovePackage = MovePackage::new(
+        Ok(MovePackage::new(
             package.id,
             object.version,
             package.module_map,
             ProtocolConfig::get_for_min_version().max_move_package_size(),
             package.type_origin_table,
             package.linkage_table,
-        )?;
+        )?)
+    }
 
-        let json_args_and_tokens = resolve_move_function_args(
+    async fn resolve_and_checks_args(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        package_id: ObjectID,
+        module: &Identifier,
+        function: &Identifier,
+        type_args: &[TypeTag],
+        call_args: Vec<ProgrammableMoveCallArg>,
+    ) -> Result<Vec<Argument>, anyhow::Error> {
+        let package = self.get_move_package(package_id).await?;
+
+        let json_args = call_args
+            .iter()
+            .map(|arg| match arg {
+                ProgrammableMoveCallArg::Json(json) => Some(json.clone()),
+                ProgrammableMoveCallArg::Argument(_) => None,
+            })
+            .collect();
+        let json_args_and_tokens = resolve_partial_move_function_args(
             &package,
             module.clone(),
             function.clone(),
@@ -385,7 +601,17 @@ impl TransactionBuilder {
 
         let mut args = Vec::new();
         let mut objects = BTreeMap::new();
-        for (arg, expected_type) in json_args_and_tokens {
+        for ((arg, expected_type), call_arg) in json_args_and_tokens.into_iter().zip(call_args) {
+            let arg = match (arg, call_arg) {
+                (_, ProgrammableMoveCallArg::Argument(arg)) => {
+                    args.push(arg);
+                    continue;
+                }
+                (Some(arg), ProgrammableMoveCallArg::Json(_)) => arg,
+                (None, ProgrammableMoveCallArg::Json(_)) => {
+                    bail!("JSON arg of Move call was not resolved")
+                }
+            };
             args.push(match arg {
                 ResolvedCallArg::Pure(p) => builder.input(CallArg::Pure(p)),
 
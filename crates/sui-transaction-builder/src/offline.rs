@@ -0,0 +1,179 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, ensure};
+use async_trait::async_trait;
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiObjectResponse};
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
+use sui_types::committee::EpochId;
+use sui_types::object::{Object, ObjectRead, Owner};
+use sui_types::transaction::{TransactionData, TransactionDataAPI};
+
+use crate::DataReader;
+
+/// The part of the chain state needed to build transactions without access to a full node: the
+/// objects used by the transactions (gas coins, inputs and called packages), the reference gas
+/// price and the protocol version of the epoch the snapshot was taken in.
+///
+/// Objects are not updated when a transaction built from the snapshot is executed, so a new
+/// snapshot has to be taken before building transactions using the objects it modified.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineSnapshot {
+    pub epoch: EpochId,
+    pub reference_gas_price: u64,
+    pub protocol_version: u64,
+    /// BCS serialized objects, as Base64 encoded strings.
+    #[serde(with = "objects_as_base64")]
+    pub objects: Vec<Object>,
+}
+
+impl OfflineSnapshot {
+    pub fn read(path: &Path) -> Result<Self, anyhow::Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read offline snapshot {:?}: {e}", path))?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Invalid offline snapshot {:?}: {e}", path))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), anyhow::Error> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .map_err(|e| anyhow!("Cannot write offline snapshot {:?}: {e}", path))
+    }
+}
+
+/// A [DataReader] answering from an [OfflineSnapshot] instead of a full node.
+pub struct OfflineDataReader {
+    epoch: EpochId,
+    reference_gas_price: u64,
+    protocol_version: u64,
+    objects: BTreeMap<ObjectID, Object>,
+}
+
+impl OfflineDataReader {
+    pub fn new(snapshot: OfflineSnapshot) -> Self {
+        Self {
+            epoch: snapshot.epoch,
+            reference_gas_price: snapshot.reference_gas_price,
+            protocol_version: snapshot.protocol_version,
+            objects: snapshot
+                .objects
+                .into_iter()
+                .map(|object| (object.id(), object))
+                .collect(),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, anyhow::Error> {
+        Ok(Self::new(OfflineSnapshot::read(path)?))
+    }
+
+    pub fn epoch(&self) -> EpochId {
+        self.epoch
+    }
+
+    /// The protocol config of the snapshot, if its protocol version is supported by this binary.
+    pub fn protocol_config(&self) -> Result<ProtocolConfig, anyhow::Error> {
+        ProtocolConfig::get_for_version_if_supported(
+            ProtocolVersion::new(self.protocol_version),
+            Chain::Unknown,
+        )
+        .ok_or_else(|| {
+            anyhow!(
+                "Protocol version {} of the offline snapshot is not supported",
+                self.protocol_version
+            )
+        })
+    }
+
+    /// Check `tx_data` against the limits of the protocol version of the snapshot, since a
+    /// transaction built offline cannot be dry run before it is signed.
+    pub fn check_transaction(&self, tx_data: &TransactionData) -> Result<(), anyhow::Error> {
+        let max_gas_budget = self.protocol_config()?.max_tx_gas();
+        ensure!(
+            tx_data.gas_budget() <= max_gas_budget,
+            "Gas budget {} is above the maximum gas budget {max_gas_budget} of protocol version {}",
+            tx_data.gas_budget(),
+            self.protocol_version
+        );
+        Ok(())
+    }
+
+    pub fn get_object(&self, object_id: &ObjectID) -> Result<&Object, anyhow::Error> {
+        self.objects
+            .get(object_id)
+            .ok_or_else(|| anyhow!("Object {object_id} is not in the offline snapshot"))
+    }
+}
+
+#[async_trait]
+impl DataReader for OfflineDataReader {
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        object_type: StructTag,
+    ) -> Result<Vec<ObjectInfo>, anyhow::Error> {
+        Ok(self
+            .objects
+            .values()
+            .filter(|object| object.owner == Owner::AddressOwner(address))
+            .filter(|object| object.struct_tag().as_ref() == Some(&object_type))
+            .map(|object| ObjectInfo::new(&object.compute_object_reference(), object))
+            .collect())
+    }
+
+    async fn get_object_with_options(
+        &self,
+        object_id: ObjectID,
+        options: SuiObjectDataOptions,
+    ) -> Result<SuiObjectResponse, anyhow::Error> {
+        let object_read = match self.objects.get(&object_id) {
+            Some(object) => {
+                ObjectRead::Exists(object.compute_object_reference(), object.clone(), None)
+            }
+            None => ObjectRead::NotExists(object_id),
+        };
+        (object_read, options).try_into()
+    }
+
+    async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
+        Ok(self.reference_gas_price)
+    }
+}
+
+mod objects_as_base64 {
+    use fastcrypto::encoding::{Base64, Encoding};
+    use serde::de::Error as _;
+    use serde::ser::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use sui_types::object::Object;
+
+    pub fn serialize<S: Serializer>(objects: &[Object], serializer: S) -> Result<S::Ok, S::Error> {
+        objects
+            .iter()
+            .map(|object| bcs::to_bytes(object).map(Base64::encode))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Object>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|object| {
+                let bytes = Base64::decode(object).map_err(D::Error::custom)?;
+                bcs::from_bytes(&bytes).map_err(D::Error::custom)
+            })
+            .collect()
+    }
+}
@@ -1172,6 +1172,7 @@ impl<'a> SuiTestAdapter<'a> {
                         /* descending */ false,
                     )
                     .await
+                    .map(|(events, _)| events)
                     .unwrap_or_default()
                     .into_iter()
                     .map(|sui_event| sui_event.into())
@@ -1448,6 +1448,11 @@ impl TransactionData {
             self.gas_data().payment.clone(),
         )
     }
+
+    /// The digest of the transaction, the same as the one of any signed transaction built from it
+    pub fn digest(&self) -> TransactionDigest {
+        TransactionDigest::new(default_hash(self))
+    }
 }
 
 #[enum_dispatch]
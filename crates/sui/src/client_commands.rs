@@ -27,6 +27,8 @@ use sui_adapter::adapter::{default_verifier_config, run_metered_move_bytecode_ve
 use sui_move::build::resolve_lock_file_path;
 use sui_protocol_config::ProtocolConfig;
 use sui_source_validation::{BytecodeSourceVerifier, SourceMode};
+use sui_transaction_builder::offline::{OfflineDataReader, OfflineSnapshot};
+use sui_types::committee::EpochId;
 use sui_types::{digests::TransactionDigest, metrics::BytecodeVerifierMetrics};
 use sui_types::{dynamic_field::DynamicFieldInfo, error::SuiError};
 use sui_verifier::meter::SuiVerifierMeter;
@@ -38,7 +40,8 @@ use sui_json_rpc_types::{
     SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_json_rpc_types::{SuiExecutionStatus, SuiObjectDataOptions};
-use sui_keys::keystore::AccountKeystore;
+use sui_keys::keystore::{AccountKeystore, Keystore, RemoteKeystore};
+use sui_keys::remote_signer::RemoteSignerConfig;
 use sui_move_build::{
     build_from_resolution_graph, check_invalid_dependencies, check_unpublished_dependencies,
     gather_published_ids, BuildConfig, CompiledPackage, PackageDependencies, PublishedAtError,
@@ -65,6 +68,10 @@ use tabled::settings::{
 };
 use tracing::info;
 
+use crate::multisig_commands::{MultiSigCommand, MultiSigCommandResult};
+use crate::ptb_commands::{PtbCommand, PtbCommandResult};
+use crate::sponsor_commands::{SponsorCommand, SponsorCommandResult};
+
 macro_rules! serialize_or_execute {
     ($tx_data:expr, $serialize_unsigned:expr, $serialize_signed:expr, $context:expr, $result_variant:ident) => {{
         assert!(
@@ -86,6 +93,11 @@ macro_rules! serialize_or_execute {
             );
             if $serialize_signed {
                 SuiClientCommandResult::SerializedSignedTransaction(sender_signed_data)
+            } else if let Some(offline) = $context.offline_snapshot() {
+                SuiClientCommandResult::OfflineSignedTransaction(OfflineSignedTransaction::new(
+                    offline,
+                    &sender_signed_data,
+                )?)
             } else {
                 let transaction = Transaction::new(sender_signed_data);
                 let response = $context.execute_transaction_may_fail(transaction).await?;
@@ -200,6 +212,22 @@ pub enum SuiClientCommands {
         #[clap(name = "owner_address")]
         address: Option<SuiAddress>,
     },
+    /// Save the objects owned by an address and the given objects, with the reference gas price
+    /// and the protocol version of the current epoch, to a snapshot file. Transactions using
+    /// these objects can then be built and signed without access to a full node with
+    /// `sui client --offline-snapshot <file>`.
+    #[clap(name = "offline-snapshot")]
+    OfflineSnapshot {
+        /// Address whose objects are saved, `sui client active-address` if not provided
+        #[clap(long)]
+        address: Option<SuiAddress>,
+        /// Other objects the transactions use, e.g. shared objects or the packages of Move calls
+        #[clap(long, num_args(1..))]
+        object_ids: Vec<ObjectID>,
+        /// File to write the snapshot to
+        #[clap(long)]
+        output: PathBuf,
+    },
 
     /// Merge two coin objects into one coin
     MergeCoin {
@@ -228,6 +256,14 @@ pub enum SuiClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Collect the signatures of a MultiSig address on a transaction through a pending
+    /// transaction file, and execute it once the threshold is reached.
+    #[clap(name = "multisig")]
+    MultiSig {
+        #[clap(subcommand)]
+        cmd: MultiSigCommand,
+    },
+
     /// Generate new address and keypair with keypair scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word length can be
@@ -362,6 +398,14 @@ pub enum SuiClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Build a programmable transaction block out of a sequence of commands read from a JSON
+    /// file, where commands can use the results of earlier commands, and execute it.
+    #[clap(name = "ptb")]
+    Ptb {
+        #[clap(flatten)]
+        ptb: PtbCommand,
+    },
+
     /// Publish Move modules
     #[clap(name = "publish")]
     Publish {
@@ -437,6 +481,32 @@ pub enum SuiClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Sign with the keys held by an external signing daemon (e.g. backed by an HSM or an
+    /// air-gapped machine) instead of the local keystore. The keystore file is left untouched,
+    /// but the client config no longer points to it.
+    #[clap(name = "remote-signer")]
+    RemoteSigner {
+        /// `unix:///path/to/signer.sock`, `tcp://host:port` or `http://host:port/path`.
+        #[clap(long)]
+        endpoint: String,
+        /// How long to wait for the daemon to answer a request, in milliseconds.
+        #[clap(long)]
+        timeout_ms: Option<u64>,
+        /// Replace the local keystore in the client config. Required unless the client already
+        /// uses a remote signer. The replaced keystore is printed so it can be restored in the
+        /// client config later.
+        #[clap(long, required = false)]
+        replace_keystore: bool,
+    },
+
+    /// Build, sign and combine the signatures of transactions whose gas is paid by a sponsor
+    /// rather than by their sender.
+    #[clap(name = "sponsor")]
+    Sponsor {
+        #[clap(subcommand)]
+        cmd: SponsorCommand,
+    },
+
     /// Switch active address and network(e.g., devnet, local rpc server)
     #[clap(name = "switch")]
     Switch {
@@ -874,9 +944,9 @@ impl SuiClientCommands {
                 serialize_signed_transaction,
             } => {
                 let from = context.get_object_owner(&object_id).await?;
-                let client = context.get_client().await?;
-                let data = client
+                let data = context
                     .transaction_builder()
+                    .await?
                     .transfer_object(from, object_id, gas, gas_budget, to)
                     .await?;
                 serialize_or_execute!(
@@ -898,9 +968,9 @@ impl SuiClientCommands {
             } => {
                 let from = context.get_object_owner(&object_id).await?;
 
-                let client = context.get_client().await?;
-                let data = client
+                let data = context
                     .transaction_builder()
+                    .await?
                     .transfer_sui(from, object_id, gas_budget, to, amount)
                     .await?;
                 serialize_or_execute!(
@@ -938,9 +1008,9 @@ impl SuiClientCommands {
                     ),
                 );
                 let from = context.get_object_owner(&input_coins[0]).await?;
-                let client = context.get_client().await?;
-                let data = client
+                let data = context
                     .transaction_builder()
+                    .await?
                     .pay(from, input_coins, recipients, amounts, gas, gas_budget)
                     .await?;
                 serialize_or_execute!(
@@ -977,9 +1047,9 @@ impl SuiClientCommands {
                     ),
                 );
                 let signer = context.get_object_owner(&input_coins[0]).await?;
-                let client = context.get_client().await?;
-                let data = client
+                let data = context
                     .transaction_builder()
+                    .await?
                     .pay_sui(signer, input_coins, recipients, amounts, gas_budget)
                     .await?;
                 serialize_or_execute!(
@@ -1003,9 +1073,9 @@ impl SuiClientCommands {
                     "PayAllSui transaction requires a non-empty list of input coins"
                 );
                 let signer = context.get_object_owner(&input_coins[0]).await?;
-                let client = context.get_client().await?;
-                let data = client
+                let data = context
                     .transaction_builder()
+                    .await?
                     .pay_all_sui(signer, input_coins, recipient, gas_budget)
                     .await?;
 
@@ -1046,6 +1116,69 @@ impl SuiClientCommands {
                 SuiClientCommandResult::Objects(objects)
             }
 
+            SuiClientCommands::OfflineSnapshot {
+                address,
+                object_ids,
+                output,
+            } => {
+                let address = address.unwrap_or(context.active_address()?);
+                let client = context.get_client().await?;
+                let mut responses: Vec<SuiObjectResponse> = Vec::new();
+                let mut cursor = None;
+                loop {
+                    let response = client
+                        .read_api()
+                        .get_owned_objects(
+                            address,
+                            Some(SuiObjectResponseQuery::new_with_options(
+                                SuiObjectDataOptions::bcs_lossless(),
+                            )),
+                            cursor,
+                            None,
+                        )
+                        .await?;
+                    responses.extend(response.data);
+
+                    if response.has_next_page {
+                        cursor = response.next_cursor;
+                    } else {
+                        break;
+                    }
+                }
+                if !object_ids.is_empty() {
+                    responses.extend(
+                        client
+                            .read_api()
+                            .multi_get_object_with_options(
+                                object_ids,
+                                SuiObjectDataOptions::bcs_lossless(),
+                            )
+                            .await?,
+                    );
+                }
+                let objects = responses
+                    .into_iter()
+                    .map(|response| response.into_object()?.try_into())
+                    .collect::<Result<Vec<sui_types::object::Object>, anyhow::Error>>()?;
+
+                let system_state = client
+                    .governance_api()
+                    .get_latest_sui_system_state()
+                    .await?;
+                let snapshot = OfflineSnapshot {
+                    epoch: system_state.epoch,
+                    reference_gas_price: system_state.reference_gas_price,
+                    protocol_version: system_state.protocol_version,
+                    objects,
+                };
+                snapshot.write(&output)?;
+                SuiClientCommandResult::OfflineSnapshot {
+                    path: output,
+                    epoch: snapshot.epoch,
+                    objects: snapshot.objects.len(),
+                }
+            }
+
             SuiClientCommands::NewAddress {
                 key_scheme,
                 derivation_path,
@@ -1093,11 +1226,10 @@ impl SuiClientCommands {
                 serialize_signed_transaction,
             } => {
                 let signer = context.get_object_owner(&coin_id).await?;
-                let client = context.get_client().await?;
+                let tx_builder = context.transaction_builder().await?;
                 let data = match (amounts, count) {
                     (Some(amounts), None) => {
-                        client
-                            .transaction_builder()
+                        tx_builder
                             .split_coin(signer, coin_id, amounts, gas, gas_budget)
                             .await?
                     }
@@ -1105,8 +1237,7 @@ impl SuiClientCommands {
                         if count == 0 {
                             return Err(anyhow!("Coin split count must be greater than 0"));
                         }
-                        client
-                            .transaction_builder()
+                        tx_builder
                             .split_coin_equal(signer, coin_id, count, gas, gas_budget)
                             .await?
                     }
@@ -1130,10 +1261,10 @@ impl SuiClientCommands {
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
             } => {
-                let client = context.get_client().await?;
                 let signer = context.get_object_owner(&primary_coin).await?;
-                let data = client
+                let data = context
                     .transaction_builder()
+                    .await?
                     .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
                     .await?;
                 serialize_or_execute!(
@@ -1144,6 +1275,60 @@ impl SuiClientCommands {
                     MergeCoin
                 )
             }
+            SuiClientCommands::MultiSig { cmd } => {
+                SuiClientCommandResult::MultiSig(cmd.execute(context).await?)
+            }
+
+            SuiClientCommands::Ptb { ptb } => {
+                SuiClientCommandResult::Ptb(ptb.execute(context).await?)
+            }
+
+            SuiClientCommands::RemoteSigner {
+                endpoint,
+                timeout_ms,
+                replace_keystore,
+            } => {
+                // The keys of a local keystore would no longer be reachable from the client
+                if !replace_keystore && !matches!(context.config.keystore, Keystore::Remote(_)) {
+                    return Err(anyhow!(
+                        "The client config uses a local keystore:\n{}\nPass --replace-keystore \
+                         to replace it with the remote signer",
+                        context.config.keystore
+                    ));
+                }
+                let mut config = RemoteSignerConfig::new(endpoint);
+                if let Some(timeout_ms) = timeout_ms {
+                    config.timeout_ms = timeout_ms;
+                }
+                // Check the daemon is reachable before switching to it.
+                let mut keystore = RemoteKeystore::new(config);
+                keystore.refresh_keys()?;
+                let addresses = keystore.addresses();
+                let Some(first) = addresses.first() else {
+                    return Err(anyhow!(
+                        "Remote signer at {} does not hold any key",
+                        keystore.config().endpoint
+                    ));
+                };
+                if !context
+                    .config
+                    .active_address
+                    .is_some_and(|active| addresses.contains(&active))
+                {
+                    context.config.active_address = Some(*first);
+                }
+                let previous_keystore =
+                    std::mem::replace(&mut context.config.keystore, Keystore::from(keystore));
+                context.config.save()?;
+                SuiClientCommandResult::RemoteSigner {
+                    addresses,
+                    previous_keystore: previous_keystore.to_string(),
+                }
+            }
+
+            SuiClientCommands::Sponsor { cmd } => {
+                SuiClientCommandResult::Sponsor(cmd.execute(context).await?)
+            }
             SuiClientCommands::Switch { address, env } => {
                 match (address, &env) {
                     (None, Some(env)) => {
@@ -1286,7 +1471,7 @@ fn compile_package_simple(
     )?)
 }
 
-async fn compile_package(
+pub(crate) async fn compile_package(
     client: &SuiClient,
     build_config: MoveBuildConfig,
     package_path: PathBuf,
@@ -1457,6 +1642,16 @@ impl Display for SuiClientCommandResult {
                 };
                 writeln!(writer, "{}", raw_object)?;
             }
+            SuiClientCommandResult::RemoteSigner {
+                addresses,
+                previous_keystore,
+            } => {
+                writeln!(writer, "Switched to remote signer holding the keys of:")?;
+                for address in addresses {
+                    writeln!(writer, "{}", address)?;
+                }
+                writeln!(writer, "Replaced keystore:\n{}", previous_keystore)?;
+            }
             SuiClientCommandResult::Call(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
@@ -1489,6 +1684,20 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::PayAllSui(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
+            SuiClientCommandResult::OfflineSnapshot {
+                path,
+                epoch,
+                objects,
+            } => {
+                writeln!(
+                    writer,
+                    "Saved {objects} objects of epoch {epoch} to offline snapshot {}",
+                    path.display()
+                )?;
+            }
+            SuiClientCommandResult::OfflineSignedTransaction(signed_tx) => {
+                write!(writer, "{}", signed_tx)?;
+            }
             SuiClientCommandResult::Objects(object_refs) => {
                 writeln!(
                     writer,
@@ -1546,6 +1755,15 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::MergeCoin(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
+            SuiClientCommandResult::MultiSig(result) => {
+                write!(writer, "{}", result)?;
+            }
+            SuiClientCommandResult::Ptb(result) => {
+                write!(writer, "{}", result)?;
+            }
+            SuiClientCommandResult::Sponsor(result) => {
+                write!(writer, "{}", result)?;
+            }
             SuiClientCommandResult::Switch(response) => {
                 write!(writer, "{}", response)?;
             }
@@ -1635,9 +1853,9 @@ async fn construct_move_call_transaction(
     let gas_owner = context.try_get_object_owner(&gas).await?;
     let sender = gas_owner.unwrap_or(context.active_address()?);
 
-    let client = context.get_client().await?;
-    client
+    context
         .transaction_builder()
+        .await?
         .move_call(
             sender, package, module, function, type_args, args, gas, gas_budget,
         )
@@ -1745,6 +1963,9 @@ impl SuiClientCommandResult {
             Upgrade(b) | Publish(b) | TransactionBlock(b) | Call(b) | Transfer(b)
             | TransferSui(b) | Pay(b) | PaySui(b) | PayAllSui(b) | SplitCoin(b) | MergeCoin(b)
             | ExecuteSignedTx(b) => Some(b),
+            MultiSig(MultiSigCommandResult::Execute(b)) => Some(b),
+            Ptb(result) => result.tx_block_response(),
+            Sponsor(SponsorCommandResult::Execute(b)) => Some(b),
             _ => None,
         }
     }
@@ -1765,6 +1986,58 @@ pub struct DynamicFieldOutput {
     pub data: Vec<DynamicFieldInfo>,
 }
 
+/// A transaction built and signed offline, in the form taken by `sui client execute-signed-tx`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineSignedTransaction {
+    /// Epoch of the snapshot the transaction was built from, its gas price is the reference gas
+    /// price of that epoch.
+    pub epoch: EpochId,
+    /// Base64 encoded BCS bytes of the transaction data.
+    pub tx_bytes: String,
+    /// Base64 encoded `flag || signature || pubkey` signatures.
+    pub signatures: Vec<String>,
+}
+
+impl OfflineSignedTransaction {
+    /// Check the transaction against the protocol limits of `snapshot`, which it was built from.
+    pub fn new(
+        snapshot: &OfflineDataReader,
+        data: &SenderSignedData,
+    ) -> Result<Self, anyhow::Error> {
+        snapshot.check_transaction(data.transaction_data())?;
+        Ok(Self {
+            epoch: snapshot.epoch(),
+            tx_bytes: Base64::encode(bcs::to_bytes(data.transaction_data()).unwrap()),
+            signatures: data
+                .tx_signatures()
+                .iter()
+                .map(|signature| Base64::encode(signature.as_ref()))
+                .collect(),
+        })
+    }
+}
+
+impl Display for OfflineSignedTransaction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Transaction built from the offline snapshot of epoch {}",
+            self.epoch
+        )?;
+        writeln!(f, "Transaction bytes: {}", self.tx_bytes)?;
+        for signature in &self.signatures {
+            writeln!(f, "Signature: {signature}")?;
+        }
+        write!(
+            f,
+            "Execute it with `sui client execute-signed-tx --tx-bytes {} --signatures {}`",
+            self.tx_bytes,
+            self.signatures.join(" ")
+        )
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NewAddressOutput {
@@ -1786,18 +2059,31 @@ pub enum SuiClientCommandResult {
     ExecuteSignedTx(SuiTransactionBlockResponse),
     Gas(Vec<GasCoin>),
     MergeCoin(SuiTransactionBlockResponse),
+    MultiSig(MultiSigCommandResult),
     NewAddress(NewAddressOutput),
     NewEnv(SuiEnv),
     Object(SuiObjectResponse),
     Objects(Vec<SuiObjectResponse>),
+    OfflineSignedTransaction(OfflineSignedTransaction),
+    OfflineSnapshot {
+        path: PathBuf,
+        epoch: EpochId,
+        objects: usize,
+    },
     Pay(SuiTransactionBlockResponse),
     PayAllSui(SuiTransactionBlockResponse),
     PaySui(SuiTransactionBlockResponse),
+    Ptb(PtbCommandResult),
     Publish(SuiTransactionBlockResponse),
     RawObject(SuiObjectResponse),
+    RemoteSigner {
+        addresses: Vec<SuiAddress>,
+        previous_keystore: String,
+    },
     SerializedSignedTransaction(SenderSignedData),
     SerializedUnsignedTransaction(TransactionData),
     SplitCoin(SuiTransactionBlockResponse),
+    Sponsor(SponsorCommandResult),
     Switch(SwitchResponse),
     SyncClientState,
     TransactionBlock(SuiTransactionBlockResponse),
@@ -22,12 +22,14 @@ use shared_crypto::intent::{Intent, IntentMessage};
 use std::fmt::{Debug, Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
+use sui_config::{sui_config_dir, Config, PersistedConfig, SUI_CLIENT_CONFIG};
 use sui_keys::key_derive::generate_new_key;
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
     write_keypair_to_file,
 };
-use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_keys::keystore::{AccountKeystore, EncryptedFileKeystore, Keystore};
+use sui_sdk::sui_client_config::SuiClientConfig;
 use sui_types::base_types::SuiAddress;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{get_authority_key_pair, EncodeDecodeBase64, SignatureScheme, SuiKeyPair};
@@ -69,6 +71,15 @@ pub enum KeyToolCommand {
         #[clap(long)]
         tx_bytes: Option<String>,
     },
+    /// Encrypt the private keys in sui.keystore at rest with a passphrase (Argon2id + AES-256-GCM).
+    /// The keystore file is rewritten in place and the client config using it is switched to the
+    /// encrypted keystore. The passphrase is read from the `SUI_KEYSTORE_PASSPHRASE` environment
+    /// variable if set, otherwise it is prompted for.
+    EncryptKeystore {
+        /// The client config to update, defaults to ~/.sui/sui_config/client.yaml.
+        #[clap(long = "client.config")]
+        client_config: Option<PathBuf>,
+    },
     /// Generate a new keypair with key scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word
@@ -221,6 +232,14 @@ pub struct DecodedMultiSigOutput {
     transaction_result: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptKeystoreOutput {
+    keystore_path: PathBuf,
+    encrypted_keys: usize,
+    client_config_updated: bool,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Key {
@@ -321,6 +340,7 @@ pub enum CommandOutput {
     Convert(ConvertOutput),
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeTxBytes(TransactionData),
+    EncryptKeystore(EncryptKeystoreOutput),
     Error(String),
     Generate(Key),
     GenerateZkLoginAddress(SuiAddress, AddressParams),
@@ -404,6 +424,56 @@ impl KeyToolCommand {
                 CommandOutput::DecodeTxBytes(tx_data)
             }
 
+            KeyToolCommand::EncryptKeystore { client_config } => {
+                let file_keystore = match keystore {
+                    Keystore::File(file_keystore) => file_keystore,
+                    Keystore::Encrypted(_) => return Err(anyhow!("Keystore is already encrypted")),
+                    Keystore::InMem(_) | Keystore::Remote(_) => {
+                        return Err(anyhow!("Only a file keystore can be encrypted"))
+                    }
+                };
+                let keystore_path = file_keystore
+                    .path()
+                    .ok_or_else(|| anyhow!("Keystore has no file path"))?
+                    .to_path_buf();
+                let client_config = match client_config {
+                    Some(client_config) => client_config,
+                    None => sui_config_dir()?.join(SUI_CLIENT_CONFIG),
+                };
+                // The client config has to be read before the keystore is rewritten, as it
+                // loads the keystore it points to in the current format.
+                let config = if client_config.exists() {
+                    let config: SuiClientConfig = PersistedConfig::read(&client_config)?;
+                    match &config.keystore {
+                        Keystore::File(file) if file.path() == Some(keystore_path.as_path()) => {
+                            Some(config)
+                        }
+                        _ => None,
+                    }
+                } else {
+                    None
+                };
+
+                let mut encrypted = EncryptedFileKeystore::from_file_keystore(file_keystore, None)?;
+                encrypted.set_path(&keystore_path);
+                encrypted.save()?;
+                let encrypted_keys = encrypted.keys().len();
+
+                let client_config_updated = config.is_some();
+                if let Some(mut config) = config {
+                    config.keystore =
+                        Keystore::Encrypted(EncryptedFileKeystore::new(&keystore_path)?);
+                    config.persisted(&client_config).save()?;
+                }
+                *keystore = Keystore::Encrypted(encrypted);
+
+                CommandOutput::EncryptKeystore(EncryptKeystoreOutput {
+                    keystore_path,
+                    encrypted_keys,
+                    client_config_updated,
+                })
+            }
+
             KeyToolCommand::Generate {
                 key_scheme,
                 derivation_path,
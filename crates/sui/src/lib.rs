@@ -6,7 +6,10 @@ pub mod client_commands;
 pub mod console;
 pub mod fire_drill;
 pub mod keytool;
+pub mod multisig_commands;
+pub mod ptb_commands;
 pub mod shell;
+pub mod sponsor_commands;
 pub mod sui_commands;
 pub mod validator_commands;
 pub mod zklogin_commands_util;
@@ -0,0 +1,358 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, ensure};
+use clap::*;
+use fastcrypto::encoding::{Base64, Encoding};
+use json_to_table::json_to_table;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_json_rpc_types::SuiTransactionBlockResponse;
+use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+use sui_keys::keystore::AccountKeystore;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, Signature, SuiSignature};
+use sui_types::digests::TransactionDigest;
+use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit};
+use sui_types::signature::GenericSignature;
+use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI};
+use tabled::settings::Style as TableStyle;
+
+use crate::client_commands::write_transaction_response;
+
+/// Commands to collect the signatures of a MultiSig address on a transaction. The transaction
+/// and the signatures gathered so far are kept in a pending transaction file that is passed
+/// from one signer to the next, until enough weight has been collected to execute it.
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum MultiSigCommand {
+    /// Create a pending transaction file for a transaction sent by a MultiSig address. The
+    /// transaction can be built by any `sui client` command with
+    /// `--serialize-unsigned-transaction`.
+    Create {
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        #[clap(long)]
+        tx_bytes: String,
+        /// The threshold of the MultiSig address.
+        #[clap(long)]
+        threshold: ThresholdUnit,
+        /// A list of all public keys `flag || pk` in Base64 of the MultiSig address.
+        #[clap(long, num_args(1..))]
+        pks: Vec<PublicKey>,
+        /// The weights of the public keys, in the same order.
+        #[clap(long, num_args(1..))]
+        weights: Vec<WeightUnit>,
+        /// Path of the pending transaction file to create.
+        #[clap(long)]
+        file: PathBuf,
+    },
+    /// Sign a pending transaction with the keys of the local keystore that are part of the
+    /// MultiSig, or only with the key of `--address` if given, and add the signatures to the file.
+    Sign {
+        /// Path of the pending transaction file.
+        #[clap(long)]
+        file: PathBuf,
+        /// Only sign with the key of this address.
+        #[clap(long)]
+        address: Option<SuiAddress>,
+    },
+    /// Show the signers of a pending transaction and the weight collected so far.
+    Status {
+        /// Path of the pending transaction file.
+        #[clap(long)]
+        file: PathBuf,
+    },
+    /// Combine the signatures of a pending transaction into a MultiSig and execute it. Fails if
+    /// the collected weight is below the threshold.
+    Execute {
+        /// Path of the pending transaction file.
+        #[clap(long)]
+        file: PathBuf,
+    },
+}
+
+/// Content of a pending transaction file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingMultiSigTransaction {
+    pub multisig_pk: MultiSigPublicKey,
+    /// Base64 encoded BCS bytes of the [TransactionData].
+    pub tx_bytes: String,
+    /// Base64 encoded `flag || signature || pubkey` of the signers so far.
+    pub signatures: Vec<String>,
+}
+
+impl PendingMultiSigTransaction {
+    pub fn new(
+        multisig_pk: MultiSigPublicKey,
+        tx_data: &TransactionData,
+    ) -> Result<Self, anyhow::Error> {
+        let multisig_address = SuiAddress::from(&multisig_pk);
+        ensure!(
+            tx_data.sender() == multisig_address,
+            "Transaction sender {} is not the MultiSig address {}",
+            tx_data.sender(),
+            multisig_address
+        );
+        Ok(Self {
+            multisig_pk,
+            tx_bytes: Base64::encode(bcs::to_bytes(tx_data)?),
+            signatures: vec![],
+        })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, anyhow::Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read pending transaction file {:?}: {e}", path))?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Invalid pending transaction file {:?}: {e}", path))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), anyhow::Error> {
+        Ok(fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+
+    pub fn multisig_address(&self) -> SuiAddress {
+        (&self.multisig_pk).into()
+    }
+
+    pub fn tx_data(&self) -> Result<TransactionData, anyhow::Error> {
+        let bytes = Base64::decode(&self.tx_bytes)
+            .map_err(|e| anyhow!("Invalid base64 tx bytes: {:?}", e))?;
+        Ok(bcs::from_bytes(&bytes)?)
+    }
+
+    /// The verified signatures collected so far, keyed by the index of their public key in the
+    /// MultiSig.
+    pub fn signatures(&self) -> Result<Vec<(usize, Signature)>, anyhow::Error> {
+        let intent_msg = IntentMessage::new(Intent::sui_transaction(), self.tx_data()?);
+        let mut signatures = self
+            .signatures
+            .iter()
+            .map(|s| {
+                let signature = Signature::decode_base64(s)
+                    .map_err(|e| anyhow!("Invalid signature {s}: {e}"))?;
+                let index = self.index_of(&signature)?;
+                let signer = SuiAddress::from(&self.multisig_pk.pubkeys()[index].0);
+                signature
+                    .verify_secure(&intent_msg, signer, signature.scheme())
+                    .map_err(|e| anyhow!("Invalid signature of {signer}: {e}"))?;
+                Ok((index, signature))
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+        // MultiSig::combine expects the signatures in the order of the public keys.
+        signatures.sort_by_key(|(index, _)| *index);
+        Ok(signatures)
+    }
+
+    /// Add `signature` to the file, replacing any previous signature from the same signer.
+    pub fn add_signature(&mut self, signature: Signature) -> Result<(), anyhow::Error> {
+        let index = self.index_of(&signature)?;
+        let mut signatures = self.signatures()?;
+        signatures.retain(|(i, _)| *i != index);
+        signatures.push((index, signature));
+        signatures.sort_by_key(|(index, _)| *index);
+        self.signatures = signatures
+            .iter()
+            .map(|(_, signature)| signature.encode_base64())
+            .collect();
+        // Check the new signature before it gets written out.
+        self.signatures()?;
+        Ok(())
+    }
+
+    pub fn status(&self) -> Result<MultiSigTransactionStatus, anyhow::Error> {
+        let signed = self
+            .signatures()?
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let signers = self
+            .multisig_pk
+            .pubkeys()
+            .iter()
+            .enumerate()
+            .map(|(index, (pk, weight))| MultiSigSignerStatus {
+                address: pk.into(),
+                public_base64_key: pk.encode_base64(),
+                weight: *weight,
+                signed: signed.contains(&index),
+            })
+            .collect::<Vec<_>>();
+        let collected_weight = signers
+            .iter()
+            .filter(|signer| signer.signed)
+            .map(|signer| signer.weight as ThresholdUnit)
+            .sum();
+        Ok(MultiSigTransactionStatus {
+            multisig_address: self.multisig_address(),
+            tx_digest: self.tx_data()?.digest(),
+            threshold: *self.multisig_pk.threshold(),
+            collected_weight,
+            signers,
+        })
+    }
+
+    /// Combine the collected signatures into a transaction signed by the MultiSig.
+    pub fn to_transaction(&self) -> Result<Transaction, anyhow::Error> {
+        let status = self.status()?;
+        ensure!(
+            status.collected_weight >= status.threshold,
+            "Collected weight {} is below the threshold {} of MultiSig address {}",
+            status.collected_weight,
+            status.threshold,
+            status.multisig_address
+        );
+        let signatures = self
+            .signatures()?
+            .into_iter()
+            .map(|(_, signature)| signature)
+            .collect();
+        let multisig = MultiSig::combine(signatures, self.multisig_pk.clone())?;
+        Ok(Transaction::from_generic_sig_data(
+            self.tx_data()?,
+            Intent::sui_transaction(),
+            vec![GenericSignature::MultiSig(multisig)],
+        ))
+    }
+
+    fn index_of(&self, signature: &Signature) -> Result<usize, anyhow::Error> {
+        let pk = signature.to_public_key()?;
+        self.multisig_pk
+            .get_index(&pk)
+            .map(|index| index as usize)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Signer {} is not part of MultiSig address {}",
+                    SuiAddress::from(&pk),
+                    self.multisig_address()
+                )
+            })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigSignerStatus {
+    pub address: SuiAddress,
+    pub public_base64_key: String,
+    pub weight: WeightUnit,
+    pub signed: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigTransactionStatus {
+    pub multisig_address: SuiAddress,
+    pub tx_digest: TransactionDigest,
+    pub threshold: ThresholdUnit,
+    pub collected_weight: ThresholdUnit,
+    pub signers: Vec<MultiSigSignerStatus>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum MultiSigCommandResult {
+    Status(MultiSigTransactionStatus),
+    Execute(SuiTransactionBlockResponse),
+}
+
+impl MultiSigCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<MultiSigCommandResult, anyhow::Error> {
+        Ok(match self {
+            MultiSigCommand::Create {
+                tx_bytes,
+                threshold,
+                pks,
+                weights,
+                file,
+            } => {
+                ensure!(
+                    !file.exists(),
+                    "Pending transaction file {:?} already exists",
+                    file
+                );
+                let tx_data: TransactionData = bcs::from_bytes(
+                    &Base64::decode(&tx_bytes)
+                        .map_err(|e| anyhow!("Invalid base64 tx bytes: {:?}", e))?,
+                )?;
+                let multisig_pk = MultiSigPublicKey::new(pks, weights, threshold)?;
+                let pending = PendingMultiSigTransaction::new(multisig_pk, &tx_data)?;
+                pending.write(&file)?;
+                MultiSigCommandResult::Status(pending.status()?)
+            }
+
+            MultiSigCommand::Sign { file, address } => {
+                let mut pending = PendingMultiSigTransaction::read(&file)?;
+                let tx_data = pending.tx_data()?;
+                let local_addresses = context.config.keystore.addresses();
+                let signers = pending
+                    .multisig_pk
+                    .pubkeys()
+                    .iter()
+                    .map(|(pk, _)| SuiAddress::from(pk))
+                    .filter(|signer| local_addresses.contains(signer))
+                    .filter(|signer| address.map_or(true, |address| address == *signer))
+                    .collect::<Vec<_>>();
+                ensure!(
+                    !signers.is_empty(),
+                    "No key of the local keystore can sign for MultiSig address {}",
+                    pending.multisig_address()
+                );
+                for signer in signers {
+                    let signature = context.config.keystore.sign_secure(
+                        &signer,
+                        &tx_data,
+                        Intent::sui_transaction(),
+                    )?;
+                    pending.add_signature(signature)?;
+                }
+                pending.write(&file)?;
+                MultiSigCommandResult::Status(pending.status()?)
+            }
+
+            MultiSigCommand::Status { file } => {
+                MultiSigCommandResult::Status(PendingMultiSigTransaction::read(&file)?.status()?)
+            }
+
+            MultiSigCommand::Execute { file } => {
+                let transaction = PendingMultiSigTransaction::read(&file)?.to_transaction()?;
+                let response = context.execute_transaction_may_fail(transaction).await?;
+                let effects = response.effects.as_ref().ok_or_else(|| {
+                    anyhow!("Effects from SuiTransactionBlockResult should not be empty")
+                })?;
+                if matches!(effects.status(), SuiExecutionStatus::Failure { .. }) {
+                    return Err(anyhow!(
+                        "Error executing transaction: {:#?}",
+                        effects.status()
+                    ));
+                }
+                MultiSigCommandResult::Execute(response)
+            }
+        })
+    }
+}
+
+impl Display for MultiSigCommandResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MultiSigCommandResult::Status(status) => {
+                let mut table = json_to_table(&json!(status));
+                table.with(TableStyle::rounded().horizontals([]));
+                write!(f, "{}", table)
+            }
+            MultiSigCommandResult::Execute(response) => {
+                write!(f, "{}", write_transaction_response(response)?)
+            }
+        }
+    }
+}
@@ -0,0 +1,598 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Display, Formatter, Write};
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, ensure};
+use clap::*;
+use colored::Colorize;
+use fastcrypto::encoding::{Base64, Encoding};
+use json_to_table::json_to_table;
+use move_core_types::language_storage::TypeTag;
+use move_core_types::value::MoveTypeLayout;
+use move_package::BuildConfig as MoveBuildConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_crypto::intent::Intent;
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::{
+    DevInspectResults, DryRunTransactionBlockResponse, SuiExecutionStatus,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTypeTag,
+};
+use sui_keys::keystore::AccountKeystore;
+use sui_sdk::wallet_context::WalletContext;
+use sui_transaction_builder::{ProgrammableMoveCallArg, TransactionBuilder};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::{
+    Argument, Command, ProgrammableTransaction, SenderSignedData, Transaction, TransactionData,
+    TransactionDataAPI, TransactionKind,
+};
+use tabled::settings::Style as TableStyle;
+
+use crate::client_commands::{
+    compile_package, write_transaction_response, OfflineSignedTransaction,
+};
+
+/// Build a programmable transaction block out of a sequence of commands and execute it. The
+/// commands are read from a JSON file holding an array like:
+///
+/// [
+///   {"command": "split-coins", "coin": "gas", "amounts": [1000, 2000]},
+///   {"command": "transfer-objects", "objects": ["result(0,0)", "result(0,1)"], "recipient": "0x.."},
+///   {"command": "move-call", "package": "0x2", "module": "coin", "function": "join",
+///    "type_args": ["0x2::sui::SUI"], "args": ["0x..", "0x.."]},
+///   {"command": "merge-coins", "coin": "0x..", "coins": ["0x.."]},
+///   {"command": "make-move-vec", "type": "u64", "elements": [1, 2]},
+///   {"command": "publish", "package_path": "path/to/package"}
+/// ]
+///
+/// Arguments are JSON values like the args of `sui client call`, or `"gas"` for the gas coin,
+/// `"result(i)"` for the result of the i-th command and `"result(i,j)"` for the j-th result of
+/// the i-th command.
+#[derive(Args)]
+#[clap(rename_all = "kebab-case")]
+pub struct PtbCommand {
+    /// Path of the JSON file with the commands of the transaction.
+    #[clap(name = "commands_file")]
+    pub commands_file: PathBuf,
+
+    /// ID of the gas object for gas payment, in 20 bytes Hex string
+    /// If not provided, a gas object with at least gas_budget value will be selected
+    #[clap(long)]
+    pub gas: Option<ObjectID>,
+
+    /// Gas budget for this transaction
+    #[clap(long)]
+    pub gas_budget: u64,
+
+    /// Address paying for gas, if not the sender. The gas object is then selected among the
+    /// coins of the sponsor, and the transaction has to be signed by both the sender and the
+    /// sponsor, see `sui client sponsor`.
+    #[clap(long)]
+    pub gas_sponsor: Option<SuiAddress>,
+
+    /// Run the transaction against the current state of the network without executing it.
+    #[clap(long, required = false)]
+    pub dry_run: bool,
+
+    /// Inspect the transaction without executing it, reporting the return values of each
+    /// command. Gas coin and budget are not checked.
+    #[clap(long, required = false)]
+    pub dev_inspect: bool,
+
+    /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
+    /// (TransactionData) using base64 encoding, and print out the string.
+    #[clap(long, alias = "serialize-unsigned", required = false)]
+    pub serialize_unsigned_transaction: bool,
+
+    /// Instead of executing the transaction, serialize the bcs bytes of the signed transaction data
+    /// (SenderSignedData) using base64 encoding, and print out the string.
+    #[clap(long, alias = "serialize-signed", required = false)]
+    pub serialize_signed_transaction: bool,
+
+    /// Package build options, used by `publish` commands
+    #[clap(flatten)]
+    pub build_config: MoveBuildConfig,
+}
+
+/// A command of the commands file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+pub enum PtbCommandSpec {
+    SplitCoins {
+        coin: PtbArg,
+        amounts: Vec<PtbArg>,
+    },
+    MergeCoins {
+        coin: PtbArg,
+        coins: Vec<PtbArg>,
+    },
+    TransferObjects {
+        objects: Vec<PtbArg>,
+        recipient: PtbArg,
+    },
+    MoveCall {
+        package: ObjectID,
+        module: String,
+        function: String,
+        #[serde(default)]
+        type_args: Vec<SuiTypeTag>,
+        #[serde(default)]
+        args: Vec<PtbArg>,
+    },
+    MakeMoveVec {
+        /// Required if the elements are pure values, e.g. `u64` or `address`.
+        #[serde(rename = "type", default)]
+        type_: Option<SuiTypeTag>,
+        elements: Vec<PtbArg>,
+    },
+    Publish {
+        package_path: PathBuf,
+        #[serde(default)]
+        with_unpublished_dependencies: bool,
+        #[serde(default)]
+        skip_dependency_verification: bool,
+    },
+}
+
+/// An argument of a command of the commands file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "Value")]
+pub enum PtbArg {
+    /// `"gas"`
+    Gas,
+    /// `"result(i)"`
+    Result(u16),
+    /// `"result(i,j)"`
+    NestedResult(u16, u16),
+    /// Any other JSON value.
+    Value(SuiJsonValue),
+}
+
+impl TryFrom<Value> for PtbArg {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let Some(s) = value.as_str() else {
+            return Ok(PtbArg::Value(SuiJsonValue::new(value)?));
+        };
+        let s = s.trim();
+        if s == "gas" {
+            return Ok(PtbArg::Gas);
+        }
+        let Some(indices) = s.strip_prefix("result(").and_then(|s| s.strip_suffix(')')) else {
+            return Ok(PtbArg::Value(SuiJsonValue::new(value)?));
+        };
+        let indices = indices
+            .split(',')
+            .map(|i| i.trim().parse::<u16>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow!("Invalid result reference {s}: {e}"))?;
+        match indices[..] {
+            [i] => Ok(PtbArg::Result(i)),
+            [i, j] => Ok(PtbArg::NestedResult(i, j)),
+            _ => Err(anyhow!(
+                "Invalid result reference {s}, expected result(i) or result(i,j)"
+            )),
+        }
+    }
+}
+
+impl PtbArg {
+    /// The argument this refers to, for args that are not JSON values. `command` is the index
+    /// of the command using the argument, which can only refer to the results of earlier
+    /// commands.
+    fn reference(&self, command: usize) -> Result<Argument, anyhow::Error> {
+        let (argument, result) = match self {
+            PtbArg::Gas => return Ok(Argument::GasCoin),
+            PtbArg::Result(i) => (Argument::Result(*i), *i),
+            PtbArg::NestedResult(i, j) => (Argument::NestedResult(*i, *j), *i),
+            PtbArg::Value(value) => bail!("{} is not a reference", value.to_json_value()),
+        };
+        ensure!(
+            (result as usize) < command,
+            "Command {command} refers to the result of command {result}, which does not come before it"
+        );
+        Ok(argument)
+    }
+}
+
+/// The outcome of a single command of the transaction.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtbCommandOutput {
+    pub index: usize,
+    pub command: String,
+    /// Only known when the transaction is dev-inspected.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub return_values: Vec<PtbValue>,
+    /// Only known when the transaction is dev-inspected.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mutable_reference_outputs: Vec<PtbValue>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtbValue {
+    #[serde(rename = "type")]
+    pub type_: SuiTypeTag,
+    /// Base64 encoded BCS bytes of the value.
+    pub bcs: String,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum PtbCommandResult {
+    Execute {
+        commands: Vec<PtbCommandOutput>,
+        response: SuiTransactionBlockResponse,
+    },
+    DryRun {
+        commands: Vec<PtbCommandOutput>,
+        response: DryRunTransactionBlockResponse,
+    },
+    DevInspect {
+        commands: Vec<PtbCommandOutput>,
+        results: DevInspectResults,
+    },
+    SerializedUnsignedTransaction(TransactionData),
+    SerializedSignedTransaction(SenderSignedData),
+    OfflineSignedTransaction(OfflineSignedTransaction),
+}
+
+impl PtbCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<PtbCommandResult, anyhow::Error> {
+        let modes = [
+            self.dry_run,
+            self.dev_inspect,
+            self.serialize_unsigned_transaction,
+            self.serialize_signed_transaction,
+        ];
+        ensure!(
+            modes.iter().filter(|mode| **mode).count() <= 1,
+            "Only one of --dry-run, --dev-inspect, --serialize-unsigned-transaction and \
+             --serialize-signed-transaction can be specified"
+        );
+        let content = fs::read_to_string(&self.commands_file)
+            .map_err(|e| anyhow!("Cannot read commands file {:?}: {e}", self.commands_file))?;
+        let specs: Vec<PtbCommandSpec> = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Invalid commands file {:?}: {e}", self.commands_file))?;
+        ensure!(!specs.is_empty(), "Commands file contains no commands");
+
+        // The gas object of a sponsored transaction belongs to the sponsor.
+        let sender = match self.gas_sponsor {
+            Some(_) => None,
+            None => context.try_get_object_owner(&self.gas).await?,
+        };
+        let sender = sender.unwrap_or(context.active_address()?);
+        let sponsor = self.gas_sponsor.unwrap_or(sender);
+        let pt = build_programmable_transaction(context, specs, self.build_config).await?;
+        let mut commands = pt
+            .commands
+            .iter()
+            .enumerate()
+            .map(|(index, command)| PtbCommandOutput {
+                index,
+                command: command.to_string(),
+                return_values: vec![],
+                mutable_reference_outputs: vec![],
+            })
+            .collect::<Vec<_>>();
+
+        if self.dev_inspect {
+            let results = context
+                .get_client()
+                .await?
+                .read_api()
+                .dev_inspect_transaction_block(
+                    sender,
+                    TransactionKind::programmable(pt),
+                    None,
+                    None,
+                )
+                .await?;
+            for (output, result) in commands.iter_mut().zip(results.results.iter().flatten()) {
+                output.return_values = result
+                    .return_values
+                    .iter()
+                    .map(|(bytes, type_)| PtbValue {
+                        type_: type_.clone(),
+                        bcs: Base64::encode(bytes),
+                    })
+                    .collect();
+                output.mutable_reference_outputs = result
+                    .mutable_reference_outputs
+                    .iter()
+                    .map(|(_, bytes, type_)| PtbValue {
+                        type_: type_.clone(),
+                        bcs: Base64::encode(bytes),
+                    })
+                    .collect();
+            }
+            return Ok(PtbCommandResult::DevInspect { commands, results });
+        }
+
+        let tx_data = context
+            .transaction_builder()
+            .await?
+            .programmable_transaction_allow_sponsor(sender, pt, self.gas, self.gas_budget, sponsor)
+            .await?;
+        if self.dry_run {
+            let response = context
+                .get_client()
+                .await?
+                .read_api()
+                .dry_run_transaction_block(tx_data)
+                .await?;
+            return Ok(PtbCommandResult::DryRun { commands, response });
+        }
+        if self.serialize_unsigned_transaction {
+            return Ok(PtbCommandResult::SerializedUnsignedTransaction(tx_data));
+        }
+
+        let mut signatures = vec![];
+        for signer in tx_data.signers() {
+            ensure!(
+                context.config.keystore.addresses().contains(&signer),
+                "Key of {signer} is not in the local keystore. Use --serialize-unsigned-transaction \
+                 and `sui client sponsor` to collect the signatures of the sender and the sponsor"
+            );
+            signatures.push(
+                context
+                    .config
+                    .keystore
+                    .sign_secure(&signer, &tx_data, Intent::sui_transaction())?
+                    .into(),
+            );
+        }
+        let sender_signed_data =
+            SenderSignedData::new(tx_data, Intent::sui_transaction(), signatures);
+        if self.serialize_signed_transaction {
+            return Ok(PtbCommandResult::SerializedSignedTransaction(
+                sender_signed_data,
+            ));
+        }
+        if let Some(offline) = context.offline_snapshot() {
+            return Ok(PtbCommandResult::OfflineSignedTransaction(
+                OfflineSignedTransaction::new(offline, &sender_signed_data)?,
+            ));
+        }
+        let response = context
+            .execute_transaction_may_fail(Transaction::new(sender_signed_data))
+            .await?;
+        let effects = response
+            .effects
+            .as_ref()
+            .ok_or_else(|| anyhow!("Effects from SuiTransactionBlockResult should not be empty"))?;
+        if matches!(effects.status(), SuiExecutionStatus::Failure { .. }) {
+            return Err(anyhow!(
+                "Error executing transaction: {:#?}",
+                effects.status()
+            ));
+        }
+        Ok(PtbCommandResult::Execute { commands, response })
+    }
+}
+
+/// Turn the commands of a commands file into a programmable transaction, resolving object IDs to
+/// object inputs and checking Move call args against the signature of the called function.
+pub async fn build_programmable_transaction(
+    context: &WalletContext,
+    specs: Vec<PtbCommandSpec>,
+    build_config: MoveBuildConfig,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let tx_builder = &context.transaction_builder().await?;
+    let mut builder = ProgrammableTransactionBuilder::new();
+    for (index, spec) in specs.into_iter().enumerate() {
+        match spec {
+            PtbCommandSpec::SplitCoins { coin, amounts } => {
+                let coin = object_arg(tx_builder, &mut builder, index, &coin, true).await?;
+                let amounts = amounts
+                    .iter()
+                    .map(|amount| pure_arg(&mut builder, index, amount, &MoveTypeLayout::U64))
+                    .collect::<Result<_, _>>()?;
+                builder.command(Command::SplitCoins(coin, amounts));
+            }
+            PtbCommandSpec::MergeCoins { coin, coins } => {
+                let coin = object_arg(tx_builder, &mut builder, index, &coin, true).await?;
+                let mut sources = vec![];
+                for source in &coins {
+                    sources.push(object_arg(tx_builder, &mut builder, index, source, true).await?);
+                }
+                builder.command(Command::MergeCoins(coin, sources));
+            }
+            PtbCommandSpec::TransferObjects { objects, recipient } => {
+                let mut args = vec![];
+                for object in &objects {
+                    args.push(object_arg(tx_builder, &mut builder, index, object, false).await?);
+                }
+                let recipient =
+                    pure_arg(&mut builder, index, &recipient, &MoveTypeLayout::Address)?;
+                builder.command(Command::TransferObjects(args, recipient));
+            }
+            PtbCommandSpec::MoveCall {
+                package,
+                module,
+                function,
+                type_args,
+                args,
+            } => {
+                let args = args
+                    .iter()
+                    .map(|arg| {
+                        Ok(match arg {
+                            PtbArg::Value(value) => ProgrammableMoveCallArg::Json(value.clone()),
+                            _ => ProgrammableMoveCallArg::Argument(arg.reference(index)?),
+                        })
+                    })
+                    .collect::<Result<_, anyhow::Error>>()?;
+                tx_builder
+                    .programmable_move_call(
+                        &mut builder,
+                        package,
+                        &module,
+                        &function,
+                        type_args,
+                        args,
+                    )
+                    .await?;
+            }
+            PtbCommandSpec::MakeMoveVec { type_, elements } => {
+                let type_: Option<TypeTag> = type_.map(|type_| type_.try_into()).transpose()?;
+                let layout = type_.as_ref().and_then(pure_layout);
+                let mut args = vec![];
+                for element in &elements {
+                    args.push(match &layout {
+                        Some(layout) => pure_arg(&mut builder, index, element, layout)?,
+                        None => object_arg(tx_builder, &mut builder, index, element, false).await?,
+                    });
+                }
+                builder.command(Command::MakeMoveVec(type_, args));
+            }
+            PtbCommandSpec::Publish {
+                package_path,
+                with_unpublished_dependencies,
+                skip_dependency_verification,
+            } => {
+                let (dependencies, compiled_modules, _, _) = compile_package(
+                    &context.get_client().await?,
+                    build_config.clone(),
+                    package_path,
+                    with_unpublished_dependencies,
+                    skip_dependency_verification,
+                    false,
+                )
+                .await?;
+                builder.publish_upgradeable(
+                    compiled_modules,
+                    dependencies.published.into_values().collect(),
+                );
+            }
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Resolve an argument expected to be an object, given either as a reference or an object ID.
+async fn object_arg(
+    tx_builder: &TransactionBuilder,
+    builder: &mut ProgrammableTransactionBuilder,
+    index: usize,
+    arg: &PtbArg,
+    is_mutable_ref: bool,
+) -> Result<Argument, anyhow::Error> {
+    let PtbArg::Value(value) = arg else {
+        return arg.reference(index);
+    };
+    let id = match value.to_json_value() {
+        Value::String(s) => ObjectID::from_hex_literal(&s)
+            .map_err(|e| anyhow!("Invalid object ID {s} in command {index}: {e}"))?,
+        v => bail!("Expected an object ID in command {index}, found {v}"),
+    };
+    tx_builder.object_input(builder, id, is_mutable_ref).await
+}
+
+/// Resolve an argument expected to be a pure value of type `layout`.
+fn pure_arg(
+    builder: &mut ProgrammableTransactionBuilder,
+    index: usize,
+    arg: &PtbArg,
+    layout: &MoveTypeLayout,
+) -> Result<Argument, anyhow::Error> {
+    let PtbArg::Value(value) = arg else {
+        return arg.reference(index);
+    };
+    let bytes = value
+        .to_bcs_bytes(layout)
+        .map_err(|e| anyhow!("Invalid {layout} value in command {index}: {e}"))?;
+    Ok(builder.pure_bytes(bytes, /* force separate */ false))
+}
+
+/// The layout of `type_` if values of this type can be passed as pure arguments.
+fn pure_layout(type_: &TypeTag) -> Option<MoveTypeLayout> {
+    Some(match type_ {
+        TypeTag::Bool => MoveTypeLayout::Bool,
+        TypeTag::U8 => MoveTypeLayout::U8,
+        TypeTag::U16 => MoveTypeLayout::U16,
+        TypeTag::U32 => MoveTypeLayout::U32,
+        TypeTag::U64 => MoveTypeLayout::U64,
+        TypeTag::U128 => MoveTypeLayout::U128,
+        TypeTag::U256 => MoveTypeLayout::U256,
+        TypeTag::Address => MoveTypeLayout::Address,
+        TypeTag::Vector(inner) => MoveTypeLayout::Vector(Box::new(pure_layout(inner)?)),
+        TypeTag::Signer | TypeTag::Struct(_) => return None,
+    })
+}
+
+impl Display for PtbCommandResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+        match self {
+            PtbCommandResult::Execute { commands, response } => {
+                write_commands(&mut writer, commands)?;
+                write!(writer, "{}", write_transaction_response(response)?)?;
+            }
+            PtbCommandResult::DryRun { commands, response } => {
+                write_commands(&mut writer, commands)?;
+                writeln!(writer, "{}", "----- Transaction Effects ----".bold())?;
+                writeln!(writer, "{}", response.effects)?;
+                writeln!(writer, "{}", "----- Events ----".bold())?;
+                writeln!(writer, "{:#?}", json!(response.events))?;
+                writeln!(writer, "{}", "----- Object changes ----".bold())?;
+                writeln!(writer, "{:#?}", json!(response.object_changes))?;
+                writeln!(writer, "{}", "----- Balance changes ----".bold())?;
+                writeln!(writer, "{:#?}", json!(response.balance_changes))?;
+            }
+            PtbCommandResult::DevInspect { commands, results } => {
+                write_commands(&mut writer, commands)?;
+                if let Some(error) = &results.error {
+                    writeln!(writer, "{} {error}", "Execution error:".bold().red())?;
+                }
+                writeln!(writer, "{}", "----- Transaction Effects ----".bold())?;
+                writeln!(writer, "{}", results.effects)?;
+                writeln!(writer, "{}", "----- Events ----".bold())?;
+                writeln!(writer, "{:#?}", json!(results.events))?;
+            }
+            PtbCommandResult::SerializedUnsignedTransaction(tx_data) => {
+                writeln!(
+                    writer,
+                    "{}",
+                    Base64::encode(bcs::to_bytes(tx_data).unwrap())
+                )?;
+            }
+            PtbCommandResult::SerializedSignedTransaction(sender_signed_tx) => {
+                writeln!(
+                    writer,
+                    "{}",
+                    Base64::encode(bcs::to_bytes(sender_signed_tx).unwrap())
+                )?;
+            }
+            PtbCommandResult::OfflineSignedTransaction(signed_tx) => {
+                writeln!(writer, "{}", signed_tx)?;
+            }
+        }
+        write!(f, "{}", writer.trim_end_matches('\n'))
+    }
+}
+
+fn write_commands(writer: &mut String, commands: &[PtbCommandOutput]) -> std::fmt::Result {
+    writeln!(writer, "{}", "----- Commands ----".bold())?;
+    let mut table = json_to_table(&json!(commands));
+    table.with(TableStyle::rounded().horizontals([]));
+    writeln!(writer, "{}", table)
+}
+
+impl PtbCommandResult {
+    pub fn tx_block_response(&self) -> Option<&SuiTransactionBlockResponse> {
+        match self {
+            PtbCommandResult::Execute { response, .. } => Some(response),
+            _ => None,
+        }
+    }
+}
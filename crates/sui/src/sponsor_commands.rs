@@ -0,0 +1,245 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Display, Formatter};
+
+use anyhow::{anyhow, ensure};
+use clap::*;
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::traits::ToFromBytes;
+use json_to_table::json_to_table;
+use serde::Serialize;
+use serde_json::json;
+use shared_crypto::intent::Intent;
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+};
+use sui_keys::keystore::AccountKeystore;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::crypto::EncodeDecodeBase64;
+use sui_types::signature::{GenericSignature, VerifyParams};
+use sui_types::transaction::{SenderSignedData, Transaction, TransactionData, TransactionDataAPI};
+use tabled::settings::Style as TableStyle;
+
+use crate::client_commands::write_transaction_response;
+
+/// Commands for transactions whose gas is paid by a sponsor rather than by their sender. Such a
+/// transaction has to be signed by both the sender and the sponsor, who usually do not share a
+/// keystore: each of them signs the same transaction bytes with `sign`, and the signatures are
+/// put together with `combine`.
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum SponsorCommand {
+    /// Make a sponsor pay the gas of an unsigned programmable transaction, e.g. one built by any
+    /// `sui client` command with `--serialize-unsigned-transaction`. Use `sui client ptb
+    /// --gas-sponsor` to build a sponsored transaction for a sender that owns no gas coin.
+    Create {
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        #[clap(long)]
+        tx_bytes: String,
+        /// The address paying for gas.
+        #[clap(long)]
+        sponsor: SuiAddress,
+        /// ID of the gas object of the sponsor, in 20 bytes Hex string
+        /// If not provided, a gas object of the sponsor with at least gas_budget value will be
+        /// selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget for the transaction, the budget of the original transaction if not provided
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+    /// Sign a sponsored transaction with the keys of the local keystore of its sender and its
+    /// sponsor, or only with the key of `--address` if given.
+    Sign {
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        #[clap(long)]
+        tx_bytes: String,
+        /// Only sign with the key of this address.
+        #[clap(long)]
+        address: Option<SuiAddress>,
+    },
+    /// Combine the signatures of the sender and the sponsor into a signed transaction
+    /// (SenderSignedData), checking that both of them signed.
+    Combine {
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        #[clap(long)]
+        tx_bytes: String,
+        /// The Base64 encoded signatures `flag || signature || pubkey` of the sender and the
+        /// sponsor, in any order.
+        #[clap(long, num_args(1..))]
+        signatures: Vec<String>,
+        /// Execute the signed transaction instead of printing it.
+        #[clap(long)]
+        execute: bool,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SponsorSignature {
+    pub address: SuiAddress,
+    /// `sender` or `sponsor`.
+    pub role: String,
+    /// Base64 encoded `flag || signature || pubkey`.
+    pub signature: String,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum SponsorCommandResult {
+    Create(TransactionData),
+    Sign(Vec<SponsorSignature>),
+    Combine(SenderSignedData),
+    Execute(SuiTransactionBlockResponse),
+}
+
+fn decode_tx_data(tx_bytes: &str) -> Result<TransactionData, anyhow::Error> {
+    let bytes =
+        Base64::decode(tx_bytes).map_err(|e| anyhow!("Invalid base64 tx bytes: {:?}", e))?;
+    Ok(bcs::from_bytes(&bytes)?)
+}
+
+fn ensure_sponsored(tx_data: &TransactionData) -> Result<(), anyhow::Error> {
+    ensure!(
+        tx_data.is_sponsored_tx(),
+        "Transaction of {} is not sponsored, its gas is paid by its sender",
+        tx_data.sender()
+    );
+    Ok(())
+}
+
+/// Put together the signatures of the sender and the sponsor of `tx_data`, and check them.
+pub fn combine_sponsored_signatures(
+    tx_data: TransactionData,
+    signatures: Vec<GenericSignature>,
+) -> Result<Transaction, anyhow::Error> {
+    ensure_sponsored(&tx_data)?;
+    let transaction =
+        Transaction::from_generic_sig_data(tx_data, Intent::sui_transaction(), signatures);
+    transaction
+        .verify_signature(&VerifyParams::default())
+        .map_err(|e| anyhow!("Invalid signatures for sponsored transaction: {e}"))?;
+    Ok(transaction)
+}
+
+impl SponsorCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SponsorCommandResult, anyhow::Error> {
+        Ok(match self {
+            SponsorCommand::Create {
+                tx_bytes,
+                sponsor,
+                gas,
+                gas_budget,
+            } => {
+                let tx_data = decode_tx_data(&tx_bytes)?;
+                ensure!(
+                    tx_data.sender() != sponsor,
+                    "The sponsor must be different from the sender {}",
+                    sponsor
+                );
+                let tx_data = context
+                    .transaction_builder()
+                    .await?
+                    .sponsor_transaction(tx_data, sponsor, gas, gas_budget)
+                    .await?;
+                SponsorCommandResult::Create(tx_data)
+            }
+
+            SponsorCommand::Sign { tx_bytes, address } => {
+                let tx_data = decode_tx_data(&tx_bytes)?;
+                ensure_sponsored(&tx_data)?;
+                let local_addresses = context.config.keystore.addresses();
+                let signers = [
+                    (tx_data.sender(), "sender"),
+                    (tx_data.gas_owner(), "sponsor"),
+                ]
+                .into_iter()
+                .filter(|(signer, _)| local_addresses.contains(signer))
+                .filter(|(signer, _)| address.map_or(true, |address| address == *signer))
+                .collect::<Vec<_>>();
+                ensure!(
+                    !signers.is_empty(),
+                    "No key of the local keystore can sign for sender {} or sponsor {}",
+                    tx_data.sender(),
+                    tx_data.gas_owner()
+                );
+                let mut signatures = vec![];
+                for (signer, role) in signers {
+                    let signature = context.config.keystore.sign_secure(
+                        &signer,
+                        &tx_data,
+                        Intent::sui_transaction(),
+                    )?;
+                    signatures.push(SponsorSignature {
+                        address: signer,
+                        role: role.to_string(),
+                        signature: signature.encode_base64(),
+                    });
+                }
+                SponsorCommandResult::Sign(signatures)
+            }
+
+            SponsorCommand::Combine {
+                tx_bytes,
+                signatures,
+                execute,
+            } => {
+                let tx_data = decode_tx_data(&tx_bytes)?;
+                let signatures = signatures
+                    .iter()
+                    .map(|s| {
+                        let bytes = Base64::decode(s)
+                            .map_err(|e| anyhow!("Invalid base64 signature {s}: {:?}", e))?;
+                        GenericSignature::from_bytes(&bytes)
+                            .map_err(|e| anyhow!("Invalid signature {s}: {e}"))
+                    })
+                    .collect::<Result<Vec<_>, anyhow::Error>>()?;
+                let transaction = combine_sponsored_signatures(tx_data, signatures)?;
+                if !execute {
+                    return Ok(SponsorCommandResult::Combine(transaction.into_data()));
+                }
+                let response = context.execute_transaction_may_fail(transaction).await?;
+                let effects = response.effects.as_ref().ok_or_else(|| {
+                    anyhow!("Effects from SuiTransactionBlockResult should not be empty")
+                })?;
+                if matches!(effects.status(), SuiExecutionStatus::Failure { .. }) {
+                    return Err(anyhow!(
+                        "Error executing transaction: {:#?}",
+                        effects.status()
+                    ));
+                }
+                SponsorCommandResult::Execute(response)
+            }
+        })
+    }
+}
+
+impl Display for SponsorCommandResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SponsorCommandResult::Create(tx_data) => {
+                write!(f, "{}", Base64::encode(bcs::to_bytes(tx_data).unwrap()))
+            }
+            SponsorCommandResult::Sign(signatures) => {
+                let mut table = json_to_table(&json!(signatures));
+                table.with(TableStyle::rounded().horizontals([]));
+                write!(f, "{}", table)
+            }
+            SponsorCommandResult::Combine(sender_signed_data) => {
+                write!(
+                    f,
+                    "{}",
+                    Base64::encode(bcs::to_bytes(sender_signed_data).unwrap())
+                )
+            }
+            SponsorCommandResult::Execute(response) => {
+                write!(f, "{}", write_transaction_response(response)?)
+            }
+        }
+    }
+}
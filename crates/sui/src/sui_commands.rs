@@ -35,6 +35,7 @@ use sui_swarm_config::genesis_config::{GenesisConfig, DEFAULT_NUMBER_OF_AUTHORIT
 use sui_swarm_config::network_config::NetworkConfig;
 use sui_swarm_config::network_config_builder::ConfigBuilder;
 use sui_swarm_config::node_config_builder::FullnodeConfigBuilder;
+use sui_transaction_builder::offline::OfflineDataReader;
 use sui_types::crypto::{SignatureScheme, SuiKeyPair};
 use sui_types::multiaddr::Multiaddr;
 use tracing::info;
@@ -121,6 +122,10 @@ pub enum SuiCommand {
         json: bool,
         #[clap(short = 'y', long = "yes")]
         accept_defaults: bool,
+        /// Build and sign transactions from a snapshot saved with `sui client offline-snapshot`,
+        /// without connecting to a full node.
+        #[clap(long, global = true)]
+        offline_snapshot: Option<PathBuf>,
     },
     /// A tool for validators and validator candidates.
     #[clap(name = "validator")]
@@ -267,7 +272,7 @@ impl SuiCommand {
             } => {
                 let keystore_path =
                     keystore_path.unwrap_or(sui_config_dir()?.join(SUI_KEYSTORE_FILENAME));
-                let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+                let mut keystore = Keystore::from_path(&keystore_path)?;
                 cmd.execute(&mut keystore).await?.print(!json);
                 Ok(())
             }
@@ -282,10 +287,15 @@ impl SuiCommand {
                 cmd,
                 json,
                 accept_defaults,
+                offline_snapshot,
             } => {
                 let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 let mut context = WalletContext::new(&config_path, None, None).await?;
+                if let Some(snapshot) = offline_snapshot {
+                    context =
+                        context.with_offline_snapshot(OfflineDataReader::from_file(&snapshot)?);
+                }
                 if let Some(cmd) = cmd {
                     cmd.execute(&mut context).await?.print(!json);
                 } else {
@@ -7,16 +7,21 @@ use std::{fmt::Write, fs::read_dir, path::PathBuf, str, thread, time::Duration};
 
 use expect_test::expect;
 use serde_json::json;
+use sui_protocol_config::ProtocolConfig;
 use sui_test_transaction_builder::batch_make_transfer_transactions;
 use sui_types::object::Owner;
 use sui_types::transaction::{
-    TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
+    TransactionDataAPI, TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
     TEST_ONLY_GAS_UNIT_FOR_PUBLISH, TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
     TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
 };
 use tokio::time::sleep;
 
+use fastcrypto::encoding::{Base64, Encoding};
 use sui::client_commands::SwitchResponse;
+use sui::multisig_commands::{MultiSigCommand, MultiSigCommandResult};
+use sui::ptb_commands::{PtbCommand, PtbCommandResult};
+use sui::sponsor_commands::{SponsorCommand, SponsorCommandResult};
 use sui::{
     client_commands::{SuiClientCommandResult, SuiClientCommands},
     sui_commands::SuiCommand,
@@ -30,18 +35,20 @@ use sui_json_rpc_types::{
     OwnedObjectRef, SuiObjectData, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
     SuiObjectResponseQuery, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
 };
-use sui_keys::keystore::AccountKeystore;
+use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_macros::sim_test;
 use sui_move_build::{BuildConfig, SuiPackageHooks};
 use sui_sdk::sui_client_config::SuiClientConfig;
 use sui_sdk::wallet_context::WalletContext;
 use sui_swarm_config::genesis_config::{AccountConfig, GenesisConfig};
 use sui_swarm_config::network_config::NetworkConfig;
+use sui_transaction_builder::offline::OfflineDataReader;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{
     Ed25519SuiSignature, Secp256k1SuiSignature, SignatureScheme, SuiKeyPair, SuiSignatureInner,
 };
 use sui_types::error::SuiObjectResponseError;
+use sui_types::multisig::MultiSigPublicKey;
 use sui_types::{base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin};
 use test_cluster::TestClusterBuilder;
 
@@ -141,6 +148,28 @@ async fn test_addresses_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_remote_signer_keeps_local_keystore() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let mut context = test_cluster.wallet;
+    let addresses = context.config.keystore.addresses();
+
+    // Switching away from the local keystore has to be asked for explicitly
+    let err = SuiClientCommands::RemoteSigner {
+        endpoint: "tcp://127.0.0.1:1".to_string(),
+        timeout_ms: None,
+        replace_keystore: false,
+    }
+    .execute(&mut context)
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("--replace-keystore"));
+    assert!(matches!(context.config.keystore, Keystore::File(_)));
+    assert_eq!(context.config.keystore.addresses(), addresses);
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_objects_command() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await;
@@ -1853,6 +1882,370 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_multisig_workflow() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    // Three keys of the local keystore make up a 2-of-3 MultiSig address.
+    let mut signers = vec![];
+    let mut pks = vec![];
+    for _ in 0..3 {
+        let (signer, _, _) = context.config.keystore.generate_and_add_new_key(
+            SignatureScheme::ED25519,
+            None,
+            None,
+        )?;
+        pks.push(context.config.keystore.get_key(&signer)?.public());
+        signers.push(signer);
+    }
+    let multisig_address =
+        SuiAddress::from(&MultiSigPublicKey::new(pks.clone(), vec![1, 1, 1], 2)?);
+
+    // Fund the MultiSig address with a coin.
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_owned_objects(
+            address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new().with_type().with_owner(),
+            )),
+            None,
+            None,
+        )
+        .await?
+        .data;
+    let coin = object_refs.get(1).unwrap().object().unwrap().object_id;
+    SuiClientCommands::Transfer {
+        to: multisig_address,
+        object_id: coin,
+        gas: None,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+
+    let tx_data = match (SuiClientCommands::TransferSui {
+        to: address,
+        sui_coin_object_id: coin,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        amount: Some(1),
+        serialize_unsigned_transaction: true,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?)
+    {
+        SuiClientCommandResult::SerializedUnsignedTransaction(tx_data) => tx_data,
+        _ => panic!("Expected an unsigned transaction"),
+    };
+
+    let temp_dir = tempfile::tempdir()?;
+    let file = temp_dir.path().join("pending.json");
+    MultiSigCommand::Create {
+        tx_bytes: Base64::encode(bcs::to_bytes(&tx_data)?),
+        threshold: 2,
+        pks,
+        weights: vec![1, 1, 1],
+        file: file.clone(),
+    }
+    .execute(context)
+    .await?;
+
+    // A single signature does not reach the threshold.
+    MultiSigCommand::Sign {
+        file: file.clone(),
+        address: Some(signers[2]),
+    }
+    .execute(context)
+    .await?;
+    assert!(MultiSigCommand::Execute { file: file.clone() }
+        .execute(context)
+        .await
+        .is_err());
+
+    MultiSigCommand::Sign {
+        file: file.clone(),
+        address: Some(signers[0]),
+    }
+    .execute(context)
+    .await?;
+    let MultiSigCommandResult::Status(status) = MultiSigCommand::Status { file: file.clone() }
+        .execute(context)
+        .await? else {
+        panic!("Expected the status of the pending transaction");
+    };
+    assert_eq!(status.multisig_address, multisig_address);
+    assert_eq!(status.collected_weight, 2);
+    assert_eq!(
+        status.signers.iter().map(|s| s.signed).collect::<Vec<_>>(),
+        vec![true, false, true]
+    );
+
+    let MultiSigCommandResult::Execute(response) = MultiSigCommand::Execute { file }
+        .execute(context)
+        .await? else {
+        panic!("Expected a transaction response");
+    };
+    assert!(response.status_ok().unwrap());
+    Ok(())
+}
+
+#[sim_test]
+async fn test_ptb() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let recipient = SuiAddress::random_for_testing_only();
+    let context = &mut test_cluster.wallet;
+
+    // Split two coins off the gas coin, merge them, read the value of the merged coin and
+    // transfer it.
+    let temp_dir = tempfile::tempdir()?;
+    let commands_file = temp_dir.path().join("commands.json");
+    std::fs::write(
+        &commands_file,
+        json!([
+            {"command": "split-coins", "coin": "gas", "amounts": [1000, 2000]},
+            {"command": "merge-coins", "coin": "result(0,0)", "coins": ["result(0,1)"]},
+            {
+                "command": "move-call",
+                "package": "0x2",
+                "module": "coin",
+                "function": "value",
+                "type_args": ["0x2::sui::SUI"],
+                "args": ["result(0,0)"]
+            },
+            {"command": "transfer-objects", "objects": ["result(0,0)"], "recipient": recipient},
+        ])
+        .to_string(),
+    )?;
+    let ptb = |dev_inspect| PtbCommand {
+        commands_file: commands_file.clone(),
+        gas: None,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
+        gas_sponsor: None,
+        dry_run: false,
+        dev_inspect,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+        build_config: BuildConfig::new_for_testing().config,
+    };
+
+    let PtbCommandResult::DevInspect { commands, results } = ptb(true).execute(context).await? else {
+        panic!("Expected dev-inspect results");
+    };
+    assert!(results.error.is_none());
+    assert_eq!(commands.len(), 4);
+    assert_eq!(commands[2].return_values.len(), 1);
+    assert_eq!(
+        commands[2].return_values[0].bcs,
+        Base64::encode(bcs::to_bytes(&3000u64)?)
+    );
+
+    let PtbCommandResult::Execute { commands, response } = ptb(false).execute(context).await? else {
+        panic!("Expected a transaction response");
+    };
+    assert_eq!(commands.len(), 4);
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+    assert_eq!(effects.created().len(), 1);
+    assert_eq!(effects.created()[0].owner, Owner::AddressOwner(recipient));
+
+    // Commands can only use the results of earlier commands.
+    std::fs::write(
+        &commands_file,
+        json!([
+            {"command": "transfer-objects", "objects": ["result(1)"], "recipient": recipient},
+            {"command": "split-coins", "coin": "gas", "amounts": [1000]},
+        ])
+        .to_string(),
+    )?;
+    assert!(ptb(false).execute(context).await.is_err());
+    Ok(())
+}
+
+#[sim_test]
+async fn test_sponsored_transaction() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let sponsor = test_cluster.get_address_0();
+    let sender = test_cluster.get_address_1();
+    let recipient = SuiAddress::random_for_testing_only();
+    let context = &mut test_cluster.wallet;
+    context.config.active_address = Some(sender);
+
+    // The sender transfers one of its coins, the sponsor pays for gas.
+    let coin = context
+        .get_one_gas_object_owned_by_address(sender)
+        .await?
+        .unwrap();
+    let temp_dir = tempfile::tempdir()?;
+    let commands_file = temp_dir.path().join("commands.json");
+    std::fs::write(
+        &commands_file,
+        json!([{"command": "transfer-objects", "objects": [coin.0], "recipient": recipient}])
+            .to_string(),
+    )?;
+    let PtbCommandResult::SerializedUnsignedTransaction(tx_data) = PtbCommand {
+        commands_file,
+        gas: None,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        gas_sponsor: Some(sponsor),
+        dry_run: false,
+        dev_inspect: false,
+        serialize_unsigned_transaction: true,
+        serialize_signed_transaction: false,
+        build_config: BuildConfig::new_for_testing().config,
+    }
+    .execute(context)
+    .await? else {
+        panic!("Expected an unsigned transaction");
+    };
+    assert_eq!(tx_data.sender(), sender);
+    assert_eq!(tx_data.gas_owner(), sponsor);
+    let tx_bytes = Base64::encode(bcs::to_bytes(&tx_data)?);
+
+    // Sender and sponsor sign separately.
+    let mut signatures = vec![];
+    for signer in [sender, sponsor] {
+        let SponsorCommandResult::Sign(signed) = SponsorCommand::Sign {
+            tx_bytes: tx_bytes.clone(),
+            address: Some(signer),
+        }
+        .execute(context)
+        .await? else {
+            panic!("Expected signatures");
+        };
+        assert_eq!(signed.len(), 1);
+        assert_eq!(signed[0].address, signer);
+        signatures.push(signed[0].signature.clone());
+    }
+
+    // The signature of the sponsor is required.
+    assert!(SponsorCommand::Combine {
+        tx_bytes: tx_bytes.clone(),
+        signatures: signatures[..1].to_vec(),
+        execute: false,
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    let SponsorCommandResult::Execute(response) = SponsorCommand::Combine {
+        tx_bytes,
+        signatures,
+        execute: true,
+    }
+    .execute(context)
+    .await? else {
+        panic!("Expected a transaction response");
+    };
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+    assert_eq!(effects.gas_object().owner, Owner::AddressOwner(sponsor));
+    let transferred = effects
+        .mutated()
+        .iter()
+        .find(|o| o.reference.object_id == coin.0)
+        .unwrap();
+    assert_eq!(transferred.owner, Owner::AddressOwner(recipient));
+
+    // An existing transaction of the sender can be sponsored too.
+    let coin = context
+        .get_one_gas_object_owned_by_address(sender)
+        .await?
+        .unwrap();
+    let SuiClientCommandResult::SerializedUnsignedTransaction(tx_data) =
+        (SuiClientCommands::Transfer {
+            to: recipient,
+            object_id: coin.0,
+            gas: None,
+            gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+            serialize_unsigned_transaction: true,
+            serialize_signed_transaction: false,
+        }
+        .execute(context)
+        .await?) else {
+        panic!("Expected an unsigned transaction");
+    };
+    let SponsorCommandResult::Create(tx_data) = SponsorCommand::Create {
+        tx_bytes: Base64::encode(bcs::to_bytes(&tx_data)?),
+        sponsor,
+        gas: None,
+        gas_budget: None,
+    }
+    .execute(context)
+    .await? else {
+        panic!("Expected an unsigned transaction");
+    };
+    assert_eq!(tx_data.sender(), sender);
+    assert_eq!(tx_data.gas_owner(), sponsor);
+
+    // The sender cannot spend the gas coin of the sponsor.
+    let commands_file = temp_dir.path().join("gas_commands.json");
+    std::fs::write(
+        &commands_file,
+        json!([
+            {"command": "split-coins", "coin": "gas", "amounts": [1000]},
+            {"command": "transfer-objects", "objects": ["result(0,0)"], "recipient": sender}
+        ])
+        .to_string(),
+    )?;
+    let ptb = |commands_file: &PathBuf, gas_sponsor| PtbCommand {
+        commands_file: commands_file.clone(),
+        gas: None,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
+        gas_sponsor,
+        dry_run: false,
+        dev_inspect: false,
+        serialize_unsigned_transaction: true,
+        serialize_signed_transaction: false,
+        build_config: BuildConfig::new_for_testing().config,
+    };
+    assert!(ptb(&commands_file, Some(sponsor))
+        .execute(context)
+        .await
+        .is_err());
+    let PtbCommandResult::SerializedUnsignedTransaction(tx_data) =
+        ptb(&commands_file, None).execute(context).await?
+    else {
+        panic!("Expected an unsigned transaction");
+    };
+    assert!(SponsorCommand::Create {
+        tx_bytes: Base64::encode(bcs::to_bytes(&tx_data)?),
+        sponsor,
+        gas: None,
+        gas_budget: None,
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    // Value split off the gas coin can go back to it or to the sponsor.
+    let commands_file = temp_dir.path().join("sponsor_gas_commands.json");
+    std::fs::write(
+        &commands_file,
+        json!([
+            {"command": "split-coins", "coin": "gas", "amounts": [1000, 2000]},
+            {"command": "merge-coins", "coin": "gas", "coins": ["result(0,0)"]},
+            {"command": "transfer-objects", "objects": ["result(0,1)"], "recipient": sponsor}
+        ])
+        .to_string(),
+    )?;
+    let PtbCommandResult::SerializedUnsignedTransaction(tx_data) =
+        ptb(&commands_file, Some(sponsor)).execute(context).await?
+    else {
+        panic!("Expected an unsigned transaction");
+    };
+    assert_eq!(tx_data.gas_owner(), sponsor);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_stake_with_none_amount() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await;
@@ -2042,3 +2435,76 @@ async fn test_get_owned_objects_owned_by_address_and_check_pagination() -> Resul
 
     Ok(())
 }
+
+#[sim_test]
+async fn test_offline_transaction() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address = test_cluster.get_address_0();
+    let recipient = SuiAddress::random_for_testing_only();
+    let config_path = test_cluster.swarm.dir().join(SUI_CLIENT_CONFIG);
+    let context = &mut test_cluster.wallet;
+    let coin = context
+        .get_one_gas_object_owned_by_address(address)
+        .await?
+        .unwrap();
+
+    // Save the objects of the address while online.
+    let temp_dir = tempfile::tempdir()?;
+    let snapshot_path = temp_dir.path().join("snapshot.json");
+    let SuiClientCommandResult::OfflineSnapshot { objects, .. } = SuiClientCommands::OfflineSnapshot {
+        address: Some(address),
+        object_ids: vec![],
+        output: snapshot_path.clone(),
+    }
+    .execute(context)
+    .await? else {
+        panic!("Expected an offline snapshot");
+    };
+    assert!(objects > 1);
+
+    // Build and sign a transfer from the snapshot, without connecting to a full node.
+    let mut offline_context = WalletContext::new(&config_path, None, None)
+        .await?
+        .with_offline_snapshot(OfflineDataReader::from_file(&snapshot_path)?);
+    let SuiClientCommandResult::OfflineSignedTransaction(signed_tx) = SuiClientCommands::Transfer {
+        to: recipient,
+        object_id: coin.0,
+        gas: None,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(&mut offline_context)
+    .await? else {
+        panic!("Expected a transaction signed offline");
+    };
+    assert_eq!(signed_tx.epoch, 0);
+    assert!(offline_context.get_client().await.is_err());
+
+    // Gas budgets above the limit of the protocol version of the snapshot are refused.
+    assert!(SuiClientCommands::Transfer {
+        to: recipient,
+        object_id: coin.0,
+        gas: None,
+        gas_budget: ProtocolConfig::get_for_max_version_UNSAFE().max_tx_gas() + 1,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(&mut offline_context)
+    .await
+    .is_err());
+
+    // Execute it later while online.
+    let SuiClientCommandResult::ExecuteSignedTx(response) = SuiClientCommands::ExecuteSignedTx {
+        tx_bytes: signed_tx.tx_bytes,
+        signatures: signed_tx.signatures,
+    }
+    .execute(context)
+    .await? else {
+        panic!("Expected the transaction to be executed");
+    };
+    assert!(response.status_ok().unwrap());
+    assert_eq!(context.get_object_owner(&coin.0).await?, recipient);
+    Ok(())
+}
@@ -107,6 +107,18 @@ fn extract_struct_info(
     )
 }
 
+/// Whether a table value type is `Versioned<V>`
+fn is_versioned_value(value: &syn::GenericArgument) -> bool {
+    match value {
+        syn::GenericArgument::Type(Type::Path(p)) => p
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "Versioned"),
+        _ => false,
+    }
+}
+
 /// Extracts the table options override function
 /// The function must take no args and return Options
 fn get_options_override_function(attr: &Attribute) -> syn::Result<String> {
@@ -303,6 +315,18 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
         .map(|q| (q.args.first().unwrap(), q.args.last().unwrap()))
         .unzip();
 
+    // Tables whose values are `Versioned<V>` can be migrated to the current schema version
+    let versioned_field_names: Vec<_> = if simple_field_type_name_str == "DBMap" {
+        field_names
+            .iter()
+            .zip(value_names.iter())
+            .filter(|(_, value)| is_versioned_value(value))
+            .map(|(field_name, _)| field_name)
+            .collect()
+    } else {
+        vec![]
+    };
+
     // This is the actual name of the type which was found
     let post_process_fn_str = allowed_types_with_post_process_fn
         .get(&simple_field_type_name_str.as_str())
@@ -493,6 +517,23 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 )*].into_iter().collect()
             }
 
+            /// Upgrades every value of the given table written with an older schema version to the current one
+            /// Only tables of `Versioned` values can be migrated. Returns the number of upgraded values
+            pub fn migrate_table(&self, table_name: &str) -> eyre::Result<u64> {
+                match table_name {
+                    #(
+                        stringify!(#versioned_field_names) => {
+                            Ok(typed_store::rocks::versioned::migrate_table(
+                                &self.#versioned_field_names,
+                                typed_store::rocks::versioned::DEFAULT_MIGRATION_BATCH_SIZE,
+                            )?)
+                        }
+                    )*
+
+                    _ => eyre::bail!("No versioned table named: {}", table_name),
+                }
+            }
+
             /// This opens the DB in read only mode and returns a struct which exposes debug features
             pub fn get_read_only_handle (
                 primary_path: std::path::PathBuf,
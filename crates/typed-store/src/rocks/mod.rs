@@ -6,6 +6,7 @@ pub(crate) mod keys;
 pub(crate) mod safe_iter;
 pub mod util;
 pub(crate) mod values;
+pub mod versioned;
 
 use crate::{
     metrics::{DBMetrics, RocksDBPerfContext, SamplingInterval},
@@ -0,0 +1,381 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Schema versioning for table values.
+//!
+//! A table declared as `DBMap<K, Versioned<V>>` stores every value together with the schema
+//! version of `V` it was written with. When the layout of `V` changes, bump
+//! [`VersionedValue::VERSION`] and register an upgrade from the previous version in
+//! [`VersionedValue::register_upgrades`]. The code for reading every older version must be kept,
+//! as each upgrade only goes from version `n` to `n + 1`:
+//!
+//! ```ignore
+//! impl VersionedValue for StoreRecord {
+//!     const VERSION: u32 = 2;
+//!
+//!     fn register_upgrades(upgrades: &mut SchemaUpgrades) {
+//!         upgrades.register(0, |v0: StoreRecordV0| StoreRecordV1::from(v0));
+//!         upgrades.register(1, |v1: StoreRecordV1| StoreRecord::from(v1));
+//!     }
+//! }
+//! ```
+//!
+//! Values written with an older version are upgraded lazily, every time they are read, until
+//! they are written again. [`migrate_table`] rewrites all of them at once, and is meant to be run
+//! offline while nothing else writes to the table.
+//!
+//! Values are only readable through `Versioned` if they were written through it, so an existing
+//! table can not switch to versioned values without rewriting it.
+
+use super::{DBMap, TypedStoreError};
+use once_cell::sync::Lazy;
+use serde::{
+    de::{DeserializeOwned, Error as _},
+    ser::Error as _,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    any::TypeId,
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
+use tracing::info;
+
+/// Number of upgraded values written in each batch by [`migrate_table`].
+pub const DEFAULT_MIGRATION_BATCH_SIZE: usize = 10_000;
+
+/// A value type whose stored layout is versioned.
+pub trait VersionedValue: Serialize + DeserializeOwned + 'static {
+    /// The schema version of the values written by this build. Start at 0 and increment it every
+    /// time the layout changes.
+    const VERSION: u32;
+
+    /// Registers an upgrade from each older schema version to the next one.
+    fn register_upgrades(_upgrades: &mut SchemaUpgrades) {}
+}
+
+type UpgradeFn = Box<dyn Fn(&[u8]) -> Result<Vec<u8>, TypedStoreError> + Send + Sync>;
+
+/// The upgrades between consecutive schema versions of a value type.
+#[derive(Default)]
+pub struct SchemaUpgrades {
+    upgrades: BTreeMap<u32, UpgradeFn>,
+}
+
+impl SchemaUpgrades {
+    /// Registers the upgrade of values written with schema `from_version` to
+    /// schema `from_version + 1`.
+    pub fn register<Old, New>(&mut self, from_version: u32, upgrade: fn(Old) -> New)
+    where
+        Old: DeserializeOwned,
+        New: Serialize,
+    {
+        self.upgrades.insert(
+            from_version,
+            Box::new(move |bytes| {
+                let old: Old = bcs::from_bytes(bytes)?;
+                Ok(bcs::to_bytes(&upgrade(old))?)
+            }),
+        );
+    }
+
+    /// Upgrades the serialized value `bytes`, written with schema `version`, to schema `target`.
+    pub fn upgrade(
+        &self,
+        version: u32,
+        target: u32,
+        bytes: &[u8],
+    ) -> Result<Vec<u8>, TypedStoreError> {
+        let mut bytes = bytes.to_vec();
+        for from_version in version..target {
+            let upgrade = self.upgrades.get(&from_version).ok_or_else(|| {
+                TypedStoreError::SerializationError(format!(
+                    "no upgrade registered from schema version {from_version}"
+                ))
+            })?;
+            bytes = upgrade(&bytes)?;
+        }
+        Ok(bytes)
+    }
+}
+
+// Upgrades are only needed when reading old values, so they are registered the first time such
+// a value is read and kept for the lifetime of the process.
+static SCHEMA_UPGRADES: Lazy<Mutex<HashMap<TypeId, Arc<SchemaUpgrades>>>> =
+    Lazy::new(Default::default);
+
+fn schema_upgrades<V: VersionedValue>() -> Arc<SchemaUpgrades> {
+    SCHEMA_UPGRADES
+        .lock()
+        .unwrap()
+        .entry(TypeId::of::<V>())
+        .or_insert_with(|| {
+            let mut upgrades = SchemaUpgrades::default();
+            V::register_upgrades(&mut upgrades);
+            Arc::new(upgrades)
+        })
+        .clone()
+}
+
+/// A table value stored with the schema version it was written with, and upgraded to the
+/// current version when it is read.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Versioned<V>(pub V);
+
+impl<V> Versioned<V> {
+    pub fn into_inner(self) -> V {
+        self.0
+    }
+}
+
+impl<V> From<V> for Versioned<V> {
+    fn from(value: V) -> Self {
+        Self(value)
+    }
+}
+
+impl<V> Deref for Versioned<V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        &self.0
+    }
+}
+
+impl<V: VersionedValue> Versioned<V> {
+    /// Decodes a value written with schema `version`, upgrading it if needed.
+    fn decode(version: u32, bytes: &[u8]) -> Result<V, TypedStoreError> {
+        match version.cmp(&V::VERSION) {
+            Ordering::Equal => Ok(bcs::from_bytes(bytes)?),
+            Ordering::Less => {
+                let upgraded = schema_upgrades::<V>().upgrade(version, V::VERSION, bytes)?;
+                Ok(bcs::from_bytes(&upgraded)?)
+            }
+            Ordering::Greater => Err(TypedStoreError::SerializationError(format!(
+                "value has schema version {version}, newer than the supported version {}",
+                V::VERSION
+            ))),
+        }
+    }
+}
+
+// Stored as the schema version followed by the serialized value, so that values of older
+// versions can be read back as bytes and upgraded.
+impl<V: VersionedValue> Serialize for Versioned<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = bcs::to_bytes(&self.0).map_err(S::Error::custom)?;
+        (V::VERSION, bytes).serialize(serializer)
+    }
+}
+
+impl<'de, V: VersionedValue> Deserialize<'de> for Versioned<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (version, bytes): (u32, Vec<u8>) = Deserialize::deserialize(deserializer)?;
+        Self::decode(version, &bytes)
+            .map(Versioned)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Returns the schema version a stored `Versioned` value was written with.
+pub fn schema_version(bytes: &[u8]) -> Result<u32, TypedStoreError> {
+    let version = bytes.get(..4).ok_or_else(|| {
+        TypedStoreError::SerializationError("value is too short for a schema version".to_string())
+    })?;
+    Ok(u32::from_le_bytes(version.try_into().unwrap()))
+}
+
+/// Rewrites every value of `table` written with an older schema version with the current one,
+/// in batches of `batch_size` values. Returns the number of values that were upgraded.
+pub fn migrate_table<K, V: VersionedValue>(
+    table: &DBMap<K, Versioned<V>>,
+    batch_size: usize,
+) -> Result<u64, TypedStoreError> {
+    let cf = table.cf();
+    let mut batch = table.batch();
+    let mut pending = 0;
+    let mut migrated = 0;
+    for item in table.iterator_cf() {
+        let (key, value) = item?;
+        if schema_version(&value)? >= V::VERSION {
+            continue;
+        }
+        let upgraded: Versioned<V> = bcs::from_bytes(&value)?;
+        batch.batch.put_cf(&cf, key, bcs::to_bytes(&upgraded)?);
+        pending += 1;
+        migrated += 1;
+        if pending >= batch_size {
+            batch.write()?;
+            batch = table.batch();
+            pending = 0;
+            info!("Upgraded {migrated} values of table {}", table.cf);
+        }
+    }
+    if pending > 0 {
+        batch.write()?;
+    }
+    info!(
+        "Upgraded {migrated} values of table {} to schema version {}",
+        table.cf,
+        V::VERSION
+    );
+    Ok(migrated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rocks::{open_cf, MetricConf, ReadWriteOptions, RocksDB};
+    use crate::Map;
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct RecordV0 {
+        name: String,
+    }
+
+    impl VersionedValue for RecordV0 {
+        const VERSION: u32 = 0;
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct RecordV1 {
+        name: String,
+        balance: u64,
+    }
+
+    impl VersionedValue for RecordV1 {
+        const VERSION: u32 = 1;
+
+        fn register_upgrades(upgrades: &mut SchemaUpgrades) {
+            upgrades.register(0, |v0: RecordV0| RecordV1 {
+                name: v0.name,
+                balance: 0,
+            });
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct RecordV2 {
+        names: Vec<String>,
+        balance: u64,
+    }
+
+    impl VersionedValue for RecordV2 {
+        const VERSION: u32 = 2;
+
+        fn register_upgrades(upgrades: &mut SchemaUpgrades) {
+            RecordV1::register_upgrades(upgrades);
+            upgrades.register(1, |v1: RecordV1| RecordV2 {
+                names: vec![v1.name],
+                balance: v1.balance,
+            });
+        }
+    }
+
+    fn open_db() -> Arc<RocksDB> {
+        let path = tempfile::tempdir()
+            .expect("Failed to open temporary directory")
+            .into_path();
+        open_cf(path, None, MetricConf::default(), &["records"]).unwrap()
+    }
+
+    // Opens the table with the schema `V`, so that values of different versions can be written
+    // to it by opening it more than once.
+    fn open_table<V>(db: &Arc<RocksDB>) -> DBMap<u32, Versioned<V>> {
+        DBMap::reopen(db, Some("records"), &ReadWriteOptions::default()).unwrap()
+    }
+
+    fn v0(name: &str) -> RecordV0 {
+        RecordV0 {
+            name: name.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let table = open_table::<RecordV1>(&open_db());
+        let record = RecordV1 {
+            name: "a".to_string(),
+            balance: 10,
+        };
+        table.insert(&1, &record.clone().into()).unwrap();
+        assert_eq!(
+            schema_version(&table.get_raw_bytes(&1).unwrap().unwrap()),
+            Ok(1)
+        );
+        assert_eq!(table.get(&1).unwrap().unwrap().into_inner(), record);
+    }
+
+    #[tokio::test]
+    async fn test_upgrade_on_read() {
+        let db = open_db();
+        let table_v0 = open_table::<RecordV0>(&db);
+        table_v0.insert(&1, &v0("a").into()).unwrap();
+        table_v0.insert(&2, &v0("b").into()).unwrap();
+
+        let table = open_table::<RecordV2>(&db);
+        assert_eq!(
+            table.get(&1).unwrap().unwrap().into_inner(),
+            RecordV2 {
+                names: vec!["a".to_string()],
+                balance: 0,
+            }
+        );
+        let names: Vec<_> = table
+            .unbounded_iter()
+            .map(|(_, record)| record.names.clone())
+            .collect();
+        assert_eq!(names, vec![vec!["a".to_string()], vec!["b".to_string()]]);
+        // Reads do not rewrite the stored values.
+        assert_eq!(
+            schema_version(&table.get_raw_bytes(&1).unwrap().unwrap()),
+            Ok(0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_newer_version_is_rejected() {
+        let db = open_db();
+        let record = RecordV1 {
+            name: "a".to_string(),
+            balance: 10,
+        };
+        open_table::<RecordV1>(&db)
+            .insert(&1, &record.into())
+            .unwrap();
+
+        assert!(matches!(
+            open_table::<RecordV0>(&db).get(&1),
+            Err(TypedStoreError::SerializationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_migrate_table() {
+        let db = open_db();
+        open_table::<RecordV0>(&db)
+            .multi_insert((0..10).map(|i| (i, Versioned(v0(&i.to_string())))))
+            .unwrap();
+        let record = RecordV1 {
+            name: "10".to_string(),
+            balance: 1,
+        };
+        open_table::<RecordV1>(&db)
+            .insert(&10, &record.into())
+            .unwrap();
+
+        let table = open_table::<RecordV2>(&db);
+        assert_eq!(migrate_table(&table, 3), Ok(11));
+        for i in 0..11 {
+            let bytes = table.get_raw_bytes(&i).unwrap().unwrap();
+            assert_eq!(schema_version(&bytes), Ok(2));
+            let record = table.get(&i).unwrap().unwrap();
+            assert_eq!(record.names, vec![i.to_string()]);
+        }
+        assert_eq!(table.get(&10).unwrap().unwrap().balance, 1);
+        // Nothing is left to upgrade.
+        assert_eq!(migrate_table(&table, 3), Ok(0));
+    }
+}
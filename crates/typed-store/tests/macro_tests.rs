@@ -12,7 +12,9 @@ use std::sync::Mutex;
 use std::time::Duration;
 use typed_store::metrics::SamplingInterval;
 use typed_store::rocks::list_tables;
+use typed_store::rocks::versioned::{SchemaUpgrades, Versioned, VersionedValue};
 use typed_store::rocks::DBMap;
+use typed_store::rocks::ReadWriteOptions;
 use typed_store::rocks::RocksDBAccessType;
 use typed_store::rocks::{be_fix_int_ser, MetricConf};
 use typed_store::sally::SallyColumn;
@@ -269,6 +271,70 @@ async fn macro_transactional_test() {
     assert_eq!(tables.table1.get(&key), Ok(Some("1".to_string())));
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct RecordV0 {
+    name: String,
+}
+
+impl VersionedValue for RecordV0 {
+    const VERSION: u32 = 0;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct RecordV1 {
+    name: String,
+    count: u64,
+}
+
+impl VersionedValue for RecordV1 {
+    const VERSION: u32 = 1;
+
+    fn register_upgrades(upgrades: &mut SchemaUpgrades) {
+        upgrades.register(0, |v0: RecordV0| RecordV1 {
+            name: v0.name,
+            count: 0,
+        });
+    }
+}
+
+#[derive(DBMapUtils)]
+struct TablesVersioned {
+    records: DBMap<u32, Versioned<RecordV1>>,
+    counts: DBMap<u32, u64>,
+}
+
+#[tokio::test]
+async fn macro_versioned_test() {
+    let primary_path = temp_dir();
+    let tables =
+        TablesVersioned::open_tables_read_write(primary_path, MetricConf::default(), None, None);
+    // Write a record with the previous schema of the table
+    let records_v0 = DBMap::<u32, Versioned<RecordV0>>::reopen(
+        &tables.records.rocksdb,
+        Some("records"),
+        &ReadWriteOptions::default(),
+    )
+    .unwrap();
+    let record = RecordV0 {
+        name: "record".to_string(),
+    };
+    records_v0.insert(&1, &record.into()).unwrap();
+
+    let expected = RecordV1 {
+        name: "record".to_string(),
+        count: 0,
+    };
+    assert_eq!(
+        tables.records.get(&1),
+        Ok(Some(Versioned(expected.clone())))
+    );
+
+    assert_eq!(tables.migrate_table("records").unwrap(), 1);
+    assert_eq!(tables.migrate_table("records").unwrap(), 0);
+    assert_eq!(tables.records.get(&1), Ok(Some(Versioned(expected))));
+    assert!(tables.migrate_table("counts").is_err());
+}
+
 /// We show that custom functions can be applied
 #[derive(DBMapUtils)]
 struct TablesCustomOptions {